@@ -75,6 +75,33 @@ fn reverb_bench(_dummy: usize) -> Wave {
     )
 }
 
+fn fdn_bench(_dummy: usize) -> Wave {
+    Wave::render(
+        44100.0,
+        1.0,
+        &mut (noise()
+            >> split::<U32>()
+            >> fdn::<U32, _>(stacki::<U32, _, _>(|i| {
+                delay(0.03 + 0.001 * i as f32) >> fir((0.2, 0.4, 0.2))
+            }))
+            >> join::<U32>()),
+    )
+}
+
+fn fdn_delay_bench(_dummy: usize) -> Wave {
+    Wave::render(
+        44100.0,
+        1.0,
+        &mut (noise()
+            >> split::<U32>()
+            >> fdn_delay::<U32, _>(
+                0.03,
+                stacki::<U32, _, _>(|i| delay(0.001 * i as f32) >> fir((0.2, 0.4, 0.2))),
+            )
+            >> join::<U32>()),
+    )
+}
+
 fn limiter_bench(_dummy: usize) -> Wave {
     Wave::render(44100.0, 1.0, &mut (noise() >> limiter(0.1, 1.0)))
 }
@@ -87,6 +114,23 @@ fn phaser_bench(_dummy: usize) -> Wave {
     )
 }
 
+fn delay_bench(_dummy: usize) -> Wave {
+    Wave::render(
+        44100.0,
+        1.0,
+        &mut (noise() >> busi::<U16, _, _>(|i| delay(0.01 * (i + 1) as f32))),
+    )
+}
+
+fn tap_bench(_dummy: usize) -> Wave {
+    Wave::render(
+        44100.0,
+        1.0,
+        &mut (noise()
+            >> busi::<U16, _, _>(|i| (pass() | dc(0.01 * (i + 1) as f32)) >> tap(0.0, 0.2))),
+    )
+}
+
 fn lowpass_bench(_dummy: usize) -> Wave {
     Wave::render(
         44100.0,
@@ -128,10 +172,14 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("chorus", |b| b.iter(|| chorus_bench(black_box(0))));
     c.bench_function("equalizer", |b| b.iter(|| equalizer_bench(black_box(0))));
     c.bench_function("reverb", |b| b.iter(|| reverb_bench(black_box(0))));
+    c.bench_function("fdn", |b| b.iter(|| fdn_bench(black_box(0))));
+    c.bench_function("fdn_delay", |b| b.iter(|| fdn_delay_bench(black_box(0))));
     c.bench_function("limiter", |b| b.iter(|| limiter_bench(black_box(0))));
     c.bench_function("phaser", |b| b.iter(|| phaser_bench(black_box(0))));
     c.bench_function("lowpass", |b| b.iter(|| lowpass_bench(black_box(0))));
     c.bench_function("lowpass16", |b| b.iter(|| lowpass16_bench(black_box(0))));
+    c.bench_function("delay", |b| b.iter(|| delay_bench(black_box(0))));
+    c.bench_function("tap", |b| b.iter(|| tap_bench(black_box(0))));
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -9,6 +9,8 @@ use super::*;
 use core::marker::PhantomData;
 use num_complex::Complex64;
 use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec::Vec;
 
 /*
 Order of type arguments in nodes:
@@ -78,6 +80,13 @@ pub trait AudioNode: Clone + Sync + Send {
     /// If `size` is zero then this is a no-op, which is permitted.
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         // The default implementation is a fallback that calls into `tick`.
+        self.process_scalar(size, input, output);
+    }
+
+    /// Process up to 64 (`MAX_BUFFER_SIZE`) samples one at a time using `tick`.
+    /// The output is bit-identical to calling `tick` for each sample.
+    /// With the `scalar` feature enabled, all block processing is routed here.
+    fn process_scalar(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         debug_assert!(size <= MAX_BUFFER_SIZE);
         debug_assert!(input.channels() == self.inputs());
         debug_assert!(output.channels() == self.outputs());
@@ -119,10 +128,66 @@ pub trait AudioNode: Clone + Sync + Send {
         }
     }
 
+    /// Process one sample in double precision.
+    /// The default implementation rounds the input to `f32` and calls `tick`.
+    /// Nodes with native double precision processing override this;
+    /// see [`AudioNode::process64`] for a list.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// assert_eq!(pass().tick64(&Frame::from([0.1f64])), Frame::from([0.1f64]));
+    /// ```
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        let output = self.tick(&Frame::generate(|i| input[i] as f32));
+        Frame::generate(|i| output[i] as f64)
+    }
+
+    /// Process `size` samples in double precision one at a time using `tick64`.
+    /// Input and output are slices of channels, each at least `size` samples long.
+    /// Unlike `process`, there is no limit on `size`.
+    ///
+    /// Double precision is maintained through nodes that process it natively:
+    /// `pass`, `multipass`, the Simper SVF filters with fixed parameters
+    /// (for example, `lowpass_hz`), `biquad` filters with fixed parameters
+    /// (for example, `butterpass_hz`), `lowpole_hz`, `highpole_hz` and `dcblock_hz`,
+    /// and the operators `>>`, `|`, `^` and `&` when their operands do.
+    /// Other nodes process in single precision.
+    /// Internal precision is that of the node: use the `hacker` prelude
+    /// for 64-bit internal state.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut node = lowpole_hz(0.01) >> dcblock_hz(0.001);
+    /// let input = [1.0e-9f64; 100];
+    /// let mut output = [0.0f64; 100];
+    /// node.process64(100, &[&input], &mut [&mut output]);
+    /// assert!(output[99] > 0.0);
+    /// ```
+    fn process64(&mut self, size: usize, input: &[&[f64]], output: &mut [&mut [f64]]) {
+        debug_assert!(input.len() == self.inputs());
+        debug_assert!(output.len() == self.outputs());
+        for i in 0..size {
+            let output_frame = self.tick64(&Frame::generate(|channel| input[channel][i]));
+            for (channel, x) in output_frame.into_iter().enumerate() {
+                output[channel][i] = x;
+            }
+        }
+    }
+
     /// Set a parameter. What formats are recognized depends on the component.
     #[allow(unused_variables)]
     fn set(&mut self, setting: Setting) {}
 
+    /// Set a parameter, interpolating to the new value over `ramp_time` seconds.
+    /// Nodes that do not support ramps apply the setting instantaneously.
+    /// See [`Setting`] for a list of nodes that support ramps.
+    fn set_ramped(&mut self, setting: Setting, ramp_time: f32) {
+        self.set(setting.ramp(ramp_time));
+    }
+
     /// Set node pseudorandom phase hash.
     /// This is called from `ping` (only). It should not be called by users.
     /// The node is allowed to reset itself here.
@@ -159,6 +224,15 @@ pub trait AudioNode: Clone + Sync + Send {
         // The default implementation does nothing.
     }
 
+    /// Heap memory used by buffers, delay lines and other allocations of this node, in bytes.
+    /// This is a best-effort estimate that does not include the size of the node itself.
+    /// Nodes that allocate buffers override this. Nodes that contain other nodes
+    /// return the sum of their children.
+    fn memory_usage(&self) -> usize {
+        // The default implementation reports no allocations.
+        0
+    }
+
     /// Route constants, latencies and frequency responses at `frequency` Hz
     /// from inputs to outputs. Return output signal.
     /// If there are no frequency responses in `input`, then `frequency` is ignored.
@@ -303,6 +377,90 @@ pub trait AudioNode: Clone + Sync + Send {
         self.response(output, frequency).map(|r| amp_db(r.norm()))
     }
 
+    /// Evaluate frequency response of `output` at each of `frequencies` (in Hz).
+    /// The input frame is built once and reused for every frequency,
+    /// which makes this suitable for plotting filter curves.
+    /// Only linear responses can be evaluated: entries are `None`
+    /// where there is no response or it could not be calculated.
+    ///
+    /// ### Example: Plotting A Lowpass Filter
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut filter = lowpass_hz(1000.0, 1.0);
+    /// let frequencies = [100.0, 1000.0, 10000.0];
+    /// let curve = filter.response_curve(0, &frequencies);
+    /// assert!((curve[0].unwrap().norm() - 1.0).abs() < 0.02);
+    /// assert!((curve[1].unwrap().norm() - 1.0).abs() < 0.01);
+    /// assert!(curve[2].unwrap().norm() < 0.02);
+    /// // Nonlinear nodes have no response.
+    /// assert_eq!(shape(Tanh(1.0)).response_curve(0, &frequencies), vec![None; 3]);
+    /// ```
+    fn response_curve(&mut self, output: usize, frequencies: &[f64]) -> Vec<Option<Complex64>> {
+        assert!(output < self.outputs());
+        let mut input = SignalFrame::new(self.inputs());
+        for i in 0..self.inputs() {
+            input.set(i, Signal::Response(Complex64::new(1.0, 0.0), 0.0));
+        }
+        frequencies
+            .iter()
+            .map(
+                |&frequency| match self.route(&input, frequency).at(output) {
+                    Signal::Response(rx, _) => Some(rx),
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+
+    /// Evaluate frequency response of `output` at each of `frequencies` (in Hz)
+    /// as pairs of magnitude (in dB) and phase (in radians).
+    /// Only linear responses can be evaluated: entries are `None`
+    /// where there is no response or it could not be calculated.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let curve = lowpass_hz(1000.0, 1.0).response_curve_db(0, &[1000.0]);
+    /// let (db, phase) = curve[0].unwrap();
+    /// assert!(db.abs() < 0.1);
+    /// assert!((phase + f64::PI / 2.0).abs() < 0.01);
+    /// ```
+    fn response_curve_db(&mut self, output: usize, frequencies: &[f64]) -> Vec<Option<(f64, f64)>> {
+        self.response_curve(output, frequencies)
+            .into_iter()
+            .map(|r| r.map(|r| (amp_db(r.norm()), r.arg())))
+            .collect()
+    }
+
+    /// Evaluate group delay of `output` at `frequency` Hz, in seconds.
+    /// Multiply by the sample rate to get the delay in samples.
+    /// Only linear responses can be evaluated.
+    /// Return `None` if there is no response or it could not be calculated.
+    ///
+    /// The group delay is the negative derivative of the phase response.
+    /// It is estimated with a central difference over a span of 0.02 Hz,
+    /// using the phase of the ratio of the two responses so that phase wraps
+    /// do not disturb the estimate. The estimate is unreliable near zeros of the response,
+    /// where the phase jumps, and aliases for delays longer than 25 seconds.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let delay = tick().group_delay(0, 1000.0).unwrap();
+    /// assert!((delay * DEFAULT_SR - 1.0).abs() < 1.0e-6);
+    /// ```
+    fn group_delay(&mut self, output: usize, frequency: f64) -> Option<f64> {
+        let f0 = max(frequency - 0.01, 0.0);
+        let f1 = frequency + 0.01;
+        let curve = self.response_curve(output, &[f0, f1]);
+        match (curve[0], curve[1]) {
+            (Some(r0), Some(r1)) if r0.norm() > 0.0 && r1.norm() > 0.0 => {
+                Some(-(r1 * r0.conj()).arg() / (f64::TAU * (f1 - f0)))
+            }
+            _ => None,
+        }
+    }
+
     /// Causal latency in (fractional) samples, if any.
     /// After a reset, we can discard this many samples from the output to avoid incurring a pre-delay.
     /// The latency may depend on the sample rate.
@@ -362,6 +520,10 @@ impl<N: Size<f32>> AudioNode for MultiPass<N> {
     fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
         input.clone()
     }
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        input.clone()
+    }
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         for channel in 0..self.outputs() {
             for i in 0..simd_items(size) {
@@ -395,6 +557,10 @@ impl AudioNode for Pass {
     fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
         *input
     }
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        *input
+    }
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         for i in 0..simd_items(size) {
             output.set(0, i, input.at(0, i));
@@ -434,30 +600,68 @@ impl<N: Size<f32>> AudioNode for Sink<N> {
 }
 
 /// Output a constant value.
+/// Setting: value. Supports ramped settings.
 #[derive(Clone)]
 pub struct Constant<N: Size<f32>> {
     output: Frame<f32, N>,
+    /// Target value of the ongoing ramp.
+    target: Frame<f32, N>,
+    /// Per sample increment of the ongoing ramp.
+    increment: Frame<f32, N>,
+    /// Number of samples left in the ongoing ramp.
+    ramp_samples: usize,
+    sample_rate: f64,
 }
 
 impl<N: Size<f32>> Constant<N> {
     /// Construct constant.
     pub fn new(output: Frame<f32, N>) -> Self {
-        Constant { output }
+        Constant {
+            output: output.clone(),
+            target: output,
+            increment: Frame::default(),
+            ramp_samples: 0,
+            sample_rate: DEFAULT_SR,
+        }
     }
-    /// Set the value of the constant.
+    /// Set the value of the constant. Any ongoing ramp is canceled.
     #[inline]
     pub fn set_value(&mut self, output: Frame<f32, N>) {
-        self.output = output;
+        self.output = output.clone();
+        self.target = output;
+        self.ramp_samples = 0;
     }
     /// Get the value of the constant.
     #[inline]
     pub fn value(&self) -> Frame<f32, N> {
         self.output.clone()
     }
-    /// Set a scalar value on all channels.
+    /// Set a scalar value on all channels. Any ongoing ramp is canceled.
     #[inline]
     pub fn set_scalar(&mut self, output: f32) {
-        self.output = Frame::splat(output);
+        self.set_value(Frame::splat(output));
+    }
+    /// Interpolate linearly to a new value over `ramp_time` seconds.
+    pub fn ramp_value(&mut self, target: Frame<f32, N>, ramp_time: f32) {
+        let samples = round(ramp_time as f64 * self.sample_rate) as usize;
+        if samples == 0 {
+            self.set_value(target);
+        } else {
+            let scale = 1.0 / samples as f32;
+            self.increment = Frame::generate(|i| (target[i] - self.output[i]) * scale);
+            self.target = target;
+            self.ramp_samples = samples;
+        }
+    }
+    /// Advance the ongoing ramp by one sample.
+    #[inline]
+    fn advance(&mut self) {
+        self.ramp_samples -= 1;
+        if self.ramp_samples == 0 {
+            self.output = self.target.clone();
+        } else {
+            self.output += self.increment.clone();
+        }
     }
 }
 
@@ -466,12 +670,36 @@ impl<N: Size<f32>> AudioNode for Constant<N> {
     type Inputs = U0;
     type Outputs = N;
 
+    fn reset(&mut self) {
+        // Finish any ongoing ramp.
+        self.output = self.target.clone();
+        self.ramp_samples = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+    }
+
     #[inline]
     fn tick(&mut self, _input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if self.ramp_samples > 0 {
+            self.advance();
+        }
         self.output.clone()
     }
 
     fn process(&mut self, size: usize, _input: &BufferRef, output: &mut BufferMut) {
+        if self.ramp_samples > 0 {
+            for i in 0..size {
+                if self.ramp_samples > 0 {
+                    self.advance();
+                }
+                for channel in 0..self.outputs() {
+                    output.set_f32(channel, i, self.output[channel]);
+                }
+            }
+            return;
+        }
         for channel in 0..self.outputs() {
             let channel_value = F32x::splat(self.output[channel].to_f32());
             for j in 0..simd_items(size) {
@@ -482,7 +710,7 @@ impl<N: Size<f32>> AudioNode for Constant<N> {
 
     fn set(&mut self, setting: Setting) {
         if let Parameter::Value(value) = setting.parameter() {
-            self.set_scalar(*value);
+            self.ramp_value(Frame::splat(*value), setting.ramp_time());
         }
     }
 
@@ -945,6 +1173,10 @@ where
         self.y.allocate();
     }
 
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.y.memory_usage()
+    }
+
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         let mut signal_x = self
             .x
@@ -970,6 +1202,17 @@ pub trait FrameUnop<N: Size<f32>>: Clone + Sync + Send {
     fn assign(&self, size: usize, x: &mut [f32]);
     /// Do unary op on signal.
     fn route(&self, x: Signal) -> Signal;
+    /// Do unary op in-place on the first `size` frames of `buffer`.
+    /// The default implementation processes one frame at a time.
+    fn block(&self, size: usize, buffer: &mut BufferVec) {
+        for i in 0..size {
+            let x = Frame::generate(|channel| buffer.at_f32(channel, i));
+            let y = self.frame(&x);
+            for channel in 0..N::USIZE {
+                buffer.set_f32(channel, i, y[channel]);
+            }
+        }
+    }
 }
 
 /// Negation operator.
@@ -1225,6 +1468,10 @@ where
         self.x.allocate();
     }
 
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
+
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         let mut signal_x = self.x.route(input, frequency);
         for i in 0..Self::Outputs::USIZE {
@@ -1279,6 +1526,66 @@ where
     }
 }
 
+/// Map channels with a function declared to be linear.
+/// The user promises that output `i` is input `i` multiplied by `gains[i]`,
+/// possibly followed by other linear time-invariant processing that does not affect the response.
+/// Signal flow analysis relies on the promise, so frequency responses propagate through.
+#[derive(Clone)]
+pub struct LinearMap<M, N, O>
+where
+    N: Size<f32>,
+{
+    f: M,
+    gains: Frame<f32, N>,
+    _marker: PhantomData<O>,
+}
+
+impl<M, N, O> LinearMap<M, N, O>
+where
+    M: Fn(&Frame<f32, N>) -> O + Clone + Send + Sync,
+    N: Size<f32>,
+    O: ConstantFrame<Sample = f32, Size = N>,
+{
+    /// Create a new linear map with per-channel `gains`.
+    pub fn new(f: M, gains: Frame<f32, N>) -> Self {
+        Self {
+            f,
+            gains,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Declared gain of channel `channel`.
+    #[inline]
+    pub fn gain(&self, channel: usize) -> f32 {
+        self.gains[channel]
+    }
+}
+
+impl<M, N, O> AudioNode for LinearMap<M, N, O>
+where
+    M: Fn(&Frame<f32, N>) -> O + Clone + Send + Sync,
+    N: Size<f32>,
+    O: ConstantFrame<Sample = f32, Size = N>,
+{
+    const ID: u64 = 122;
+    type Inputs = N;
+    type Outputs = N;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        (self.f)(input).frame()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        for i in 0..N::USIZE {
+            output.set(i, input.at(i).scale(self.gains[i].to_f64()));
+        }
+        output
+    }
+}
+
 /// Pipe the output of `X` to `Y`.
 #[derive(Clone)]
 pub struct Pipe<X, Y>
@@ -1351,6 +1658,11 @@ where
         self.y.tick(&self.x.tick(input))
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        self.y.tick64(&self.x.tick64(input))
+    }
+
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         let mut buffer = BufferArray::<X::Outputs>::uninitialized();
         self.x.process(size, input, &mut buffer.buffer_mut());
@@ -1374,6 +1686,10 @@ where
         self.y.allocate();
     }
 
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.y.memory_usage()
+    }
+
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         self.y.route(&self.x.route(input, frequency), frequency)
     }
@@ -1465,6 +1781,21 @@ where
         })
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        let input_x = &input[..X::Inputs::USIZE];
+        let input_y = &input[X::Inputs::USIZE..];
+        let output_x = self.x.tick64(input_x.into());
+        let output_y = self.y.tick64(input_y.into());
+        Frame::generate(|i| {
+            if i < X::Outputs::USIZE {
+                output_x[i]
+            } else {
+                output_y[i - X::Outputs::USIZE]
+            }
+        })
+    }
+
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         self.x.process(
             size,
@@ -1509,6 +1840,10 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.y.memory_usage()
+    }
 }
 
 /// Send the same input to `X` and `Y`. Concatenate outputs.
@@ -1591,6 +1926,19 @@ where
         })
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        let output_x = self.x.tick64(input);
+        let output_y = self.y.tick64(input);
+        Frame::generate(|i| {
+            if i < X::Outputs::USIZE {
+                output_x[i]
+            } else {
+                output_y[i - X::Outputs::USIZE]
+            }
+        })
+    }
+
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         self.x
             .process(size, input, &mut output.subset(0, X::Outputs::USIZE));
@@ -1628,6 +1976,10 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.y.memory_usage()
+    }
 }
 
 /// Mix together `X` and `Y` sourcing from the same inputs.
@@ -1704,6 +2056,13 @@ where
         output_x + output_y
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        let output_x = self.x.tick64(input);
+        let output_y = self.y.tick64(input);
+        output_x + output_y
+    }
+
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         let mut buffer = BufferArray::<X::Outputs>::uninitialized();
         self.x.process(size, input, output);
@@ -1745,6 +2104,10 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.y.memory_usage()
+    }
 }
 
 /// Pass through inputs without matching outputs.
@@ -1833,6 +2196,10 @@ impl<X: AudioNode> AudioNode for Thru<X> {
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
 }
 
 /// Mix together a bunch of similar nodes sourcing from the same inputs.
@@ -1947,6 +2314,10 @@ where
             x.allocate();
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.iter().map(|x| x.memory_usage()).sum()
+    }
 }
 
 /// Stack a bunch of similar nodes in parallel.
@@ -2082,6 +2453,10 @@ where
             x.allocate();
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.iter().map(|x| x.memory_usage()).sum()
+    }
 }
 
 /// Combine outputs of a bunch of similar nodes with a binary operation.
@@ -2220,6 +2595,10 @@ where
             x.allocate();
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.iter().map(|x| x.memory_usage()).sum()
+    }
 }
 
 /// Branch into a bunch of similar nodes in parallel.
@@ -2339,6 +2718,10 @@ where
             x.allocate();
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.iter().map(|x| x.memory_usage()).sum()
+    }
 }
 
 /// A pipeline of multiple nodes.
@@ -2443,6 +2826,10 @@ where
         }
     }
 
+    fn memory_usage(&self) -> usize {
+        self.x.iter().map(|x| x.memory_usage()).sum()
+    }
+
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         let mut output = self.x[0].route(input, frequency);
         for i in 1..self.x.len() {
@@ -7,18 +7,84 @@ use super::math::*;
 use super::setting::*;
 use super::signal::*;
 use super::*;
+use core::any::Any;
 use core::marker::PhantomData;
 use dyn_clone::DynClone;
 use num_complex::Complex64;
 extern crate alloc;
 use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
 use core::fmt::Write;
 
+/// Conversion to `Any` for downcasting to a concrete type.
+/// This is implemented for all `AudioUnit` types, so implementors get it for free.
+/// `Any` is available from `core`, so this works without `std`.
+pub trait AsAny {
+    /// Return `self` as `Any` for downcasting to its concrete type.
+    /// Boxed units (`Box<dyn AudioUnit>`) are always `'static` and can be downcast.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let unit: Box<dyn AudioUnit> = Box::new(lowpass_hz(1000.0, 1.0));
+    /// assert!(unit.as_any().downcast_ref::<An<FixedSvf<f64, LowpassMode<f64>>>>().is_some());
+    /// assert!(unit.as_any().downcast_ref::<Net>().is_none());
+    /// ```
+    fn as_any(&self) -> &dyn Any
+    where
+        Self: 'static;
+
+    /// Return `self` as mutable `Any` for downcasting to its concrete type.
+    fn as_any_mut(&mut self) -> &mut dyn Any
+    where
+        Self: 'static;
+}
+
+impl<T: AudioUnit> AsAny for T {
+    fn as_any(&self) -> &dyn Any
+    where
+        Self: 'static,
+    {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any
+    where
+        Self: 'static,
+    {
+        self
+    }
+}
+
+/// Largest number of channels that the default `AudioUnit::process64` keeps on the stack.
+const TICK64_FRAME: usize = 64;
+
+/// Process `size` samples in double precision by rounding them to `f32` and calling `tick`
+/// with the scratch frames `input_frame` and `output_frame`.
+fn tick64_frames<U: AudioUnit + ?Sized>(
+    unit: &mut U,
+    size: usize,
+    input: &[&[f64]],
+    output: &mut [&mut [f64]],
+    input_frame: &mut [f32],
+    output_frame: &mut [f32],
+) {
+    for i in 0..size {
+        for (x, channel) in input_frame.iter_mut().zip(input.iter()) {
+            *x = channel[i] as f32;
+        }
+        unit.tick(input_frame, output_frame);
+        for (x, channel) in output_frame.iter().zip(output.iter_mut()) {
+            channel[i] = *x as f64;
+        }
+    }
+}
+
 /// An audio processor with an object safe interface.
 /// Once constructed, it has a fixed number of inputs and outputs.
-pub trait AudioUnit: Send + Sync + DynClone {
+pub trait AudioUnit: Send + Sync + DynClone + AsAny {
     /// Reset the input state of the unit to an initial state where it has not processed any data.
     /// In other words, reset time to zero.
     fn reset(&mut self) {
@@ -42,10 +108,54 @@ pub trait AudioUnit: Send + Sync + DynClone {
     /// If `size` is zero then this is a no-op, which is permitted.
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut);
 
+    /// Process `size` samples in double precision.
+    /// Input and output are slices of channels, each at least `size` samples long.
+    /// Unlike `process`, there is no limit on `size`.
+    /// Units built from nodes keep double precision where the nodes support it
+    /// (see [`AudioNode::process64`]). Networks round to `f32` and process in blocks.
+    /// The default implementation, used by other dynamic units, rounds to `f32` and calls `tick`.
+    /// It keeps its frames on the stack for units with up to 64 inputs and outputs;
+    /// wider units allocate temporary frames, which is not real-time safe.
+    fn process64(&mut self, size: usize, input: &[&[f64]], output: &mut [&mut [f64]]) {
+        debug_assert!(input.len() == self.inputs());
+        debug_assert!(output.len() == self.outputs());
+        let (inputs, outputs) = (self.inputs(), self.outputs());
+        if inputs <= TICK64_FRAME && outputs <= TICK64_FRAME {
+            let mut input_frame = [0.0f32; TICK64_FRAME];
+            let mut output_frame = [0.0f32; TICK64_FRAME];
+            tick64_frames(
+                self,
+                size,
+                input,
+                output,
+                &mut input_frame[..inputs],
+                &mut output_frame[..outputs],
+            );
+        } else {
+            let mut input_frame = vec![0.0f32; inputs];
+            let mut output_frame = vec![0.0f32; outputs];
+            tick64_frames(
+                self,
+                size,
+                input,
+                output,
+                &mut input_frame,
+                &mut output_frame,
+            );
+        }
+    }
+
     /// Set a parameter. What formats are recognized depends on the component.
     #[allow(unused_variables)]
     fn set(&mut self, setting: Setting) {}
 
+    /// Set a parameter, interpolating to the new value over `ramp_time` seconds.
+    /// Nodes that do not support ramps apply the setting instantaneously.
+    /// See [`Setting`] for a list of nodes that support ramps.
+    fn set_ramped(&mut self, setting: Setting, ramp_time: f32) {
+        self.set(setting.ramp(ramp_time));
+    }
+
     /// Number of inputs to this unit.
     /// Equals size of the input argument in `tick` and `process`.
     /// This should be fixed after construction.
@@ -92,6 +202,15 @@ pub trait AudioUnit: Send + Sync + DynClone {
         // The default implementation does nothing.
     }
 
+    /// Heap memory used by buffers, delay lines and other allocations of this unit, in bytes.
+    /// This is a best-effort estimate that does not include `footprint`.
+    /// Units that contain other units return the sum of their children
+    /// plus their own buffers.
+    fn memory_usage(&self) -> usize {
+        // The default implementation reports no allocations.
+        0
+    }
+
     // End of interface. There is no need to override the following.
 
     /// Retrieve the next mono sample from a generator.
@@ -219,6 +338,90 @@ pub trait AudioUnit: Send + Sync + DynClone {
         self.response(output, frequency).map(|r| amp_db(r.norm()))
     }
 
+    /// Evaluate frequency response of `output` at each of `frequencies` (in Hz).
+    /// The input frame is built once and reused for every frequency,
+    /// which makes this suitable for plotting filter curves.
+    /// Only linear responses can be evaluated: entries are `None`
+    /// where there is no response or it could not be calculated.
+    ///
+    /// ### Example: Plotting A Lowpass Filter
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut filter = lowpass_hz(1000.0, 1.0);
+    /// let frequencies = [100.0, 1000.0, 10000.0];
+    /// let curve = filter.response_curve(0, &frequencies);
+    /// assert!((curve[0].unwrap().norm() - 1.0).abs() < 0.02);
+    /// assert!((curve[1].unwrap().norm() - 1.0).abs() < 0.01);
+    /// assert!(curve[2].unwrap().norm() < 0.02);
+    /// // Nonlinear nodes have no response.
+    /// assert_eq!(shape(Tanh(1.0)).response_curve(0, &frequencies), vec![None; 3]);
+    /// ```
+    fn response_curve(&mut self, output: usize, frequencies: &[f64]) -> Vec<Option<Complex64>> {
+        assert!(output < self.outputs());
+        let mut input = SignalFrame::new(self.inputs());
+        for i in 0..self.inputs() {
+            input.set(i, Signal::Response(Complex64::new(1.0, 0.0), 0.0));
+        }
+        frequencies
+            .iter()
+            .map(
+                |&frequency| match self.route(&input, frequency).at(output) {
+                    Signal::Response(rx, _) => Some(rx),
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+
+    /// Evaluate frequency response of `output` at each of `frequencies` (in Hz)
+    /// as pairs of magnitude (in dB) and phase (in radians).
+    /// Only linear responses can be evaluated: entries are `None`
+    /// where there is no response or it could not be calculated.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let curve = lowpass_hz(1000.0, 1.0).response_curve_db(0, &[1000.0]);
+    /// let (db, phase) = curve[0].unwrap();
+    /// assert!(db.abs() < 0.1);
+    /// assert!((phase + f64::PI / 2.0).abs() < 0.01);
+    /// ```
+    fn response_curve_db(&mut self, output: usize, frequencies: &[f64]) -> Vec<Option<(f64, f64)>> {
+        self.response_curve(output, frequencies)
+            .into_iter()
+            .map(|r| r.map(|r| (amp_db(r.norm()), r.arg())))
+            .collect()
+    }
+
+    /// Evaluate group delay of `output` at `frequency` Hz, in seconds.
+    /// Multiply by the sample rate to get the delay in samples.
+    /// Only linear responses can be evaluated.
+    /// Return `None` if there is no response or it could not be calculated.
+    ///
+    /// The group delay is the negative derivative of the phase response.
+    /// It is estimated with a central difference over a span of 0.02 Hz,
+    /// using the phase of the ratio of the two responses so that phase wraps
+    /// do not disturb the estimate. The estimate is unreliable near zeros of the response,
+    /// where the phase jumps, and aliases for delays longer than 25 seconds.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let delay = tick().group_delay(0, 1000.0).unwrap();
+    /// assert!((delay * DEFAULT_SR - 1.0).abs() < 1.0e-6);
+    /// ```
+    fn group_delay(&mut self, output: usize, frequency: f64) -> Option<f64> {
+        let f0 = max(frequency - 0.01, 0.0);
+        let f1 = frequency + 0.01;
+        let curve = self.response_curve(output, &[f0, f1]);
+        match (curve[0], curve[1]) {
+            (Some(r0), Some(r1)) if r0.norm() > 0.0 && r1.norm() > 0.0 => {
+                Some(-(r1 * r0.conj()).arg() / (f64::TAU * (f1 - f0)))
+            }
+            _ => None,
+        }
+    }
+
     /// Causal latency in (fractional) samples.
     /// After a reset, we can discard this many samples from the output to avoid incurring a pre-delay.
     /// The latency may depend on the sample rate.
@@ -383,7 +586,13 @@ where
     }
     #[inline]
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        #[cfg(not(feature = "scalar"))]
         self.0.process(size, input, output);
+        #[cfg(feature = "scalar")]
+        self.0.process_scalar(size, input, output);
+    }
+    fn process64(&mut self, size: usize, input: &[&[f64]], output: &mut [&mut [f64]]) {
+        self.0.process64(size, input, output);
     }
     #[inline]
     fn set(&mut self, setting: Setting) {
@@ -416,6 +625,9 @@ where
     fn allocate(&mut self) {
         self.0.allocate();
     }
+    fn memory_usage(&self) -> usize {
+        self.0.memory_usage()
+    }
 }
 
 /// Converts an AudioUnit into an AudioNode with `I` inputs and `O` outputs.
@@ -475,6 +687,10 @@ impl<I: Size<f32>, O: Size<f32>> AudioNode for Unit<I, O> {
     fn allocate(&mut self) {
         self.unit.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.unit.footprint() + self.unit.memory_usage()
+    }
 }
 
 /// A big block adapter.
@@ -557,6 +773,9 @@ impl AudioUnit for BigBlockAdapter {
     fn allocate(&mut self) {
         self.source.allocate();
     }
+    fn memory_usage(&self) -> usize {
+        self.source.memory_usage() + self.input.memory_usage() + self.output.memory_usage()
+    }
 }
 
 /// Block rate adapter converts all processing calls to maximum length block processing.
@@ -648,6 +867,9 @@ impl AudioUnit for BlockRateAdapter {
     fn allocate(&mut self) {
         self.unit.allocate();
     }
+    fn memory_usage(&self) -> usize {
+        self.unit.memory_usage() + self.buffer.memory_usage()
+    }
 }
 
 /// A dummy unit with zero output. It has an arbitrary number of inputs and outputs.
@@ -129,7 +129,12 @@ impl<F: Float> BiquadCoefs<F> {
 }
 
 /// 2nd order IIR filter implemented in normalized Direct Form I.
+/// Coefficient changes can be smoothed to avoid zipper noise:
+/// new coefficients are then interpolated linearly from the current ones
+/// over the smoothing time. Interpolation between stable filters stays stable,
+/// as the stability region of `(a1, a2)` is convex.
 /// - Setting: coefficients as tuple `Setting::biquad(a1, a2, b0, b1, b2)`.
+///   Supports ramped settings; settings without a ramp use the smoothing time.
 /// - Input 0: input signal.
 /// - Output 0: filtered signal.
 #[derive(Default, Clone)]
@@ -140,6 +145,14 @@ pub struct Biquad<F> {
     y1: F,
     y2: F,
     sample_rate: f64,
+    /// Smoothing time in seconds for settings without a ramp.
+    smoothing: f32,
+    /// Target coefficients of the ongoing ramp.
+    target: BiquadCoefs<F>,
+    /// Per sample coefficient increments of the ongoing ramp.
+    increment: BiquadCoefs<F>,
+    /// Number of samples left in the ongoing ramp.
+    ramp_samples: usize,
 }
 
 impl<F: Float> Biquad<F> {
@@ -159,8 +172,68 @@ impl<F: Float> Biquad<F> {
     pub fn coefs(&self) -> &BiquadCoefs<F> {
         &self.coefs
     }
+    /// Set coefficients immediately. Any ongoing ramp is canceled.
     pub fn set_coefs(&mut self, coefs: BiquadCoefs<F>) {
         self.coefs = coefs;
+        self.ramp_samples = 0;
+    }
+    /// Interpolate linearly to new coefficients over `ramp_time` seconds.
+    pub fn ramp_coefs(&mut self, coefs: BiquadCoefs<F>, ramp_time: f32) {
+        let samples = round(ramp_time as f64 * self.sample_rate) as usize;
+        if samples == 0 {
+            self.set_coefs(coefs);
+        } else {
+            let scale = F::from_f64(1.0 / samples as f64);
+            self.increment = BiquadCoefs::arbitrary(
+                (coefs.a1 - self.coefs.a1) * scale,
+                (coefs.a2 - self.coefs.a2) * scale,
+                (coefs.b0 - self.coefs.b0) * scale,
+                (coefs.b1 - self.coefs.b1) * scale,
+                (coefs.b2 - self.coefs.b2) * scale,
+            );
+            self.target = coefs;
+            self.ramp_samples = samples;
+        }
+    }
+    /// Smoothing time in seconds for coefficient settings without a ramp.
+    pub fn smoothing(&self) -> f32 {
+        self.smoothing
+    }
+    /// Set smoothing time in seconds (`time` >= 0) for coefficient settings without a ramp.
+    /// For example, 0.01 removes zipper noise from sweeps updated once per block.
+    /// The default is zero, which applies settings immediately.
+    pub fn set_smoothing(&mut self, time: f32) {
+        assert!(time >= 0.0);
+        self.smoothing = time;
+    }
+    /// Filter one sample.
+    #[inline]
+    fn filter(&mut self, x0: F) -> F {
+        if self.ramp_samples > 0 {
+            self.advance();
+        }
+        let y0 = self.coefs.b0 * x0 + self.coefs.b1 * self.x1 + self.coefs.b2 * self.x2
+            - self.coefs.a1 * self.y1
+            - self.coefs.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y0;
+        y0
+    }
+    /// Advance the ongoing ramp by one sample.
+    #[inline]
+    fn advance(&mut self) {
+        self.ramp_samples -= 1;
+        if self.ramp_samples == 0 {
+            self.coefs = self.target;
+        } else {
+            self.coefs.a1 += self.increment.a1;
+            self.coefs.a2 += self.increment.a2;
+            self.coefs.b0 += self.increment.b0;
+            self.coefs.b1 += self.increment.b1;
+            self.coefs.b2 += self.increment.b2;
+        }
     }
 }
 
@@ -182,26 +255,31 @@ impl<F: Float> AudioNode for Biquad<F> {
 
     #[inline]
     fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
-        let x0 = convert(input[0]);
-        let y0 = self.coefs.b0 * x0 + self.coefs.b1 * self.x1 + self.coefs.b2 * self.x2
-            - self.coefs.a1 * self.y1
-            - self.coefs.a2 * self.y2;
-        self.x2 = self.x1;
-        self.x1 = x0;
-        self.y2 = self.y1;
-        self.y1 = y0;
-        [convert(y0)].into()
+        [convert(self.filter(convert(input[0])))].into()
+    }
+
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        [convert(self.filter(convert(input[0])))].into()
     }
 
     fn set(&mut self, setting: Setting) {
         if let Parameter::Biquad(a1, a2, b0, b1, b2) = setting.parameter() {
-            self.set_coefs(BiquadCoefs::arbitrary(
-                F::from_f32(*a1),
-                F::from_f32(*a2),
-                F::from_f32(*b0),
-                F::from_f32(*b1),
-                F::from_f32(*b2),
-            ));
+            let ramp_time = if setting.ramp_time() > 0.0 {
+                setting.ramp_time()
+            } else {
+                self.smoothing
+            };
+            self.ramp_coefs(
+                BiquadCoefs::arbitrary(
+                    F::from_f32(*a1),
+                    F::from_f32(*a2),
+                    F::from_f32(*b0),
+                    F::from_f32(*b1),
+                    F::from_f32(*b2),
+                ),
+                ramp_time,
+            );
         }
     }
 
@@ -277,6 +355,17 @@ impl<F: Real, N: Size<f32>> AudioNode for ButterLowpass<F, N> {
         self.biquad.tick(&[input[0]].into())
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        if N::USIZE > 1 {
+            let cutoff: F = convert(input[1]);
+            if cutoff != self.cutoff {
+                self.set_cutoff(cutoff);
+            }
+        }
+        self.biquad.tick64(&[input[0]].into())
+    }
+
     fn set(&mut self, setting: Setting) {
         if let Parameter::Center(cutoff) = setting.parameter() {
             self.set_cutoff(F::from_f32(*cutoff));
@@ -366,6 +455,21 @@ impl<F: Real, N: Size<f32>> AudioNode for Resonator<F, N> {
         self.biquad.tick(&[input[0]].into())
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        if N::USIZE >= 3 {
+            let center: F = convert(input[1]);
+            let q: F = convert(input[2]);
+            if center != self.center || q != self.q {
+                self.biquad
+                    .set_coefs(BiquadCoefs::resonator(self.sample_rate, center, q));
+                self.center = center;
+                self.q = q;
+            }
+        }
+        self.biquad.tick64(&[input[0]].into())
+    }
+
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         let mut output = SignalFrame::new(self.outputs());
         output.set(
@@ -2,9 +2,14 @@
 
 use super::audionode::*;
 use super::biquad::*;
+use super::math::*;
 use super::setting::*;
 use super::signal::*;
 use super::*;
+use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// 2nd order IIR filter bank implemented in normalized Direct Form I and SIMD.
 /// - Setting channel `i` coefficients: `Setting::biquad(a1, a2, b0, b1, b2).index(i)`.
@@ -115,3 +120,160 @@ where
         output
     }
 }
+
+/// Bandwidth in Hz of a resonator that decays by 60 dB in one second.
+/// A resonator with bandwidth `B` Hz decays by 60 dB in `RESONATOR_T60_BANDWIDTH / B` seconds.
+pub const RESONATOR_T60_BANDWIDTH: f32 = 2.1988068;
+
+/// Bank of constant-gain bandpass resonators tuned to a chord, excited by the input.
+/// The resonators run in SIMD accelerated biquad banks.
+/// The bandwidth of each resonator in Hz is `bandwidth + RESONATOR_T60_BANDWIDTH / decay`:
+/// `bandwidth` widens the resonances and `decay` is the time in seconds
+/// it takes a resonance to decay by 60 dB when `bandwidth` is zero.
+/// The output is the sum of the resonator outputs divided by the square root of
+/// the number of resonators, so that the output level of a noise excited chord
+/// is independent of the number of notes.
+/// - Allocates: the filter banks.
+/// - Setting: decay time in seconds (`Setting::time`).
+/// - Input 0: excitation
+/// - Output 0: resonant body
+#[derive(Clone)]
+pub struct ResonatorChord {
+    frequencies: Vec<f32>,
+    bandwidth: f32,
+    decay: f32,
+    banks: Vec<BiquadBank<F32x>>,
+    sample_rate: f64,
+}
+
+impl ResonatorChord {
+    /// Create a new resonator chord with resonances at `frequencies` in Hz (at least one),
+    /// extra `bandwidth` in Hz and 60 dB `decay` time in seconds.
+    pub fn new(frequencies: &[f32], bandwidth: f32, decay: f32) -> Self {
+        assert!(!frequencies.is_empty() && bandwidth >= 0.0 && decay > 0.0);
+        let mut node = Self {
+            frequencies: frequencies.to_vec(),
+            bandwidth,
+            decay,
+            banks: vec![BiquadBank::new(); simd_items(frequencies.len())],
+            sample_rate: DEFAULT_SR,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Resonance frequencies in Hz.
+    pub fn frequencies(&self) -> &[f32] {
+        &self.frequencies
+    }
+
+    /// Extra bandwidth in Hz.
+    pub fn bandwidth(&self) -> f32 {
+        self.bandwidth
+    }
+
+    /// Decay time to -60 dB in seconds (with zero extra bandwidth).
+    pub fn decay(&self) -> f32 {
+        self.decay
+    }
+
+    /// Set decay time to -60 dB in seconds (with zero extra bandwidth).
+    pub fn set_decay(&mut self, decay: f32) {
+        assert!(decay > 0.0);
+        self.decay = decay;
+        self.calculate_coefs();
+    }
+
+    /// Total bandwidth of each resonator in Hz.
+    #[inline]
+    fn resonator_bandwidth(&self) -> f32 {
+        self.bandwidth + RESONATOR_T60_BANDWIDTH / self.decay
+    }
+
+    /// Output gain applied to the sum of the resonators.
+    #[inline]
+    fn gain(&self) -> f32 {
+        1.0 / sqrt(self.frequencies.len() as f32)
+    }
+
+    fn calculate_coefs(&mut self) {
+        let bandwidth = self.resonator_bandwidth();
+        for j in 0..self.banks.len() {
+            // Unused lanes stay silent.
+            let mut coefs = BiquadCoefs::<F32x>::default();
+            for k in 0..min(SIMD_N, self.frequencies.len() - j * SIMD_N) {
+                let center = self.frequencies[j * SIMD_N + k];
+                let c = BiquadCoefs::resonator(self.sample_rate as f32, center, center / bandwidth);
+                coefs.a1.set(k, c.a1);
+                coefs.a2.set(k, c.a2);
+                coefs.b0.set(k, c.b0);
+                coefs.b1.set(k, c.b1);
+                coefs.b2.set(k, c.b2);
+            }
+            self.banks[j].set_coefs(coefs);
+        }
+    }
+}
+
+impl AudioNode for ResonatorChord {
+    const ID: u64 = 153;
+    type Inputs = U1;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        for bank in self.banks.iter_mut() {
+            bank.reset();
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        for bank in self.banks.iter_mut() {
+            bank.set_sample_rate(sample_rate);
+        }
+        self.calculate_coefs();
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let x = Frame::splat(input[0]);
+        let mut output = 0.0;
+        for bank in self.banks.iter_mut() {
+            // Unused lanes are silent, so we can sum all of them.
+            output += bank.tick(&x).iter().sum::<f32>();
+        }
+        [output * self.gain()].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Time(decay) = setting.parameter() {
+            self.set_decay(*decay);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut response = Complex64::new(0.0, 0.0);
+        for bank in self.banks.iter() {
+            let coefs = bank.coefs();
+            for k in 0..SIMD_N {
+                response += BiquadCoefs::<f32>::arbitrary(
+                    coefs.a1.get(k),
+                    coefs.a2.get(k),
+                    coefs.b0.get(k),
+                    coefs.b1.get(k),
+                    coefs.b2.get(k),
+                )
+                .response(frequency / self.sample_rate);
+            }
+        }
+        let response = response * self.gain() as f64;
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).filter(0.0, |r| r * response));
+        output
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.frequencies.capacity() * core::mem::size_of::<f32>()
+            + self.banks.capacity() * core::mem::size_of::<BiquadBank<F32x>>()
+    }
+}
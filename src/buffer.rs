@@ -1,4 +1,64 @@
 //! SIMD accelerated audio buffers for block processing.
+//!
+//! Blocks hold up to 64 (`MAX_BUFFER_SIZE`) samples per channel. Channels are stored
+//! non-interleaved, one after another, each as 8 (`SIMD_LEN`) SIMD elements of type `F32x`.
+//! Each SIMD element contains 8 (`SIMD_N`) successive `f32` samples, so sample `i` of a channel
+//! is lane `i & SIMD_M` of element `i >> SIMD_S`. Every channel starts at a SIMD element boundary
+//! and the elements are aligned to their size (32 bytes), so channels can be processed
+//! with aligned SIMD loads and stores. When processing a partial block of `size` samples,
+//! the first `simd_items(size)` elements of each channel are valid; lanes past `size`
+//! in the last element may be processed but their values are ignored.
+//!
+//! `BlockBuffer` is an owned block buffer for custom nodes that need scratch space.
+//! It has the same layout as the buffers passed to `AudioNode::process`
+//! and is an alias of `BufferVec` (heap allocated, channel count set at runtime).
+//! `BufferArray` stores the same layout inline when the channel count is known at compile time.
+//! Allocate the buffer when the node is created, not in `process`.
+//!
+//! ### Example: Custom Node With Scratch Buffer
+//! ```
+//! use fundsp::hacker32::*;
+//!
+//! /// Adds the output of filter `x` to the dry signal.
+//! #[derive(Clone)]
+//! struct AddDry<X: AudioNode<Inputs = U1, Outputs = U1>> {
+//!     x: X,
+//!     scratch: BlockBuffer,
+//! }
+//!
+//! impl<X: AudioNode<Inputs = U1, Outputs = U1>> AudioNode for AddDry<X> {
+//!     const ID: u64 = 0x1000;
+//!     type Inputs = U1;
+//!     type Outputs = U1;
+//!
+//!     fn reset(&mut self) {
+//!         self.x.reset();
+//!     }
+//!
+//!     fn set_sample_rate(&mut self, sample_rate: f64) {
+//!         self.x.set_sample_rate(sample_rate);
+//!     }
+//!
+//!     fn tick(&mut self, input: &Frame<f32, U1>) -> Frame<f32, U1> {
+//!         [input[0] + self.x.tick(input)[0]].into()
+//!     }
+//!
+//!     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+//!         self.x.process(size, input, &mut self.scratch.buffer_mut());
+//!         for i in 0..simd_items(size) {
+//!             output.set(0, i, input.at(0, i) + self.scratch.at(0, i));
+//!         }
+//!     }
+//! }
+//!
+//! let x = lowpass_hz(1000.0, 1.0).0;
+//! let scratch = BlockBuffer::new(1);
+//! assert_eq!(scratch.channel(0).as_ptr() as usize % 32, 0);
+//! let mut node = An(AddDry { x, scratch });
+//! let mut output = BufferVec::new(1);
+//! node.process(MAX_BUFFER_SIZE, &BufferVec::new(1).buffer_ref(), &mut output.buffer_mut());
+//! assert_eq!(output.at_f32(0, 0), 0.0);
+//! ```
 
 use super::*;
 extern crate alloc;
@@ -185,8 +245,15 @@ impl<'a> BufferRef<'a> {
     }
 }
 
+/// Block buffer for custom nodes. Create it with `BlockBuffer::new(channels)`
+/// when the node is created and borrow it with `buffer_ref` and `buffer_mut` in `process`.
+/// Each channel holds 64 (`MAX_BUFFER_SIZE`) samples as 8 (`SIMD_LEN`) SIMD elements
+/// of type `F32x`, aligned to 32 bytes, in the layout described in the module documentation.
+pub type BlockBuffer = BufferVec;
+
 /// An owned buffer on the heap with an arbitrary number of channels
 /// containing 64 (`MAX_BUFFER_SIZE`) samples per channel. Samples are stored non-interleaved.
+/// Suitable as block scratch space for custom nodes; see `BlockBuffer`.
 #[derive(Clone, Default)]
 pub struct BufferVec {
     buffer: Vec<F32x>,
@@ -219,6 +286,12 @@ impl BufferVec {
         SIMD_LEN
     }
 
+    /// Heap memory used by this buffer in bytes.
+    #[inline]
+    pub fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<F32x>()
+    }
+
     /// Access value at index `i` (0 <= `i` <= 7) of `channel`.
     #[inline]
     pub fn at(&self, channel: usize, i: usize) -> F32x {
@@ -308,6 +381,7 @@ impl BufferVec {
 /// Samples are stored non-interleaved.
 /// The number of channels must be known at compile time:
 /// the size `N` is given as a type-level integer (`U0`, `U1`, ...).
+/// Suitable as block scratch space for custom nodes; see the module documentation for the layout.
 #[repr(C)]
 #[derive(Clone, Default)]
 pub struct BufferArray<N: ArrayLength> {
@@ -0,0 +1,545 @@
+//! Bypass, wet/dry and retrigger wrappers.
+
+use super::audionode::*;
+use super::buffer::*;
+use super::math::*;
+use super::pan::*;
+use super::setting::*;
+use super::shared::*;
+use super::signal::*;
+use super::*;
+use num_complex::Complex64;
+use numeric_array::typenum::{Sum, Unsigned, U1};
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Crossfade time in seconds when the bypass flag changes.
+const BYPASS_FADE_TIME: f32 = 0.01;
+
+/// Runtime bypass of an effect. When the shared flag is engaged (> 0.5),
+/// the dry input is passed through; otherwise, the output of the inner node.
+/// Toggling the flag crossfades over 10 ms to avoid clicks.
+/// The inner node must have an equal number of inputs and outputs.
+///
+/// By default, the inner node keeps running while bypassed, so its state is warm
+/// and there is no glitch when it is re-enabled. The CPU cost is then the same
+/// whether bypassed or not. In freeze mode, the inner node is paused while bypassed:
+/// this saves its processing cost but it resumes from its frozen state.
+///
+/// Settings are forwarded to the inner node.
+/// - Input(s): signal.
+/// - Output(s): processed or dry signal.
+#[derive(Clone)]
+pub struct Bypass<N, X>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    x: X,
+    /// Bypass flag.
+    flag: Shared,
+    /// Whether the inner node is paused while bypassed.
+    freeze: bool,
+    /// Current amount of bypass in 0...1. Bypass is complete at 1.
+    amount: f32,
+    /// Crossfade step per sample.
+    fade_step: f32,
+}
+
+impl<N, X> Bypass<N, X>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    /// Create a new bypass wrapper controlled by `flag`.
+    /// If `freeze` is true, the inner node is paused while bypassed.
+    pub fn new(x: X, flag: &Shared, freeze: bool) -> Self {
+        let mut node = Self {
+            x,
+            flag: flag.clone(),
+            freeze,
+            amount: 0.0,
+            fade_step: 0.0,
+        };
+        node.reset();
+        node.set_sample_rate(DEFAULT_SR);
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node
+    }
+
+    /// Whether the bypass flag is currently engaged.
+    #[inline]
+    pub fn is_bypassed(&self) -> bool {
+        self.flag.value() > 0.5
+    }
+
+    /// Whether the inner node is paused while bypassed.
+    #[inline]
+    pub fn freeze(&self) -> bool {
+        self.freeze
+    }
+
+    /// Set freeze mode. If `freeze` is true, the inner node is paused while bypassed.
+    #[inline]
+    pub fn set_freeze(&mut self, freeze: bool) {
+        self.freeze = freeze;
+    }
+
+    /// Target bypass amount from the flag.
+    #[inline]
+    fn target(&self) -> f32 {
+        if self.is_bypassed() {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<N, X> AudioNode for Bypass<N, X>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    const ID: u64 = 136;
+    type Inputs = N;
+    type Outputs = N;
+
+    fn reset(&mut self) {
+        self.x.reset();
+        self.amount = self.target();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.x.set_sample_rate(sample_rate);
+        self.fade_step = 1.0 / (BYPASS_FADE_TIME * sample_rate as f32);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let target = self.target();
+        if self.amount == target && target == 1.0 {
+            if !self.freeze {
+                self.x.tick(input);
+            }
+            return input.clone();
+        }
+        let output = self.x.tick(input);
+        if self.amount == target {
+            return output;
+        }
+        self.amount = if target > self.amount {
+            min(target, self.amount + self.fade_step)
+        } else {
+            max(target, self.amount - self.fade_step)
+        };
+        let weight = smooth5(self.amount);
+        Frame::generate(|i| output[i] + (input[i] - output[i]) * weight)
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        let target = self.target();
+        if self.amount != target {
+            for i in 0..size {
+                let frame = Frame::generate(|channel| input.at_f32(channel, i));
+                let output_frame = self.tick(&frame);
+                for channel in 0..N::USIZE {
+                    output.set_f32(channel, i, output_frame[channel]);
+                }
+            }
+            return;
+        }
+        if target < 1.0 || !self.freeze {
+            self.x.process(size, input, output);
+        }
+        if target == 1.0 {
+            for channel in 0..N::USIZE {
+                output.channel_mut(channel)[..simd_items(size)]
+                    .copy_from_slice(&input.channel(channel)[..simd_items(size)]);
+            }
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        self.x.set(setting);
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        if self.is_bypassed() {
+            input.clone()
+        } else {
+            self.x.route(input, frequency)
+        }
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
+}
+
+/// Wet/dry mix of an effect. The effect output is mixed with the dry input
+/// according to a crossfade law. With `CrossfadeLaw::Power`, the dry and wet gains are
+/// `cos(mix * pi / 2)` and `sin(mix * pi / 2)`, so the total power of uncorrelated
+/// signals stays constant across the mix. With `CrossfadeLaw::Linear`, the gains are
+/// `1 - mix` and `mix`, which suits effects that are coherent with the dry signal.
+/// Mix 0 is fully dry and mix 1 is fully wet.
+/// The effect must have an equal number of inputs and outputs.
+///
+/// The dry path is delayed by the latency reported by the effect (rounded to the nearest sample)
+/// so the two paths stay aligned. The latency is measured when the sample rate is set.
+///
+/// The mix is set with `Setting::value`, which supports ramps.
+/// Other settings are forwarded to the effect.
+/// - Input(s): signal.
+/// - Output(s): mixed signal.
+#[derive(Clone)]
+pub struct WetDry<N, X>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    x: X,
+    /// Current mix in 0...1.
+    mix: f32,
+    /// Target mix of an ongoing ramp.
+    target: f32,
+    /// Mix increment per sample during a ramp.
+    increment: f32,
+    /// Number of samples remaining in the ramp.
+    ramp_samples: usize,
+    /// Dry gain.
+    dry: f32,
+    /// Wet gain.
+    wet: f32,
+    /// Crossfade law.
+    law: CrossfadeLaw,
+    sample_rate: f64,
+    /// Dry path delay line for latency compensation.
+    line: Vec<Frame<f32, N>>,
+    /// Next index to read and write in the delay line.
+    index: usize,
+}
+
+impl<N, X> WetDry<N, X>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    /// Create a new wet/dry mixer of effect `x` with `mix` in 0...1 and crossfade `law`.
+    pub fn new(x: X, mix: f32, law: CrossfadeLaw) -> Self {
+        let mut node = Self {
+            x,
+            mix: 0.0,
+            target: 0.0,
+            increment: 0.0,
+            ramp_samples: 0,
+            dry: 1.0,
+            wet: 0.0,
+            law,
+            sample_rate: DEFAULT_SR,
+            line: Vec::new(),
+            index: 0,
+        };
+        node.set_mix(mix);
+        node.set_sample_rate(DEFAULT_SR);
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node
+    }
+
+    /// Current mix in 0...1.
+    #[inline]
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    /// Set mix in 0...1. Any ongoing ramp is canceled.
+    #[inline]
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = clamp01(mix);
+        self.target = self.mix;
+        self.ramp_samples = 0;
+        self.update_gains();
+    }
+
+    /// Interpolate linearly to a new `mix` over `ramp_time` seconds.
+    pub fn ramp_mix(&mut self, mix: f32, ramp_time: f32) {
+        let samples = round(ramp_time as f64 * self.sample_rate) as usize;
+        if samples == 0 {
+            self.set_mix(mix);
+        } else {
+            self.target = clamp01(mix);
+            self.increment = (self.target - self.mix) / samples as f32;
+            self.ramp_samples = samples;
+        }
+    }
+
+    /// Crossfade law.
+    #[inline]
+    pub fn law(&self) -> CrossfadeLaw {
+        self.law
+    }
+
+    /// Dry path delay in samples.
+    #[inline]
+    pub fn dry_delay(&self) -> usize {
+        self.line.len()
+    }
+
+    #[inline]
+    fn update_gains(&mut self) {
+        (self.dry, self.wet) = self.law.weights(self.mix);
+    }
+
+    /// Advance the mix ramp, if any, by one sample.
+    #[inline]
+    fn advance(&mut self) {
+        if self.ramp_samples > 0 {
+            self.ramp_samples -= 1;
+            self.mix = if self.ramp_samples == 0 {
+                self.target
+            } else {
+                self.mix + self.increment
+            };
+            self.update_gains();
+        }
+    }
+
+    /// Delay the dry signal.
+    #[inline]
+    fn delay_dry(&mut self, input: &Frame<f32, N>) -> Frame<f32, N> {
+        if self.line.is_empty() {
+            return input.clone();
+        }
+        let output = core::mem::replace(&mut self.line[self.index], input.clone());
+        self.index += 1;
+        if self.index == self.line.len() {
+            self.index = 0;
+        }
+        output
+    }
+}
+
+impl<N, X> AudioNode for WetDry<N, X>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    const ID: u64 = 137;
+    type Inputs = N;
+    type Outputs = N;
+
+    fn reset(&mut self) {
+        self.x.reset();
+        self.line.fill(Frame::default());
+        self.index = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.x.set_sample_rate(sample_rate);
+        let delay = round(self.x.latency().unwrap_or_default().max(0.0)) as usize;
+        self.line = vec![Frame::default(); delay];
+        self.index = 0;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let output = self.x.tick(input);
+        let dry = self.delay_dry(input);
+        self.advance();
+        Frame::generate(|i| output[i] * self.wet + dry[i] * self.dry)
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        self.x.process(size, input, output);
+        for i in 0..size {
+            let dry = self.delay_dry(&Frame::generate(|channel| input.at_f32(channel, i)));
+            self.advance();
+            for channel in 0..N::USIZE {
+                output.set_f32(
+                    channel,
+                    i,
+                    output.at_f32(channel, i) * self.wet + dry[channel] * self.dry,
+                );
+            }
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Value(mix) => self.ramp_mix(*mix, setting.ramp_time()),
+            _ => self.x.set(setting),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let wet = self.x.route(input, frequency);
+        let delay = self.line.len() as f64;
+        let phase = Complex64::from_polar(
+            1.0,
+            -core::f64::consts::TAU * frequency / self.sample_rate * delay,
+        );
+        let (wet_gain, dry_gain) = (self.wet as f64, self.dry as f64);
+        let mut output = SignalFrame::new(self.outputs());
+        for i in 0..N::USIZE {
+            let dry = input.at(i).filter(delay, |r| r * phase);
+            output.set(
+                i,
+                wet.at(i).combine_linear(
+                    dry,
+                    0.0,
+                    |x, y| x * wet_gain + y * dry_gain,
+                    |x, y| x * wet_gain + y * dry_gain,
+                ),
+            );
+        }
+        output
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.line.capacity() * core::mem::size_of::<Frame<f32, N>>()
+    }
+}
+
+/// Trigger threshold of `Triggered`. A rising edge is detected
+/// when the trigger input moves from at or below the threshold to above it.
+const TRIGGER_THRESHOLD: f32 = 0.5;
+
+/// Retrigger wrapper. A rising edge on the trigger input resets the inner node,
+/// so any generator (envelope, pluck, sample player) can be restarted from a sequence.
+/// A rising edge is detected when the trigger moves from at or below 0.5 to above 0.5,
+/// so both gates and impulses work as triggers. The inner node is reset
+/// before it processes the triggering sample.
+///
+/// Settings are forwarded to the inner node.
+/// - Input(s): inputs of the inner node followed by the trigger.
+/// - Output(s): outputs of the inner node.
+#[derive(Clone)]
+pub struct Triggered<X>
+where
+    X: AudioNode,
+    X::Inputs: Add<U1>,
+    <X::Inputs as Add<U1>>::Output: Size<f32>,
+{
+    x: X,
+    /// Previous trigger input.
+    previous: f32,
+}
+
+impl<X> Triggered<X>
+where
+    X: AudioNode,
+    X::Inputs: Add<U1>,
+    <X::Inputs as Add<U1>>::Output: Size<f32>,
+{
+    /// Create a new retrigger wrapper for node `x`.
+    pub fn new(x: X) -> Self {
+        let mut node = Self { x, previous: 0.0 };
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node
+    }
+
+    /// Whether `trigger` is a rising edge. Updates the previous trigger value.
+    #[inline]
+    fn edge(&mut self, trigger: f32) -> bool {
+        let edge = self.previous <= TRIGGER_THRESHOLD && trigger > TRIGGER_THRESHOLD;
+        self.previous = trigger;
+        edge
+    }
+}
+
+impl<X> AudioNode for Triggered<X>
+where
+    X: AudioNode,
+    X::Inputs: Add<U1>,
+    <X::Inputs as Add<U1>>::Output: Size<f32>,
+{
+    const ID: u64 = 144;
+    type Inputs = Sum<X::Inputs, U1>;
+    type Outputs = X::Outputs;
+
+    fn reset(&mut self) {
+        self.x.reset();
+        self.previous = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.x.set_sample_rate(sample_rate);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if self.edge(input[X::Inputs::USIZE]) {
+            self.x.reset();
+        }
+        self.x.tick(&Frame::generate(|i| input[i]))
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        let trigger = X::Inputs::USIZE;
+        let previous = self.previous;
+        let mut quiet = true;
+        for i in 0..size {
+            if self.edge(input.at_f32(trigger, i)) {
+                quiet = false;
+                break;
+            }
+        }
+        if quiet {
+            self.x
+                .process(size, &input.subset(0, X::Inputs::USIZE), output);
+            return;
+        }
+        // A trigger occurs in this block: process sample by sample.
+        self.previous = previous;
+        for i in 0..size {
+            let frame = Frame::generate(|channel| input.at_f32(channel, i));
+            let output_frame = self.tick(&frame);
+            for channel in 0..X::Outputs::USIZE {
+                output.set_f32(channel, i, output_frame[channel]);
+            }
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        self.x.set(setting);
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        // The trigger only resets `x`, so report the response and latency of `x`.
+        self.x
+            .route(&SignalFrame::copy(input, 0, X::Inputs::USIZE), frequency)
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
+}
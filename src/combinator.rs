@@ -209,13 +209,28 @@ impl<X: AudioNode> An<X> {
     }
     #[inline(always)]
     pub fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        #[cfg(not(feature = "scalar"))]
         self.0.process(size, input, output);
+        #[cfg(feature = "scalar")]
+        self.0.process_scalar(size, input, output);
+    }
+    #[inline(always)]
+    pub fn tick64(&mut self, input: &Frame<f64, X::Inputs>) -> Frame<f64, X::Outputs> {
+        self.0.tick64(input)
+    }
+    #[inline(always)]
+    pub fn process64(&mut self, size: usize, input: &[&[f64]], output: &mut [&mut [f64]]) {
+        self.0.process64(size, input, output);
     }
     #[inline(always)]
     pub fn set(&mut self, setting: Setting) {
         self.0.set(setting);
     }
     #[inline(always)]
+    pub fn set_ramped(&mut self, setting: Setting, ramp_time: f32) {
+        self.0.set_ramped(setting, ramp_time);
+    }
+    #[inline(always)]
     pub fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         self.0.route(input, frequency)
     }
@@ -10,8 +10,45 @@ use core::marker::PhantomData;
 use num_complex::Complex64;
 use numeric_array::typenum::*;
 extern crate alloc;
+use alloc::vec;
 use alloc::vec::Vec;
 
+/// Load `SIMD_N` consecutive samples from circular `buffer` starting at index `i`.
+/// The length of the buffer must be a power of two.
+#[inline]
+fn load_window(buffer: &[f32], i: usize) -> F32x {
+    if i + SIMD_N <= buffer.len() {
+        F32x::new(buffer[i..i + SIMD_N].try_into().unwrap())
+    } else {
+        let mask = buffer.len() - 1;
+        F32x::new(core::array::from_fn(|k| buffer[(i + k) & mask]))
+    }
+}
+
+/// Whether the first `size` samples of `x` are all equal.
+#[inline]
+fn is_constant(x: &[f32], size: usize) -> bool {
+    x[..size].iter().all(|&y| y == x[0])
+}
+
+/// Copy `output.len()` samples from circular `buffer` starting at index `i`.
+#[inline]
+fn read_ring(buffer: &[f32], i: usize, output: &mut [f32]) {
+    let n = min(output.len(), buffer.len() - i);
+    output[..n].copy_from_slice(&buffer[i..i + n]);
+    let rest = output.len() - n;
+    output[n..].copy_from_slice(&buffer[..rest]);
+}
+
+/// Copy `input` into circular `buffer` starting at index `i`.
+#[inline]
+fn write_ring(buffer: &mut [f32], i: usize, input: &[f32]) {
+    let n = min(input.len(), buffer.len() - i);
+    buffer[i..i + n].copy_from_slice(&input[..n]);
+    let rest = input.len() - n;
+    buffer[..rest].copy_from_slice(&input[n..]);
+}
+
 /// Single sample delay with `N` channels.
 /// - Input(s): input signal.
 /// - Output(s): input signal delayed by one sample.
@@ -102,6 +139,10 @@ impl AudioNode for Delay {
         self.buffer.fill(0.0);
     }
 
+    fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<f32>()
+    }
+
     fn set_sample_rate(&mut self, sample_rate: f64) {
         if self.sample_rate != sample_rate {
             self.sample_rate = sample_rate;
@@ -123,6 +164,28 @@ impl AudioNode for Delay {
         [output].into()
     }
 
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        let input = &input.channel_f32(0)[..size];
+        let output = &mut output.channel_f32_mut(0)[..size];
+        let delay = self.time_in_samples;
+        if delay == 0 {
+            output.copy_from_slice(input);
+            return;
+        }
+        let length = self.buffer.len();
+        let mut k = 0;
+        while k < size {
+            // Within a chunk of at most `delay` samples, the samples we read
+            // are older than any we write, so we can read first and then write.
+            let n = min(size - k, delay);
+            let read_i = if self.i + 1 == length { 0 } else { self.i + 1 };
+            read_ring(&self.buffer, read_i, &mut output[k..k + n]);
+            write_ring(&mut self.buffer, self.i, &input[k..k + n]);
+            self.i = (self.i + n) % length;
+            k += n;
+        }
+    }
+
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         let mut output = SignalFrame::new(self.outputs());
         output.set(
@@ -138,6 +201,102 @@ impl AudioNode for Delay {
     }
 }
 
+/// Channel that is delayed in the Haas effect.
+/// The sound appears to come from the opposite side.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum HaasSide {
+    /// Delay the left channel. The sound appears to come from the right.
+    Left,
+    /// Delay the right channel. The sound appears to come from the left.
+    #[default]
+    Right,
+}
+
+/// Haas (precedence effect) stereo widener.
+/// The mono input is sent to both channels and one channel is delayed.
+/// - Allocates: the delay line.
+/// - Input 0: mono signal
+/// - Output 0: left signal
+/// - Output 1: right signal
+#[derive(Clone, Default)]
+pub struct Haas {
+    delay: Delay,
+    side: HaasSide,
+}
+
+impl Haas {
+    /// Create a new Haas widener that delays the `side` channel by `time` seconds (`time` >= 0).
+    /// The delay time is rounded to the nearest sample.
+    pub fn new(time: f64, side: HaasSide) -> Self {
+        Self {
+            delay: Delay::new(time),
+            side,
+        }
+    }
+
+    /// Delay time in seconds.
+    #[inline]
+    pub fn delay(&self) -> f64 {
+        self.delay.time
+    }
+
+    /// The delayed channel.
+    #[inline]
+    pub fn side(&self) -> HaasSide {
+        self.side
+    }
+
+    /// Index of the delayed output channel.
+    #[inline]
+    fn delayed_channel(&self) -> usize {
+        match self.side {
+            HaasSide::Left => 0,
+            HaasSide::Right => 1,
+        }
+    }
+}
+
+impl AudioNode for Haas {
+    const ID: u64 = 157;
+    type Inputs = U1;
+    type Outputs = U2;
+
+    fn reset(&mut self) {
+        self.delay.reset();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.delay.memory_usage()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.delay.set_sample_rate(sample_rate);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let delayed = self.delay.tick(input)[0];
+        match self.side {
+            HaasSide::Left => [delayed, input[0]].into(),
+            HaasSide::Right => [input[0], delayed].into(),
+        }
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        let delayed = self.delayed_channel();
+        output.channel_f32_mut(1 - delayed)[..size].copy_from_slice(&input.channel_f32(0)[..size]);
+        self.delay.process(size, input, &mut output.subset(delayed, 1));
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let delayed = self.delay.route(input, frequency);
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(self.delayed_channel(), delayed.at(0));
+        output.set(1 - self.delayed_channel(), input.at(0));
+        output
+    }
+}
+
 /// Variable delay line using cubic interpolation.
 /// The number of taps is `N`.
 /// - Allocates: the delay line.
@@ -179,6 +338,40 @@ where
         node.set_sample_rate(DEFAULT_SR);
         node
     }
+
+    /// Process a block where the delay of each tap is constant.
+    /// Consecutive lanes then read consecutive samples from the delay line.
+    fn process_fixed(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        let mask = self.buffer.len().wrapping_sub(1);
+        for i in 0..full_simd_items(size) {
+            for j in 0..SIMD_N {
+                self.buffer[self.i] = input.at_f32(0, (i << SIMD_S) + j);
+                self.i = self.i.wrapping_add(1) & mask;
+            }
+            let mut out = F32x::ZERO;
+            for tap_i in 1..N::USIZE + 1 {
+                let tap = clamp(
+                    self.min_delay_clamped,
+                    self.max_delay_clamped,
+                    input.at_f32(tap_i, 0),
+                ) * self.sample_rate;
+                // Safety: the value has been clamped.
+                let tap_floor = unsafe { f32::to_int_unchecked::<usize>(tap) };
+                let tap_d = F32x::splat(tap - tap_floor as f32);
+                // Index of the first lane.
+                let tap_i1 = self.i.wrapping_sub(SIMD_N + tap_floor) & mask;
+                out += spline(
+                    load_window(&self.buffer, tap_i1.wrapping_add(1) & mask),
+                    load_window(&self.buffer, tap_i1),
+                    load_window(&self.buffer, tap_i1.wrapping_sub(1) & mask),
+                    load_window(&self.buffer, tap_i1.wrapping_sub(2) & mask),
+                    tap_d,
+                );
+            }
+            output.set(0, i, out);
+        }
+        self.process_remainder(size, input, output);
+    }
 }
 
 impl<N> AudioNode for Tap<N>
@@ -195,6 +388,10 @@ where
         self.buffer.fill(0.0);
     }
 
+    fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<f32>()
+    }
+
     fn set_sample_rate(&mut self, sample_rate: f64) {
         let sample_rate = sample_rate as f32;
         if self.sample_rate != sample_rate {
@@ -236,6 +433,10 @@ where
     }
 
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        if (1..N::USIZE + 1).all(|tap_i| is_constant(input.channel_f32(tap_i), size)) {
+            self.process_fixed(size, input, output);
+            return;
+        }
         let scalar_mask = self.buffer.len().wrapping_sub(1);
         let mask = I32x::splat(scalar_mask as i32);
         let d_vector = I32x::new(core::array::from_fn(|k| (SIMD_N - k) as i32));
@@ -371,6 +572,10 @@ where
         self.x.allocate();
     }
 
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
+
     fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
         Routing::Arbitrary(0.0).route(input, self.outputs())
     }
@@ -417,6 +622,35 @@ where
         node.set_sample_rate(DEFAULT_SR);
         node
     }
+
+    /// Process a block where the delay of each tap is constant.
+    /// Consecutive lanes then read consecutive samples from the delay line.
+    fn process_fixed(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        let mask = self.buffer.len().wrapping_sub(1);
+        for i in 0..full_simd_items(size) {
+            for j in 0..SIMD_N {
+                self.buffer[self.i] = input.at_f32(0, (i << SIMD_S) + j);
+                self.i = self.i.wrapping_add(1) & mask;
+            }
+            let mut out = F32x::ZERO;
+            for tap_i in 1..N::USIZE + 1 {
+                let tap = clamp(self.min_delay, self.max_delay, input.at_f32(tap_i, 0))
+                    * self.sample_rate;
+                // Safety: the value has been clamped.
+                let tap_floor = unsafe { f32::to_int_unchecked::<usize>(tap) };
+                let tap_d = F32x::splat(tap - tap_floor as f32);
+                // Index of the first lane.
+                let tap_i1 = self.i.wrapping_sub(SIMD_N + tap_floor) & mask;
+                out += lerp(
+                    load_window(&self.buffer, tap_i1),
+                    load_window(&self.buffer, tap_i1.wrapping_sub(1) & mask),
+                    tap_d,
+                );
+            }
+            output.set(0, i, out);
+        }
+        self.process_remainder(size, input, output);
+    }
 }
 
 impl<N> AudioNode for TapLinear<N>
@@ -433,11 +667,15 @@ where
         self.buffer.fill(0.0);
     }
 
+    fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<f32>()
+    }
+
     fn set_sample_rate(&mut self, sample_rate: f64) {
         let sample_rate = sample_rate as f32;
         if self.sample_rate != sample_rate {
             self.sample_rate = sample_rate;
-            let buffer_length = ceil(self.max_delay * sample_rate) + 2.0;
+            let buffer_length = ceil(self.max_delay * sample_rate) + 2.0 + SIMD_N as f32;
             let buffer_length = (buffer_length as usize).next_power_of_two();
             self.buffer.resize(buffer_length, 0.0);
             self.reset();
@@ -463,6 +701,10 @@ where
     }
 
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        if (1..N::USIZE + 1).all(|tap_i| is_constant(input.channel_f32(tap_i), size)) {
+            self.process_fixed(size, input, output);
+            return;
+        }
         let scalar_mask = self.buffer.len().wrapping_sub(1);
         let mask = I32x::splat(scalar_mask as i32);
         let d_vector = I32x::new(core::array::from_fn(|k| (SIMD_N - k) as i32));
@@ -503,3 +745,512 @@ where
         output
     }
 }
+
+/// Maximum order of the Thiran allpass fractional delay.
+pub const MAX_THIRAN_ORDER: usize = 8;
+
+/// Fractional delay line with Thiran allpass interpolation.
+/// The delay is split into an integer delay and an allpass delay near the filter order.
+/// Unlike the interpolating taps (`tap`, `tap_linear`), the magnitude response
+/// is exactly flat at any fractional delay; the tradeoff is that group delay is accurate
+/// at low frequencies but deviates near Nyquist, and the recursive filter
+/// produces transients if the delay is modulated quickly.
+/// - Allocates: the delay line.
+/// - Input 0: signal
+/// - Input 1: delay in samples
+/// - Output 0: delayed signal
+#[derive(Clone)]
+pub struct ThiranDelay {
+    buffer: Vec<f32>,
+    i: usize,
+    order: usize,
+    min_delay: f64,
+    max_delay: f64,
+    /// Current delay in samples.
+    delay: f64,
+    /// Integer part of the delay, realized with the delay line.
+    integer_delay: usize,
+    /// Allpass denominator coefficients. The numerator is the reverse.
+    a: [f64; MAX_THIRAN_ORDER + 1],
+    /// Allpass input history.
+    x: [f64; MAX_THIRAN_ORDER],
+    /// Allpass output history.
+    y: [f64; MAX_THIRAN_ORDER],
+    sample_rate: f64,
+}
+
+impl ThiranDelay {
+    /// Create a new Thiran allpass fractional delay line with maximum delay `max_delay`
+    /// in samples and allpass order `order` (1 <= `order` <= `MAX_THIRAN_ORDER`).
+    /// The minimum delay is `order - 0.5` samples.
+    pub fn new(max_delay: f64, order: usize) -> Self {
+        assert!((1..=MAX_THIRAN_ORDER).contains(&order));
+        let min_delay = order as f64 - 0.5;
+        assert!(max_delay >= min_delay);
+        let buffer_length = (round(max_delay - order as f64) as usize + 1).next_power_of_two();
+        let mut node = Self {
+            buffer: vec![0.0; buffer_length],
+            i: 0,
+            order,
+            min_delay,
+            max_delay,
+            delay: 0.0,
+            integer_delay: 0,
+            a: [0.0; MAX_THIRAN_ORDER + 1],
+            x: [0.0; MAX_THIRAN_ORDER],
+            y: [0.0; MAX_THIRAN_ORDER],
+            sample_rate: DEFAULT_SR,
+        };
+        node.set_delay(min_delay);
+        node
+    }
+
+    /// Allpass order.
+    #[inline]
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Current delay in samples.
+    #[inline]
+    pub fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    /// Set delay in samples. The delay is clamped to the supported range.
+    pub fn set_delay(&mut self, delay: f64) {
+        let delay = clamp(self.min_delay, self.max_delay, delay);
+        if delay == self.delay {
+            return;
+        }
+        self.delay = delay;
+        let n = self.order;
+        // Keep the allpass delay in `order` - 0.5...`order` + 0.5 where it is most accurate.
+        self.integer_delay = max(0.0, round(delay - n as f64)) as usize;
+        let d = delay - self.integer_delay as f64;
+        // Thiran coefficients: a_k = (-1)^k C(N, k) prod_{i=0}^{N} (d - N + i) / (d - N + k + i).
+        let mut binomial = 1.0;
+        for k in 0..=n {
+            let mut a = binomial;
+            for i in 0..=n {
+                a *= (d - n as f64 + i as f64) / (d - n as f64 + (k + i) as f64);
+            }
+            self.a[k] = if k & 1 == 1 { -a } else { a };
+            binomial = binomial * (n - k) as f64 / (k + 1) as f64;
+        }
+    }
+
+    /// Frequency response of the delay at `frequency` Hz.
+    fn delay_response(&self, frequency: f64) -> Complex64 {
+        let n = self.order;
+        let w = -f64::TAU * frequency / self.sample_rate;
+        let mut numerator = Complex64::new(0.0, 0.0);
+        let mut denominator = Complex64::new(0.0, 0.0);
+        for k in 0..=n {
+            let z = Complex64::from_polar(1.0, w * k as f64);
+            numerator += z * self.a[n - k];
+            denominator += z * self.a[k];
+        }
+        Complex64::from_polar(1.0, w * self.integer_delay as f64) * numerator / denominator
+    }
+}
+
+impl AudioNode for ThiranDelay {
+    const ID: u64 = 119;
+    type Inputs = U2;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.i = 0;
+        self.buffer.fill(0.0);
+        self.x = [0.0; MAX_THIRAN_ORDER];
+        self.y = [0.0; MAX_THIRAN_ORDER];
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<f32>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.set_delay(input[1] as f64);
+        let mask = self.buffer.len() - 1;
+        self.buffer[self.i] = input[0];
+        let u = self.buffer[self.i.wrapping_sub(self.integer_delay) & mask] as f64;
+        self.i = (self.i + 1) & mask;
+        let n = self.order;
+        let mut v = self.a[n] * u;
+        for k in 1..=n {
+            v += self.a[n - k] * self.x[k - 1] - self.a[k] * self.y[k - 1];
+        }
+        self.x.copy_within(0..n - 1, 1);
+        self.y.copy_within(0..n - 1, 1);
+        self.x[0] = u;
+        self.y[0] = v;
+        [v as f32].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        // Pretend the delay is constant.
+        let response = self.delay_response(frequency);
+        output.set(0, input.at(0).filter(0.0, |r| r * response));
+        output
+    }
+}
+
+/// Latency compensation for the outputs of enclosed node `X`.
+/// The latency of each output is queried from `X`, rounded to the nearest sample,
+/// and outputs with less latency are delayed to match the output with the most latency.
+/// Latencies are queried again when the sample rate changes.
+/// Outputs with unknown latency are treated as having zero latency.
+/// - Allocates: the compensating delay lines.
+/// - Inputs: from `X`.
+/// - Outputs: from `X`, aligned.
+#[derive(Clone)]
+pub struct Align<X: AudioNode> {
+    x: X,
+    /// Compensating delay lines for each output channel.
+    lines: Vec<Vec<f32>>,
+    /// Current position in each delay line.
+    positions: Vec<usize>,
+    sample_rate: f64,
+}
+
+impl<X: AudioNode> Align<X> {
+    /// Create a new latency aligner for the outputs of `node`.
+    pub fn new(node: X) -> Self {
+        let mut node = Self {
+            x: node,
+            lines: Vec::new(),
+            positions: Vec::new(),
+            sample_rate: DEFAULT_SR,
+        };
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node.align();
+        node
+    }
+
+    /// Access enclosed node.
+    pub fn node(&self) -> &X {
+        &self.x
+    }
+
+    /// Access enclosed node.
+    pub fn node_mut(&mut self) -> &mut X {
+        &mut self.x
+    }
+
+    /// Compensating delay in samples for output `channel`.
+    pub fn compensation(&self, channel: usize) -> usize {
+        self.lines[channel].len()
+    }
+
+    /// Query output latencies and resize compensating delay lines to match.
+    fn align(&mut self) {
+        let mut input = SignalFrame::new(X::Inputs::USIZE);
+        for i in 0..X::Inputs::USIZE {
+            input.set(i, Signal::Latency(0.0));
+        }
+        let response = self.x.route(&input, 1.0);
+        let latency: Vec<usize> = (0..X::Outputs::USIZE)
+            .map(|i| match response.at(i) {
+                Signal::Latency(x) | Signal::Response(_, x) => round(max(x, 0.0)) as usize,
+                _ => 0,
+            })
+            .collect();
+        let max_latency = latency.iter().copied().max().unwrap_or_default();
+        self.lines.resize(X::Outputs::USIZE, Vec::new());
+        self.positions.clear();
+        self.positions.resize(X::Outputs::USIZE, 0);
+        for (line, latency) in self.lines.iter_mut().zip(latency) {
+            line.clear();
+            line.resize(max_latency - latency, 0.0);
+        }
+    }
+}
+
+impl<X: AudioNode> AudioNode for Align<X> {
+    const ID: u64 = 118;
+    type Inputs = X::Inputs;
+    type Outputs = X::Outputs;
+
+    fn reset(&mut self) {
+        self.x.reset();
+        for line in self.lines.iter_mut() {
+            line.fill(0.0);
+        }
+        self.positions.fill(0);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.x.set_sample_rate(sample_rate);
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.align();
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let mut output = self.x.tick(input);
+        for (i, x) in output.iter_mut().enumerate() {
+            let line = &mut self.lines[i];
+            if !line.is_empty() {
+                let position = self.positions[i];
+                let delayed = line[position];
+                line[position] = *x;
+                self.positions[i] = if position + 1 < line.len() {
+                    position + 1
+                } else {
+                    0
+                };
+                *x = delayed;
+            }
+        }
+        output
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        self.x.process(size, input, output);
+        for i in 0..X::Outputs::USIZE {
+            let line = &mut self.lines[i];
+            if line.is_empty() {
+                continue;
+            }
+            let mut position = self.positions[i];
+            for x in output.channel_f32_mut(i)[..size].iter_mut() {
+                let delayed = line[position];
+                line[position] = *x;
+                position += 1;
+                if position >= line.len() {
+                    position = 0;
+                }
+                *x = delayed;
+            }
+            self.positions[i] = position;
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        self.x.set(setting);
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = self.x.route(input, frequency);
+        for i in 0..X::Outputs::USIZE {
+            let delay = self.lines[i].len() as f64;
+            if delay > 0.0 {
+                output.set(
+                    i,
+                    output.at(i).filter(delay, |r| {
+                        r * Complex64::from_polar(
+                            1.0,
+                            -f64::TAU * delay * frequency / self.sample_rate,
+                        )
+                    }),
+                );
+            }
+        }
+        output
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+            + self
+                .lines
+                .iter()
+                .map(|line| line.capacity() * core::mem::size_of::<f32>())
+                .sum::<usize>()
+            + self.positions.capacity() * core::mem::size_of::<usize>()
+    }
+}
+
+/// Wow modulation frequency of `Tape` in Hz.
+const TAPE_WOW_HZ: f64 = 0.5;
+/// Flutter modulation frequencies of `Tape` in Hz.
+const TAPE_FLUTTER_HZ: [f64; 2] = [6.5, 11.3];
+/// Delay deviation of `Tape` at full wow depth in seconds.
+const TAPE_WOW_DELAY: f32 = 0.002;
+/// Delay deviation of `Tape` at full flutter depth in seconds.
+const TAPE_FLUTTER_DELAY: f32 = 0.0001;
+/// Cutoff frequency of the `Tape` high frequency rolloff in Hz.
+const TAPE_ROLLOFF_HZ: f64 = 12_000.0;
+
+/// Tape emulation with wow, flutter, saturation and a gentle high frequency rolloff.
+/// Wow and flutter modulate a fractional delay, which modulates pitch.
+/// Wow is a 0.5 Hz sine wave. Flutter is a mix of 6.5 Hz and 11.3 Hz sine waves.
+/// At full depth, wow deviates the delay by 2 ms (about 0.6% in pitch)
+/// and flutter by 0.1 ms (about 0.5% in pitch).
+/// Saturation is a `tanh` curve followed by a one-pole lowpass at 12 kHz.
+/// - Allocates: the delay line.
+/// - Input 0: input
+/// - Output 0: processed input
+#[derive(Clone)]
+pub struct Tape {
+    buffer: Vec<f32>,
+    i: usize,
+    sample_rate: f64,
+    wow_depth: f32,
+    flutter_depth: f32,
+    saturation: f32,
+    /// Phases of wow and flutter oscillators in 0...1.
+    wow_phase: f64,
+    flutter_phase: [f64; 2],
+    /// Rolloff lowpass coefficient and state.
+    rolloff: f32,
+    lowpass: f32,
+}
+
+impl Tape {
+    /// Create new tape emulation. `wow_depth` and `flutter_depth` are in 0...1.
+    /// `saturation` is in 0...1, where 0 is mild saturation and 1 is heavy distortion.
+    pub fn new(wow_depth: f32, flutter_depth: f32, saturation: f32) -> Self {
+        let mut node = Self {
+            buffer: Vec::new(),
+            i: 0,
+            sample_rate: 0.0,
+            wow_depth: clamp01(wow_depth),
+            flutter_depth: clamp01(flutter_depth),
+            saturation: clamp01(saturation),
+            wow_phase: 0.0,
+            flutter_phase: [0.0; 2],
+            rolloff: 0.0,
+            lowpass: 0.0,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Wow depth in 0...1.
+    #[inline]
+    pub fn wow_depth(&self) -> f32 {
+        self.wow_depth
+    }
+
+    /// Set wow depth in 0...1.
+    #[inline]
+    pub fn set_wow_depth(&mut self, wow_depth: f32) {
+        self.wow_depth = clamp01(wow_depth);
+    }
+
+    /// Flutter depth in 0...1.
+    #[inline]
+    pub fn flutter_depth(&self) -> f32 {
+        self.flutter_depth
+    }
+
+    /// Set flutter depth in 0...1.
+    #[inline]
+    pub fn set_flutter_depth(&mut self, flutter_depth: f32) {
+        self.flutter_depth = clamp01(flutter_depth);
+    }
+
+    /// Saturation amount in 0...1.
+    #[inline]
+    pub fn saturation(&self) -> f32 {
+        self.saturation
+    }
+
+    /// Set saturation amount in 0...1.
+    #[inline]
+    pub fn set_saturation(&mut self, saturation: f32) {
+        self.saturation = clamp01(saturation);
+    }
+
+    /// Center delay in seconds. Leaves room for full wow and flutter deviation.
+    #[inline]
+    fn center_delay() -> f32 {
+        TAPE_WOW_DELAY + TAPE_FLUTTER_DELAY + 0.0005
+    }
+}
+
+impl AudioNode for Tape {
+    const ID: u64 = 134;
+    type Inputs = U1;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.i = 0;
+        self.buffer.fill(0.0);
+        self.wow_phase = 0.0;
+        self.flutter_phase = [0.0; 2];
+        self.lowpass = 0.0;
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<f32>()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            let maximum = 2.0 * Self::center_delay() as f64 * sample_rate + 4.0;
+            self.buffer
+                .resize((maximum as usize).next_power_of_two(), 0.0);
+            self.rolloff = exp(-f64::TAU * TAPE_ROLLOFF_HZ / sample_rate) as f32;
+            self.reset();
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let drive = 1.0 + 9.0 * self.saturation;
+        let x = tanh(input[0] * drive) / drive;
+        let mask = self.buffer.len() - 1;
+        self.buffer[self.i & mask] = x;
+
+        let wow = sin(self.wow_phase * f64::TAU) as f32;
+        let flutter = (0.7 * sin(self.flutter_phase[0] * f64::TAU)
+            + 0.3 * sin(self.flutter_phase[1] * f64::TAU)) as f32;
+        let delay = Self::center_delay()
+            + self.wow_depth * TAPE_WOW_DELAY * wow
+            + self.flutter_depth * TAPE_FLUTTER_DELAY * flutter;
+        self.wow_phase += TAPE_WOW_HZ / self.sample_rate;
+        self.wow_phase -= floor(self.wow_phase);
+        for (phase, hz) in self.flutter_phase.iter_mut().zip(TAPE_FLUTTER_HZ) {
+            *phase += hz / self.sample_rate;
+            *phase -= floor(*phase);
+        }
+
+        let position = delay * self.sample_rate as f32;
+        let n = position as usize;
+        let d = position - n as f32;
+        let j = self.i.wrapping_sub(n);
+        let y = spline(
+            self.buffer[j.wrapping_add(1) & mask],
+            self.buffer[j & mask],
+            self.buffer[j.wrapping_sub(1) & mask],
+            self.buffer[j.wrapping_sub(2) & mask],
+            d,
+        );
+        self.i = self.i.wrapping_add(1);
+
+        self.lowpass = y + self.rolloff * (self.lowpass - y);
+        [self.lowpass].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(
+            0,
+            input
+                .at(0)
+                .distort(Self::center_delay() as f64 * self.sample_rate),
+        );
+        output
+    }
+}
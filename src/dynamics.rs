@@ -54,6 +54,24 @@ impl<T: Num> Monoidal<T> for Maximum<T> {
     }
 }
 
+#[derive(Default, Clone)]
+pub struct Addition<T: Num> {
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T: Num> Addition<T> {
+    pub fn new() -> Self {
+        Addition::default()
+    }
+}
+
+impl<T: Num> Monoidal<T> for Addition<T> {
+    #[inline]
+    fn binop(&self, x: T, y: T) -> T {
+        x + y
+    }
+}
+
 /// Hierarchic reducer for a monoid.
 #[derive(Clone)]
 pub struct ReduceBuffer<T, B>
@@ -93,6 +111,11 @@ where
         buffer
     }
 
+    /// Heap memory used by this buffer in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<T>()
+    }
+
     #[inline]
     pub fn length(&self) -> usize {
         self.length
@@ -235,6 +258,216 @@ where
                 .reserve(self.reducer.length() - self.buffer.capacity());
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        self.reducer.memory_usage() + self.buffer.capacity() * core::mem::size_of::<Frame<f32, N>>()
+    }
+}
+
+/// Number of taps per phase in the true peak interpolator.
+const TRUE_PEAK_TAPS: usize = 16;
+
+/// Polyphase coefficients of the 4x true peak interpolator for the three
+/// intermediate phases. The filter is a Hann windowed sinc.
+fn true_peak_coefficients() -> [[f32; TRUE_PEAK_TAPS]; 3] {
+    let half = (TRUE_PEAK_TAPS / 2) as f64;
+    core::array::from_fn(|phase| {
+        core::array::from_fn(|i| {
+            let u = i as f64 - (half - 1.0) - (phase + 1) as f64 * 0.25;
+            let sinc = sin(u * f64::PI) / (u * f64::PI);
+            let window = 0.5 + 0.5 * cos(u * f64::PI / half);
+            (sinc * window) as f32
+        })
+    })
+}
+
+/// Look-ahead limiter with true peak detection. Inter-sample peaks are estimated
+/// with 4x oversampling and the gain is reduced so that the output true peak
+/// stays under the ceiling. The gain envelope is a moving average
+/// of the look-ahead peak, which reaches the required reduction before each peak.
+/// Number of inputs and outputs is `N`.
+/// - Latency: attack time plus 7 samples.
+/// - Allocates: look-ahead buffers.
+/// - Input(s): input signal
+/// - Output(s): limited signal
+#[derive(Clone)]
+pub struct TruePeakLimiter<N>
+where
+    N: Size<f32>,
+{
+    lookahead: f64,
+    release: f64,
+    ceiling: f32,
+    sample_rate: f64,
+    coefficients: [[f32; TRUE_PEAK_TAPS]; 3],
+    history: [Frame<f32, N>; TRUE_PEAK_TAPS],
+    history_index: usize,
+    previous_peak: f32,
+    reducer: ReduceBuffer<f32, Maximum<f32>>,
+    average: ReduceBuffer<f32, Addition<f32>>,
+    index: usize,
+    buffer: Vec<Frame<f32, N>>,
+    buffer_index: usize,
+    level: f32,
+    release_coeff: f32,
+}
+
+impl<N> TruePeakLimiter<N>
+where
+    N: Size<f32>,
+{
+    fn window_length(sample_rate: f64, lookahead: f64) -> usize {
+        max(1, round(sample_rate * lookahead) as usize)
+    }
+
+    /// Create new true peak limiter. The ceiling is given in dB.
+    pub fn new(sample_rate: f64, attack_time: f32, release_time: f32, ceiling_db: f32) -> Self {
+        let length = Self::window_length(sample_rate, attack_time as f64);
+        let mut node = TruePeakLimiter {
+            lookahead: attack_time as f64,
+            release: release_time as f64,
+            ceiling: db_amp(ceiling_db),
+            sample_rate,
+            coefficients: true_peak_coefficients(),
+            history: core::array::from_fn(|_| Frame::default()),
+            history_index: 0,
+            previous_peak: 0.0,
+            reducer: ReduceBuffer::new(length, Maximum::new()),
+            average: ReduceBuffer::new(length, Addition::new()),
+            index: 0,
+            buffer: Vec::new(),
+            buffer_index: 0,
+            level: 0.0,
+            release_coeff: 0.0,
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Ceiling in dB.
+    pub fn ceiling_db(&self) -> f32 {
+        amp_db(self.ceiling)
+    }
+
+    /// Length of the look-ahead delay (latency) in samples.
+    fn buffer_length(&self) -> usize {
+        self.reducer.length() + TRUE_PEAK_TAPS / 2 - 1
+    }
+
+    /// Estimate the peak level of the current sample and its neighboring
+    /// inter-sample intervals, relative to the ceiling.
+    #[inline]
+    fn peak_level(&mut self) -> f32 {
+        let mut sample_peak = 0.0;
+        let mut inter_peak = 0.0;
+        for channel in 0..N::USIZE {
+            // The oldest sample is at `history_index + 1`.
+            let center = (self.history_index + TRUE_PEAK_TAPS / 2) & (TRUE_PEAK_TAPS - 1);
+            sample_peak = max(sample_peak, abs(self.history[center][channel]));
+            for phase in self.coefficients.iter() {
+                let mut y = 0.0;
+                for (i, c) in phase.iter().enumerate() {
+                    y += c * self.history[(self.history_index + 1 + i) & (TRUE_PEAK_TAPS - 1)]
+                        [channel];
+                }
+                inter_peak = max(inter_peak, abs(y));
+            }
+        }
+        let peak = max(max(sample_peak, inter_peak), self.previous_peak);
+        self.previous_peak = inter_peak;
+        // Leave some headroom for interpolation error.
+        peak * 1.02 / self.ceiling
+    }
+}
+
+impl<N> AudioNode for TruePeakLimiter<N>
+where
+    N: Size<f32>,
+{
+    const ID: u64 = 105;
+    type Inputs = N;
+    type Outputs = N;
+
+    fn reset(&mut self) {
+        self.set_sample_rate(self.sample_rate);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        let length = Self::window_length(sample_rate, self.lookahead);
+        if length != self.reducer.length() {
+            self.reducer = ReduceBuffer::new(length, Maximum::new());
+            self.average = ReduceBuffer::new(length, Addition::new());
+        }
+        self.reducer.clear();
+        self.average.clear();
+        self.buffer.clear();
+        self.history = core::array::from_fn(|_| Frame::default());
+        self.history_index = 0;
+        self.previous_peak = 0.0;
+        self.index = 0;
+        self.buffer_index = 0;
+        self.level = 0.0;
+        self.release_coeff = pow(0.5, 1.0 / (self.release * sample_rate)) as f32;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.history[self.history_index] = input.clone();
+        let peak = self.peak_level();
+        self.history_index = (self.history_index + 1) & (TRUE_PEAK_TAPS - 1);
+
+        // The moving average of the look-ahead maximum is at least the peak level
+        // when the peak sample is output.
+        let length = self.reducer.length();
+        self.reducer.set(self.index, peak);
+        self.average.set(self.index, self.reducer.total());
+        self.index += 1;
+        if self.index >= length {
+            self.index = 0;
+        }
+        let average = self.average.total() / length as f32;
+        self.level = max(
+            average,
+            self.release_coeff * self.level + (1.0 - self.release_coeff) * average,
+        );
+
+        let delay = self.buffer_length();
+        if self.buffer.len() < delay {
+            // We are filling up the initial buffer.
+            self.buffer.push(input.clone());
+            return Frame::default();
+        }
+        let output = self.buffer[self.buffer_index].clone();
+        self.buffer[self.buffer_index] = input.clone();
+        self.buffer_index += 1;
+        if self.buffer_index >= delay {
+            self.buffer_index = 0;
+        }
+        output * Frame::splat(1.0 / max(1.0, self.level))
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        for i in 0..N::USIZE {
+            // We pretend that the limiter does not alter the frequency response.
+            output.set(i, input.at(i).delay(self.buffer_length() as f64));
+        }
+        output
+    }
+
+    fn allocate(&mut self) {
+        if self.buffer.capacity() < self.buffer_length() {
+            self.buffer
+                .reserve(self.buffer_length() - self.buffer.capacity());
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.reducer.memory_usage()
+            + self.average.memory_usage()
+            + self.buffer.capacity() * core::mem::size_of::<Frame<f32, N>>()
+    }
 }
 
 /// Transient filter. Multiply the signal with a fade-in curve.
@@ -312,6 +545,15 @@ impl<F: Real> AudioNode for Declick<F> {
     }
 }
 
+/// Level detection modes for envelope followers.
+#[derive(Copy, Clone)]
+pub enum Detector {
+    /// Peak level: the absolute value of the signal is smoothed.
+    Peak,
+    /// RMS level: the square of the signal is smoothed and the square root taken.
+    Rms,
+}
+
 /// Metering modes.
 #[derive(Copy, Clone)]
 pub enum Meter {
@@ -323,6 +565,10 @@ pub enum Meter {
     /// RMS meter with smoothing timescale in seconds.
     /// Smoothing timescale is the time it takes for level estimation to move halfway to a new level.
     Rms(f64),
+    /// DC offset meter with smoothing timescale in seconds.
+    /// Reports the mean of the signal, low-pass filtered with a one-pole smoother.
+    /// Smoothing timescale is the time it takes for level estimation to move halfway to a new level.
+    Dc(f64),
 }
 
 impl Meter {
@@ -364,6 +610,7 @@ impl MeterState {
             }
             Meter::Peak(timescale) => timescale,
             Meter::Rms(timescale) => timescale,
+            Meter::Dc(timescale) => timescale,
         };
         self.smoothing = (pow(0.5f64, 1.0 / (timescale * sample_rate))).to_f32();
     }
@@ -377,6 +624,9 @@ impl MeterState {
             Meter::Rms(_) => {
                 self.state = self.state * self.smoothing + squared(value) * (1.0 - self.smoothing)
             }
+            Meter::Dc(_) => {
+                self.state = self.state * self.smoothing + value * (1.0 - self.smoothing)
+            }
         }
     }
 
@@ -387,6 +637,7 @@ impl MeterState {
             Meter::Sample => self.state,
             Meter::Peak(_) => self.state,
             Meter::Rms(_) => sqrt(self.state),
+            Meter::Dc(_) => self.state,
         }
     }
 }
@@ -506,3 +757,53 @@ impl AudioNode for Monitor {
         input.clone()
     }
 }
+
+/// Clip monitor. Passes through input unchanged.
+/// Counts samples with absolute value greater than one into a shared counter.
+#[derive(Clone)]
+pub struct ClipMonitor {
+    counter: SharedCounter,
+}
+
+impl ClipMonitor {
+    /// Create a new clip monitor node that adds clipped sample counts to `counter`.
+    pub fn new(counter: &SharedCounter) -> Self {
+        Self {
+            counter: counter.clone(),
+        }
+    }
+
+    /// Add `count` to the shared counter.
+    #[inline]
+    fn add(&self, count: usize) {
+        if count > 0 {
+            self.counter.add(count as u32);
+        }
+    }
+}
+
+impl AudioNode for ClipMonitor {
+    const ID: u64 = 115;
+    type Inputs = U1;
+    type Outputs = U1;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.add((abs(input[0]) > 1.0) as usize);
+        *input
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        let count = input.channel_f32(0)[..size]
+            .iter()
+            .filter(|x| abs(**x) > 1.0)
+            .count();
+        self.add(count);
+        output.channel_mut(0)[..simd_items(size)]
+            .clone_from_slice(&input.channel(0)[..simd_items(size)]);
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        input.clone()
+    }
+}
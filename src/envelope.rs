@@ -178,6 +178,77 @@ where
     }
 }
 
+/// Evaluate a time varying function at every sample, without jitter or interpolation.
+/// The output is exactly `f(t)` where `t` is the time of the sample.
+/// The return type can be scalar or tuple.
+/// It determines the number of output channels.
+#[derive(Default, Clone)]
+pub struct ExactEnvelope<F, E, R>
+where
+    F: Real,
+    E: FnMut(F) -> R + Clone + Send + Sync,
+    R: ConstantFrame<Sample = F>,
+    R::Size: Size<F> + Size<f32>,
+{
+    /// Control function.
+    envelope: E,
+    /// Index of next sample.
+    index: u64,
+    sample_rate: f64,
+    _marker: PhantomData<F>,
+}
+
+impl<F, E, R> ExactEnvelope<F, E, R>
+where
+    F: Real,
+    E: FnMut(F) -> R + Clone + Send + Sync,
+    R: ConstantFrame<Sample = F>,
+    R::Size: Size<F> + Size<f32>,
+{
+    /// Create new exact envelope.
+    pub fn new(envelope: E) -> Self {
+        Self {
+            envelope,
+            index: 0,
+            sample_rate: DEFAULT_SR,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, E, R> AudioNode for ExactEnvelope<F, E, R>
+where
+    F: Real,
+    E: FnMut(F) -> R + Clone + Send + Sync,
+    R: ConstantFrame<Sample = F>,
+    R::Size: Size<F> + Size<f32>,
+{
+    const ID: u64 = 149;
+    type Inputs = typenum::U0;
+    type Outputs = R::Size;
+
+    fn reset(&mut self) {
+        self.index = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+    }
+
+    #[inline]
+    fn tick(&mut self, _input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        // Compute time from the sample index so it does not accumulate error.
+        let t = F::from_f64(self.index as f64 / self.sample_rate);
+        self.index += 1;
+        let value: Frame<_, _> = (self.envelope)(t).frame();
+        Frame::generate(|i| convert(value[i]))
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Generator(0.0).route(input, self.outputs())
+    }
+}
+
 /// Sample a time varying, input dependent function.
 /// The return type can be scalar or tuple.
 /// It determines the number of output channels.
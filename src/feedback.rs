@@ -62,6 +62,34 @@ impl<N: Size<f32>> FrameUnop<N> for FrameHadamard<N> {
     fn assign(&self, _size: usize, _x: &mut [f32]) {
         panic!()
     }
+    /// Apply the transform to a whole block with an in-place butterfly.
+    /// Each butterfly processes a pair of channels across time in SIMD lanes.
+    /// The operations per sample are the same as in `frame`, so the results are identical.
+    fn block(&self, size: usize, buffer: &mut BufferVec) {
+        let items = simd_items(size);
+        let mut h = 1;
+        while h < N::USIZE {
+            let mut i = 0;
+            while i < N::USIZE {
+                for j in i..i + h {
+                    for k in 0..items {
+                        let x = buffer.at(j, k);
+                        let y = buffer.at(j + h, k);
+                        buffer.set(j, k, x + y);
+                        buffer.set(j + h, k, x - y);
+                    }
+                }
+                i += h * 2;
+            }
+            h *= 2;
+        }
+        let scale = F32x::splat((1.0 / sqrt(N::I32 as f64)) as f32);
+        for channel in 0..N::USIZE {
+            for x in buffer.channel_mut(channel)[..items].iter_mut() {
+                *x *= scale;
+            }
+        }
+    }
 }
 
 /// Mix back output of contained node to its input.
@@ -156,6 +184,9 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
 }
 
 /// Mix back output of contained node `X` to its input, with extra feedback processing `Y`.
@@ -268,6 +299,199 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.y.memory_usage()
+    }
+}
+
+/// Mix back output of contained node `X` to its input with an integrated feedback delay,
+/// with feedback operator `U` applied to the delayed output.
+/// The contained node must have an equal number of inputs and outputs.
+///
+/// When the block size does not exceed the feedback delay, the delayed output
+/// of a whole block is available in advance, so `process` applies the feedback operator
+/// to the whole block at once and processes `X` in blocks instead of one sample at a time.
+/// With a Hadamard operator, this is the fast path for feedback delay networks.
+/// As in `Feedback`, the feedback path has one sample of delay in addition to the integrated delay,
+/// so delay lines in `X` can be shortened by the integrated delay to keep loop lengths the same.
+/// - Allocates: the feedback delay line.
+/// - Input(s): signal.
+/// - Output(s): signal with feedback.
+#[derive(Clone)]
+pub struct DelayedFeedback<N, X, U>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+    U: FrameUnop<N>,
+{
+    x: X,
+    /// Feedback operator.
+    feedback: U,
+    /// Delay in seconds.
+    delay: f64,
+    /// Feedback delay in samples, including the extra sample.
+    samples: usize,
+    sample_rate: f64,
+    /// Feedback delay lines, one per channel, power-of-two sized.
+    lines: Vec<Vec<f32>>,
+    /// Feedback line length minus one.
+    mask: usize,
+    /// Current write index into feedback lines.
+    index: usize,
+    /// Buffer for assembling blocks.
+    buffer: BufferVec,
+}
+
+impl<N, X, U> DelayedFeedback<N, X, U>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+    U: FrameUnop<N>,
+{
+    /// Create new feedback node with integrated feedback `delay` in seconds.
+    /// The delay is rounded to the nearest sample. The feedback path is delayed
+    /// by one more sample, so a zero `delay` is equivalent to `Feedback`.
+    pub fn new(delay: f64, x: X, feedback: U) -> Self {
+        assert!(delay >= 0.0);
+        let mut node = Self {
+            x,
+            feedback,
+            delay,
+            samples: 0,
+            sample_rate: 0.0,
+            lines: vec![Vec::new(); N::USIZE],
+            mask: 0,
+            index: 0,
+            buffer: BufferVec::new(N::USIZE),
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node
+    }
+
+    /// Feedback delay in samples, including the extra sample.
+    #[inline]
+    pub fn delay_samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Calculate read index to delayed sample.
+    #[inline]
+    fn read_index(&self) -> usize {
+        (self.index + self.mask + 1 - self.samples) & self.mask
+    }
+}
+
+impl<N, X, U> AudioNode for DelayedFeedback<N, X, U>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+    U: FrameUnop<N>,
+{
+    const ID: u64 = 139;
+    type Inputs = N;
+    type Outputs = N;
+
+    fn reset(&mut self) {
+        self.x.reset();
+        for line in self.lines.iter_mut() {
+            line.fill(0.0);
+        }
+        self.index = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.x.set_sample_rate(sample_rate);
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.samples = round(self.delay * sample_rate) as usize + 1;
+            let line_samples = (self.samples + 1).next_power_of_two();
+            self.mask = line_samples - 1;
+            for line in self.lines.iter_mut() {
+                line.fill(0.0);
+                line.resize(line_samples, 0.0);
+            }
+            self.index = 0;
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        super::denormal::prevent_denormals();
+        let read_i = self.read_index();
+        let delayed = Frame::generate(|channel| self.lines[channel][read_i]);
+        let output = self.x.tick(&(input + self.feedback.frame(&delayed)));
+        for channel in 0..N::USIZE {
+            self.lines[channel][self.index] = output[channel];
+        }
+        self.index = (self.index + 1) & self.mask;
+        output
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        if size > self.samples {
+            // The feedback delay is small so we proceed sample by sample.
+            for i in 0..size {
+                let input_frame = Frame::generate(|channel| input.at_f32(channel, i));
+                let output_frame = self.tick(&input_frame);
+                for channel in 0..N::USIZE {
+                    output.set_f32(channel, i, output_frame[channel]);
+                }
+            }
+            return;
+        }
+        super::denormal::prevent_denormals();
+        // We have enough feedback samples to process the whole block at once.
+        let read_i = self.read_index();
+        for channel in 0..N::USIZE {
+            let line = &self.lines[channel];
+            let buffer = &mut self.buffer.channel_f32_mut(channel)[..size];
+            let n = min(size, line.len() - read_i);
+            buffer[..n].copy_from_slice(&line[read_i..read_i + n]);
+            buffer[n..].copy_from_slice(&line[..size - n]);
+        }
+        self.feedback.block(size, &mut self.buffer);
+        for channel in 0..N::USIZE {
+            for (x, y) in self.buffer.channel_mut(channel)[..simd_items(size)]
+                .iter_mut()
+                .zip(input.channel(channel)[..simd_items(size)].iter())
+            {
+                *x += *y;
+            }
+        }
+        self.x.process(size, &self.buffer.buffer_ref(), output);
+        for channel in 0..N::USIZE {
+            let line = &mut self.lines[channel];
+            let output = &output.channel_f32(channel)[..size];
+            let n = min(size, line.len() - self.index);
+            line[self.index..self.index + n].copy_from_slice(&output[..n]);
+            line[..size - n].copy_from_slice(&output[n..]);
+        }
+        self.index = (self.index + size) & self.mask;
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+            + self
+                .lines
+                .iter()
+                .map(|x| x.capacity() * core::mem::size_of::<f32>())
+                .sum::<usize>()
+            + self.buffer.memory_usage()
+    }
 }
 
 /// Feedback unit with integrated delay.
@@ -436,4 +660,17 @@ impl AudioUnit for FeedbackUnit {
     fn allocate(&mut self) {
         self.x.allocate();
     }
+    fn memory_usage(&self) -> usize {
+        self.x.footprint()
+            + self.x.memory_usage()
+            + self.feedback.capacity() * core::mem::size_of::<Vec<f32>>()
+            + self
+                .feedback
+                .iter()
+                .map(|x| x.capacity() * core::mem::size_of::<f32>())
+                .sum::<usize>()
+            + (self.tick_buffer.capacity() + self.tick_buffer2.capacity())
+                * core::mem::size_of::<f32>()
+            + self.buffer.memory_usage()
+    }
 }
@@ -1,13 +1,18 @@
 //! Various filters.
 
 use super::audionode::*;
+use super::buffer::*;
 use super::math::*;
+use super::moog::ode_step;
 use super::setting::*;
 use super::signal::*;
 use super::*;
 use core::marker::PhantomData;
 use numeric_array::typenum::*;
 use numeric_array::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// One-pole lowpass filter.
 /// Setting: cutoff.
@@ -73,6 +78,19 @@ impl<F: Real, N: Size<f32>> AudioNode for Lowpole<F, N> {
         [convert(self.value)].into()
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        if N::USIZE > 1 {
+            let cutoff: F = convert(input[1]);
+            if cutoff != self.cutoff {
+                self.set_cutoff(cutoff);
+            }
+        }
+        let x = convert(input[0]);
+        self.value = (F::one() - self.coeff) * x + self.coeff * self.value;
+        [convert(self.value)].into()
+    }
+
     fn set(&mut self, setting: Setting) {
         if let Parameter::Center(cutoff) = setting.parameter() {
             self.set_cutoff(F::from_f32(*cutoff));
@@ -94,6 +112,117 @@ impl<F: Real, N: Size<f32>> AudioNode for Lowpole<F, N> {
     }
 }
 
+/// One-pole smoothing filter for control signals.
+/// The time constant is given either as a cutoff frequency in Hz,
+/// which is independent of the sample rate, or in samples.
+/// The step response reaches 1 - 1/e (about 63%) of its final value in one time constant.
+/// Time constant `tau` seconds corresponds to cutoff frequency `1 / (2 * PI * tau)` Hz.
+/// Setting: cutoff (`Setting::center`) or time constant in seconds (`Setting::time`).
+/// - Input 0: input signal
+/// - Output 0: smoothed signal
+#[derive(Default, Clone)]
+pub struct SmoothPole<F: Real> {
+    value: F,
+    coeff: F,
+    /// Time constant in samples.
+    samples: F,
+    /// Cutoff frequency in Hz, if the time constant follows the sample rate.
+    cutoff: Option<F>,
+    sample_rate: F,
+}
+
+impl<F: Real> SmoothPole<F> {
+    /// Create new smoothing filter with `cutoff` frequency in Hz.
+    pub fn new(cutoff: F) -> Self {
+        let mut node = Self {
+            sample_rate: convert(DEFAULT_SR),
+            ..Self::default()
+        };
+        node.set_cutoff(cutoff);
+        node
+    }
+
+    /// Create new smoothing filter with time constant `samples` in samples.
+    /// The time constant does not change with the sample rate.
+    pub fn new_samples(samples: F) -> Self {
+        let mut node = Self {
+            sample_rate: convert(DEFAULT_SR),
+            ..Self::default()
+        };
+        node.set_samples(samples);
+        node
+    }
+
+    /// Set the cutoff frequency (in Hz).
+    pub fn set_cutoff(&mut self, cutoff: F) {
+        assert!(cutoff > F::zero());
+        self.cutoff = Some(cutoff);
+        self.samples = self.sample_rate / (F::TAU * cutoff);
+        self.coeff = exp(-F::one() / self.samples);
+    }
+
+    /// Set the time constant in samples (`samples` > 0).
+    pub fn set_samples(&mut self, samples: F) {
+        assert!(samples > F::zero());
+        self.cutoff = None;
+        self.samples = samples;
+        self.coeff = exp(-F::one() / samples);
+    }
+
+    /// Time constant in samples.
+    pub fn samples(&self) -> F {
+        self.samples
+    }
+}
+
+impl<F: Real> AudioNode for SmoothPole<F> {
+    const ID: u64 = 135;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.value = F::zero();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        if let Some(cutoff) = self.cutoff {
+            self.set_cutoff(cutoff);
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let x = convert(input[0]);
+        self.value = (F::one() - self.coeff) * x + self.coeff * self.value;
+        [convert(self.value)].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Center(cutoff) => self.set_cutoff(F::from_f32(*cutoff)),
+            Parameter::Time(time) => {
+                self.set_cutoff(F::one() / (F::TAU * F::from_f32(*time)));
+            }
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(
+            0,
+            input.at(0).filter(0.0, |r| {
+                let c = self.coeff.to_f64();
+                let f = frequency * f64::TAU / self.sample_rate.to_f64();
+                let z1 = Complex64::from_polar(1.0, -f);
+                r * ((1.0 - c) / (1.0 - c * z1))
+            }),
+        );
+        output
+    }
+}
+
 /// DC blocking filter with cutoff frequency in Hz.
 /// Setting: cutoff.
 /// - Input 0: signal
@@ -150,6 +279,15 @@ impl<F: Real> AudioNode for DCBlock<F> {
         [convert(y0)].into()
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        let x = convert(input[0]);
+        let y0 = x - self.x1 + self.coeff * self.y1;
+        self.x1 = x;
+        self.y1 = y0;
+        [convert(y0)].into()
+    }
+
     fn set(&mut self, setting: Setting) {
         if let Parameter::Center(cutoff) = setting.parameter() {
             self.set_cutoff(F::from_f32(*cutoff));
@@ -171,6 +309,91 @@ impl<F: Real> AudioNode for DCBlock<F> {
     }
 }
 
+/// Generator wrapper that keeps every output zero centered
+/// with a DC blocking filter (`DCBlock`) per output channel.
+/// The filters have a cutoff of 10 Hz. Settings are forwarded to the inner node.
+/// - Input(s): from inner node.
+/// - Output(s): zero centered outputs of inner node.
+#[derive(Clone)]
+pub struct DCSafe<X: AudioNode, F: Real> {
+    x: X,
+    blockers: Vec<DCBlock<F>>,
+}
+
+impl<X: AudioNode, F: Real> DCSafe<X, F> {
+    /// Wrap node `x`, blocking DC in each of its outputs.
+    pub fn new(x: X) -> Self {
+        let mut node = Self {
+            x,
+            blockers: vec![DCBlock::new(F::new(10)); X::Outputs::USIZE],
+        };
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node
+    }
+}
+
+impl<X: AudioNode, F: Real> AudioNode for DCSafe<X, F> {
+    const ID: u64 = 155;
+    type Inputs = X::Inputs;
+    type Outputs = X::Outputs;
+
+    fn reset(&mut self) {
+        self.x.reset();
+        for blocker in self.blockers.iter_mut() {
+            blocker.reset();
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.x.set_sample_rate(sample_rate);
+        for blocker in self.blockers.iter_mut() {
+            blocker.set_sample_rate(sample_rate);
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let output = self.x.tick(input);
+        Frame::generate(|i| self.blockers[i].filter_mono(output[i]))
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        self.x.process(size, input, output);
+        for (channel, blocker) in self.blockers.iter_mut().enumerate() {
+            for x in output.channel_f32_mut(channel)[..size].iter_mut() {
+                *x = blocker.filter_mono(*x);
+            }
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        self.x.set(setting);
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = self.x.route(input, frequency);
+        for (channel, blocker) in self.blockers.iter_mut().enumerate() {
+            let mut frame = SignalFrame::new(1);
+            frame.set(0, output.at(channel));
+            output.set(channel, blocker.route(&frame, frequency).at(0));
+        }
+        output
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage() + self.blockers.capacity() * core::mem::size_of::<DCBlock<F>>()
+    }
+}
+
 /// Pinking filter (3 dB/octave lowpass).
 /// - Input 0: input signal
 /// - Output 0: filtered signal
@@ -259,6 +482,139 @@ impl<F: Float> AudioNode for Pinkpass<F> {
     }
 }
 
+/// Number of pole-zero sections in the spectral tilt filter.
+const TILT_SECTIONS: usize = 8;
+
+/// Lowest pole frequency (Hz) of the spectral tilt filter.
+const TILT_LOW: f64 = 10.0;
+
+/// Spacing between pole frequencies of the spectral tilt filter (1.5 octaves).
+const TILT_SPACING: f64 = 2.828_427_124_746_190_3;
+
+/// Reference frequency (Hz) where the spectral tilt filter has unity gain.
+const TILT_REFERENCE: f64 = 1000.0;
+
+/// Spectral tilt filter with a tilt input in dB/octave.
+/// The tilt ranges from -6 dB/octave (brown) through -3 dB/octave (pink),
+/// 0 dB/octave (white) and 3 dB/octave (blue) to 6 dB/octave (violet).
+/// The slope is approximated by a cascade of first order pole-zero sections
+/// spaced 1.5 octaves apart, which yields the tilt from about 20 Hz up to 15 kHz.
+/// Gain is normalized to unity at 1 kHz.
+/// Coefficients are recalculated when the tilt changes.
+/// - Input 0: input signal
+/// - Input 1: tilt in dB/octave (-6...6)
+/// - Output 0: filtered signal
+#[derive(Clone)]
+pub struct Tiltpass<F: Float> {
+    /// Current tilt in dB/octave.
+    tilt: f32,
+    b0: [F; TILT_SECTIONS],
+    b1: [F; TILT_SECTIONS],
+    a1: [F; TILT_SECTIONS],
+    x1: [F; TILT_SECTIONS],
+    y1: [F; TILT_SECTIONS],
+    gain: F,
+    sample_rate: f64,
+}
+
+impl<F: Float> Tiltpass<F> {
+    /// Create spectral tilt filter with initial `tilt` in dB/octave.
+    pub fn new(tilt: f32) -> Self {
+        let mut node = Self {
+            tilt,
+            b0: [F::one(); TILT_SECTIONS],
+            b1: [F::zero(); TILT_SECTIONS],
+            a1: [F::zero(); TILT_SECTIONS],
+            x1: [F::zero(); TILT_SECTIONS],
+            y1: [F::zero(); TILT_SECTIONS],
+            gain: F::one(),
+            sample_rate: DEFAULT_SR,
+        };
+        node.set_tilt(tilt);
+        node
+    }
+
+    /// Tilt in dB/octave.
+    #[inline]
+    pub fn tilt(&self) -> f32 {
+        self.tilt
+    }
+
+    /// Set tilt in dB/octave (-6...6).
+    pub fn set_tilt(&mut self, tilt: f32) {
+        self.tilt = clamp(-6.0, 6.0, tilt);
+        // Each section is a shelf from a pole to a zero placed so that
+        // the average slope over the section spacing equals the tilt.
+        let exponent = self.tilt as f64 / (20.0 * log10(2.0));
+        let ratio = pow(TILT_SPACING, -exponent);
+        let nyquist = 0.49 * self.sample_rate;
+        let warp = |f: f64| tan(f64::PI * min(f, nyquist) / self.sample_rate);
+        for i in 0..TILT_SECTIONS {
+            let pole = TILT_LOW * pow(TILT_SPACING, i as f64);
+            let wp = warp(pole);
+            let wz = warp(pole * ratio);
+            self.b0[i] = F::from_f64(wp / wz * (wz + 1.0) / (wp + 1.0));
+            self.b1[i] = F::from_f64(wp / wz * (wz - 1.0) / (wp + 1.0));
+            self.a1[i] = F::from_f64((wp - 1.0) / (wp + 1.0));
+        }
+        self.gain = F::one();
+        let reference = self.tilt_response(TILT_REFERENCE).norm();
+        self.gain = F::from_f64(1.0 / reference);
+    }
+
+    /// Frequency response at `frequency` Hz.
+    fn tilt_response(&self, frequency: f64) -> Complex64 {
+        let z1 = Complex64::from_polar(1.0, -frequency * f64::TAU / self.sample_rate);
+        let mut response = Complex64::new(self.gain.to_f64(), 0.0);
+        for i in 0..TILT_SECTIONS {
+            response *=
+                (self.b0[i].to_f64() + self.b1[i].to_f64() * z1) / (1.0 + self.a1[i].to_f64() * z1);
+        }
+        response
+    }
+}
+
+impl<F: Float> AudioNode for Tiltpass<F> {
+    const ID: u64 = 127;
+    type Inputs = U2;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.x1 = [F::zero(); TILT_SECTIONS];
+        self.y1 = [F::zero(); TILT_SECTIONS];
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.set_tilt(self.tilt);
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if input[1] != self.tilt {
+            self.set_tilt(input[1]);
+        }
+        let mut x: F = convert(input[0]);
+        for i in 0..TILT_SECTIONS {
+            let y = self.b0[i] * x + self.b1[i] * self.x1[i] - self.a1[i] * self.y1[i];
+            self.x1[i] = x;
+            self.y1[i] = y;
+            x = y;
+        }
+        [convert(x * self.gain)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        // Pretend the tilt is constant.
+        let response = self.tilt_response(frequency);
+        output.set(0, input.at(0).filter(0.0, |r| r * response));
+        output
+    }
+}
+
 /// 1st order allpass filter.
 /// Setting: delay.
 /// The number of inputs is `N`, either `U1` or `U2`.
@@ -409,6 +765,21 @@ impl<F: Real, N: Size<f32>> AudioNode for Highpole<F, N> {
         [convert(y0)].into()
     }
 
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        if N::USIZE > 1 {
+            let cutoff: F = convert(input[1]);
+            if cutoff != self.cutoff {
+                self.set_cutoff(cutoff);
+            }
+        }
+        let x0 = convert(input[0]);
+        let y0 = self.coeff * (self.y1 + x0 - self.x1);
+        self.x1 = x0;
+        self.y1 = y0;
+        [convert(y0)].into()
+    }
+
     fn set(&mut self, setting: Setting) {
         if let Parameter::Center(cutoff) = setting.parameter() {
             self.set_cutoff(F::from_f32(*cutoff));
@@ -429,3 +800,226 @@ impl<F: Real, N: Size<f32>> AudioNode for Highpole<F, N> {
         output
     }
 }
+
+/// Number of integration steps per sample in the analog filter models.
+const ANALOG_STEPS: usize = 2;
+
+/// Amplifier gain of the Sallen-Key lowpass at resonance 1.
+/// The linear filter self-oscillates at an amplifier gain of 3.
+const SALLEN_KEY_GAIN: f64 = 2.2;
+
+/// Sallen-Key lowpass filter (2nd order) with equal components
+/// and a saturating amplifier, in the style of the Korg MS-20.
+/// Resonance comes from positive feedback through the amplifier,
+/// so passband gain rises with resonance from 1 to 3.2.
+/// The amplifier saturates softly at unit level. This gives an aggressive,
+/// distorted resonance and limits the amplitude of self-oscillation,
+/// which begins when resonance exceeds 10/11 (about 0.91).
+/// The number of inputs is `N`, either `U1` or `U3`.
+/// - Input 0: input signal
+/// - Input 1 (optional): cutoff frequency (Hz)
+/// - Input 2 (optional): resonance in 0...1
+/// - Output 0: filtered signal
+#[derive(Default, Clone)]
+pub struct SallenKey<F: Real, N: Size<f32>> {
+    _marker: PhantomData<N>,
+    cutoff: F,
+    resonance: F,
+    sample_rate: F,
+    h: F,
+    gain: F,
+    y: [F; 2],
+}
+
+impl<F: Real, N: Size<f32>> SallenKey<F, N> {
+    /// Create new Sallen-Key lowpass filter with `cutoff` frequency (Hz) and `resonance` in 0...1.
+    pub fn new(cutoff: F, resonance: F) -> Self {
+        let mut node = Self {
+            sample_rate: convert(DEFAULT_SR),
+            ..Self::default()
+        };
+        node.set_cutoff_resonance(cutoff, resonance);
+        node
+    }
+
+    /// Set cutoff frequency (in Hz) and resonance (in 0...1).
+    /// This has no effect if the filter has cutoff and resonance inputs.
+    #[inline]
+    pub fn set_cutoff_resonance(&mut self, cutoff: F, resonance: F) {
+        self.cutoff = cutoff;
+        self.resonance = resonance;
+        self.h = min(
+            F::TAU * cutoff / (self.sample_rate * F::new(ANALOG_STEPS as i64)),
+            F::one(),
+        );
+        self.gain = F::one() + F::from_f64(SALLEN_KEY_GAIN) * clamp01(resonance);
+    }
+
+    /// Q factor of the linearized filter. Infinite or negative past the self-oscillation threshold.
+    #[inline]
+    pub fn q(&self) -> F {
+        F::one() / (F::new(3) - self.gain)
+    }
+
+    /// Passband gain of the linearized filter.
+    #[inline]
+    pub fn gain(&self) -> F {
+        self.gain
+    }
+}
+
+impl<F: Real, N: Size<f32>> AudioNode for SallenKey<F, N> {
+    const ID: u64 = 142;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.y = [F::zero(); 2];
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.set_cutoff_resonance(self.cutoff, self.resonance);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 {
+            self.set_cutoff_resonance(convert(input[1]), convert(input[2]));
+        }
+        let x: F = convert(input[0]);
+        let gain = self.gain;
+        // State: voltage across the feedback capacitor and voltage of the grounded capacitor.
+        ode_step(&mut self.y, self.h, ANALOG_STEPS, |y| {
+            let output = tanh(gain * y[1]);
+            let a = y[0] + output;
+            [x - F::new(2) * a + y[1], a - y[1]]
+        });
+        [convert(tanh(self.gain * self.y[1]))].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Center(cutoff) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), self.resonance)
+            }
+            Parameter::CenterQ(cutoff, resonance) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), F::from_f32(*resonance))
+            }
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
+
+/// Q factor of the OTA lowpass at resonance 1.
+const OTA_MAX_Q: f64 = 10.0;
+
+/// OTA cascade lowpass filter (2nd order), as found in many polysynths.
+/// Two transconductance stages with saturating inputs are placed
+/// in a negative feedback loop. Resonance ranges from 0 to 1
+/// and maps exponentially to Q from 0.5 to 10. Unlike the Sallen-Key filter,
+/// the loop cannot self-oscillate: loud resonant peaks are compressed
+/// by the saturating stages, giving a round, stable resonance.
+/// Passband gain is normalized to unity.
+/// The number of inputs is `N`, either `U1` or `U3`.
+/// - Input 0: input signal
+/// - Input 1 (optional): cutoff frequency (Hz)
+/// - Input 2 (optional): resonance in 0...1
+/// - Output 0: filtered signal
+#[derive(Default, Clone)]
+pub struct OtaLowpass<F: Real, N: Size<f32>> {
+    _marker: PhantomData<N>,
+    cutoff: F,
+    resonance: F,
+    sample_rate: F,
+    h: F,
+    k: F,
+    y: [F; 2],
+}
+
+impl<F: Real, N: Size<f32>> OtaLowpass<F, N> {
+    /// Create new OTA lowpass filter with `cutoff` frequency (Hz) and `resonance` in 0...1.
+    pub fn new(cutoff: F, resonance: F) -> Self {
+        let mut node = Self {
+            sample_rate: convert(DEFAULT_SR),
+            ..Self::default()
+        };
+        node.set_cutoff_resonance(cutoff, resonance);
+        node
+    }
+
+    /// Set cutoff frequency (in Hz) and resonance (in 0...1).
+    /// This has no effect if the filter has cutoff and resonance inputs.
+    #[inline]
+    pub fn set_cutoff_resonance(&mut self, cutoff: F, resonance: F) {
+        self.cutoff = cutoff;
+        self.resonance = resonance;
+        let q = F::from_f64(0.5) * pow(F::from_f64(2.0 * OTA_MAX_Q), clamp01(resonance));
+        // Feedback gain `k` places the poles at `-1 +- i sqrt(k)` in stage units,
+        // so the stages run at `cutoff / sqrt(1 + k)`.
+        self.k = F::new(4) * q * q - F::one();
+        self.h = min(
+            F::TAU * cutoff
+                / (self.sample_rate * F::new(ANALOG_STEPS as i64) * sqrt(F::one() + self.k)),
+            F::one(),
+        );
+    }
+
+    /// Q factor of the linearized filter.
+    #[inline]
+    pub fn q(&self) -> F {
+        sqrt(F::one() + self.k) * F::from_f64(0.5)
+    }
+}
+
+impl<F: Real, N: Size<f32>> AudioNode for OtaLowpass<F, N> {
+    const ID: u64 = 143;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.y = [F::zero(); 2];
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.set_cutoff_resonance(self.cutoff, self.resonance);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 {
+            self.set_cutoff_resonance(convert(input[1]), convert(input[2]));
+        }
+        let x: F = convert(input[0]);
+        let k = self.k;
+        ode_step(&mut self.y, self.h, ANALOG_STEPS, |y| {
+            [tanh(x - k * y[1] - y[0]), tanh(y[0] - y[1])]
+        });
+        [convert((F::one() + self.k) * self.y[1])].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Center(cutoff) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), self.resonance)
+            }
+            Parameter::CenterQ(cutoff, resonance) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), F::from_f32(*resonance))
+            }
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
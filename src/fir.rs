@@ -2,10 +2,14 @@
 
 use super::audionode::*;
 use super::combinator::*;
+use super::math::*;
 use super::signal::*;
 use super::*;
-use num_complex::Complex64;
+use num_complex::{Complex32, Complex64};
 use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// FIR filter.
 /// - Input 0: input signal
@@ -87,3 +91,283 @@ impl<N: Size<f32>> AudioNode for Fir<N> {
         output
     }
 }
+
+/// Design a linear phase windowed sinc lowpass kernel with an odd number of `taps`
+/// and unity gain at DC. `cutoff` is the -6 dB point relative to the sample rate.
+fn sinc_lowpass(cutoff: f64, taps: usize) -> Vec<f32> {
+    let middle = (taps - 1) as f64 / 2.0;
+    let mut kernel: Vec<f64> = (0..taps)
+        .map(|i| {
+            let x = i as f64 - middle;
+            let sinc = if x == 0.0 {
+                2.0 * cutoff
+            } else {
+                sin(f64::TAU * cutoff * x) / (f64::PI * x)
+            };
+            // Blackman window.
+            let phase = f64::TAU * i as f64 / (taps - 1) as f64;
+            sinc * (0.42 - 0.5 * cos(phase) + 0.08 * cos(2.0 * phase))
+        })
+        .collect();
+    let sum: f64 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|x| *x /= sum);
+    kernel.into_iter().map(|x| x as f32).collect()
+}
+
+/// Partition length of the FFT convolution in `LinearPhaseCrossover`.
+const PARTITION: usize = 64;
+
+/// Linear phase crossover. Splits the input into `B` bands with FIR filters
+/// that have no phase distortion. The bands are complementary:
+/// their sum is the input delayed by the latency of the crossover.
+///
+/// The lowpass filters are windowed sinc (Blackman) kernels. The latency is half
+/// the filter length, `(taps - 1) / 2` samples. Longer filters have steeper slopes
+/// at the cost of latency. Like all linear phase filters, the bands ring symmetrically,
+/// so transients are preceded by pre-ringing near the crossover frequencies.
+///
+/// The filters are applied with uniformly partitioned FFT convolution that adds no latency:
+/// the first 64 taps of each kernel are convolved directly and the rest
+/// in the frequency domain, one 128-point inverse FFT per band every 64 samples.
+/// The cost per sample and crossover frequency is about 64 multiply-adds plus
+/// 4 multiply-adds per 64 taps, so kernels of thousands of taps are practical.
+/// - Input 0: signal
+/// - Outputs: bands from lowest to highest
+#[derive(Clone)]
+pub struct LinearPhaseCrossover<B: Size<f32>> {
+    /// Crossover frequencies in Hz in ascending order.
+    frequencies: Vec<f32>,
+    /// Lowpass kernels, one per crossover frequency.
+    kernels: Vec<Vec<f32>>,
+    /// Spectra of the kernel partitions after the first, one vector per kernel.
+    /// Spectra are packed with the Nyquist frequency in the imaginary part of DC.
+    partitions: Vec<Vec<Complex32>>,
+    /// Spectra of the latest input segments in a ring buffer, one partition each.
+    spectra: Vec<Complex32>,
+    /// Partition of the latest spectrum in the ring buffer.
+    spectrum_index: usize,
+    /// The previous block followed by the current block of input.
+    segment: Vec<f32>,
+    /// Position in the current block.
+    position: usize,
+    /// Contribution of the later kernel partitions to the current block, one per kernel.
+    tails: Vec<Vec<f32>>,
+    /// Delay line for the highest band.
+    line: Vec<f32>,
+    /// Position of the latest sample in the delay line.
+    index: usize,
+    /// Scratch space for the forward and inverse FFTs.
+    scratch: Vec<f32>,
+    scratch_complex: Vec<Complex32>,
+    taps: usize,
+    sample_rate: f64,
+    _marker: core::marker::PhantomData<B>,
+}
+
+impl<B: Size<f32>> LinearPhaseCrossover<B> {
+    /// Create a new linear phase crossover. There must be `B - 1` crossover `frequencies` (in Hz)
+    /// in ascending order. The number of `taps` is rounded up to an odd number.
+    pub fn new(frequencies: &[f32], taps: usize) -> Self {
+        assert_eq!(frequencies.len() + 1, B::USIZE);
+        assert!(frequencies.windows(2).all(|w| w[0] < w[1]));
+        let mut node = Self {
+            frequencies: frequencies.to_vec(),
+            kernels: Vec::new(),
+            partitions: Vec::new(),
+            spectra: Vec::new(),
+            spectrum_index: 0,
+            segment: vec![0.0; 2 * PARTITION],
+            position: 0,
+            tails: Vec::new(),
+            line: Vec::new(),
+            index: 0,
+            scratch: vec![0.0; 2 * PARTITION],
+            scratch_complex: vec![Complex32::ZERO; 2 * PARTITION],
+            taps: max(3, taps | 1),
+            sample_rate: DEFAULT_SR,
+            _marker: core::marker::PhantomData,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Crossover frequencies in Hz.
+    #[inline]
+    pub fn frequencies(&self) -> &[f32] {
+        &self.frequencies
+    }
+
+    /// Filter length in samples. This is an odd number.
+    #[inline]
+    pub fn taps(&self) -> usize {
+        self.taps
+    }
+
+    /// Latency in samples, which is half the filter length.
+    #[inline]
+    pub fn delay(&self) -> usize {
+        (self.taps - 1) / 2
+    }
+
+    /// Number of kernel partitions convolved in the frequency domain.
+    #[inline]
+    fn tail_partitions(&self) -> usize {
+        self.taps.div_ceil(PARTITION) - 1
+    }
+
+    /// The current block is complete: transform it and compute
+    /// the contribution of the later kernel partitions to the next block.
+    fn end_block(&mut self) {
+        let tail_partitions = self.tail_partitions();
+        if tail_partitions > 0 {
+            self.spectrum_index = (self.spectrum_index + 1) % tail_partitions;
+            self.scratch.copy_from_slice(&self.segment);
+            let spectrum = super::fft::real_fft(&mut self.scratch);
+            let offset = self.spectrum_index * PARTITION;
+            self.spectra[offset..offset + PARTITION].copy_from_slice(spectrum);
+            for (partitions, tail) in self.partitions.iter().zip(self.tails.iter_mut()) {
+                let output = &mut self.scratch_complex;
+                output.fill(Complex32::ZERO);
+                // Partition `p` (1 ≤ p ≤ tail_partitions) meets the input segment `p - 1` blocks ago.
+                for p in 0..tail_partitions {
+                    let offset =
+                        ((self.spectrum_index + tail_partitions - p) % tail_partitions) * PARTITION;
+                    let x = &self.spectra[offset..offset + PARTITION];
+                    let h = &partitions[p * PARTITION..(p + 1) * PARTITION];
+                    // DC and Nyquist are packed into the first bin.
+                    output[0].re += x[0].re * h[0].re;
+                    output[0].im += x[0].im * h[0].im;
+                    for i in 1..PARTITION {
+                        output[i] += x[i] * h[i];
+                    }
+                }
+                // Unpack into a full spectrum for the inverse FFT.
+                output[PARTITION] = Complex32::new(output[0].im, 0.0);
+                output[0].im = 0.0;
+                super::fft::fix_negative(output);
+                super::fft::inverse_fft(output);
+                // Overlap-save: the second half is the linear convolution.
+                for (y, x) in tail.iter_mut().zip(output[PARTITION..].iter()) {
+                    *y = x.re;
+                }
+            }
+        }
+        self.segment.copy_within(PARTITION.., 0);
+        self.position = 0;
+    }
+}
+
+impl<B: Size<f32>> AudioNode for LinearPhaseCrossover<B> {
+    const ID: u64 = 146;
+    type Inputs = U1;
+    type Outputs = B;
+
+    fn reset(&mut self) {
+        self.spectra.fill(Complex32::ZERO);
+        self.spectrum_index = 0;
+        self.segment.fill(0.0);
+        self.position = 0;
+        self.tails.iter_mut().for_each(|tail| tail.fill(0.0));
+        self.line.fill(0.0);
+        self.index = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.kernels = self
+            .frequencies
+            .iter()
+            .map(|f| sinc_lowpass(*f as f64 / sample_rate, self.taps))
+            .collect();
+        let tail_partitions = self.tail_partitions();
+        self.partitions = self
+            .kernels
+            .iter()
+            .map(|kernel| {
+                let mut spectra = Vec::with_capacity(tail_partitions * PARTITION);
+                for p in 1..=tail_partitions {
+                    let end = min((p + 1) * PARTITION, kernel.len());
+                    self.scratch.fill(0.0);
+                    self.scratch[..end - p * PARTITION]
+                        .copy_from_slice(&kernel[p * PARTITION..end]);
+                    spectra.extend_from_slice(super::fft::real_fft(&mut self.scratch));
+                }
+                spectra
+            })
+            .collect();
+        self.spectra = vec![Complex32::ZERO; tail_partitions * PARTITION];
+        self.tails = vec![vec![0.0; PARTITION]; self.kernels.len()];
+        self.line = vec![0.0; self.delay() + 1];
+        self.reset();
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let latest = PARTITION + self.position;
+        self.segment[latest] = input[0];
+        self.index = if self.index == 0 {
+            self.line.len() - 1
+        } else {
+            self.index - 1
+        };
+        self.line[self.index] = input[0];
+        // The first partition of each kernel is convolved with the latest samples directly.
+        let head = min(PARTITION, self.taps);
+        let window = &self.segment[latest + 1 - head..=latest];
+        let mut output = Frame::default();
+        // Each band is the difference of adjacent lowpass filters.
+        let mut below = 0.0;
+        for (band, (kernel, tail)) in self.kernels.iter().zip(self.tails.iter()).enumerate() {
+            let lowpass: f32 = kernel[..head]
+                .iter()
+                .zip(window.iter().rev())
+                .map(|(w, x)| w * x)
+                .sum::<f32>()
+                + tail[self.position];
+            output[band] = lowpass - below;
+            below = lowpass;
+        }
+        output[B::USIZE - 1] = self.line[(self.index + self.delay()) % self.line.len()] - below;
+        self.position += 1;
+        if self.position == PARTITION {
+            self.end_block();
+        }
+        output
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let z1 = Complex64::from_polar(1.0, -f64::TAU * frequency / self.sample_rate);
+        let delay = self.delay();
+        let mut output = SignalFrame::new(self.outputs());
+        let mut below = Complex64::new(0.0, 0.0);
+        for band in 0..B::USIZE {
+            let lowpass = if band < self.kernels.len() {
+                let mut z = Complex64::new(1.0, 0.0);
+                let mut x = Complex64::default();
+                for w in self.kernels[band].iter() {
+                    x += z * w.to_f64();
+                    z *= z1;
+                }
+                x
+            } else {
+                z1.powu(delay as u32)
+            };
+            let response = lowpass - below;
+            output.set(band, input.at(0).filter(delay as f64, |r| r * response));
+            below = lowpass;
+        }
+        output
+    }
+
+    fn memory_usage(&self) -> usize {
+        let floats = self.segment.capacity()
+            + self.line.capacity()
+            + self.scratch.capacity()
+            + self.kernels.iter().map(|k| k.capacity()).sum::<usize>()
+            + self.tails.iter().map(|t| t.capacity()).sum::<usize>();
+        let complexes = self.spectra.capacity()
+            + self.scratch_complex.capacity()
+            + self.partitions.iter().map(|p| p.capacity()).sum::<usize>();
+        floats * core::mem::size_of::<f32>() + complexes * core::mem::size_of::<Complex32>()
+    }
+}
@@ -231,4 +231,8 @@ impl<
     fn footprint(&self) -> usize {
         core::mem::size_of::<Self>()
     }
+
+    fn memory_usage(&self) -> usize {
+        self.voices.capacity() * core::mem::size_of::<Voice>() + self.sequencer.memory_usage()
+    }
 }
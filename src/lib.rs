@@ -830,6 +830,7 @@ pub mod audiounit;
 pub mod biquad;
 pub mod biquad_bank;
 pub mod buffer;
+pub mod bypass;
 pub mod combinator;
 pub mod delay;
 pub mod denormal;
@@ -848,9 +849,11 @@ pub mod math;
 pub mod moog;
 pub mod net;
 pub mod noise;
+pub mod onset;
 pub mod oscillator;
 pub mod oversample;
 pub mod pan;
+pub mod pitch;
 pub mod prelude;
 pub mod realnet;
 pub mod realseq;
@@ -858,18 +861,22 @@ pub mod resample;
 pub mod resynth;
 pub mod reverb;
 pub mod rez;
+pub mod rhythm;
 pub mod ring;
+pub mod rotary;
 pub mod sequencer;
 pub mod setting;
 pub mod shape;
 pub mod shared;
 pub mod signal;
+pub mod sizes;
 pub mod slot;
 pub mod snoop;
 pub mod sound;
 pub mod svf;
 pub mod system;
 pub mod vertex;
+pub mod vocoder;
 pub mod wave;
 pub mod wavetable;
 
@@ -889,3 +896,6 @@ pub mod write;
 
 #[cfg(all(feature = "std", feature = "files"))]
 pub mod read;
+
+#[cfg(all(feature = "std", feature = "midi"))]
+pub mod midi;
@@ -295,11 +295,45 @@ pub fn db_amp<T: Real>(db: T) -> T {
 }
 
 /// Convert amplitude `gain` (`gain` > 0) to decibels. Gain 1.0 = 0 dB (unity gain).
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// assert!(amp_db(2.0) > 6.0205 && amp_db(2.0) < 6.0207);
+/// ```
 #[inline]
 pub fn amp_db<T: Real>(gain: T) -> T {
     log10(gain) * T::new(20)
 }
 
+/// Convert decibels to gain (aka amplitude) in SIMD. 0 dB = 1.0 (unity gain).
+/// For use in block processing.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// let gain = db_amp_simd(F32x::splat(-6.0));
+/// assert!(gain.reduce_add() > 8.0 * 0.5011 && gain.reduce_add() < 8.0 * 0.5013);
+/// ```
+#[inline]
+pub fn db_amp_simd(db: F32x) -> F32x {
+    (db * F32x::splat(core::f32::consts::LN_10 / 20.0)).exp()
+}
+
+/// Convert amplitude `gain` (`gain` > 0) to decibels in SIMD. Gain 1.0 = 0 dB (unity gain).
+/// For use in block processing.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// let db = amp_db_simd(F32x::splat(0.5));
+/// assert!(db.reduce_add() > 8.0 * -6.0207 && db.reduce_add() < 8.0 * -6.0205);
+/// ```
+#[inline]
+pub fn amp_db_simd(gain: F32x) -> F32x {
+    gain.ln() * F32x::splat(20.0 / core::f32::consts::LN_10)
+}
+
 /// A-weighted response function.
 /// Returns equal loudness amplitude response of the human ear at `f` Hz.
 /// Normalized to 1.0 at 1 kHz.
@@ -521,6 +555,18 @@ pub fn semitone_ratio<T: Real>(x: T) -> T {
     exp2(x * (T::one() / T::new(12)))
 }
 
+/// Converts from interval in cents (hundredths of a semitone) to frequency ratio.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// assert_eq!(cents_ratio(1200.0), 2.0);
+/// ```
+#[inline]
+pub fn cents_ratio<T: Real>(x: T) -> T {
+    exp2(x * (T::one() / T::new(1200)))
+}
+
 /// SplitMix hash as an indexed random number generator.
 /// Using successive values of the hash as an RNG
 /// passes statistical tests of randomness.
@@ -568,7 +614,7 @@ pub fn hash2(x: u64) -> u64 {
 
 /// Convert MIDI note number to frequency in Hz. Returns 440 Hz for A_4 (note number 69).
 /// The lowest key on a grand piano is A_0 at 27.5 Hz (note number 21).
-/// Note number 0 is C_-1.
+/// Note number 0 is C_-1. Fractional note numbers are supported.
 ///
 /// ### Example (Major Chord)
 /// ```
@@ -580,12 +626,43 @@ pub fn midi_hz<T: Real>(x: T) -> T {
     T::new(440) * exp2((x - T::new(69)) / T::new(12))
 }
 
-/// Convert BPM (beats per minute) to Hz.
+/// Convert frequency in Hz to fractional MIDI note number. Inverse of `midi_hz`.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// assert_eq!(hz_midi(440.0), 69.0);
+/// assert!(hz_midi(midi_hz(60.5)) > 60.4999 && hz_midi(midi_hz(60.5)) < 60.5001);
+/// ```
+#[inline]
+pub fn hz_midi<T: Real>(hz: T) -> T {
+    T::new(69) + T::new(12) * log2(hz / T::new(440))
+}
+
+/// Convert BPM (beats per minute) to Hz (beats per second).
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// assert_eq!(bpm_hz(120.0), 2.0);
+/// ```
 #[inline]
 pub fn bpm_hz<T: Num>(bpm: T) -> T {
     bpm * (T::one() / T::new(60))
 }
 
+/// Convert Hz (beats per second) to BPM (beats per minute). Inverse of `bpm_hz`.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// assert_eq!(hz_bpm(2.0), 120.0);
+/// ```
+#[inline]
+pub fn hz_bpm<T: Num>(hz: T) -> T {
+    hz * T::new(60)
+}
+
 /// Pico sized hasher.
 /// It is used in computing deterministic pseudorandom phase hashes.
 #[derive(Default, Clone)]
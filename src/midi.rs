@@ -0,0 +1,421 @@
+//! MIDI integration via the midly crate.
+
+use super::audiounit::*;
+use super::math::*;
+use super::sequencer::*;
+use super::shared::*;
+use std::fmt;
+use std::path::Path;
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use midly::live::LiveEvent;
+use midly::{MetaMessage, MidiMessage, Smf, Timing, TrackEventKind};
+
+/// Error from loading a MIDI file.
+#[derive(Debug)]
+pub enum MidiError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not a valid standard MIDI file.
+    Parse(midly::Error),
+}
+
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiError::Io(error) => write!(f, "MIDI file could not be read: {}", error),
+            MidiError::Parse(error) => write!(f, "MIDI file could not be parsed: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MidiError {}
+
+impl From<std::io::Error> for MidiError {
+    fn from(error: std::io::Error) -> Self {
+        MidiError::Io(error)
+    }
+}
+
+impl From<midly::Error> for MidiError {
+    fn from(error: midly::Error) -> Self {
+        MidiError::Parse(error)
+    }
+}
+
+/// Result of loading a MIDI file.
+pub type MidiResult<T> = Result<T, MidiError>;
+
+/// A note read from a MIDI file. Times are in seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiNote {
+    /// MIDI channel (0...15).
+    pub channel: u8,
+    /// MIDI key (0...127). Key 69 is A4 (440 Hz).
+    pub key: u8,
+    /// Note-on velocity (1...127).
+    pub velocity: u8,
+    /// Start time in seconds.
+    pub start_time: f64,
+    /// End time in seconds.
+    pub end_time: f64,
+}
+
+impl MidiNote {
+    /// Duration of the note in seconds.
+    pub fn duration(&self) -> f64 {
+        self.end_time - self.start_time
+    }
+}
+
+/// Parse notes from a standard MIDI file in `bytes`, sorted by start time.
+/// All tracks are merged. Note-on events with zero velocity count as note-offs.
+/// Tempo changes are respected for metrical timing;
+/// SMPTE timecode timing ignores tempo.
+/// Notes left hanging at the end of the file end at the last event.
+/// Other events, such as controllers, program changes and SysEx, are ignored.
+pub fn midi_notes(bytes: &[u8]) -> MidiResult<Vec<MidiNote>> {
+    let smf = Smf::parse(bytes)?;
+
+    // Merge all tracks into a single list of events in absolute ticks.
+    // The sort is stable, so events at the same tick stay in track order.
+    let mut events = Vec::new();
+    for track in smf.tracks.iter() {
+        let mut ticks = 0u64;
+        for event in track.iter() {
+            ticks += event.delta.as_int() as u64;
+            events.push((ticks, event.kind));
+        }
+    }
+    events.sort_by_key(|(ticks, _)| *ticks);
+
+    // Default tempo is 120 BPM.
+    let mut tempo = 500_000.0;
+    let mut last_ticks = 0u64;
+    let mut time = 0.0;
+    let mut notes = Vec::new();
+    // Pending notes are paired with note-offs in first in, first out order.
+    let mut pending: HashMap<(u8, u8), Vec<(f64, u8)>> = HashMap::new();
+
+    for (ticks, kind) in events {
+        let delta = (ticks - last_ticks) as f64;
+        time += match smf.header.timing {
+            Timing::Metrical(ticks_per_beat) => {
+                delta * tempo * 1.0e-6 / ticks_per_beat.as_int() as f64
+            }
+            Timing::Timecode(fps, subframes) => delta / (fps.as_f32() as f64 * subframes as f64),
+        };
+        last_ticks = ticks;
+        match kind {
+            TrackEventKind::Meta(MetaMessage::Tempo(microseconds)) => {
+                tempo = microseconds.as_int() as f64;
+            }
+            TrackEventKind::Midi { channel, message } => {
+                let channel = channel.as_int();
+                match message {
+                    MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => {
+                        pending
+                            .entry((channel, key.as_int()))
+                            .or_default()
+                            .push((time, vel.as_int()));
+                    }
+                    MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. } => {
+                        if let Some(stack) = pending.get_mut(&(channel, key.as_int())) {
+                            if !stack.is_empty() {
+                                let (start_time, velocity) = stack.remove(0);
+                                notes.push(MidiNote {
+                                    channel,
+                                    key: key.as_int(),
+                                    velocity,
+                                    start_time,
+                                    end_time: time,
+                                });
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+    for ((channel, key), stack) in pending {
+        for (start_time, velocity) in stack {
+            notes.push(MidiNote {
+                channel,
+                key,
+                velocity,
+                start_time,
+                end_time: time,
+            });
+        }
+    }
+    notes.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    Ok(notes)
+}
+
+impl Sequencer {
+    /// Load a standard MIDI file from `path` and schedule one event per note.
+    /// `voice_factory` creates the unit for each note; it must have zero inputs
+    /// and the same number of outputs as the sequencer.
+    /// Times are absolute, starting from zero.
+    /// Returns the number of scheduled events.
+    ///
+    /// Supported events are note-ons, note-offs and tempo changes.
+    /// Other events, such as controllers, pitch bend and SysEx, are ignored.
+    /// Each event ends at its note-off without fades, so the voice should shape
+    /// its own attack. Any release tail is cut at the note-off.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer::new(false, 1);
+    /// sequencer
+    ///     .load_midi("song.mid", |note| {
+    ///         Box::new(sine_hz(midi_hz(note.key as f32)) * (note.velocity as f32 / 127.0))
+    ///     })
+    ///     .expect("Could not load MIDI file");
+    /// ```
+    pub fn load_midi<P, F>(&mut self, path: P, voice_factory: F) -> MidiResult<usize>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&MidiNote) -> Box<dyn AudioUnit>,
+    {
+        let bytes = std::fs::read(path)?;
+        self.load_midi_slice(&bytes, voice_factory)
+    }
+
+    /// Load a standard MIDI file from `bytes` and schedule one event per note.
+    /// See [`Sequencer::load_midi`]. Returns the number of scheduled events.
+    pub fn load_midi_slice<F>(&mut self, bytes: &[u8], mut voice_factory: F) -> MidiResult<usize>
+    where
+        F: FnMut(&MidiNote) -> Box<dyn AudioUnit>,
+    {
+        let notes = midi_notes(bytes)?;
+        for note in notes.iter() {
+            let unit = voice_factory(note);
+            self.push(note.start_time, note.end_time, Fade::Smooth, 0.0, 0.0, unit);
+        }
+        Ok(notes.len())
+    }
+}
+
+/// Controls for a single voice updated by [`MidiControl`].
+#[derive(Clone)]
+pub struct MidiVoice {
+    /// Note frequency in Hz, not including pitch bend.
+    pub frequency: Shared,
+    /// Note-on velocity in 0...1.
+    pub velocity: Shared,
+    /// Gate: 1 while the note is held, 0 after it is released.
+    /// Suitable as input to `adsr_live`.
+    pub gate: Shared,
+    /// Key that is playing or was last played.
+    key: Option<u8>,
+    /// Order of the last note-on or note-off, for voice allocation.
+    age: u64,
+}
+
+impl MidiVoice {
+    fn new() -> Self {
+        Self {
+            frequency: Shared::new(440.0),
+            velocity: Shared::new(0.0),
+            gate: Shared::new(0.0),
+            key: None,
+            age: 0,
+        }
+    }
+
+    /// Key that is held down on this voice, if any.
+    pub fn key(&self) -> Option<u8> {
+        if self.gate.value() > 0.0 {
+            self.key
+        } else {
+            None
+        }
+    }
+}
+
+/// Translates live MIDI messages, for example, from `midir`,
+/// into updates on a set of shared variables.
+/// Messages are given as raw bytes to [`MidiControl::handle`].
+///
+/// Notes are allocated to a fixed number of voices. Each voice has shared
+/// frequency, velocity and gate variables (see [`MidiVoice`]).
+/// A note-on takes the voice released longest ago; if all voices are held,
+/// the oldest note is stolen. A note-on with zero velocity is a note-off.
+///
+/// Pitch bend is available as a frequency factor in [`MidiControl::pitch_bend`],
+/// shared by all voices, with a default range of 2 semitones.
+/// The mod wheel (CC 1) is available in [`MidiControl::mod_wheel`] in 0...1.
+/// Other continuous controllers are mapped to shared variables with
+/// [`MidiControl::map_control`]: the controller value 0...127 is scaled linearly
+/// to the range given in the mapping.
+///
+/// ### Example: Monophonic Synth Controlled By MIDI
+/// ```
+/// use fundsp::hacker::*;
+/// use fundsp::midi::*;
+/// let mut control = MidiControl::new(1);
+/// let voice = control.voice(0).clone();
+/// let cutoff = shared(1000.0);
+/// control.map_control(74, &cutoff, 100.0, 10000.0);
+/// let synth = (var(&voice.frequency) * var(control.pitch_bend()) >> saw())
+///     * (var(&voice.gate) >> adsr_live(0.01, 0.2, 0.5, 0.3))
+///     * var(&voice.velocity);
+/// let _synth = (synth | var(&cutoff)) >> lowpole();
+/// // In a `midir` input callback, pass each message on to the controller.
+/// control.handle(&[0x90, 60, 100]);
+/// assert!(voice.gate.value() == 1.0);
+/// ```
+pub struct MidiControl {
+    voices: Vec<MidiVoice>,
+    /// Pitch bend as a frequency factor.
+    pitch_bend: Shared,
+    /// Pitch bend range in semitones.
+    bend_range: f32,
+    /// Mod wheel position in 0...1.
+    mod_wheel: Shared,
+    /// Controller mappings: controller number, shared variable, and value range.
+    controls: Vec<(u8, Shared, f32, f32)>,
+    /// MIDI channel to listen to, or all channels.
+    channel: Option<u8>,
+    /// Counter for voice allocation.
+    counter: u64,
+}
+
+impl MidiControl {
+    /// Create a controller with the given number of `voices`.
+    pub fn new(voices: usize) -> Self {
+        assert!(voices > 0);
+        Self {
+            voices: (0..voices).map(|_| MidiVoice::new()).collect(),
+            pitch_bend: Shared::new(1.0),
+            bend_range: 2.0,
+            mod_wheel: Shared::new(0.0),
+            controls: Vec::new(),
+            channel: None,
+            counter: 0,
+        }
+    }
+
+    /// Number of voices.
+    pub fn voices(&self) -> usize {
+        self.voices.len()
+    }
+
+    /// Access voice `i`.
+    pub fn voice(&self, i: usize) -> &MidiVoice {
+        &self.voices[i]
+    }
+
+    /// Pitch bend as a frequency factor (1 is neutral).
+    pub fn pitch_bend(&self) -> &Shared {
+        &self.pitch_bend
+    }
+
+    /// Mod wheel position in 0...1.
+    pub fn mod_wheel(&self) -> &Shared {
+        &self.mod_wheel
+    }
+
+    /// Set pitch bend range in semitones (default is 2).
+    pub fn set_bend_range(&mut self, semitones: f32) {
+        self.bend_range = semitones;
+    }
+
+    /// Listen only to MIDI `channel` (0...15), or all channels if `None` (the default).
+    pub fn set_channel(&mut self, channel: Option<u8>) {
+        self.channel = channel;
+    }
+
+    /// Map continuous controller number `controller` (0...127) to `shared`.
+    /// Controller values 0...127 are mapped linearly to `min`...`max`.
+    pub fn map_control(&mut self, controller: u8, shared: &Shared, min: f32, max: f32) {
+        self.controls.push((controller, shared.clone(), min, max));
+    }
+
+    /// Handle a raw MIDI message. Returns true if the message updated any shared variable.
+    pub fn handle(&mut self, message: &[u8]) -> bool {
+        let Ok(LiveEvent::Midi { channel, message }) = LiveEvent::parse(message) else {
+            return false;
+        };
+        if self.channel.is_some_and(|c| c != channel.as_int()) {
+            return false;
+        }
+        match message {
+            MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => {
+                self.note_on(key.as_int(), vel.as_int());
+                true
+            }
+            MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. } => {
+                self.note_off(key.as_int())
+            }
+            MidiMessage::PitchBend { bend } => {
+                self.pitch_bend
+                    .set_value(exp2(bend.as_f32() * self.bend_range / 12.0));
+                true
+            }
+            MidiMessage::Controller { controller, value } => {
+                let controller = controller.as_int();
+                let x = value.as_int() as f32 / 127.0;
+                let mut handled = false;
+                if controller == 1 {
+                    self.mod_wheel.set_value(x);
+                    handled = true;
+                }
+                for (number, shared, min, max) in self.controls.iter() {
+                    if *number == controller {
+                        shared.set_value(lerp(*min, *max, x));
+                        handled = true;
+                    }
+                }
+                handled
+            }
+            _ => false,
+        }
+    }
+
+    /// Release all voices.
+    pub fn release_all(&mut self) {
+        for voice in self.voices.iter_mut() {
+            voice.gate.set_value(0.0);
+        }
+    }
+
+    fn note_on(&mut self, key: u8, velocity: u8) {
+        self.counter += 1;
+        // Prefer the voice released longest ago, then the oldest held voice.
+        let mut best = 0;
+        for i in 1..self.voices.len() {
+            let held = self.voices[i].gate.value() > 0.0;
+            let best_held = self.voices[best].gate.value() > 0.0;
+            if (!held && best_held)
+                || (held == best_held && self.voices[i].age < self.voices[best].age)
+            {
+                best = i;
+            }
+        }
+        let voice = &mut self.voices[best];
+        voice.key = Some(key);
+        voice.age = self.counter;
+        voice.frequency.set_value(midi_hz(key as f32));
+        voice.velocity.set_value(velocity as f32 / 127.0);
+        voice.gate.set_value(1.0);
+    }
+
+    fn note_off(&mut self, key: u8) -> bool {
+        self.counter += 1;
+        for voice in self.voices.iter_mut() {
+            if voice.key() == Some(key) {
+                voice.age = self.counter;
+                voice.gate.set_value(0.0);
+                return true;
+            }
+        }
+        false
+    }
+}
@@ -1,4 +1,4 @@
-//! Moog ladder filter.
+//! Moog, diode and transistor ladder filters.
 
 use super::audionode::*;
 use super::math::*;
@@ -115,3 +115,273 @@ impl<F: Real, N: Size<f32>> AudioNode for Moog<F, N> {
         output
     }
 }
+
+/// Number of integration steps per sample in the ladder models.
+const LADDER_STEPS: usize = 2;
+
+/// Advance the state `y` of a continuous time filter model by `steps`
+/// fourth order Runge-Kutta steps of size `h`.
+/// `f` computes state derivatives normalized to unit angular cutoff.
+#[inline]
+pub(crate) fn ode_step<F: Real, const N: usize>(
+    y: &mut [F; N],
+    h: F,
+    steps: usize,
+    f: impl Fn(&[F; N]) -> [F; N],
+) {
+    let half = h * F::from_f64(0.5);
+    let sixth = h / F::new(6);
+    for _ in 0..steps {
+        let k1 = f(y);
+        let k2 = f(&core::array::from_fn(|i| y[i] + half * k1[i]));
+        let k3 = f(&core::array::from_fn(|i| y[i] + half * k2[i]));
+        let k4 = f(&core::array::from_fn(|i| y[i] + h * k3[i]));
+        for i in 0..N {
+            y[i] += sixth * (k1[i] + F::new(2) * (k2[i] + k3[i]) + k4[i]);
+        }
+    }
+}
+
+/// Initial charge of the first ladder stage. A real circuit is never completely
+/// silent; this lets the ladder self-oscillate without input.
+const LADDER_SEED: f64 = 1.0e-6;
+
+/// Feedback gain of the transistor ladder at resonance 1.
+/// The linear ladder self-oscillates at a feedback gain of 4.
+const TRANSISTOR_FEEDBACK: f64 = 4.4;
+
+/// Transistor ladder lowpass filter (4th order) with a saturating nonlinearity
+/// at every stage. The model is integrated in continuous time with the
+/// feedback path included, so resonance does not detune the filter.
+/// Resonance ranges from 0 to 1. The filter self-oscillates at the cutoff
+/// frequency when resonance exceeds 10/11 (about 0.91).
+/// Passband gain drops as resonance increases, as in the analog circuit.
+/// The number of inputs is `N`, either `U1` or `U3`.
+/// - Input 0: input signal
+/// - Input 1 (optional): cutoff frequency (Hz)
+/// - Input 2 (optional): resonance in 0...1
+/// - Output 0: filtered signal
+#[derive(Default, Clone)]
+pub struct TransistorLadder<F: Real, N: Size<f32>> {
+    _marker: core::marker::PhantomData<N>,
+    cutoff: F,
+    resonance: F,
+    sample_rate: F,
+    h: F,
+    k: F,
+    y: [F; 4],
+}
+
+impl<F: Real, N: Size<f32>> TransistorLadder<F, N> {
+    pub fn new(cutoff: F, resonance: F) -> Self {
+        let mut node = Self {
+            sample_rate: convert(DEFAULT_SR),
+            ..Self::default()
+        };
+        node.set_cutoff_resonance(cutoff, resonance);
+        node.reset();
+        node
+    }
+
+    /// Set cutoff frequency (in Hz) and resonance (in 0...1).
+    /// This has no effect if the filter has cutoff and resonance inputs.
+    #[inline]
+    pub fn set_cutoff_resonance(&mut self, cutoff: F, resonance: F) {
+        self.cutoff = cutoff;
+        self.resonance = resonance;
+        // Keep integration steps within the stability region.
+        self.h = min(
+            F::TAU * cutoff / (self.sample_rate * F::new(LADDER_STEPS as i64)),
+            F::new(1),
+        );
+        self.k = F::from_f64(TRANSISTOR_FEEDBACK) * clamp01(resonance);
+    }
+
+    /// Cutoff frequency (Hz).
+    #[inline]
+    pub fn cutoff(&self) -> F {
+        self.cutoff
+    }
+
+    /// Resonance in 0...1.
+    #[inline]
+    pub fn resonance(&self) -> F {
+        self.resonance
+    }
+}
+
+impl<F: Real, N: Size<f32>> AudioNode for TransistorLadder<F, N> {
+    const ID: u64 = 140;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.y = [F::from_f64(LADDER_SEED), F::zero(), F::zero(), F::zero()];
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.set_cutoff_resonance(self.cutoff, self.resonance);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 {
+            self.set_cutoff_resonance(convert(input[1]), convert(input[2]));
+        }
+        let x: F = convert(input[0]);
+        let k = self.k;
+        ode_step(&mut self.y, self.h, LADDER_STEPS, |y| {
+            let t = [tanh(y[0]), tanh(y[1]), tanh(y[2]), tanh(y[3])];
+            [
+                tanh(x - k * y[3]) - t[0],
+                t[0] - t[1],
+                t[1] - t[2],
+                t[2] - t[3],
+            ]
+        });
+        [convert(self.y[3])].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Center(cutoff) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), self.resonance)
+            }
+            Parameter::CenterQ(cutoff, resonance) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), F::from_f32(*resonance))
+            }
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
+
+/// Feedback gain of the diode ladder at resonance 1.
+/// The linear ladder self-oscillates at a feedback gain of 17.
+const DIODE_FEEDBACK: f64 = 18.7;
+
+/// Diode ladder lowpass filter (4th order) in the style of the TB-303.
+/// Unlike in the transistor ladder, the stages are coupled and load each other,
+/// which gives a softer slope and a brighter resonance.
+/// The model is integrated in continuous time with the feedback path included.
+/// Resonance ranges from 0 to 1. The filter self-oscillates at the cutoff
+/// frequency when resonance exceeds 10/11 (about 0.91).
+/// Passband gain drops as resonance increases, as in the analog circuit.
+/// The number of inputs is `N`, either `U1` or `U3`.
+/// - Input 0: input signal
+/// - Input 1 (optional): cutoff frequency (Hz)
+/// - Input 2 (optional): resonance in 0...1
+/// - Output 0: filtered signal
+#[derive(Default, Clone)]
+pub struct DiodeLadder<F: Real, N: Size<f32>> {
+    _marker: core::marker::PhantomData<N>,
+    cutoff: F,
+    resonance: F,
+    sample_rate: F,
+    h: F,
+    k: F,
+    y: [F; 4],
+}
+
+impl<F: Real, N: Size<f32>> DiodeLadder<F, N> {
+    pub fn new(cutoff: F, resonance: F) -> Self {
+        let mut node = Self {
+            sample_rate: convert(DEFAULT_SR),
+            ..Self::default()
+        };
+        node.set_cutoff_resonance(cutoff, resonance);
+        node.reset();
+        node
+    }
+
+    /// Set cutoff frequency (in Hz) and resonance (in 0...1).
+    /// This has no effect if the filter has cutoff and resonance inputs.
+    #[inline]
+    pub fn set_cutoff_resonance(&mut self, cutoff: F, resonance: F) {
+        self.cutoff = cutoff;
+        self.resonance = resonance;
+        // The coupled ladder peaks at 1/sqrt(2) of its stage frequency.
+        // Keep integration steps within the stability region.
+        self.h = min(
+            F::SQRT_2 * F::TAU * cutoff / (self.sample_rate * F::new(LADDER_STEPS as i64)),
+            F::new(1),
+        );
+        self.k = F::from_f64(DIODE_FEEDBACK) * clamp01(resonance);
+    }
+
+    /// Cutoff frequency (Hz).
+    #[inline]
+    pub fn cutoff(&self) -> F {
+        self.cutoff
+    }
+
+    /// Resonance in 0...1.
+    #[inline]
+    pub fn resonance(&self) -> F {
+        self.resonance
+    }
+}
+
+impl<F: Real, N: Size<f32>> AudioNode for DiodeLadder<F, N> {
+    const ID: u64 = 141;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.y = [F::from_f64(LADDER_SEED), F::zero(), F::zero(), F::zero()];
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.set_cutoff_resonance(self.cutoff, self.resonance);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 {
+            self.set_cutoff_resonance(convert(input[1]), convert(input[2]));
+        }
+        let x: F = convert(input[0]);
+        let k = self.k;
+        let half = F::from_f64(0.5);
+        ode_step(&mut self.y, self.h, LADDER_STEPS, |y| {
+            let d = [
+                tanh(y[0] - y[1]),
+                tanh(y[1] - y[2]),
+                tanh(y[2] - y[3]),
+                tanh(y[3]),
+            ];
+            [
+                tanh(x - k * y[3]) - d[0],
+                half * (d[0] - d[1]),
+                half * (d[1] - d[2]),
+                half * (d[2] - d[3]),
+            ]
+        });
+        [convert(self.y[3])].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Center(cutoff) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), self.resonance)
+            }
+            Parameter::CenterQ(cutoff, resonance) => {
+                self.set_cutoff_resonance(F::from_f32(*cutoff), F::from_f32(*resonance))
+            }
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
@@ -4,10 +4,11 @@ use super::audionode::*;
 use super::audiounit::*;
 use super::buffer::*;
 use super::combinator::*;
+use super::dynamics::{Meter, Monitor};
 use super::math::*;
 use super::realnet::*;
 use super::setting::*;
-use super::shared::IdGenerator;
+use super::shared::{IdGenerator, Shared};
 use super::signal::*;
 use super::vertex::*;
 use super::*;
@@ -116,7 +117,11 @@ pub(crate) fn edge(source: Port, target: Port) -> Edge {
 /// Network unit. It can contain other units and maintain connections between them.
 /// Outputs of the network are sourced from user specified unit outputs or
 /// global inputs, or are filled with zeros if not connected.
-#[derive(Default)]
+///
+/// By default, the network compensates for latency: when the inputs of a unit arrive
+/// with different latencies, for example, when a dry path is mixed with an FFT path,
+/// the earlier inputs are delayed to align them with the latest one.
+/// See `set_latency_compensation`.
 pub struct Net {
     /// Global input buffers.
     input: BufferVec,
@@ -145,6 +150,14 @@ pub struct Net {
     revision: u64,
     /// Current error, if any.
     error: Option<NetError>,
+    /// Whether to delay unit inputs to align their latencies.
+    latency_compensation: bool,
+}
+
+impl Default for Net {
+    fn default() -> Self {
+        Net::new(0, 0)
+    }
 }
 
 impl Clone for Net {
@@ -165,6 +178,7 @@ impl Clone for Net {
             edit_queue: Vec::new(),
             revision: self.revision,
             error: self.error.clone(),
+            latency_compensation: self.latency_compensation,
         }
     }
 }
@@ -196,6 +210,7 @@ impl Net {
             edit_queue: Vec::new(),
             revision: 0,
             error: None,
+            latency_compensation: true,
         };
         for channel in 0..outputs {
             net.output_edge
@@ -466,6 +481,8 @@ impl Net {
         unit.set_sample_rate(self.sample_rate as f64);
         core::mem::swap(&mut self.vertex[node_index].unit, &mut unit);
         self.vertex[node_index].changed = self.revision;
+        // The replacement may have a different latency.
+        self.invalidate_order();
         unit
     }
 
@@ -803,6 +820,32 @@ impl Net {
         id
     }
 
+    /// Tap `node` output `port` with a meter, for debugging and observability.
+    /// A monitor node is added that reads the signal without altering
+    /// any existing connections. Returns the ID of the tap node
+    /// and a shared variable that holds the latest meter reading.
+    /// Each tap adds the small overhead of one node;
+    /// remove it with `Net::remove` when it is no longer needed.
+    ///
+    /// ### Example (Metering An Internal Node)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net::new(0, 1);
+    /// let id = net.chain(Box::new(dc(0.5)));
+    /// net.chain(Box::new(mul(2.0)));
+    /// let (tap, level) = net.tap(id, 0, Meter::Peak(0.1));
+    /// assert_eq!(net.get_mono(), 1.0);
+    /// assert_eq!(level.value(), 0.5);
+    /// net.remove(tap);
+    /// net.check();
+    /// ```
+    pub fn tap(&mut self, node: NodeId, port: PortIndex, meter: Meter) -> (NodeId, Shared) {
+        let level = Shared::new(0.0);
+        let id = self.push(Box::new(An(Monitor::new(&level, meter))));
+        self.connect(node, port, id, 0);
+        (id, level)
+    }
+
     /// Return whether the given `node` is contained in the network.
     pub fn contains(&self, node: NodeId) -> bool {
         self.node_index.contains_key(&node)
@@ -833,10 +876,6 @@ impl Net {
 
     /// Compute and store node order for this network.
     fn determine_order(&mut self) {
-        // Update source vertex shortcut.
-        for j in 0..self.vertex.len() {
-            self.vertex[j].update_source_vertex();
-        }
         // Update net hash. We have designed the hash to depend on vertices but not edges.
         let hash = self.ping(true, AttoHash::new(ID));
         self.ping(false, hash);
@@ -849,7 +888,100 @@ impl Net {
         } else {
             self.error = Some(NetError::Cycle);
         }
+        self.determine_compensation(&order);
         self.order = Some(order);
+        // Update source vertex shortcut.
+        for j in 0..self.vertex.len() {
+            self.vertex[j].update_source_vertex();
+        }
+    }
+
+    /// Compute latency compensation delays for unit inputs, given node `order`.
+    /// Inputs with known latency are delayed to align with the latest input of the same unit.
+    /// Latencies are rounded to whole samples.
+    fn determine_compensation(&mut self, order: &[NodeIndex]) {
+        if !self.latency_compensation {
+            for vertex in self.vertex.iter_mut() {
+                if vertex.compensated {
+                    vertex.compensation.fill(LatencyDelay::default());
+                    vertex.compensated = false;
+                }
+            }
+            return;
+        }
+        let mut inner_signal: Vec<SignalFrame> = Vec::with_capacity(self.vertex.len());
+        for vertex in self.vertex.iter() {
+            inner_signal.push(SignalFrame::new(vertex.outputs()));
+        }
+        for &index in order.iter() {
+            let inputs = self.vertex[index].inputs();
+            let mut input_signal = SignalFrame::new(inputs);
+            for channel in 0..inputs {
+                match self.vertex[index].source[channel].source {
+                    Port::Local(j, port) => input_signal.set(channel, inner_signal[j].at(port)),
+                    Port::Global(_) => input_signal.set(channel, Signal::Latency(0.0)),
+                    Port::Zero => input_signal.set(channel, Signal::Value(0.0)),
+                }
+            }
+            let mut arrival: Option<f64> = None;
+            for channel in 0..inputs {
+                if let Some(latency) = input_signal.at(channel).latency() {
+                    arrival = Some(arrival.map_or(latency, |arrival| max(arrival, latency)));
+                }
+            }
+            let vertex = &mut self.vertex[index];
+            vertex.compensated = false;
+            for channel in 0..inputs {
+                let length = match (arrival, input_signal.at(channel).latency()) {
+                    (Some(arrival), Some(latency)) => round(arrival - latency) as usize,
+                    _ => 0,
+                };
+                if vertex.compensation[channel].length() != length {
+                    vertex.compensation[channel] = LatencyDelay::new(length);
+                }
+                if length > 0 {
+                    vertex.compensated = true;
+                    input_signal.set(channel, input_signal.at(channel).delay(length as f64));
+                }
+            }
+            // The frequency argument is irrelevant as we propagate only latencies.
+            inner_signal[index] = vertex.unit.route(&input_signal, 1.0);
+        }
+    }
+
+    /// Whether latency compensation is enabled. It is enabled by default.
+    pub fn latency_compensation(&self) -> bool {
+        self.latency_compensation
+    }
+
+    /// Enable or disable latency compensation. It is enabled by default.
+    ///
+    /// With compensation enabled, the network queries the latency of each unit
+    /// via signal routing and, wherever the inputs of a unit arrive with different
+    /// latencies, delays the earlier inputs to align them with the latest one.
+    /// For example, a dry path mixed with a `resynth` path is delayed by the window length.
+    /// Global outputs are not aligned with each other.
+    /// The delays are included in the latency reported by the network.
+    ///
+    /// Limitations: latencies are rounded to whole samples, so fractional latencies
+    /// leave a residual misalignment of up to half a sample.
+    /// Inputs with unknown latency, for example, units that do not implement
+    /// signal routing or paths through feedback, are not compensated
+    /// and do not contribute to the alignment. Delays are recomputed
+    /// when the network structure, a replaced unit or the sample rate changes,
+    /// but not when a unit changes its latency otherwise, for example, during a crossfade.
+    ///
+    /// ### Example: Aligning A Dry Path With A Limiter
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net::wrap(Box::new(limiter(0.01, 0.01))) & Net::wrap(Box::new(pass()));
+    /// assert_eq!(net.latency(), Some(441.0));
+    /// net.set_latency_compensation(false);
+    /// assert_eq!(net.latency(), Some(0.0));
+    /// ```
+    pub fn set_latency_compensation(&mut self, enabled: bool) {
+        self.latency_compensation = enabled;
+        self.invalidate_order();
     }
 
     /// Determine node order in the supplied vector. Returns true if successful, false
@@ -1070,6 +1202,21 @@ impl Net {
                         &mut new.vertex[new_index].latest,
                     );
                     new.vertex[new_index].fade_phase = self.vertex[index].fade_phase;
+                    // Keep latency compensation state if the delays did not change.
+                    if self.vertex[index]
+                        .compensation
+                        .iter()
+                        .map(|delay| delay.length())
+                        .eq(new.vertex[new_index]
+                            .compensation
+                            .iter()
+                            .map(|delay| delay.length()))
+                    {
+                        core::mem::swap(
+                            &mut self.vertex[index].compensation,
+                            &mut new.vertex[new_index].compensation,
+                        );
+                    }
                 }
             }
         }
@@ -1116,6 +1263,53 @@ impl Net {
         self.front.is_some()
     }
 
+    /// Current sample rate.
+    pub(crate) fn sample_rate(&self) -> f64 {
+        self.sample_rate as f64
+    }
+
+    /// Schedule `setting` to be applied in the backend at `time` seconds.
+    /// Address the target node with `Setting::node`, as with `set`.
+    /// This may be called only if the network has a backend.
+    ///
+    /// Time is counted from backend creation or its latest reset.
+    /// The setting is applied sample-accurately, just before the backend processes
+    /// the sample at `time` rounded to the nearest sample; a block containing
+    /// an automation event is processed one sample at a time.
+    /// An event scheduled in the past is applied on the next sample.
+    /// Events are applied in time order, and events at the same time
+    /// in the order they were scheduled.
+    ///
+    /// Events are delivered to the backend lock-free over the same message queue
+    /// as commits and settings, so they arrive in order with them.
+    /// Up to `AUTOMATION_CAPACITY` events can be pending in the backend. The backend does not
+    /// allocate: events that arrive while it is full are dropped, as are events that do not fit
+    /// in the message queue. Pending events are kept in a binary heap, so scheduling is
+    /// logarithmic in the number of pending events.
+    ///
+    /// ### Example: Level Change At 10 ms
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net::new(0, 1);
+    /// let id = net.chain(Box::new(dc(1.0)));
+    /// net.set_sample_rate(44100.0);
+    /// let mut backend = net.backend();
+    /// net.set_at(0.01, Setting::value(0.5).node(id));
+    /// for _ in 0..441 {
+    ///     assert_eq!(backend.get_mono(), 1.0);
+    /// }
+    /// assert_eq!(backend.get_mono(), 0.5);
+    /// ```
+    pub fn set_at(&mut self, time: f64, setting: Setting) {
+        assert!(self.has_backend());
+        if let Some((sender, _receiver)) = &mut self.front {
+            if sender
+                .try_send(NetMessage::Automation(time, setting))
+                .is_ok()
+            {}
+        }
+    }
+
     /// Commit changes made to this frontend to the backend.
     /// This may be called only if the network has a backend.
     pub fn commit(&mut self) {
@@ -1202,6 +1396,12 @@ impl Net {
                 }
             }
             let vertex = &mut self.vertex[node_index];
+            if vertex.compensated {
+                for channel in 0..vertex.inputs() {
+                    vertex.tick_input[channel] =
+                        vertex.compensation[channel].tick(vertex.tick_input[channel]);
+                }
+            }
             vertex.tick(self.sample_rate, sender);
         }
 
@@ -1264,6 +1464,12 @@ impl Net {
                 let vertex = &mut self.vertex[node_index];
                 // Safety: we know there is no aliasing, as self connections are prohibited.
                 unsafe {
+                    if vertex.compensated {
+                        for channel in 0..vertex.inputs() {
+                            vertex.compensation[channel]
+                                .process(&mut (*ptr).channel_f32_mut(channel)[..size]);
+                        }
+                    }
                     vertex.process(size, &(*ptr).buffer_ref(), self.sample_rate, sender);
                 }
             }
@@ -1324,16 +1530,17 @@ impl AudioUnit for Net {
                 // - it may allocate or do something else inappropriate.
                 vertex.changed = self.revision;
             }
-            // Take the opportunity to unload some calculations.
-            if !self.is_ordered() {
-                self.determine_order();
-            }
+            // Latencies may depend on the sample rate.
+            self.determine_order();
         }
     }
 
     fn reset(&mut self) {
         for vertex in &mut self.vertex {
             vertex.unit.reset();
+            for delay in vertex.compensation.iter_mut() {
+                delay.reset();
+            }
             // Reseting a unit counts as a change
             // to be sent to the backend because
             // we cannot reset in the backend
@@ -1354,6 +1561,43 @@ impl AudioUnit for Net {
         self.process_2(size, input, output, &None);
     }
 
+    fn process64(&mut self, size: usize, input: &[&[f64]], output: &mut [&mut [f64]]) {
+        debug_assert!(input.len() == self.inputs());
+        debug_assert!(output.len() == self.outputs());
+        // Convert blocks through the preallocated global buffers, which are otherwise unused.
+        let mut input_buffer = core::mem::take(&mut self.input);
+        let mut output_buffer = core::mem::take(&mut self.output);
+        let mut i = 0;
+        while i < size {
+            let n = min(size - i, MAX_BUFFER_SIZE);
+            for (channel, x) in input.iter().enumerate() {
+                for (y, x) in input_buffer.channel_f32_mut(channel)[..n]
+                    .iter_mut()
+                    .zip(x[i..i + n].iter())
+                {
+                    *y = *x as f32;
+                }
+            }
+            self.process_2(
+                n,
+                &input_buffer.buffer_ref(),
+                &mut output_buffer.buffer_mut(),
+                &None,
+            );
+            for (channel, y) in output.iter_mut().enumerate() {
+                for (y, x) in y[i..i + n]
+                    .iter_mut()
+                    .zip(output_buffer.channel_f32(channel)[..n].iter())
+                {
+                    *y = *x as f64;
+                }
+            }
+            i += n;
+        }
+        self.input = input_buffer;
+        self.output = output_buffer;
+    }
+
     fn set(&mut self, setting: Setting) {
         if let Some((sender, _receiver)) = &mut self.front {
             if sender.try_send(NetMessage::Setting(setting)).is_ok() {}
@@ -1392,6 +1636,10 @@ impl AudioUnit for Net {
                     Port::Global(j) => input_signal.set(channel, input.at(j)),
                     Port::Zero => input_signal.set(channel, Signal::Value(0.0)),
                 }
+                let length = self.vertex[unit_index].compensation[channel].length();
+                if length > 0 {
+                    input_signal.set(channel, input_signal.at(channel).delay(length as f64));
+                }
             }
             inner_signal[unit_index] = self.vertex[unit_index].unit.route(&input_signal, frequency);
         }
@@ -1422,6 +1670,17 @@ impl AudioUnit for Net {
             vertex.allocate();
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        self.input.memory_usage()
+            + self.output.memory_usage()
+            + self.output_edge.capacity() * core::mem::size_of::<Edge>()
+            + self.vertex.capacity() * core::mem::size_of::<Vertex>()
+            + self.vertex.iter().map(|x| x.memory_usage()).sum::<usize>()
+            + self.order.as_ref().map_or(0, |order| {
+                order.capacity() * core::mem::size_of::<NodeIndex>()
+            })
+    }
 }
 
 impl Net {
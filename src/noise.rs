@@ -233,6 +233,90 @@ impl AudioNode for Noise {
     }
 }
 
+/// Number of rows in the Voss-McCartney fractal noise generator.
+const VOSS_ROWS: usize = 16;
+
+/// Fractal (1/f) noise using the Voss-McCartney algorithm.
+/// A clock running at the given rate updates one of 16 random rows per tick:
+/// row `k` is updated every `2^(k + 1)` ticks. The output is the average of the rows,
+/// held between ticks. Because the clock is specified in Hz,
+/// the spectrum does not depend on the sample rate. The spectrum falls
+/// approximately 3 dB/octave from about `rate / 131072` Hz up to `rate / 2` Hz.
+/// The output is in -1...1, although it rarely exceeds -0.5...0.5.
+/// Setting: seed.
+/// - Input 0: clock rate in Hz (at most one tick per sample).
+/// - Output 0: noise
+#[derive(Clone, Default)]
+pub struct Voss {
+    rnd: Rnd,
+    rows: [f32; VOSS_ROWS],
+    sum: f32,
+    counter: u32,
+    phase: f64,
+    sample_duration: f64,
+    seed: Option<u64>,
+    hash: u64,
+}
+
+impl Voss {
+    /// Create new fractal noise generator.
+    pub fn new() -> Self {
+        let mut node = Self::default();
+        node.reset();
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+}
+
+impl AudioNode for Voss {
+    const ID: u64 = 130;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.rnd = Rnd::from_u64(self.seed.unwrap_or(self.hash));
+        for row in self.rows.iter_mut() {
+            *row = self.rnd.f32() * 2.0 - 1.0;
+        }
+        self.sum = self.rows.iter().sum();
+        self.counter = 0;
+        self.phase = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = 1.0 / sample_rate;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.phase += input[0] as f64 * self.sample_duration;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+            self.counter = self.counter.wrapping_add(1);
+            let row = core::cmp::min(self.counter.trailing_zeros() as usize, VOSS_ROWS - 1);
+            let value = self.rnd.f32() * 2.0 - 1.0;
+            self.sum += value - self.rows[row];
+            self.rows[row] = value;
+        }
+        [self.sum / VOSS_ROWS as f32].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Seed(seed) = setting.parameter() {
+            self.seed = Some(*seed);
+        }
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
 /// Sample-and-hold component.
 /// Setting: variability in 0...1 is the randomness in individual hold times.
 /// - Input 0: signal.
@@ -0,0 +1,187 @@
+//! Onset detection.
+
+use super::audionode::*;
+use super::fft::*;
+use super::math::*;
+use super::signal::*;
+use super::*;
+use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Smoothing factor for the running average of spectral flux, applied once per hop.
+const FLUX_SMOOTHING: f32 = 0.9;
+
+/// Onset detector based on spectral flux. Passes through input and emits triggers.
+/// The input is analyzed with a Hann windowed FFT of `fft_size` samples
+/// every `fft_size / 4` samples.
+/// Spectral flux is the average increase in bin magnitudes from the previous analysis window.
+/// An onset is detected when flux rises above a threshold derived from its running average;
+/// `sensitivity` in 0...1 lowers the threshold. Onsets closer than the minimum interval
+/// to the previous onset are ignored.
+/// Onsets are reported after the transient has moved into the analysis window,
+/// with a latency of up to `fft_size / 2` samples.
+/// - Allocates: analysis buffers.
+/// - Input 0: signal
+/// - Output 0: signal
+/// - Output 1: trigger impulse (1 at onset, 0 otherwise)
+#[derive(Clone)]
+pub struct OnsetDetector {
+    fft_size: usize,
+    /// Hann window.
+    window: Vec<f32>,
+    /// Ring buffer of the latest `fft_size` samples.
+    input: Vec<f32>,
+    /// Next write position in `input`.
+    index: usize,
+    /// Samples until next analysis.
+    countdown: usize,
+    scratch: Vec<f32>,
+    /// Bin magnitudes from the previous analysis window.
+    magnitude: Vec<f32>,
+    /// Running average of spectral flux.
+    average: f32,
+    /// Whether flux was above threshold in the previous analysis window.
+    above: bool,
+    /// Samples since the previous onset.
+    since_onset: usize,
+    sensitivity: f32,
+    /// Minimum interval between onsets in seconds.
+    min_interval: f32,
+    sample_rate: f32,
+}
+
+impl OnsetDetector {
+    /// Create a new onset detector. `fft_size` is the analysis window length in samples,
+    /// which must be a power of two between 16 and 32768. `sensitivity` is in 0...1
+    /// and `min_interval` is the minimum interval between onsets in seconds.
+    pub fn new(fft_size: usize, sensitivity: f32, min_interval: f32) -> Self {
+        assert!(fft_size.is_power_of_two() && (16..=32768).contains(&fft_size));
+        let window = (0..fft_size)
+            .map(|i| 0.5 - 0.5 * cos(i as f32 * f32::TAU / fft_size as f32))
+            .collect();
+        Self {
+            fft_size,
+            window,
+            input: vec![0.0; fft_size],
+            index: 0,
+            countdown: fft_size / 4,
+            scratch: vec![0.0; fft_size],
+            magnitude: vec![0.0; fft_size / 2],
+            average: 0.0,
+            above: false,
+            since_onset: usize::MAX,
+            sensitivity: clamp01(sensitivity),
+            min_interval: max(0.0, min_interval),
+            sample_rate: DEFAULT_SR as f32,
+        }
+    }
+
+    /// Analysis window length in samples.
+    #[inline]
+    pub fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Sensitivity in 0...1.
+    #[inline]
+    pub fn sensitivity(&self) -> f32 {
+        self.sensitivity
+    }
+
+    /// Set sensitivity in 0...1. Higher values detect weaker onsets.
+    #[inline]
+    pub fn set_sensitivity(&mut self, sensitivity: f32) {
+        self.sensitivity = clamp01(sensitivity);
+    }
+
+    /// Minimum interval between onsets in seconds.
+    #[inline]
+    pub fn min_interval(&self) -> f32 {
+        self.min_interval
+    }
+
+    /// Set minimum interval between onsets in seconds.
+    #[inline]
+    pub fn set_min_interval(&mut self, min_interval: f32) {
+        self.min_interval = max(0.0, min_interval);
+    }
+
+    /// Analyze the latest window. Returns whether an onset was detected.
+    fn analyze(&mut self) -> bool {
+        let length = self.fft_size;
+        for i in 0..length {
+            self.scratch[i] = self.input[(self.index + i) & (length - 1)] * self.window[i];
+        }
+        let spectrum = real_fft(&mut self.scratch);
+        // Skip DC, which also contains the Nyquist bin.
+        let mut flux = 0.0;
+        for (x, previous) in spectrum.iter().zip(self.magnitude.iter_mut()).skip(1) {
+            let magnitude = x.norm();
+            flux += max(0.0, magnitude - *previous);
+            *previous = magnitude;
+        }
+        flux /= (length / 2 - 1) as f32;
+        let insensitivity = 1.0 - self.sensitivity;
+        let threshold = self.average * (1.0 + 4.0 * insensitivity) + 0.01 * insensitivity + 1.0e-4;
+        self.average = self.average * FLUX_SMOOTHING + flux * (1.0 - FLUX_SMOOTHING);
+        let above = flux > threshold;
+        let onset =
+            above && !self.above && self.since_onset as f32 >= self.min_interval * self.sample_rate;
+        self.above = above;
+        onset
+    }
+}
+
+impl AudioNode for OnsetDetector {
+    const ID: u64 = 121;
+    type Inputs = U1;
+    type Outputs = U2;
+
+    fn reset(&mut self) {
+        self.input.fill(0.0);
+        self.index = 0;
+        self.countdown = self.fft_size / 4;
+        self.magnitude.fill(0.0);
+        self.average = 0.0;
+        self.above = false;
+        self.since_onset = usize::MAX;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate as f32;
+    }
+
+    fn memory_usage(&self) -> usize {
+        (self.window.capacity()
+            + self.input.capacity()
+            + self.scratch.capacity()
+            + self.magnitude.capacity())
+            * core::mem::size_of::<f32>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.input[self.index] = input[0];
+        self.index = (self.index + 1) & (self.fft_size - 1);
+        self.since_onset = self.since_onset.saturating_add(1);
+        self.countdown -= 1;
+        let mut trigger = 0.0;
+        if self.countdown == 0 {
+            self.countdown = self.fft_size / 4;
+            if self.analyze() {
+                self.since_onset = 0;
+                trigger = 1.0;
+            }
+        }
+        [input[0], trigger].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0));
+        output.set(1, input.at(0).distort(0.0));
+        output
+    }
+}
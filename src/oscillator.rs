@@ -7,11 +7,14 @@ use super::fir::*;
 use super::math::*;
 use super::setting::*;
 use super::signal::*;
+use super::wave::*;
 use super::*;
 use core::marker::PhantomData;
 use funutd::Rnd;
 use numeric_array::*;
 extern crate alloc;
+use alloc::sync::Arc;
+use alloc::vec;
 use alloc::vec::Vec;
 
 /// Sine oscillator.
@@ -45,6 +48,22 @@ impl<F: Real> Sine<F> {
         sine.set_sample_rate(DEFAULT_SR);
         sine
     }
+    /// Current phase in 0...1. This is the phase of the next output sample.
+    /// Phase advances by frequency divided by sample rate every sample.
+    /// In an `An` wrapper, call this as `node.0.phase()`,
+    /// as `An::phase` is the initial phase builder.
+    /// Phase zero is the upward zero crossing of the sine.
+    #[inline]
+    pub fn phase(&self) -> F {
+        self.phase
+    }
+    /// Set current phase in 0...1. The next output sample will be at this phase,
+    /// which makes it possible to sync the oscillator or restart it deterministically.
+    /// Resetting the oscillator still returns it to its initial phase.
+    #[inline]
+    pub fn set_phase(&mut self, phase: F) {
+        self.phase = phase - phase.floor();
+    }
 }
 
 impl<F: Real> AudioNode for Sine<F> {
@@ -207,61 +226,694 @@ impl<N: Size<f32>> AudioNode for Dsf<N> {
     }
 }
 
+/// Additive oscillator. Sums sine partials driven by a single phase accumulator.
+/// Each partial is a triple `(ratio, amplitude, phase)`, where `ratio` is the frequency
+/// relative to the fundamental and `phase` in 0...1 is the phase offset of the partial.
+/// Because all partials share the phase of the fundamental, the waveform repeats
+/// every fundamental period: ratios should be integers (harmonics) for a continuous waveform.
+/// Partials at or above the Nyquist frequency are skipped to avoid aliasing.
+/// CPU cost is proportional to the number of partials below Nyquist.
+/// Setting: phase.
+/// - Allocates: partial list.
+/// - Input 0: fundamental frequency in Hz.
+/// - Output 0: additive wave.
+#[derive(Clone)]
+pub struct Additive {
+    partials: Vec<(f32, f32, f32)>,
+    phase: f64,
+    sample_rate: f64,
+    sample_duration: f64,
+    hash: u64,
+    initial_phase: Option<f64>,
+}
+
+impl Additive {
+    /// Create additive oscillator from `(ratio, amplitude, phase)` partials.
+    pub fn new(partials: &[(f32, f32, f32)]) -> Self {
+        let mut node = Self {
+            partials: partials.to_vec(),
+            phase: 0.0,
+            sample_rate: 0.0,
+            sample_duration: 0.0,
+            hash: 0,
+            initial_phase: None,
+        };
+        node.reset();
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Partials as `(ratio, amplitude, phase)` triples.
+    #[inline]
+    pub fn partials(&self) -> &[(f32, f32, f32)] {
+        &self.partials
+    }
+}
+
+impl AudioNode for Additive {
+    const ID: u64 = 124;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.phase = match self.initial_phase {
+            Some(phase) => phase,
+            None => rnd1(self.hash),
+        };
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.sample_duration = 1.0 / sample_rate;
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.partials.capacity() * core::mem::size_of::<(f32, f32, f32)>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let frequency = abs(input[0] as f64);
+        let nyquist = 0.5 * self.sample_rate;
+        let mut value = 0.0;
+        for &(ratio, amplitude, offset) in self.partials.iter() {
+            if frequency * abs(ratio as f64) < nyquist {
+                let phase = self.phase * ratio as f64 + offset as f64;
+                value += amplitude * sin((phase - phase.floor()) as f32 * f32::TAU);
+            }
+        }
+        self.phase += input[0] as f64 * self.sample_duration;
+        self.phase -= self.phase.floor();
+        [value].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Phase(phase) = setting.parameter() {
+            self.initial_phase = Some(*phase as f64);
+        }
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
+/// FM operator.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FmOperator {
+    /// Frequency ratio relative to the fundamental.
+    pub ratio: f32,
+    /// Output level. For carriers this is the amplitude of the operator in the output mix.
+    /// For modulators it is the modulation index: the peak phase deviation (in radians)
+    /// applied to the modulated operator.
+    pub level: f32,
+    /// Self-modulation index (in radians) from the previous output of the operator.
+    pub feedback: f32,
+}
+
+impl FmOperator {
+    /// Create operator with frequency `ratio` and `level`, without feedback.
+    pub fn new(ratio: f32, level: f32) -> Self {
+        Self {
+            ratio,
+            level,
+            feedback: 0.0,
+        }
+    }
+
+    /// Set self-modulation index in radians.
+    pub fn with_feedback(mut self, feedback: f32) -> Self {
+        self.feedback = feedback;
+        self
+    }
+}
+
+/// FM algorithm: which operators modulate which, and which operators are heard.
+/// Operators are numbered from zero. As in DX-style synthesizers, modulation flows
+/// from higher numbered operators to lower numbered ones, which keeps the graph acyclic.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FmAlgorithm {
+    operators: usize,
+    modulations: Vec<(usize, usize)>,
+    carriers: Vec<usize>,
+}
+
+impl FmAlgorithm {
+    /// Create algorithm for `operators` operators from `(modulator, target)` pairs
+    /// and a list of carriers. Each modulator must be numbered higher than its target.
+    pub fn new(operators: usize, modulations: &[(usize, usize)], carriers: &[usize]) -> Self {
+        for &(modulator, target) in modulations {
+            assert!(modulator < operators && target < modulator);
+        }
+        assert!(carriers.iter().all(|&carrier| carrier < operators));
+        Self {
+            operators,
+            modulations: modulations.to_vec(),
+            carriers: carriers.to_vec(),
+        }
+    }
+
+    /// Serial stack of `operators` operators: operator `i + 1` modulates operator `i`
+    /// and operator 0 is the carrier.
+    pub fn stack(operators: usize) -> Self {
+        let modulations: Vec<(usize, usize)> = (1..operators).map(|i| (i, i - 1)).collect();
+        Self::new(operators, &modulations, &[0])
+    }
+
+    /// Parallel algorithm with `operators` unmodulated carriers (additive synthesis).
+    pub fn parallel(operators: usize) -> Self {
+        let carriers: Vec<usize> = (0..operators).collect();
+        Self::new(operators, &[], &carriers)
+    }
+
+    /// Number of operators.
+    #[inline]
+    pub fn operators(&self) -> usize {
+        self.operators
+    }
+
+    /// Modulations as `(modulator, target)` pairs.
+    #[inline]
+    pub fn modulations(&self) -> &[(usize, usize)] {
+        &self.modulations
+    }
+
+    /// Carrier operators.
+    #[inline]
+    pub fn carriers(&self) -> &[usize] {
+        &self.carriers
+    }
+}
+
+/// FM synthesizer with operators connected according to an algorithm.
+/// Number of inputs is `N`, either 1 or 2.
+/// Operators are sine oscillators using phase modulation, as in DX-style synthesizers.
+/// The frequency of each operator is the fundamental frequency times its ratio.
+/// A modulator with level `index` shifts the phase of its target by up to `index` radians.
+/// The output is the sum of carriers weighted by their levels.
+/// Modulation indices are reduced where needed to keep the significant sidebands
+/// (per Carson's rule) below Nyquist, and carriers above Nyquist are muted,
+/// which keeps aliasing low but is not a strict bandlimit.
+/// All operators start from zero phase.
+/// - Allocates: operator state.
+/// - Input 0: fundamental frequency in Hz.
+/// - Input 1 (optional): modulation depth, which scales the levels of all modulators.
+/// - Output 0: FM wave.
+#[derive(Clone)]
+pub struct FmStack<N: Size<f32>> {
+    _marker: PhantomData<N>,
+    algorithm: FmAlgorithm,
+    operators: Vec<FmOperator>,
+    phase: Vec<f64>,
+    /// Previous two outputs of each operator, for feedback.
+    previous: Vec<[f32; 2]>,
+    /// Phase modulation accumulated for each operator.
+    modulation: Vec<f32>,
+    sample_rate: f64,
+}
+
+impl<N: Size<f32>> FmStack<N> {
+    /// Create FM synthesizer. The number of `operators` must match the algorithm.
+    pub fn new(algorithm: FmAlgorithm, operators: &[FmOperator]) -> Self {
+        assert_eq!(algorithm.operators(), operators.len());
+        let n = operators.len();
+        Self {
+            _marker: PhantomData,
+            algorithm,
+            operators: operators.to_vec(),
+            phase: vec![0.0; n],
+            previous: vec![[0.0; 2]; n],
+            modulation: vec![0.0; n],
+            sample_rate: DEFAULT_SR,
+        }
+    }
+
+    /// Operator parameters.
+    #[inline]
+    pub fn operators(&self) -> &[FmOperator] {
+        &self.operators
+    }
+
+    /// Set parameters of operator `i`.
+    #[inline]
+    pub fn set_operator(&mut self, i: usize, operator: FmOperator) {
+        self.operators[i] = operator;
+    }
+
+    /// Algorithm.
+    #[inline]
+    pub fn algorithm(&self) -> &FmAlgorithm {
+        &self.algorithm
+    }
+}
+
+impl<N: Size<f32>> AudioNode for FmStack<N> {
+    const ID: u64 = 125;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.phase.fill(0.0);
+        self.previous.fill([0.0; 2]);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.operators.capacity() * core::mem::size_of::<FmOperator>()
+            + self.phase.capacity() * core::mem::size_of::<f64>()
+            + self.previous.capacity() * core::mem::size_of::<[f32; 2]>()
+            + self.modulation.capacity() * core::mem::size_of::<f32>()
+            + (self.algorithm.modulations.capacity() * 2 + self.algorithm.carriers.capacity())
+                * core::mem::size_of::<usize>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let fundamental = input[0] as f64;
+        let depth = if N::USIZE > 1 { input[1] } else { 1.0 };
+        let nyquist = 0.5 * self.sample_rate;
+        let sample_duration = 1.0 / self.sample_rate;
+        // Maximum index for a modulator at `modulator` Hz to keep sidebands of `target` Hz below Nyquist.
+        let limit = |target: f64, modulator: f64| {
+            if modulator > 0.0 {
+                max(0.0, (nyquist - target) / modulator - 1.0) as f32
+            } else {
+                f32::INFINITY
+            }
+        };
+        self.modulation.fill(0.0);
+        let mut output = 0.0;
+        for i in (0..self.operators.len()).rev() {
+            let operator = self.operators[i];
+            let frequency = abs(fundamental * operator.ratio as f64);
+            let mut modulation = self.modulation[i];
+            if operator.feedback != 0.0 {
+                let feedback = clamp(
+                    -limit(frequency, frequency),
+                    limit(frequency, frequency),
+                    operator.feedback,
+                );
+                modulation += feedback * 0.5 * (self.previous[i][0] + self.previous[i][1]);
+            }
+            let y = sin(self.phase[i] as f32 * f32::TAU + modulation);
+            self.previous[i] = [y, self.previous[i][0]];
+            self.phase[i] += fundamental * operator.ratio as f64 * sample_duration;
+            self.phase[i] -= self.phase[i].floor();
+            for &(modulator, target) in self.algorithm.modulations.iter() {
+                if modulator == i {
+                    let target_frequency = abs(fundamental * self.operators[target].ratio as f64);
+                    let index = min(
+                        abs(operator.level * depth),
+                        limit(target_frequency, frequency),
+                    );
+                    self.modulation[target] += index * signum(operator.level * depth) * y;
+                }
+            }
+            if frequency < nyquist && self.algorithm.carriers.contains(&i) {
+                output += operator.level * y;
+            }
+        }
+        [output].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
+/// Phase distortion shape for the `PhaseDistortion` oscillator.
+/// Shapes are modeled after the Casio CZ series. The distortion amount is in 0...1.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PdShape {
+    /// The first half of the cosine cycle is read faster than the second half.
+    /// At amount 0 the output is a cosine wave and it approaches a sawtooth as amount grows.
+    #[default]
+    Saw,
+    /// Cosine read with flat segments at the peaks.
+    /// At amount 0 the output is a cosine wave and it approaches a square wave as amount grows.
+    Square,
+    /// Cosine cycle compressed into the end of the period, holding the peak in between.
+    /// At amount 0 the output is a cosine wave and it approaches a narrow pulse as amount grows.
+    Pulse,
+    /// Resonant sawtooth: a cosine at `1 + 15 * amount` times the fundamental
+    /// windowed by a falling ramp. The window resets the resonance every period.
+    ResonantSaw,
+    /// Resonant triangle: a cosine at `1 + 15 * amount` times the fundamental
+    /// windowed by a triangle.
+    ResonantTriangle,
+}
+
+impl PdShape {
+    /// Return output for `phase` in 0...1 and distortion `amount` in 0...1.
+    #[inline]
+    pub fn wave(&self, phase: f32, amount: f32) -> f32 {
+        let amount = clamp01(amount);
+        match self {
+            PdShape::Saw => {
+                let d = 0.5 - 0.49 * amount;
+                let phase = if phase < d {
+                    0.5 * phase / d
+                } else {
+                    0.5 + 0.5 * (phase - d) / (1.0 - d)
+                };
+                cos(phase * f32::TAU)
+            }
+            PdShape::Square => {
+                let w = 0.5 - 0.49 * amount;
+                let half = if phase < 0.5 { phase } else { phase - 0.5 };
+                let warped = clamp01((half - (0.5 - w)) / w) * 0.5;
+                cos((if phase < 0.5 { warped } else { 0.5 + warped }) * f32::TAU)
+            }
+            PdShape::Pulse => {
+                let w = 1.0 - 0.95 * amount;
+                cos(clamp01((phase - (1.0 - w)) / w) * f32::TAU)
+            }
+            PdShape::ResonantSaw => {
+                let ratio = 1.0 + 15.0 * amount;
+                (1.0 - phase) * cos(phase * ratio * f32::TAU)
+            }
+            PdShape::ResonantTriangle => {
+                let ratio = 1.0 + 15.0 * amount;
+                let window = 1.0 - abs(2.0 * phase - 1.0);
+                window * cos((phase - 0.5) * ratio * f32::TAU)
+            }
+        }
+    }
+}
+
+/// Phase distortion oscillator in the style of the Casio CZ series.
+/// The phase of a cosine is warped according to the shape and the distortion amount.
+/// See `PdShape` for the available shapes.
+/// Setting: phase.
+/// - Input 0: frequency in Hz.
+/// - Input 1: distortion amount in 0...1.
+/// - Output 0: phase distorted wave.
+#[derive(Clone)]
+pub struct PhaseDistortion {
+    shape: PdShape,
+    phase: f64,
+    sample_duration: f64,
+    hash: u64,
+    initial_phase: Option<f64>,
+}
+
+impl PhaseDistortion {
+    /// Create phase distortion oscillator with `shape`.
+    pub fn new(shape: PdShape) -> Self {
+        let mut node = Self {
+            shape,
+            phase: 0.0,
+            sample_duration: 0.0,
+            hash: 0,
+            initial_phase: None,
+        };
+        node.reset();
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Distortion shape.
+    #[inline]
+    pub fn shape(&self) -> PdShape {
+        self.shape
+    }
+
+    /// Set distortion shape.
+    #[inline]
+    pub fn set_shape(&mut self, shape: PdShape) {
+        self.shape = shape;
+    }
+}
+
+impl AudioNode for PhaseDistortion {
+    const ID: u64 = 126;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.phase = match self.initial_phase {
+            Some(phase) => phase,
+            None => rnd1(self.hash),
+        };
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = 1.0 / sample_rate;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let value = self.shape.wave(self.phase as f32, input[1]);
+        self.phase += input[0] as f64 * self.sample_duration;
+        self.phase -= self.phase.floor();
+        [value].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Phase(phase) = setting.parameter() {
+            self.initial_phase = Some(*phase as f64);
+        }
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
+/// Crossfade time in seconds when the frequency of a `Pluck` oscillator changes.
+pub const PLUCK_CROSSFADE_TIME: f32 = 0.01;
+
+/// Highest frequency of a `Pluck` oscillator relative to the sample rate.
+/// Above this the delay loop would be shorter than 1 sample.
+pub const PLUCK_MAX_RATIO: f64 = 0.4;
+
+/// Delay loop of a Karplus-Strong string.
+#[derive(Clone)]
+struct PluckString {
+    damping: Fir<typenum::U3>,
+    tuning: Allpole<f32, typenum::U1>,
+    /// Delay line, allocated for the longest supported loop.
+    line: Vec<f32>,
+    /// Loop length in samples.
+    length: usize,
+    pos: usize,
+}
+
+impl PluckString {
+    #[inline]
+    fn tick(&mut self, x: f32, gain: f32) -> f32 {
+        let output = self.line[self.pos] * gain + x;
+        let output = self.damping.filter_mono(output);
+        let output = self.tuning.filter_mono(output);
+        self.line[self.pos] = output;
+        self.pos += 1;
+        if self.pos == self.length {
+            self.pos = 0;
+        }
+        output
+    }
+
+    /// Copy the state of `other` without allocating.
+    fn copy_from(&mut self, other: &PluckString) {
+        self.damping.clone_from(&other.damping);
+        self.tuning.clone_from(&other.tuning);
+        self.line[..other.length].copy_from_slice(&other.line[..other.length]);
+        self.length = other.length;
+        self.pos = other.pos;
+    }
+}
+
 /// Karplus-Strong oscillator.
+///
+/// The string is excited with white noise by default or with a custom excitation wave.
+/// When the frequency changes, one period of the vibrating string is resampled
+/// into the new delay loop and the output crossfades from the old string to the new one
+/// over `PLUCK_CROSSFADE_TIME` seconds. Small changes are seamless, while large jumps
+/// may produce audible glitches as the resampled period passes through the damping filter.
+/// Retuning does not allocate: the delay loops are allocated for the initial frequency,
+/// which is the lowest frequency retuning supports. Use `with_min_frequency` to retune lower.
 /// - Allocates: pluck buffer.
+/// - Setting: center (frequency).
 /// - Input 0: extra string excitation.
 /// - Output 0: plucked string.
 #[derive(Clone)]
 pub struct Pluck {
-    damping: Fir<typenum::U3>,
-    tuning: Allpole<f32, typenum::U1>,
-    line: Vec<f32>,
+    string: PluckString,
+    /// The string being faded out after a frequency change.
+    previous: PluckString,
     gain: f32,
-    pos: usize,
+    previous_gain: f32,
+    /// Weight of the previous string in 0...1.
+    fade: f32,
+    gain_per_second: f32,
     hash: u64,
     frequency: f32,
+    /// Lowest frequency the delay loops are allocated for.
+    lowest: f32,
     sample_rate: f64,
+    excitation: Option<Arc<Wave>>,
     initialized: bool,
 }
 
 impl Pluck {
     // Create new Karplus-Strong oscillator. High frequency damping is in 0...1.
     pub fn new(frequency: f32, gain_per_second: f32, high_frequency_damping: f32) -> Self {
-        Self {
+        let string = PluckString {
             damping: super::prelude::fir3(1.0 - high_frequency_damping).0,
             tuning: Allpole::new(1.0),
             line: Vec::new(),
-            gain: pow(gain_per_second.to_f64(), 1.0 / frequency.to_f64()) as f32,
+            length: 0,
             pos: 0,
+        };
+        Self {
+            previous: string.clone(),
+            string,
+            gain: Self::loop_gain(gain_per_second, frequency),
+            previous_gain: 0.0,
+            fade: 0.0,
+            gain_per_second,
             hash: 0,
             frequency,
+            lowest: frequency,
             sample_rate: DEFAULT_SR,
+            excitation: None,
             initialized: false,
         }
     }
 
-    fn initialize_line(&mut self) {
+    /// Allocate the delay loops for retuning down to `frequency` Hz (`frequency` > 0).
+    /// By default, the string cannot be retuned below its initial frequency.
+    /// Lower frequencies need longer loops and more memory.
+    pub fn with_min_frequency(mut self, frequency: f32) -> Self {
+        assert!(frequency > 0.0);
+        self.lowest = min(frequency, self.frequency);
+        self
+    }
+
+    /// Frequency in Hz.
+    #[inline]
+    pub fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    /// Lowest frequency in Hz that the string can be retuned to.
+    #[inline]
+    pub fn min_frequency(&self) -> f32 {
+        self.lowest
+    }
+
+    /// Set frequency in Hz. If the string is vibrating, it is retuned
+    /// with a crossfade of `PLUCK_CROSSFADE_TIME` seconds.
+    /// The frequency is limited to the lowest frequency the delay loops were allocated for
+    /// and to `PLUCK_MAX_RATIO` times the sample rate.
+    /// Does not allocate, so this is safe to call from the audio thread.
+    pub fn set_frequency(&mut self, frequency: f32) {
+        let frequency = min(
+            max(frequency, self.lowest),
+            (self.sample_rate * PLUCK_MAX_RATIO) as f32,
+        );
+        self.frequency = frequency;
+        self.previous_gain = self.gain;
+        self.gain = Self::loop_gain(self.gain_per_second, frequency);
+        if !self.initialized {
+            return;
+        }
+        self.previous.copy_from(&self.string);
+        let (loop_delay, allpass_delay) = self.delays();
+        self.string.tuning.set_delay(allpass_delay);
+        // Resample one period of the string, oldest sample first, into the new loop.
+        let old_length = self.previous.length;
+        let scale = old_length as f64 / loop_delay as f64;
+        self.string.length = loop_delay;
+        for i in 0..loop_delay {
+            let t = i as f64 * scale;
+            let j = t as usize;
+            let a = self.previous.line[(self.previous.pos + j) % old_length];
+            let b = self.previous.line[(self.previous.pos + j + 1) % old_length];
+            self.string.line[i] = lerp(a, b, (t - j as f64) as f32);
+        }
+        self.string.pos = 0;
+        self.fade = 1.0;
+    }
+
+    /// Set excitation wave. The first channel of `wave` seeds the delay loop
+    /// instead of white noise the next time the string is plucked (on reset).
+    /// The excitation is zero centered; it is truncated or padded with zeros
+    /// to the length of the loop. Setting `None` restores the noise excitation.
+    pub fn set_excitation(&mut self, wave: Option<&Arc<Wave>>) {
+        self.excitation = wave.cloned();
+        self.initialized = false;
+    }
+
+    /// Loop gain per period from gain per second.
+    fn loop_gain(gain_per_second: f32, frequency: f32) -> f32 {
+        pow(gain_per_second.to_f64(), 1.0 / frequency.to_f64()) as f32
+    }
+
+    /// Loop delay in samples and allpass delay.
+    fn delays(&self) -> (usize, f32) {
+        Self::delays_at(self.sample_rate, self.frequency)
+    }
+
+    /// Loop delay in samples and allpass delay at `frequency` Hz.
+    /// The loop delay is at least 1 sample.
+    fn delays_at(sample_rate: f64, frequency: f32) -> (usize, f32) {
         // Allpass filter delay is in epsilon ... epsilon + 1.
         let epsilon = 0.2;
+        let frequency = min(frequency.to_f64(), sample_rate * PLUCK_MAX_RATIO);
         // Damping filter delay is 1 sample.
-        let total_delay = self.sample_rate / self.frequency.to_f64() - 1.0;
+        let total_delay = sample_rate / frequency - 1.0;
         let loop_delay = floor(total_delay - epsilon);
         let allpass_delay = total_delay - loop_delay;
-        self.tuning.reset();
-        self.tuning.set_sample_rate(self.sample_rate);
-        self.tuning.set_delay(allpass_delay as f32);
-        self.line.resize(loop_delay as usize, 0.0);
-        let mut rnd = Rnd::from_u64(self.hash);
+        (loop_delay as usize, allpass_delay as f32)
+    }
+
+    fn initialize_line(&mut self) {
+        let (loop_delay, allpass_delay) = self.delays();
+        // Allocate for the longest loop that retuning can reach.
+        let capacity = Self::delays_at(self.sample_rate, self.lowest).0;
+        if self.string.line.len() != capacity {
+            self.string.line.resize(capacity, 0.0);
+            self.previous.line.resize(capacity, 0.0);
+        }
+        self.string.tuning.reset();
+        self.string.tuning.set_sample_rate(self.sample_rate);
+        self.string.tuning.set_delay(allpass_delay);
+        self.string.length = loop_delay;
+        let line = &mut self.string.line[..loop_delay];
+        if let Some(wave) = &self.excitation {
+            for (i, x) in line.iter_mut().enumerate() {
+                *x = if i < wave.len() { wave.at(0, i) } else { 0.0 };
+            }
+        } else {
+            let mut rnd = Rnd::from_u64(self.hash);
+            for x in line.iter_mut() {
+                *x = rnd.f32_in(-1.0, 1.0);
+            }
+        }
         let mut mean = 0.0;
-        for i in 0..self.line.len() {
-            self.line[i] = rnd.f32_in(-1.0, 1.0);
-            mean += self.line[i].to_f64();
+        for x in line.iter() {
+            mean += x.to_f64();
         }
-        mean /= self.line.len() as f64;
-        for x in self.line.iter_mut() {
+        mean /= line.len() as f64;
+        for x in line.iter_mut() {
             *x -= mean as f32;
         }
-        self.pos = 0;
+        self.string.pos = 0;
+        self.fade = 0.0;
         self.initialized = true;
     }
 }
@@ -272,14 +924,14 @@ impl AudioNode for Pluck {
     type Outputs = typenum::U1;
 
     fn reset(&mut self) {
-        self.damping.reset();
+        self.string.damping.reset();
         self.initialized = false;
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
         if self.sample_rate != sample_rate {
             self.sample_rate = sample_rate;
-            self.damping.set_sample_rate(sample_rate);
+            self.string.damping.set_sample_rate(sample_rate);
             self.initialized = false;
         }
     }
@@ -289,17 +941,24 @@ impl AudioNode for Pluck {
         if !self.initialized {
             self.initialize_line();
         }
-        let output = self.line[self.pos] * self.gain + input[0];
-        let output = self.damping.filter_mono(output);
-        let output = self.tuning.filter_mono(output);
-        self.line[self.pos] = output;
-        self.pos += 1;
-        if self.pos == self.line.len() {
-            self.pos = 0;
+        let output = self.string.tick(input[0], self.gain);
+        if self.fade > 0.0 {
+            let previous = self.previous.tick(input[0], self.previous_gain);
+            self.fade = max(
+                0.0,
+                self.fade - 1.0 / (PLUCK_CROSSFADE_TIME * self.sample_rate as f32),
+            );
+            return [lerp(output, previous, self.fade)].into();
         }
         [output].into()
     }
 
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Center(frequency) = setting.parameter() {
+            self.set_frequency(*frequency);
+        }
+    }
+
     fn set_hash(&mut self, hash: u64) {
         self.hash = hash;
         self.initialized = false;
@@ -314,6 +973,10 @@ impl AudioNode for Pluck {
             self.initialize_line();
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        (self.string.line.capacity() + self.previous.line.capacity()) * core::mem::size_of::<f32>()
+    }
 }
 
 /// Rossler dynamical system oscillator.
@@ -434,6 +1097,155 @@ impl AudioNode for Lorenz {
     }
 }
 
+/// Logistic map chaotic generator. Iterates `x <- r * x * (1 - x)`
+/// at the given rate and holds the value between iterations.
+/// The output is in 0...1 for `r` in 0...4. For `r` above about 3.57
+/// the map is chaotic for most values; at `r` = 4 it covers the whole range.
+/// The output is not centered: map it to audio range with, for example, `* 2.0 - 1.0`.
+/// - Input 0: iteration rate in Hz (at most one iteration per sample).
+/// - Output 0: map value in 0...1
+#[derive(Clone, Default)]
+pub struct Logistic {
+    r: f32,
+    x: f32,
+    phase: f64,
+    sample_duration: f64,
+    hash: u64,
+}
+
+impl Logistic {
+    /// Create new logistic map generator with parameter `r` in 0...4.
+    pub fn new(r: f32) -> Self {
+        let mut node = Self {
+            r: clamp(0.0, 4.0, r),
+            ..Self::default()
+        };
+        node.reset();
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Map parameter `r`.
+    #[inline]
+    pub fn r(&self) -> f32 {
+        self.r
+    }
+
+    /// Set map parameter `r` in 0...4.
+    #[inline]
+    pub fn set_r(&mut self, r: f32) {
+        self.r = clamp(0.0, 4.0, r);
+    }
+}
+
+impl AudioNode for Logistic {
+    const ID: u64 = 128;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        // Avoid the fixed points at 0 and 1.
+        self.x = lerp(0.1, 0.9, rnd1(self.hash) as f32);
+        self.phase = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = 1.0 / sample_rate;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.phase += input[0] as f64 * self.sample_duration;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+            self.x = clamp01(self.r * self.x * (1.0 - self.x));
+        }
+        [self.x].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
+/// Henon map chaotic generator. Iterates `(x, y) <- (1 - a * x * x + y, b * x)`
+/// at the given rate and holds `x` between iterations.
+/// With the classic parameters `a` = 1.4 and `b` = 0.3 the output stays within about -1.3...1.3.
+/// Other parameters may lead to periodic orbits or divergence; on divergence
+/// the generator restarts from its initial state.
+/// Scale the output, for example with `* 0.75`, to keep it in audio range.
+/// - Input 0: iteration rate in Hz (at most one iteration per sample).
+/// - Output 0: map value `x`
+#[derive(Clone, Default)]
+pub struct Henon {
+    a: f32,
+    b: f32,
+    x: f32,
+    y: f32,
+    phase: f64,
+    sample_duration: f64,
+    hash: u64,
+}
+
+impl Henon {
+    /// Create new Henon map generator with parameters `a` and `b`.
+    pub fn new(a: f32, b: f32) -> Self {
+        let mut node = Self {
+            a,
+            b,
+            ..Self::default()
+        };
+        node.reset();
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+}
+
+impl AudioNode for Henon {
+    const ID: u64 = 129;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.x = lerp(-0.1, 0.1, rnd1(self.hash) as f32);
+        self.y = 0.0;
+        self.phase = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = 1.0 / sample_rate;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.phase += input[0] as f64 * self.sample_duration;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+            let x = 1.0 - self.a * self.x * self.x + self.y;
+            self.y = self.b * self.x;
+            self.x = x;
+            if !(self.x.is_finite() && abs(self.x) < 1.0e3) {
+                self.reset();
+            }
+        }
+        [self.x].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
 /// Ascending ramp generator with output in 0...1. Not bandlimited.
 /// - Input 0: repetition frequency in Hz.
 /// - Output 0: current phase in 0...1.
@@ -509,7 +1321,7 @@ impl<F: Float> AudioNode for Ramp<F> {
 
 /// PolyBLEP function with phase `t` in 0...1 and phase increment `dt`.
 #[inline]
-fn polyblep<F: Real>(t: F, dt: F) -> F {
+pub(crate) fn polyblep<F: Real>(t: F, dt: F) -> F {
     if t < dt {
         let z = t / dt;
         z + z - z * z - F::one()
@@ -758,3 +1570,202 @@ impl<F: Real> AudioNode for PolyPulse<F> {
         super::signal::Routing::Arbitrary(0.0).route(input, self.outputs())
     }
 }
+
+/// Bandlimited impulse train (BLIT) oscillator.
+/// Each period contains an impulse of unit area made of the harmonics below Nyquist,
+/// so the peak value is close to 1 and the mean value is frequency divided by sample rate.
+///
+/// The impulse train is a building block for analog-style oscillators:
+/// leaky integration of the train minus its mean gives a sawtooth, and
+/// leaky integration of the difference of two trains half a period apart gives a square wave.
+/// A leaky integrator is `feedback(pass() * g)` with `g` slightly less than 1.
+/// - Input 0: frequency (Hz).
+/// - Output 0: impulse train.
+#[derive(Default, Clone)]
+pub struct Blit<F: Real> {
+    phase: F,
+    sample_rate: F,
+    hash: u64,
+    initial_phase: Option<F>,
+}
+
+impl<F: Real> Blit<F> {
+    /// Create oscillator.
+    pub fn new() -> Self {
+        let mut osc = Self::default();
+        osc.reset();
+        osc.set_sample_rate(DEFAULT_SR);
+        osc
+    }
+}
+
+impl<F: Real> AudioNode for Blit<F> {
+    const ID: u64 = 156;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.phase = match self.initial_phase {
+            Some(phase) => phase,
+            None => convert(rnd1(self.hash)),
+        };
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let frequency = F::from_f32(input[0]);
+        if frequency <= F::zero() {
+            return [0.0].into();
+        }
+        let phase = self.phase;
+        self.phase += frequency / self.sample_rate;
+        self.phase -= self.phase.floor();
+        // Period in samples and the odd number of harmonics below Nyquist, counting DC twice.
+        let period = self.sample_rate / frequency;
+        let m = F::new(2) * floor(period * F::from_f64(0.5)) + F::one();
+        let denominator = period * sin(phase * F::PI);
+        let value = if abs(denominator) < F::from_f64(1.0e-6) {
+            m / period
+        } else {
+            sin(phase * F::PI * m) / denominator
+        };
+        [value.to_f32()].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Phase(phase) = setting.parameter() {
+            self.initial_phase = Some(F::from_f32(*phase));
+        }
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
+/// Ring modulator. Multiplies the carrier with a bipolar modulator.
+/// - Input 0: carrier
+/// - Input 1: modulator
+/// - Output 0: product of carrier and modulator
+#[derive(Default, Clone)]
+pub struct RingMod {}
+
+impl RingMod {
+    /// Create new ring modulator.
+    pub fn new() -> Self {
+        RingMod::default()
+    }
+}
+
+impl AudioNode for RingMod {
+    const ID: u64 = 100;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U1;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        [input[0] * input[1]].into()
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        for i in 0..simd_items(size) {
+            output.set(0, i, input.at(0, i) * input.at(1, i));
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        // The product is linear in one input only if the other input is constant.
+        output.set(
+            0,
+            match (input.at(0), input.at(1)) {
+                (Signal::Value(x), Signal::Value(y)) => Signal::Value(x * y),
+                (x, Signal::Value(y)) => x.scale(y),
+                (Signal::Value(x), y) => y.scale(x),
+                (x, y) => x.combine_nonlinear(y, 0.0),
+            },
+        );
+        output
+    }
+}
+
+/// Amplitude modulator. The modulator, which is nominally in -1...1,
+/// is mapped to a unipolar gain in `1 - depth`...1.
+/// Setting: depth.
+/// - Input 0: carrier
+/// - Input 1: modulator
+/// - Output 0: amplitude modulated carrier
+#[derive(Default, Clone)]
+pub struct AmpMod {
+    depth: f32,
+}
+
+impl AmpMod {
+    /// Create new amplitude modulator with modulation `depth` in 0...1.
+    pub fn new(depth: f32) -> Self {
+        Self { depth }
+    }
+
+    /// Modulation depth in 0...1.
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    /// Set modulation depth in 0...1.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth;
+    }
+
+    /// Gain applied to the carrier for modulator value `x`.
+    #[inline]
+    fn gain<T: Num>(&self, x: T) -> T {
+        let half_depth = T::from_f32(self.depth * 0.5);
+        T::one() - half_depth + half_depth * x
+    }
+}
+
+impl AudioNode for AmpMod {
+    const ID: u64 = 101;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U1;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        [input[0] * self.gain(input[1])].into()
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        for i in 0..simd_items(size) {
+            output.set(0, i, input.at(0, i) * self.gain(input.at(1, i)));
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Value(depth) = setting.parameter() {
+            self.set_depth(*depth);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        // The modulator is linear only if it is constant.
+        output.set(
+            0,
+            match (input.at(0), input.at(1)) {
+                (Signal::Value(x), Signal::Value(y)) => Signal::Value(x * self.gain(y)),
+                (x, Signal::Value(y)) => x.scale(self.gain(y)),
+                (x, y) => x.combine_nonlinear(y, 0.0),
+            },
+        );
+        output
+    }
+}
@@ -7,11 +7,16 @@ use super::setting::*;
 use super::signal::*;
 use super::*;
 use core::marker::PhantomData;
+use num_complex::Complex64;
+use numeric_array::typenum::{Prod, Sum, U2};
 use numeric_array::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// Return equal power pan weights for pan value in -1...1.
 #[inline]
-fn pan_weights<T: Real>(value: T) -> (T, T) {
+pub(crate) fn pan_weights<T: Real>(value: T) -> (T, T) {
     let angle = (clamp11(value) + T::one()) * (T::PI * T::from_f32(0.25));
     (cos(angle), sin(angle))
 }
@@ -90,7 +95,524 @@ impl<N: Size<f32>> AudioNode for Panner<N> {
     }
 }
 
+/// Head radius in meters for the binaural model.
+const HEAD_RADIUS: f32 = 0.0875;
+
+/// Speed of sound in meters per second.
+const SPEED_OF_SOUND: f32 = 343.0;
+
+/// Maximum interaural time difference in seconds.
+const MAX_ITD: f32 = HEAD_RADIUS / SPEED_OF_SOUND * (f32::PI * 0.5 + 1.0);
+
+/// Binaural panner with a parametric head model. Number of inputs is `N`, either 1 or 3.
+/// Azimuth is in radians with zero in front and positive angles to the right.
+/// Elevation is in radians with positive angles above the horizontal plane.
+/// The model is a parametric approximation, not a measured HRTF:
+/// the far ear is delayed by the interaural time difference of a spherical head
+/// (Woodworth) and each ear is filtered with a one-pole, one-zero head shadow filter
+/// (Brown and Duda), which boosts high frequencies at the near ear and cuts them at the far ear.
+/// Elevation only narrows the lateral angle; front/back and elevation cues are not modeled.
+/// Input 0: mono audio
+/// Input 1 (optional): azimuth in radians
+/// Input 2 (optional): elevation in radians
+/// Output 0: left output
+/// Output 1: right output
+#[derive(Clone)]
+pub struct Binaural<N: Size<f32>> {
+    _marker: PhantomData<N>,
+    azimuth: f32,
+    elevation: f32,
+    sample_rate: f32,
+    /// Delay line for the interaural time difference.
+    buffer: Vec<f32>,
+    index: usize,
+    /// Delay of each ear in samples.
+    delay: [f32; 2],
+    /// Head shadow filter coefficients and state for each ear.
+    b0: [f32; 2],
+    b1: [f32; 2],
+    a1: [f32; 2],
+    x1: [f32; 2],
+    y1: [f32; 2],
+}
+
+impl<N: Size<f32>> Binaural<N> {
+    /// Create new binaural panner with `azimuth` and `elevation` in radians.
+    pub fn new(azimuth: f32, elevation: f32) -> Self {
+        let mut node = Self {
+            _marker: PhantomData,
+            azimuth,
+            elevation,
+            sample_rate: 0.0,
+            buffer: Vec::new(),
+            index: 0,
+            delay: [0.0; 2],
+            b0: [1.0; 2],
+            b1: [0.0; 2],
+            a1: [0.0; 2],
+            x1: [0.0; 2],
+            y1: [0.0; 2],
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Azimuth in radians.
+    #[inline]
+    pub fn azimuth(&self) -> f32 {
+        self.azimuth
+    }
+
+    /// Elevation in radians.
+    #[inline]
+    pub fn elevation(&self) -> f32 {
+        self.elevation
+    }
+
+    /// Set source direction: `azimuth` and `elevation` in radians.
+    pub fn set_direction(&mut self, azimuth: f32, elevation: f32) {
+        self.azimuth = azimuth;
+        self.elevation = elevation;
+        // Lateral angle in -pi/2...pi/2, positive to the right.
+        let lateral = libm::asinf(clamp11(sin(azimuth) * cos(elevation)));
+        let itd = HEAD_RADIUS / SPEED_OF_SOUND * (abs(lateral) + sin(abs(lateral)));
+        let far = itd * self.sample_rate;
+        self.delay = if lateral < 0.0 {
+            [0.0, far]
+        } else {
+            [far, 0.0]
+        };
+        // Head shadow filter (Brown and Duda) discretized with the bilinear transform.
+        let beta = 2.0 * SPEED_OF_SOUND / HEAD_RADIUS;
+        let k = 2.0 * self.sample_rate;
+        for (ear, angle) in [lateral + f32::PI * 0.5, f32::PI * 0.5 - lateral]
+            .into_iter()
+            .enumerate()
+        {
+            // The minimum of the response is at 150 degrees from the ear.
+            let alpha = 1.05 + 0.95 * cos(angle * 1.2);
+            self.b0[ear] = (beta + alpha * k) / (beta + k);
+            self.b1[ear] = (beta - alpha * k) / (beta + k);
+            self.a1[ear] = (beta - k) / (beta + k);
+        }
+    }
+
+    /// Read delay line at fractional `delay` samples after writing the current sample.
+    #[inline]
+    fn tap(&self, delay: f32) -> f32 {
+        let mask = self.buffer.len() - 1;
+        let whole = delay as usize;
+        let fraction = delay - whole as f32;
+        let x0 = self.buffer[self.index.wrapping_sub(whole + 1) & mask];
+        let x1 = self.buffer[self.index.wrapping_sub(whole + 2) & mask];
+        lerp(x0, x1, fraction)
+    }
+}
+
+impl<N: Size<f32>> AudioNode for Binaural<N> {
+    const ID: u64 = 114;
+    type Inputs = N;
+    type Outputs = typenum::U2;
+
+    fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.index = 0;
+        self.x1 = [0.0; 2];
+        self.y1 = [0.0; 2];
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        let sample_rate = sample_rate as f32;
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            let length = (ceil(MAX_ITD * sample_rate) as usize + 2).next_power_of_two();
+            self.buffer.clear();
+            self.buffer.resize(length, 0.0);
+            self.set_direction(self.azimuth, self.elevation);
+            self.reset();
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<f32>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 && (input[1] != self.azimuth || input[2] != self.elevation) {
+            self.set_direction(input[1], input[2]);
+        }
+        let mask = self.buffer.len() - 1;
+        self.buffer[self.index] = input[0];
+        self.index = (self.index + 1) & mask;
+        Frame::generate(|ear| {
+            let x = self.tap(self.delay[ear]);
+            let y = self.b0[ear] * x + self.b1[ear] * self.x1[ear] - self.a1[ear] * self.y1[ear];
+            self.x1[ear] = x;
+            self.y1[ear] = y;
+            y
+        })
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
+/// First order ambisonic gains `(W, Y, Z, X)` for a direction
+/// with `azimuth` and `elevation` in radians. Azimuth is zero in front and positive to the right.
+#[inline]
+pub(crate) fn ambisonic_gains(azimuth: f32, elevation: f32) -> [f32; 4] {
+    let horizontal = cos(elevation);
+    [
+        1.0,
+        -sin(azimuth) * horizontal,
+        sin(elevation),
+        cos(azimuth) * horizontal,
+    ]
+}
+
+/// First order ambisonic encoder. Number of inputs is `N`, either 1 or 3.
+/// Output is in AmbiX format: channels are in ACN order (W, Y, Z, X)
+/// with SN3D normalization, so a source has unity gain in W
+/// and the directional components have a maximum gain of one.
+/// Azimuth is in radians with zero in front and positive angles to the right,
+/// as in `binaural`. Elevation is in radians with positive angles above the horizontal plane.
+/// Following the ambisonic convention, Y is positive to the left.
+/// Input 0: mono audio
+/// Input 1 (optional): azimuth in radians
+/// Input 2 (optional): elevation in radians
+/// Output 0: W (omnidirectional)
+/// Output 1: Y (left-right)
+/// Output 2: Z (up-down)
+/// Output 3: X (front-back)
+#[derive(Clone)]
+pub struct AmbisonicEncoder<N: Size<f32>> {
+    _marker: PhantomData<N>,
+    azimuth: f32,
+    elevation: f32,
+    gains: [f32; 4],
+}
+
+impl<N: Size<f32>> AmbisonicEncoder<N> {
+    /// Create new encoder with `azimuth` and `elevation` in radians.
+    pub fn new(azimuth: f32, elevation: f32) -> Self {
+        Self {
+            _marker: PhantomData,
+            azimuth,
+            elevation,
+            gains: ambisonic_gains(azimuth, elevation),
+        }
+    }
+
+    /// Azimuth in radians.
+    #[inline]
+    pub fn azimuth(&self) -> f32 {
+        self.azimuth
+    }
+
+    /// Elevation in radians.
+    #[inline]
+    pub fn elevation(&self) -> f32 {
+        self.elevation
+    }
+
+    /// Set source direction: `azimuth` and `elevation` in radians.
+    #[inline]
+    pub fn set_direction(&mut self, azimuth: f32, elevation: f32) {
+        self.azimuth = azimuth;
+        self.elevation = elevation;
+        self.gains = ambisonic_gains(azimuth, elevation);
+    }
+}
+
+impl<N: Size<f32>> AudioNode for AmbisonicEncoder<N> {
+    const ID: u64 = 123;
+    type Inputs = N;
+    type Outputs = typenum::U4;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 && (input[1] != self.azimuth || input[2] != self.elevation) {
+            self.set_direction(input[1], input[2]);
+        }
+        Frame::generate(|i| self.gains[i] * input[0])
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        if N::USIZE == 1 {
+            for i in 0..simd_items(size) {
+                for channel in 0..4 {
+                    output.set(channel, i, input.at(0, i) * self.gains[channel]);
+                }
+            }
+        } else {
+            for i in 0..size {
+                let output_frame = self.tick(&Frame::generate(|channel| input.at_f32(channel, i)));
+                for channel in 0..4 {
+                    output.set_f32(channel, i, output_frame[channel]);
+                }
+            }
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        // Pretend the direction is constant.
+        for channel in 0..4 {
+            output.set(channel, input.at(0).scale(self.gains[channel].to_f64()));
+        }
+        output
+    }
+}
+
+/// First order ambisonic decoder matrix for speakers at the given azimuths (in radians,
+/// zero in front and positive to the right) on the horizontal plane.
+/// Each speaker feed is a virtual cardioid microphone pointing at the speaker.
+/// Input is in AmbiX format (ACN order, SN3D normalization).
+pub fn ambisonic_decoder<N>(azimuths: &Frame<f32, N>) -> Mixer<typenum::U4, N>
+where
+    N: Size<f32> + Size<Frame<f32, typenum::U4>>,
+{
+    Mixer::new(Frame::generate(|speaker| {
+        let gains = ambisonic_gains(azimuths[speaker], 0.0);
+        Frame::generate(|channel| 0.5 * gains[channel])
+    }))
+}
+
+/// Crossfade law.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CrossfadeLaw {
+    /// Linear crossfade. Gains are `1 - x` and `x` for mix value `x`.
+    /// Preserves amplitude when the signals are coherent (in phase).
+    Linear,
+    /// Equal power crossfade. Gains are `cos(x * pi / 2)` and `sin(x * pi / 2)` for mix value `x`.
+    /// The squared gains sum to one, which preserves power when the signals are uncorrelated.
+    /// At the midpoint both gains are `sqrt(0.5)`, or -3 dB.
+    #[default]
+    Power,
+}
+
+impl CrossfadeLaw {
+    /// Return gains `(a, b)` for mix value `x` in 0...1.
+    #[inline]
+    pub fn weights<T: Real>(&self, x: T) -> (T, T) {
+        let x = clamp01(x);
+        match self {
+            CrossfadeLaw::Linear => (T::one() - x, x),
+            CrossfadeLaw::Power => pan_weights(x + x - T::one()),
+        }
+    }
+}
+
+/// Gain law for converting between mono and stereo.
+/// The gain is applied to each channel.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StereoLaw {
+    /// Unity gain (0 dB). Summing two full scale channels to mono can clip.
+    /// Spreading mono to stereo gives dual mono at the original level.
+    Unity,
+    /// Gain `sqrt(0.5)` (-3 dB). Preserves power: the mono sum of uncorrelated channels
+    /// has the power of the stereo pair, and a mono signal spread to stereo
+    /// is panned to the center at equal power.
+    Minus3dB,
+    /// Gain 0.5 (-6 dB). Preserves amplitude: the mono sum of identical channels
+    /// has the level of either channel, so the sum never exceeds the peak input level.
+    Minus6dB,
+}
+
+impl StereoLaw {
+    /// Gain applied to each channel.
+    #[inline]
+    pub fn gain(&self) -> f32 {
+        match self {
+            StereoLaw::Unity => 1.0,
+            StereoLaw::Minus3dB => core::f32::consts::FRAC_1_SQRT_2,
+            StereoLaw::Minus6dB => 0.5,
+        }
+    }
+}
+
+/// Crossfader between two groups of `N` channels.
+/// Input 0...N - 1: signal A
+/// Input N...2 * N - 1: signal B
+/// Input 2 * N: mix value in 0...1 (0 = A, 1 = B)
+/// Output 0...N - 1: crossfaded signal
+#[derive(Clone)]
+pub struct Crossfade<N> {
+    _marker: PhantomData<N>,
+    law: CrossfadeLaw,
+}
+
+impl<N> Crossfade<N>
+where
+    N: Size<f32> + Mul<U2>,
+    <N as Mul<U2>>::Output: Size<f32> + Add<U1>,
+    <<N as Mul<U2>>::Output as Add<U1>>::Output: Size<f32>,
+{
+    /// Create a new crossfader with the given crossfade law.
+    pub fn new(law: CrossfadeLaw) -> Self {
+        Self {
+            _marker: PhantomData,
+            law,
+        }
+    }
+
+    /// Crossfade law.
+    #[inline]
+    pub fn law(&self) -> CrossfadeLaw {
+        self.law
+    }
+}
+
+impl<N> AudioNode for Crossfade<N>
+where
+    N: Size<f32> + Mul<U2>,
+    <N as Mul<U2>>::Output: Size<f32> + Add<U1>,
+    <<N as Mul<U2>>::Output as Add<U1>>::Output: Size<f32>,
+{
+    const ID: u64 = 116;
+    type Inputs = Sum<Prod<N, U2>, U1>;
+    type Outputs = N;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let (a, b) = self.law.weights(input[N::USIZE * 2]);
+        Frame::generate(|i| a * input[i] + b * input[N::USIZE + i])
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        for i in 0..size {
+            let (a, b) = self.law.weights(input.at_f32(N::USIZE * 2, i));
+            for channel in 0..N::USIZE {
+                output.set_f32(
+                    channel,
+                    i,
+                    a * input.at_f32(channel, i) + b * input.at_f32(N::USIZE + channel, i),
+                );
+            }
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
+/// Selector that passes through one of `N` input channels.
+/// The selection index is rounded to the nearest integer and clamped to 0...N - 1.
+/// When the selection changes, the output crossfades from the previous channel
+/// to the new one with a smooth equal amplitude curve over the crossfade time.
+/// A change during a crossfade starts a new crossfade from the previously targeted channel.
+/// The first sample after a reset selects its channel without a crossfade.
+/// Input 0...N - 1: signals
+/// Input N: selection index
+/// Output 0: selected signal
+#[derive(Clone)]
+pub struct Selector<N> {
+    _marker: PhantomData<N>,
+    /// Crossfade time in seconds.
+    fade_time: f32,
+    /// Crossfade phase increment per sample.
+    fade_step: f32,
+    sample_rate: f32,
+    /// Channel we are fading from.
+    from: usize,
+    /// Channel we are fading to.
+    to: usize,
+    /// Crossfade phase in 0...1. Crossfade is complete at 1.
+    phase: f32,
+    /// Whether a channel has been selected since the last reset.
+    primed: bool,
+}
+
+impl<N> Selector<N>
+where
+    N: Size<f32> + Add<U1>,
+    <N as Add<U1>>::Output: Size<f32>,
+{
+    /// Create a new selector with crossfade time `fade_time` in seconds (`fade_time` >= 0).
+    pub fn new(fade_time: f32) -> Self {
+        assert!(fade_time >= 0.0);
+        let mut node = Self {
+            _marker: PhantomData,
+            fade_time,
+            fade_step: 0.0,
+            sample_rate: DEFAULT_SR as f32,
+            from: 0,
+            to: 0,
+            phase: 1.0,
+            primed: false,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Crossfade time in seconds.
+    #[inline]
+    pub fn fade_time(&self) -> f32 {
+        self.fade_time
+    }
+}
+
+impl<N> AudioNode for Selector<N>
+where
+    N: Size<f32> + Add<U1>,
+    <N as Add<U1>>::Output: Size<f32>,
+{
+    const ID: u64 = 117;
+    type Inputs = Sum<N, U1>;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.from = 0;
+        self.to = 0;
+        self.phase = 1.0;
+        self.primed = false;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate as f32;
+        self.fade_step = if self.fade_time > 0.0 {
+            1.0 / (self.fade_time * self.sample_rate)
+        } else {
+            1.0
+        };
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let index = clamp(0.0, (N::USIZE - 1) as f32, round(input[N::USIZE])) as usize;
+        if !self.primed {
+            self.primed = true;
+            self.from = index;
+            self.to = index;
+            self.phase = 1.0;
+        } else if index != self.to {
+            self.from = self.to;
+            self.to = index;
+            self.phase = 0.0;
+        }
+        if self.phase < 1.0 {
+            self.phase = min(1.0, self.phase + self.fade_step);
+            let weight = smooth5(self.phase);
+            [input[self.from] + (input[self.to] - input[self.from]) * weight].into()
+        } else {
+            [input[self.to]].into()
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary(0.0).route(input, self.outputs())
+    }
+}
+
 /// Mixing matrix with `M` input channels and `N` output channels.
+/// Output `i` is the sum of inputs `j` weighted by matrix cell `(i, j)`.
+/// Cells can be updated at runtime with `Setting::matrix`.
+/// The mixer is linear, so frequency responses propagate through it.
+/// - Inputs: `M` input channels
+/// - Outputs: `N` output channels
 #[derive(Clone)]
 pub struct Mixer<M, N>
 where
@@ -105,9 +627,28 @@ where
     M: Size<f32>,
     N: Size<f32> + Size<Frame<f32, M>>,
 {
+    /// Create a new mixer. The matrix is indexed by output channel first, then input channel.
     pub fn new(matrix: Frame<Frame<f32, M>, N>) -> Self {
         Self { matrix }
     }
+
+    /// Mixing matrix indexed by output channel first, then input channel.
+    #[inline]
+    pub fn matrix(&self) -> &Frame<Frame<f32, M>, N> {
+        &self.matrix
+    }
+
+    /// Gain from channel `input` to channel `output`.
+    #[inline]
+    pub fn gain(&self, output: usize, input: usize) -> f32 {
+        self.matrix[output][input]
+    }
+
+    /// Set gain from channel `input` to channel `output`.
+    #[inline]
+    pub fn set_gain(&mut self, output: usize, input: usize, gain: f32) {
+        self.matrix[output][input] = gain;
+    }
 }
 
 impl<M, N> AudioNode for Mixer<M, N>
@@ -130,6 +671,14 @@ where
         })
     }
 
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Matrix(output, input, gain) = setting.parameter() {
+            if *output < N::USIZE && *input < M::USIZE {
+                self.set_gain(*output, *input, *gain);
+            }
+        }
+    }
+
     fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
         let mut output = SignalFrame::new(self.outputs());
         for i in 0..self.outputs() {
@@ -149,3 +698,144 @@ where
         output
     }
 }
+
+/// Delay times of the decorrelating allpass sections of `Stereoizer` in seconds.
+/// The delays are mutually prime in milliseconds to spread the phase response evenly.
+const STEREOIZER_DELAYS: [f32; 4] = [0.0013, 0.0029, 0.0047, 0.0071];
+
+/// Feedback coefficient of the decorrelating allpass sections of `Stereoizer`.
+const STEREOIZER_COEFFICIENT: f32 = 0.6;
+
+/// Schroeder allpass section: `y[n] = -g * x[n] + x[n - D] + g * y[n - D]`.
+#[derive(Clone)]
+struct AllpassSection {
+    /// Delay line of the internal state `x[n] + g * y[n - D]`.
+    line: Vec<f32>,
+    index: usize,
+}
+
+impl AllpassSection {
+    #[inline]
+    fn tick(&mut self, x: f32) -> f32 {
+        let g = STEREOIZER_COEFFICIENT;
+        let delayed = self.line[self.index];
+        let state = x + g * delayed;
+        self.line[self.index] = state;
+        self.index += 1;
+        if self.index == self.line.len() {
+            self.index = 0;
+        }
+        delayed - g * state
+    }
+}
+
+/// Mono-to-stereo widener. The input is decorrelated with a cascade of Schroeder
+/// allpass filters into a side signal, which is added to the left channel and subtracted
+/// from the right channel: `L = (x + amount * A(x)) / 2`, `R = (x - amount * A(x)) / 2`,
+/// where `A` is the allpass cascade. The channels have complementary frequency responses,
+/// so the image widens without the regular comb filtering of a Haas delay.
+/// The result is mono compatible: the sum `L + R` equals the input exactly at any amount.
+/// With zero amount, each channel is half the input.
+/// - Setting: amount in 0...1.
+/// - Input 0: mono audio
+/// - Output 0: left output
+/// - Output 1: right output
+#[derive(Clone)]
+pub struct Stereoizer {
+    amount: f32,
+    sections: [AllpassSection; 4],
+    sample_rate: f64,
+}
+
+impl Stereoizer {
+    /// Create new stereoizer with widening `amount` in 0...1.
+    pub fn new(amount: f32) -> Self {
+        let mut node = Self {
+            amount: clamp01(amount),
+            sections: core::array::from_fn(|_| AllpassSection {
+                line: Vec::new(),
+                index: 0,
+            }),
+            sample_rate: 0.0,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Widening amount in 0...1.
+    #[inline]
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    /// Set widening amount in 0...1.
+    #[inline]
+    pub fn set_amount(&mut self, amount: f32) {
+        self.amount = clamp01(amount);
+    }
+}
+
+impl AudioNode for Stereoizer {
+    const ID: u64 = 154;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U2;
+
+    fn reset(&mut self) {
+        for section in self.sections.iter_mut() {
+            section.line.fill(0.0);
+            section.index = 0;
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            for (section, delay) in self.sections.iter_mut().zip(STEREOIZER_DELAYS) {
+                let length = max(1.0, round(delay as f64 * sample_rate)) as usize;
+                section.line = vec![0.0; length];
+                section.index = 0;
+            }
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.sections
+            .iter()
+            .map(|section| section.line.capacity() * core::mem::size_of::<f32>())
+            .sum()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let x = input[0];
+        let mut side = x;
+        for section in self.sections.iter_mut() {
+            side = section.tick(side);
+        }
+        side *= self.amount;
+        [0.5 * (x + side), 0.5 * (x - side)].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Value(amount) = setting.parameter() {
+            self.set_amount(*amount);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let g = STEREOIZER_COEFFICIENT as f64;
+        let mut allpass = Complex64::new(1.0, 0.0);
+        for section in self.sections.iter() {
+            let z = Complex64::from_polar(
+                1.0,
+                -f64::TAU * frequency * section.line.len() as f64 / self.sample_rate,
+            );
+            allpass *= (z - g) / (1.0 - g * z);
+        }
+        let side = allpass * self.amount as f64;
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).filter(0.0, |r| r * 0.5 * (1.0 + side)));
+        output.set(1, input.at(0).filter(0.0, |r| r * 0.5 * (1.0 - side)));
+        output
+    }
+}
@@ -0,0 +1,177 @@
+//! Pitch detection.
+
+use super::audionode::*;
+use super::buffer::*;
+use super::math::*;
+use super::shared::*;
+use super::signal::*;
+use super::*;
+use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// YIN threshold for the cumulative mean normalized difference.
+const YIN_THRESHOLD: f32 = 0.1;
+
+/// Minimum lag in samples considered by the pitch detector.
+const MIN_LAG: usize = 2;
+
+/// Pitch detector using the YIN algorithm. Passes through input unchanged.
+/// The input is analyzed in overlapping frames of `2 * window` samples every `window / 2` samples.
+/// Detectable frequencies range from `sample_rate / window` Hz up to half the sample rate,
+/// with accuracy decreasing at high frequencies as the period shrinks to a few samples.
+/// Because each estimate is based on the most recent `2 * window` samples,
+/// estimates lag the input by about `window` samples.
+/// The estimated fundamental frequency in Hz is written to `frequency` and a confidence value
+/// in 0...1 to `confidence`. Confidence is close to 1 for periodic signals
+/// and close to 0 for noise or silence.
+/// - Allocates: analysis buffers.
+/// - Input 0: signal
+/// - Output 0: signal
+#[derive(Clone)]
+pub struct PitchDetector {
+    /// Integration window length in samples.
+    window: usize,
+    /// Ring buffer of the latest `2 * window` samples.
+    buffer: Vec<f32>,
+    /// Next write position in `buffer`.
+    i: usize,
+    /// Samples until next analysis.
+    countdown: usize,
+    /// Analysis frame in chronological order.
+    frame: Vec<f32>,
+    /// Cumulative mean normalized difference function.
+    difference: Vec<f32>,
+    frequency: Shared,
+    confidence: Shared,
+    sample_rate: f64,
+}
+
+impl PitchDetector {
+    /// Create a new pitch detector with integration `window` in samples (`window` >= 8).
+    /// Estimated fundamental frequency is written to `frequency` and confidence to `confidence`.
+    pub fn new(window: usize, frequency: &Shared, confidence: &Shared) -> Self {
+        assert!(window >= 8);
+        Self {
+            window,
+            buffer: vec![0.0; window * 2],
+            i: 0,
+            countdown: window / 2,
+            frame: vec![0.0; window * 2],
+            difference: vec![0.0; window],
+            frequency: frequency.clone(),
+            confidence: confidence.clone(),
+            sample_rate: DEFAULT_SR,
+        }
+    }
+
+    /// Integration window in samples.
+    #[inline]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Analyze the latest frame and store the results.
+    fn analyze(&mut self) {
+        let length = self.buffer.len();
+        for j in 0..length {
+            self.frame[j] = self.buffer[(self.i + j) % length];
+        }
+        let w = self.window;
+        // Cumulative mean normalized difference function.
+        self.difference[0] = 1.0;
+        let mut sum = 0.0;
+        for tau in 1..w {
+            let mut d = 0.0;
+            for j in 0..w {
+                d += squared(self.frame[j] - self.frame[j + tau]);
+            }
+            sum += d;
+            self.difference[tau] = if sum > 0.0 { d * tau as f32 / sum } else { 1.0 };
+        }
+        // Find the first dip below threshold, or the global minimum if there is none.
+        let mut lag = 0;
+        let mut tau = MIN_LAG;
+        while tau < w {
+            if self.difference[tau] < YIN_THRESHOLD {
+                while tau + 1 < w && self.difference[tau + 1] < self.difference[tau] {
+                    tau += 1;
+                }
+                lag = tau;
+                break;
+            }
+            tau += 1;
+        }
+        if lag == 0 {
+            lag = MIN_LAG;
+            for tau in MIN_LAG..w {
+                if self.difference[tau] < self.difference[lag] {
+                    lag = tau;
+                }
+            }
+        }
+        // Refine the lag with parabolic interpolation.
+        let mut period = lag as f32;
+        if lag > 1 && lag + 1 < w {
+            let a = self.difference[lag - 1];
+            let b = self.difference[lag];
+            let c = self.difference[lag + 1];
+            let denominator = a - 2.0 * b + c;
+            if denominator > 0.0 {
+                period += 0.5 * (a - c) / denominator;
+            }
+        }
+        self.frequency.set_value(self.sample_rate as f32 / period);
+        self.confidence
+            .set_value(clamp01(1.0 - self.difference[lag]));
+    }
+}
+
+impl AudioNode for PitchDetector {
+    const ID: u64 = 120;
+    type Inputs = U1;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.i = 0;
+        self.countdown = self.window / 2;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn memory_usage(&self) -> usize {
+        (self.buffer.capacity() + self.frame.capacity() + self.difference.capacity())
+            * core::mem::size_of::<f32>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.buffer[self.i] = input[0];
+        self.i += 1;
+        if self.i == self.buffer.len() {
+            self.i = 0;
+        }
+        self.countdown -= 1;
+        if self.countdown == 0 {
+            self.countdown = self.window / 2;
+            self.analyze();
+        }
+        *input
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        for i in 0..size {
+            self.tick(&Frame::from([input.at_f32(0, i)]));
+        }
+        output.channel_mut(0)[..simd_items(size)]
+            .clone_from_slice(&input.channel(0)[..simd_items(size)]);
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        input.clone()
+    }
+}
@@ -7,7 +7,9 @@ use alloc::sync::Arc;
 pub use super::audionode::*;
 pub use super::audiounit::*;
 pub use super::biquad::*;
+pub use super::biquad_bank::*;
 pub use super::buffer::*;
+pub use super::bypass::*;
 pub use super::combinator::*;
 pub use super::delay::*;
 pub use super::dynamics::*;
@@ -21,15 +23,19 @@ pub use super::math::*;
 pub use super::moog::*;
 pub use super::net::*;
 pub use super::noise::*;
+pub use super::onset::*;
 pub use super::oscillator::*;
 pub use super::oversample::*;
 pub use super::pan::*;
+pub use super::pitch::*;
 pub use super::realnet::*;
 pub use super::resample::*;
 pub use super::resynth::*;
 pub use super::reverb::*;
 pub use super::rez::*;
+pub use super::rhythm::*;
 pub use super::ring::*;
+pub use super::rotary::*;
 pub use super::sequencer::*;
 pub use super::setting::*;
 pub use super::shape::*;
@@ -39,140 +45,13 @@ pub use super::slot::*;
 pub use super::snoop::*;
 pub use super::svf::*;
 pub use super::system::*;
+pub use super::vocoder::*;
 pub use super::wave::*;
 pub use super::wavetable::*;
 pub use super::*;
 
-// Import some typenum integers for reporting arities.
-pub type U0 = numeric_array::typenum::U0;
-pub type U1 = numeric_array::typenum::U1;
-pub type U2 = numeric_array::typenum::U2;
-pub type U3 = numeric_array::typenum::U3;
-pub type U4 = numeric_array::typenum::U4;
-pub type U5 = numeric_array::typenum::U5;
-pub type U6 = numeric_array::typenum::U6;
-pub type U7 = numeric_array::typenum::U7;
-pub type U8 = numeric_array::typenum::U8;
-pub type U9 = numeric_array::typenum::U9;
-pub type U10 = numeric_array::typenum::U10;
-pub type U11 = numeric_array::typenum::U11;
-pub type U12 = numeric_array::typenum::U12;
-pub type U13 = numeric_array::typenum::U13;
-pub type U14 = numeric_array::typenum::U14;
-pub type U15 = numeric_array::typenum::U15;
-pub type U16 = numeric_array::typenum::U16;
-pub type U17 = numeric_array::typenum::U17;
-pub type U18 = numeric_array::typenum::U18;
-pub type U19 = numeric_array::typenum::U19;
-pub type U20 = numeric_array::typenum::U20;
-pub type U21 = numeric_array::typenum::U21;
-pub type U22 = numeric_array::typenum::U22;
-pub type U23 = numeric_array::typenum::U23;
-pub type U24 = numeric_array::typenum::U24;
-pub type U25 = numeric_array::typenum::U25;
-pub type U26 = numeric_array::typenum::U26;
-pub type U27 = numeric_array::typenum::U27;
-pub type U28 = numeric_array::typenum::U28;
-pub type U29 = numeric_array::typenum::U29;
-pub type U30 = numeric_array::typenum::U30;
-pub type U31 = numeric_array::typenum::U31;
-pub type U32 = numeric_array::typenum::U32;
-pub type U33 = numeric_array::typenum::U33;
-pub type U34 = numeric_array::typenum::U34;
-pub type U35 = numeric_array::typenum::U35;
-pub type U36 = numeric_array::typenum::U36;
-pub type U37 = numeric_array::typenum::U37;
-pub type U38 = numeric_array::typenum::U38;
-pub type U39 = numeric_array::typenum::U39;
-pub type U40 = numeric_array::typenum::U40;
-pub type U41 = numeric_array::typenum::U41;
-pub type U42 = numeric_array::typenum::U42;
-pub type U43 = numeric_array::typenum::U43;
-pub type U44 = numeric_array::typenum::U44;
-pub type U45 = numeric_array::typenum::U45;
-pub type U46 = numeric_array::typenum::U46;
-pub type U47 = numeric_array::typenum::U47;
-pub type U48 = numeric_array::typenum::U48;
-pub type U49 = numeric_array::typenum::U49;
-pub type U50 = numeric_array::typenum::U50;
-pub type U51 = numeric_array::typenum::U51;
-pub type U52 = numeric_array::typenum::U52;
-pub type U53 = numeric_array::typenum::U53;
-pub type U54 = numeric_array::typenum::U54;
-pub type U55 = numeric_array::typenum::U55;
-pub type U56 = numeric_array::typenum::U56;
-pub type U57 = numeric_array::typenum::U57;
-pub type U58 = numeric_array::typenum::U58;
-pub type U59 = numeric_array::typenum::U59;
-pub type U60 = numeric_array::typenum::U60;
-pub type U61 = numeric_array::typenum::U61;
-pub type U62 = numeric_array::typenum::U62;
-pub type U63 = numeric_array::typenum::U63;
-pub type U64 = numeric_array::typenum::U64;
-pub type U65 = numeric_array::typenum::U65;
-pub type U66 = numeric_array::typenum::U66;
-pub type U67 = numeric_array::typenum::U67;
-pub type U68 = numeric_array::typenum::U68;
-pub type U69 = numeric_array::typenum::U69;
-pub type U70 = numeric_array::typenum::U70;
-pub type U71 = numeric_array::typenum::U71;
-pub type U72 = numeric_array::typenum::U72;
-pub type U73 = numeric_array::typenum::U73;
-pub type U74 = numeric_array::typenum::U74;
-pub type U75 = numeric_array::typenum::U75;
-pub type U76 = numeric_array::typenum::U76;
-pub type U77 = numeric_array::typenum::U77;
-pub type U78 = numeric_array::typenum::U78;
-pub type U79 = numeric_array::typenum::U79;
-pub type U80 = numeric_array::typenum::U80;
-pub type U81 = numeric_array::typenum::U81;
-pub type U82 = numeric_array::typenum::U82;
-pub type U83 = numeric_array::typenum::U83;
-pub type U84 = numeric_array::typenum::U84;
-pub type U85 = numeric_array::typenum::U85;
-pub type U86 = numeric_array::typenum::U86;
-pub type U87 = numeric_array::typenum::U87;
-pub type U88 = numeric_array::typenum::U88;
-pub type U89 = numeric_array::typenum::U89;
-pub type U90 = numeric_array::typenum::U80;
-pub type U91 = numeric_array::typenum::U91;
-pub type U92 = numeric_array::typenum::U92;
-pub type U93 = numeric_array::typenum::U93;
-pub type U94 = numeric_array::typenum::U94;
-pub type U95 = numeric_array::typenum::U95;
-pub type U96 = numeric_array::typenum::U96;
-pub type U97 = numeric_array::typenum::U97;
-pub type U98 = numeric_array::typenum::U98;
-pub type U99 = numeric_array::typenum::U99;
-pub type U100 = numeric_array::typenum::U100;
-pub type U101 = numeric_array::typenum::U101;
-pub type U102 = numeric_array::typenum::U102;
-pub type U103 = numeric_array::typenum::U103;
-pub type U104 = numeric_array::typenum::U104;
-pub type U105 = numeric_array::typenum::U105;
-pub type U106 = numeric_array::typenum::U106;
-pub type U107 = numeric_array::typenum::U107;
-pub type U108 = numeric_array::typenum::U108;
-pub type U109 = numeric_array::typenum::U109;
-pub type U110 = numeric_array::typenum::U110;
-pub type U111 = numeric_array::typenum::U111;
-pub type U112 = numeric_array::typenum::U112;
-pub type U113 = numeric_array::typenum::U113;
-pub type U114 = numeric_array::typenum::U114;
-pub type U115 = numeric_array::typenum::U115;
-pub type U116 = numeric_array::typenum::U116;
-pub type U117 = numeric_array::typenum::U117;
-pub type U118 = numeric_array::typenum::U118;
-pub type U119 = numeric_array::typenum::U119;
-pub type U120 = numeric_array::typenum::U120;
-pub type U121 = numeric_array::typenum::U121;
-pub type U122 = numeric_array::typenum::U122;
-pub type U123 = numeric_array::typenum::U123;
-pub type U124 = numeric_array::typenum::U124;
-pub type U125 = numeric_array::typenum::U125;
-pub type U126 = numeric_array::typenum::U126;
-pub type U127 = numeric_array::typenum::U127;
-pub type U128 = numeric_array::typenum::U128;
+// Typenum integers for reporting arities.
+pub use super::sizes::*;
 
 /// Constant node. The constant can be scalar, tuple, or a Frame.
 /// Synonymous with [`dc`].
@@ -242,6 +121,31 @@ pub fn update<X: AudioNode, F: FnMut(f32, f32, &mut X) + Clone + Send + Sync>(
     An(System::new(x, dt, f))
 }
 
+/// Update enclosed node `x` with approximately `dt` seconds between updates
+/// and report a control value. The update function is `f(t, dt, samples, x)` where
+/// `t` is current time, `dt` is time from previous update, `samples` is the number
+/// of samples processed since reset, and `x` is the enclosed node.
+/// The return value of `f` is written to `shared` after each update.
+/// Updates are done in the processing thread before `x` processes the current sample or block.
+///
+/// ### Example: Cutoff Modulation With Reporting
+/// ```
+/// use fundsp::prelude::*;
+/// let cutoff = shared(0.0);
+/// update_out(lowpass_hz::<f32>(1000.0, 1.0), 0.01, &cutoff, |t, _dt, _samples, x| {
+///     let f = 1000.0 + 500.0 * sin(t);
+///     x.set(Setting::center(f));
+///     f
+/// });
+/// ```
+pub fn update_out<X, F>(x: An<X>, dt: f32, shared: &Shared, f: F) -> An<SystemOut<X, F>>
+where
+    X: AudioNode,
+    F: FnMut(f32, f32, u64, &mut X) -> f32 + Clone + Send + Sync,
+{
+    An(SystemOut::new(x, dt, shared, f))
+}
+
 /// Mono pass-through.
 /// - Input 0: signal
 /// - Output 0: signal
@@ -283,6 +187,64 @@ pub fn monitor(shared: &Shared, meter: Meter) -> An<Monitor> {
     An(Monitor::new(shared, meter))
 }
 
+/// Clip monitor node. Passes through input unchanged.
+/// Unlike `clip`, the signal is not modified: instead, each sample
+/// with absolute value greater than 1 increments the shared counter by one.
+/// - Input 0: signal
+/// - Output 0: signal
+///
+/// ### Example
+/// ```
+/// use fundsp::prelude::*;
+/// let clipped = shared_counter();
+/// clip_monitor(&clipped);
+/// ```
+pub fn clip_monitor(counter: &SharedCounter) -> An<ClipMonitor> {
+    An(ClipMonitor::new(counter))
+}
+
+/// Pitch detector. Passes through input. Estimates the fundamental frequency of the input
+/// with the YIN algorithm, writing the frequency in Hz to `frequency`
+/// and a confidence value in 0...1 to `confidence`.
+/// `window` is the integration window in samples (`window` >= 8).
+/// Detectable frequencies range from `sample_rate / window` Hz up to half the sample rate.
+/// Estimates are updated every `window / 2` samples from the latest `2 * window` samples,
+/// so they lag the input by about `window` samples.
+/// - Allocates: analysis buffers.
+/// - Input 0: signal
+/// - Output 0: signal
+///
+/// ### Example: Tuner
+/// ```
+/// use fundsp::prelude::*;
+/// let frequency = shared(0.0);
+/// let confidence = shared(0.0);
+/// pitch_detector(1024, &frequency, &confidence);
+/// ```
+pub fn pitch_detector(window: usize, frequency: &Shared, confidence: &Shared) -> An<PitchDetector> {
+    An(PitchDetector::new(window, frequency, confidence))
+}
+
+/// Onset detector. Passes through input and emits a trigger impulse on the second output
+/// when a spectral flux onset is detected. `sensitivity` is in 0...1: higher values detect
+/// weaker onsets. Onsets closer than 50 ms to the previous onset are ignored;
+/// the minimum interval can be changed with `set_min_interval`.
+/// The analysis window is 1024 samples with a hop of 256 samples,
+/// and onsets are reported with a latency of up to 512 samples.
+/// - Allocates: analysis buffers.
+/// - Input 0: signal
+/// - Output 0: signal
+/// - Output 1: trigger impulse (1 at onset, 0 otherwise)
+///
+/// ### Example: Trigger Envelopes From Onsets
+/// ```
+/// use fundsp::prelude::*;
+/// onset_detector(0.5) >> (pass() | lowpass_hz::<f32>(20.0, 1.0));
+/// ```
+pub fn onset_detector(sensitivity: f32) -> An<OnsetDetector> {
+    An(OnsetDetector::new(1024, sensitivity, 0.05))
+}
+
 /// Meter node.
 /// Outputs a summary of the input according to the chosen metering mode.
 /// - Input 0: signal
@@ -386,6 +348,39 @@ pub fn lorenz() -> An<Lorenz> {
     An(Lorenz::new())
 }
 
+/// Logistic map chaotic generator with parameter `r` in 0...4.
+/// Iterates `x <- r * x * (1 - x)` at the rate given in input 0 and holds the value in between.
+/// For `r` above about 3.57 the map is chaotic for most values.
+/// Output is in 0...1 and should be centered before use as audio,
+/// for example with `* 2.0 - 1.0`.
+/// - Input 0: iteration rate in Hz
+/// - Output 0: map value in 0...1
+///
+/// ### Example: Chaotic Stepped Modulation
+/// ```
+/// use fundsp::prelude::*;
+/// dc(8.0) >> (logistic(3.9) * 220.0 + 220.0) >> sine::<f32>();
+/// ```
+pub fn logistic(r: f32) -> An<Logistic> {
+    An(Logistic::new(r))
+}
+
+/// Henon map chaotic generator with parameters `a` and `b`.
+/// Iterates `(x, y) <- (1 - a * x * x + y, b * x)` at the rate given in input 0
+/// and holds `x` in between. With the classic parameters `a` = 1.4 and `b` = 0.3
+/// the output stays within about -1.3...1.3, so scale it to audio range, for example with `* 0.75`.
+/// - Input 0: iteration rate in Hz
+/// - Output 0: map value `x`
+///
+/// ### Example: Chaotic Noise
+/// ```
+/// use fundsp::prelude::*;
+/// dc(22050.0) >> henon(1.4, 0.3) * 0.75;
+/// ```
+pub fn henon(a: f32, b: f32) -> An<Henon> {
+    An(Henon::new(a, b))
+}
+
 /// Add constant to signal.
 /// - Input(s): signal
 /// - Output(s): signal plus constant
@@ -473,6 +468,40 @@ pub fn lowpole_hz<F: Real>(cutoff: F) -> An<Lowpole<F, U1>> {
     An(Lowpole::new(cutoff))
 }
 
+/// One-pole smoothing filter for control signals with `cutoff` frequency in Hz.
+/// Unlike `follow`, which is specified by its halfway response time,
+/// the step response reaches 63% of its final value in `1 / (2 * PI * cutoff)` seconds.
+/// Setting: cutoff (`Setting::center`) or time constant in seconds (`Setting::time`).
+/// - Input 0: control signal
+/// - Output 0: smoothed signal
+///
+/// ### Example: Smoothed Parameter
+/// ```
+/// use fundsp::prelude::*;
+/// let parameter = shared(1.0);
+/// var(&parameter) >> smooth_pole::<f32>(10.0);
+/// ```
+pub fn smooth_pole<F: Real>(cutoff: F) -> An<SmoothPole<F>> {
+    An(SmoothPole::new(cutoff))
+}
+
+/// One-pole smoothing filter for control signals with time constant `tau_samples` in samples.
+/// The step response reaches 63% of its final value in `tau_samples` samples
+/// regardless of the sample rate.
+/// Setting: cutoff (`Setting::center`) or time constant in seconds (`Setting::time`).
+/// - Input 0: control signal
+/// - Output 0: smoothed signal
+///
+/// ### Example: Smoothed Parameter
+/// ```
+/// use fundsp::prelude::*;
+/// let parameter = shared(1.0);
+/// var(&parameter) >> smooth_pole_samples::<f32>(64.0);
+/// ```
+pub fn smooth_pole_samples<F: Real>(tau_samples: F) -> An<SmoothPole<F>> {
+    An(SmoothPole::new_samples(tau_samples))
+}
+
 /// Allpass filter (1st order) with a configurable delay (delay > 0) in samples at DC.
 /// - Input 0: audio
 /// - Input 1: delay in samples
@@ -564,6 +593,78 @@ pub fn moog_hz<F: Real>(frequency: F, q: F) -> An<Moog<F, U1>> {
     An(Moog::new(frequency, q))
 }
 
+/// Transistor ladder lowpass filter (4th order) with per-stage saturation.
+/// Self-oscillates at the cutoff frequency when resonance exceeds 10/11 (about 0.91).
+/// - Input 0: input signal
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: resonance in 0...1
+/// - Output 0: filtered signal
+pub fn transistor_ladder<F: Real>() -> An<TransistorLadder<F, U3>> {
+    An(TransistorLadder::new(F::new(1000), F::zero()))
+}
+
+/// Transistor ladder lowpass filter (4th order) with fixed cutoff frequency and resonance.
+/// Self-oscillates at the cutoff frequency when resonance exceeds 10/11 (about 0.91).
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+pub fn transistor_ladder_hz<F: Real>(frequency: F, resonance: F) -> An<TransistorLadder<F, U1>> {
+    An(TransistorLadder::new(frequency, resonance))
+}
+
+/// Diode ladder lowpass filter (4th order) in the style of the TB-303.
+/// Self-oscillates at the cutoff frequency when resonance exceeds 10/11 (about 0.91).
+/// - Input 0: input signal
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: resonance in 0...1
+/// - Output 0: filtered signal
+pub fn diode_ladder<F: Real>() -> An<DiodeLadder<F, U3>> {
+    An(DiodeLadder::new(F::new(1000), F::zero()))
+}
+
+/// Diode ladder lowpass filter (4th order) with fixed cutoff frequency and resonance.
+/// Self-oscillates at the cutoff frequency when resonance exceeds 10/11 (about 0.91).
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+pub fn diode_ladder_hz<F: Real>(frequency: F, resonance: F) -> An<DiodeLadder<F, U1>> {
+    An(DiodeLadder::new(frequency, resonance))
+}
+
+/// Sallen-Key lowpass filter (2nd order) with a saturating amplifier.
+/// Passband gain rises with resonance. Self-oscillates above resonance 10/11 (about 0.91).
+/// - Input 0: input signal
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: resonance in 0...1
+/// - Output 0: filtered signal
+pub fn sallen_key_lowpass<F: Real>() -> An<SallenKey<F, U3>> {
+    An(SallenKey::new(F::new(1000), F::zero()))
+}
+
+/// Sallen-Key lowpass filter (2nd order) with fixed cutoff frequency and resonance.
+/// Passband gain rises with resonance. Self-oscillates above resonance 10/11 (about 0.91).
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+pub fn sallen_key_lowpass_hz<F: Real>(frequency: F, resonance: F) -> An<SallenKey<F, U1>> {
+    An(SallenKey::new(frequency, resonance))
+}
+
+/// OTA cascade lowpass filter (2nd order) with saturating stages.
+/// Resonance maps to Q from 0.5 to 10. Does not self-oscillate.
+/// - Input 0: input signal
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: resonance in 0...1
+/// - Output 0: filtered signal
+pub fn ota_lowpass<F: Real>() -> An<OtaLowpass<F, U3>> {
+    An(OtaLowpass::new(F::new(1000), F::zero()))
+}
+
+/// OTA cascade lowpass filter (2nd order) with fixed cutoff frequency and resonance.
+/// Resonance maps to Q from 0.5 to 10. Does not self-oscillate.
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+pub fn ota_lowpass_hz<F: Real>(frequency: F, resonance: F) -> An<OtaLowpass<F, U1>> {
+    An(OtaLowpass::new(frequency, resonance))
+}
+
 /// Control envelope from time-varying function `f(t)` with `t` in seconds.
 /// Spaces samples using pseudorandom jittering.
 /// Synonymous with [`fn@lfo`].
@@ -608,6 +709,88 @@ where
     An(Envelope::new(F::from_f64(0.002), f))
 }
 
+/// Control envelope from time-varying function `f(t)` with `t` in seconds,
+/// sampled `rate` times per second (on average). Spaces samples using pseudorandom jittering.
+/// The default rate of `envelope` is 500 Hz. Higher rates track fast changes,
+/// such as percussive attacks, more accurately, at the cost of calling `f` more often.
+/// Lower rates save CPU for slowly changing controls.
+/// Synonymous with [`fn@lfo_hz`].
+/// - Output(s): envelope linearly interpolated from samples at `rate` Hz (average).
+///
+/// ### Example: Fast Attack On White Noise
+/// ```
+/// use fundsp::prelude::*;
+/// envelope_hz(2000.0, |t: f32| 1.0 - exp(-t * 200.0)) * white();
+/// ```
+pub fn envelope_hz<F, E, R>(rate: F, f: E) -> An<Envelope<F, E, R>>
+where
+    F: Real,
+    E: FnMut(F) -> R + Clone + Send + Sync,
+    R: ConstantFrame<Sample = F>,
+    R::Size: Size<F> + Size<f32>,
+{
+    An(Envelope::new(F::one() / rate, f))
+}
+
+/// Control envelope from time-varying function `f(t)` with `t` in seconds,
+/// sampled `rate` times per second (on average). Spaces samples using pseudorandom jittering.
+/// Higher rates are more accurate but cost more CPU.
+/// Synonymous with [`fn@envelope_hz`].
+/// - Output(s): envelope linearly interpolated from samples at `rate` Hz (average).
+///
+/// ### Example: Slow LFO
+/// ```
+/// use fundsp::prelude::*;
+/// lfo_hz(100.0, |t: f32| sin_hz(2.0, t));
+/// ```
+pub fn lfo_hz<F, E, R>(rate: F, f: E) -> An<Envelope<F, E, R>>
+where
+    F: Real,
+    E: FnMut(F) -> R + Clone + Send + Sync,
+    R: ConstantFrame<Sample = F>,
+    R::Size: Size<F> + Size<f32>,
+{
+    An(Envelope::new(F::one() / rate, f))
+}
+
+/// Control envelope from time-varying function `f(t)` with `t` in seconds,
+/// evaluated exactly at every sample without jitter or interpolation.
+/// Prefer this for deterministic control signals, such as automation curves,
+/// or when `f` has sharp corners that must be reproduced exactly.
+/// Prefer `envelope` for smooth modulation: it calls `f` far less often,
+/// and its jittered sampling avoids periodic artifacts.
+/// Synonymous with [`fn@lfo_exact`].
+/// - Output(s): `f(t)` at each sample.
+///
+/// ### Example: Linear Fade-In Over One Second
+/// ```
+/// use fundsp::prelude::*;
+/// envelope_exact(|t: f32| if t < 1.0 { t } else { 1.0 }) * white();
+/// ```
+pub fn envelope_exact<F, E, R>(f: E) -> An<ExactEnvelope<F, E, R>>
+where
+    F: Real,
+    E: FnMut(F) -> R + Clone + Send + Sync,
+    R: ConstantFrame<Sample = F>,
+    R::Size: Size<F> + Size<f32>,
+{
+    An(ExactEnvelope::new(f))
+}
+
+/// Control envelope from time-varying function `f(t)` with `t` in seconds,
+/// evaluated exactly at every sample without jitter or interpolation.
+/// Synonymous with [`fn@envelope_exact`].
+/// - Output(s): `f(t)` at each sample.
+pub fn lfo_exact<F, E, R>(f: E) -> An<ExactEnvelope<F, E, R>>
+where
+    F: Real,
+    E: FnMut(F) -> R + Clone + Send + Sync,
+    R: ConstantFrame<Sample = F>,
+    R::Size: Size<F> + Size<f32>,
+{
+    An(ExactEnvelope::new(f))
+}
+
 /// Control envelope from time-varying, input dependent function `f(t, x)` with `t` in seconds.
 /// Spaces samples using pseudorandom jittering.
 /// Synonymous with `lfo2`.
@@ -819,6 +1002,23 @@ pub fn white() -> An<Noise> {
     An(Noise::new())
 }
 
+/// Fractal (1/f) noise generator using the Voss-McCartney algorithm.
+/// The clock rate in input 0 sets the upper end of the spectrum
+/// independently of the sample rate. The spectrum falls approximately 3 dB/octave
+/// from about `rate / 131072` Hz up to `rate / 2` Hz.
+/// Output is in -1...1, although it rarely exceeds -0.5...0.5.
+/// - Input 0: clock rate in Hz
+/// - Output 0: fractal noise
+///
+/// ### Example: Slow Fractal Modulation
+/// ```
+/// use fundsp::prelude::*;
+/// dc(100.0) >> voss();
+/// ```
+pub fn voss() -> An<Voss> {
+    An(Voss::new())
+}
+
 /// Sample-and-hold component. Sampling frequency `variability` is in 0...1.
 /// - Input 0: signal.
 /// - Input 1: sampling frequency (Hz).
@@ -863,6 +1063,28 @@ pub fn fir3(gain: f32) -> An<Fir<U3>> {
     An(Fir::new((beta, alpha, beta)))
 }
 
+/// Linear phase crossover into `B` bands with FIR filters of length `taps`.
+/// There are `B - 1` crossover `frequencies` in Hz in ascending order.
+/// The bands sum to the input delayed by the latency of `(taps - 1) / 2` samples
+/// (`taps` is rounded up to an odd number). Longer filters are steeper but have
+/// more latency and pre-ringing before transients. The filters are applied with
+/// partitioned FFT convolution, so filters of thousands of taps are practical.
+/// - Input 0: signal
+/// - Outputs: bands from lowest to highest
+///
+/// ### Example: 3-Band Split For Mastering
+/// ```
+/// use fundsp::prelude::*;
+/// let node = linear_phase_crossover::<U3>(&[200.0, 2000.0], 1023);
+/// assert_eq!(node.outputs(), 3);
+/// ```
+pub fn linear_phase_crossover<B: Size<f32>>(
+    frequencies: &[f32],
+    taps: usize,
+) -> An<LinearPhaseCrossover<B>> {
+    An(LinearPhaseCrossover::new(frequencies, taps))
+}
+
 /// Single sample delay.
 /// - Input 0: signal.
 /// - Output 0: delayed signal.
@@ -904,6 +1126,26 @@ pub fn delay(t: f64) -> An<Delay> {
     An(Delay::new(t))
 }
 
+/// Haas (precedence effect) stereo widener. The mono input is sent to both channels
+/// and one channel is delayed by `t` seconds (a few milliseconds, typically 0.005...0.03).
+/// The sound appears to come from the side of the undelayed channel.
+/// `side` selects the delayed channel (`HaasSide::Left` or `HaasSide::Right`).
+/// Note that the effect is not mono compatible: when the channels are summed,
+/// the delay produces comb filtering with notches at odd multiples of `1 / (2 * t)` Hz.
+/// - Allocates: the delay line.
+/// - Input 0: mono signal.
+/// - Output 0: left signal.
+/// - Output 1: right signal.
+///
+/// ### Example: Widen A Mono Saw
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> haas(0.015, HaasSide::Right);
+/// ```
+pub fn haas(t: f64, side: HaasSide) -> An<Haas> {
+    An(Haas::new(t, side))
+}
+
 /// Tapped delay line with cubic interpolation.
 /// Minimum and maximum delay times are in seconds (`min_delay`, `max_delay` >= 0).
 /// The minimum possible delay is one sample.
@@ -921,6 +1163,38 @@ pub fn tap(min_delay: f32, max_delay: f32) -> An<Tap<U1>> {
     An(Tap::new(min_delay, max_delay))
 }
 
+/// Distance and Doppler model for moving sources. The signal is delayed
+/// by the time sound takes to travel the source distance at `speed_of_sound`
+/// (in meters per second, 343 in air) and attenuated by the inverse of distance,
+/// with unity gain at distances of one meter or less.
+/// As distance changes, the varying delay produces a Doppler pitch shift.
+/// The delay line uses cubic interpolation and is sized for distances up to `max_distance` meters;
+/// larger distances are clamped.
+/// Distance should change by less than `speed_of_sound` meters per second:
+/// a source receding at the speed of sound freezes the signal and faster motion reverses it.
+/// The minimum delay is one sample, which corresponds to a distance of about
+/// `speed_of_sound / sample_rate` meters.
+/// - Allocates: the delay line.
+/// - Input 0: signal.
+/// - Input 1: source distance in meters.
+/// - Output 0: signal at the listener.
+///
+/// ### Example: Passing Siren
+/// ```
+/// use fundsp::prelude::*;
+/// (sine_hz::<f32>(800.0) | lfo(|t: f32| 1.0 + abs(100.0 - 20.0 * t))) >> doppler(343.0, 200.0);
+/// ```
+pub fn doppler(
+    speed_of_sound: f32,
+    max_distance: f32,
+) -> An<impl AudioNode<Inputs = U2, Outputs = U1>> {
+    let max_delay = max_distance / speed_of_sound;
+    (pass()
+        | (map(move |d: &Frame<f32, U1>| clamp(0.0, max_distance, d[0]) / speed_of_sound)
+            ^ map(|d: &Frame<f32, U1>| 1.0 / max(d[0], 1.0))))
+        >> (tap(0.0, max_delay) * pass())
+}
+
 /// Tapped delay line with cubic interpolation.
 /// The number of taps is `N`.
 /// Minimum and maximum delay times are in seconds (`min_delay`, `max_delay` >= 0).
@@ -959,6 +1233,27 @@ pub fn tap_linear(min_delay: f32, max_delay: f32) -> An<TapLinear<U1>> {
     An(TapLinear::new(min_delay, max_delay))
 }
 
+/// Fractional delay line with Thiran allpass interpolation of order `order` (1...8).
+/// The delay is given in samples, from `order - 0.5` up to `max_delay` samples.
+/// Unlike `tap`, the magnitude response is flat at all frequencies for any fractional delay,
+/// which makes it suitable for physical modeling and beamforming.
+/// The tradeoff is in phase: group delay is exact at low frequencies but deviates near Nyquist,
+/// and quick modulation of the delay produces transients. Higher orders extend
+/// the accurate range of group delay.
+/// - Allocates: the delay line.
+/// - Input 0: signal
+/// - Input 1: delay in samples
+/// - Output 0: delayed signal
+///
+/// ### Example: Fractional Delay
+/// ```
+/// use fundsp::prelude::*;
+/// (pass() | dc(10.5)) >> thiran_delay(16.0, 2);
+/// ```
+pub fn thiran_delay(max_delay: f64, order: usize) -> An<ThiranDelay> {
+    An(ThiranDelay::new(max_delay, order))
+}
+
 /// Tapped delay line with linear interpolation.
 /// The number of taps is `N`.
 /// Minimum and maximum delay times are in seconds.
@@ -980,6 +1275,33 @@ where
     An(TapLinear::new(min_delay, max_delay))
 }
 
+/// Stack nodes `a` and `b` and compensate for their latencies. The latency of each output,
+/// as reported via signal routing, is rounded to the nearest sample, and outputs
+/// with less latency are delayed by an integer number of samples to match the output with the most.
+/// This keeps parallel chains phase-aligned before they are mixed.
+/// Outputs with unknown latency (`None` from `latency`) are treated as having zero latency.
+/// Latencies are queried again when the sample rate changes.
+/// - Allocates: the compensating delay lines.
+/// - Inputs: inputs of `a` followed by inputs of `b`.
+/// - Outputs: outputs of `a` followed by outputs of `b`, aligned.
+///
+/// ### Example: Parallel Limiting
+/// ```
+/// use fundsp::prelude::*;
+/// split::<U2>() >> align_latency(limiter(0.01, 0.1), pass()) >> join::<U2>();
+/// ```
+pub fn align_latency<X, Y>(a: An<X>, b: An<Y>) -> An<Align<Stack<X, Y>>>
+where
+    X: AudioNode,
+    Y: AudioNode,
+    X::Inputs: Add<Y::Inputs>,
+    X::Outputs: Add<Y::Outputs>,
+    <X::Inputs as Add<Y::Inputs>>::Output: Size<f32>,
+    <X::Outputs as Add<Y::Outputs>>::Output: Size<f32>,
+{
+    An(Align::new(Stack::new(a.0, b.0)))
+}
+
 /// 2x oversample enclosed `node`.
 /// - Inputs and outputs: from `node`.
 ///
@@ -1001,6 +1323,62 @@ where
     An(Oversampler::new(DEFAULT_SR, node.0))
 }
 
+/// Oversample enclosed `node` by factor `N`, which is `U2`, `U4` or `U8`.
+/// Uses a cascade of polyphase 2x halfband stages for interpolation and decimation.
+/// The enclosed node is processed `N` times per sample, so CPU cost grows with the factor.
+/// The minimum phase filters add a frequency dependent latency of about 2, 3 and 3.5 samples
+/// at low frequencies for factors 2, 4 and 8, respectively.
+/// - Allocates: filter state.
+/// - Inputs and outputs: from `node`.
+///
+/// ### Example: 4x Oversampled Distortion
+/// ```
+/// use fundsp::prelude::*;
+/// oversample_n::<U4, _>(shape(Tanh(10.0)));
+/// ```
+pub fn oversample_n<N, X>(node: An<X>) -> An<CascadeOversampler<N, X>>
+where
+    N: Size<f32>,
+    X: AudioNode,
+{
+    An(CascadeOversampler::new(DEFAULT_SR, node.0))
+}
+
+/// Upsample by factor `N` (`U2`, `U4` or `U8`) with polyphase halfband interpolation.
+/// Each input sample becomes `N` output channels, which are consecutive samples
+/// at `N` times the sample rate. Nodes placed between `upsample` and `downsample_aa`
+/// still run at the base sample rate (`set_sample_rate` is not adjusted),
+/// so they should be stateless and process each channel as a separate sample.
+/// To run stateful or sample rate dependent nodes at a higher rate, use `oversample_n`.
+/// - Allocates: filter state.
+/// - Input 0: signal
+/// - Outputs 0...N - 1: consecutive samples at `N` times the sample rate
+///
+/// ### Example: 4x Oversampled Waveshaping
+/// ```
+/// use fundsp::prelude::*;
+/// upsample::<U4>() >> stacki::<U4, _, _>(|_| shape(Tanh(5.0))) >> downsample_aa::<U4>();
+/// ```
+pub fn upsample<N: Size<f32>>() -> An<Upsampler<N>> {
+    An(Upsampler::new())
+}
+
+/// Anti-aliased downsample by factor `N` (`U2`, `U4` or `U8`) with polyphase halfband decimation.
+/// The `N` input channels are consecutive samples at `N` times the sample rate,
+/// as output by `upsample`.
+/// - Allocates: filter state.
+/// - Inputs 0...N - 1: consecutive samples at `N` times the sample rate
+/// - Output 0: signal
+///
+/// ### Example
+/// ```
+/// use fundsp::prelude::*;
+/// sine_hz::<f32>(440.0) >> upsample::<U2>() >> downsample_aa::<U2>();
+/// ```
+pub fn downsample_aa<N: Size<f32>>() -> An<Downsampler<N>> {
+    An(Downsampler::new())
+}
+
 /// Resample enclosed generator `node` using cubic interpolation
 /// at speed obtained from input 0, where 1 is the original speed.
 /// - Input 0: Sampling speed.
@@ -1020,6 +1398,28 @@ where
     An(Resampler::new(DEFAULT_SR, node.0))
 }
 
+/// Resample enclosed generator `node` with interpolation `quality`
+/// at speed obtained from input 0, where 1 is the original speed.
+/// `ResampleQuality::Sinc` suppresses aliasing and imaging for high quality varispeed playback.
+/// - Input 0: Sampling speed.
+/// - Output(s): Resampled outputs of contained generator.
+///
+/// ### Example: High Quality Varispeed Saw Wave
+/// ```
+/// use fundsp::prelude::*;
+/// lfo(|t: f32| xerp11(0.5, 2.0, sin_hz(0.5, t))) >> resample_quality(ResampleQuality::Sinc, saw_hz(440.0));
+/// ```
+pub fn resample_quality<X>(quality: ResampleQuality, node: An<X>) -> An<Resampler<X>>
+where
+    X: AudioNode<Inputs = U0>,
+    X::Outputs: Size<f32>,
+    X::Outputs: Size<Frame<f32, U128>>,
+{
+    let mut resampler = Resampler::new(DEFAULT_SR, node.0);
+    resampler.set_quality(quality);
+    An(resampler)
+}
+
 /// Mix output of enclosed circuit `node` back to its input.
 /// Feedback circuit `node` must have an equal number of inputs and outputs.
 /// - Input(s): signal.
@@ -1064,34 +1464,193 @@ where
     An(Feedback2::new(node.0, loopback.0, FrameId::new()))
 }
 
-/// A nested allpass. The feedforward coefficient of the outer allpass
-/// is set from `coefficient`, which should have an absolute value smaller than one to prevent a blowup.
-/// The delay element of the outer allpass is replaced with `x`.
-/// The result is an allpass filter if `x` is allpass.
-/// If `x` is `pass()` then the result is a 1st order allpass.
-/// If `x` is a delay element then the result is a Schroeder allpass.
-/// If `x` is a 1st order allpass (`allpole`) then the result is a 2nd order nested allpass.
-/// - Input 0: input signal
-/// - Output 0: filtered signal
+/// Runtime bypass of effect `node`. When `flag` is engaged (> 0.5),
+/// the dry input is passed through with a 10 ms crossfade.
+/// The effect keeps running while bypassed so it stays warm,
+/// at the full CPU cost of the effect. See also `bypass_freeze`.
+/// Effect `node` must have an equal number of inputs and outputs.
+/// - Input(s): signal.
+/// - Output(s): processed or dry signal.
 ///
-/// ### Example: Schroeder Allpass
+/// ### Example: Bypassable Lowpass
 /// ```
 /// use fundsp::prelude::*;
-/// allnest_c(0.5, delay(0.01));
+/// let flag = shared(0.0);
+/// let node = bypass(&flag, lowpass_hz::<f32>(1000.0, 1.0));
+/// flag.set(1.0);
 /// ```
-pub fn allnest_c<X>(coefficient: f32, x: An<X>) -> An<AllNest<U1, X>>
+pub fn bypass<N, X>(flag: &Shared, node: An<X>) -> An<Bypass<N, X>>
 where
-    X: AudioNode<Inputs = U1, Outputs = U1>,
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
 {
-    An(AllNest::new(coefficient, x.0))
+    An(Bypass::new(node.0, flag, false))
 }
 
-/// A nested allpass. The feedforward coefficient of the outer allpass
-/// is set from the second input, which should have an absolute value smaller than one to prevent a blowup.
-/// The delay element of the outer allpass is replaced with `x`.
-/// The result is an allpass filter if `x` is allpass.
-/// If `x` is `pass()` then the result is a 1st order allpass.
-/// If `x` is a delay element then the result is a Schroeder allpass.
+/// Runtime bypass of effect `node` that pauses the effect while bypassed.
+/// When `flag` is engaged (> 0.5), the dry input is passed through with a 10 ms crossfade.
+/// This saves the CPU cost of the effect but it resumes from its frozen state.
+/// Effect `node` must have an equal number of inputs and outputs.
+/// - Input(s): signal.
+/// - Output(s): processed or dry signal.
+pub fn bypass_freeze<N, X>(flag: &Shared, node: An<X>) -> An<Bypass<N, X>>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    An(Bypass::new(node.0, flag, true))
+}
+
+/// Retrigger `node`: a rising edge on the trigger input resets the node.
+/// A rising edge is a move from at or below 0.5 to above 0.5,
+/// so gates and trigger impulses both work.
+/// - Input(s): inputs of `node` followed by the trigger.
+/// - Output(s): outputs of `node`.
+///
+/// ### Example: Retriggered Decay Envelope
+/// ```
+/// use fundsp::prelude::*;
+/// let node = triggered(envelope(|t: f32| exp(-t * 10.0)));
+/// assert_eq!(node.inputs(), 1);
+/// ```
+pub fn triggered<X>(node: An<X>) -> An<Triggered<X>>
+where
+    X: AudioNode,
+    X::Inputs: Add<U1>,
+    <X::Inputs as Add<U1>>::Output: Size<f32>,
+{
+    An(Triggered::new(node.0))
+}
+
+/// Tempo clock at `bpm` beats per minute with `division` ticks per beat
+/// (for example, 4 for sixteenth notes). Emits a sample-accurate one-sample impulse
+/// on each tick, starting at time zero. Fractional tick periods are handled
+/// with a phase accumulator, so the average period is exact.
+/// A rising edge on the reset input (crossing 0.5 upward) restarts the clock.
+/// - Setting: tempo in BPM.
+/// - Input 0: reset
+/// - Output 0: trigger impulse
+///
+/// ### Example: Retriggered Envelope On Each Beat
+/// ```
+/// use fundsp::prelude::*;
+/// let mut tempo = clock(120.0, 1.0);
+/// tempo.set(Setting::value(140.0));
+/// let node = zero() >> tempo >> triggered(envelope(|t: f32| exp(-t * 20.0)));
+/// ```
+pub fn clock(bpm: f32, division: f32) -> An<Clock> {
+    An(Clock::new(bpm, division))
+}
+
+/// Euclidean rhythm of `pulses` onsets distributed over `steps` steps with Bjorklund's algorithm,
+/// rotated left by `rotation` steps. Advances one step on each input trigger and passes
+/// the trigger through on onsets. There can be up to `EUCLID_MAX_STEPS` steps.
+/// The rhythm can be changed at runtime with `set_rhythm`.
+/// - Input 0: clock trigger
+/// - Output 0: trigger impulse on onsets
+///
+/// ### Example: Tresillo In Eighth Notes
+/// ```
+/// use fundsp::prelude::*;
+/// zero() >> clock(100.0, 2.0) >> euclid(3, 8, 0);
+/// ```
+pub fn euclid(pulses: usize, steps: usize, rotation: usize) -> An<Euclid> {
+    An(Euclid::new(pulses, steps, rotation))
+}
+
+/// Step sequencer for control values. Outputs the value of the current step, held until
+/// the next step. Triggers on input 0 advance to the next step, wrapping around,
+/// and triggers on input 1 return to the first step. Glide time is zero by default;
+/// set it with `set_glide` or `Setting::time` to ramp linearly between step values.
+/// - Allocates: the step values.
+/// - Setting: `Setting::step(index, value)` sets step values and `Setting::time(glide)` sets glide time in seconds.
+/// - Input 0: advance trigger
+/// - Input 1: reset trigger
+/// - Output 0: current step value
+///
+/// ### Example: Bass Line In Sixteenths
+/// ```
+/// use fundsp::prelude::*;
+/// let notes = [36.0, 36.0, 48.0, 39.0, 36.0, 43.0, 46.0, 48.0];
+/// (zero() >> clock(120.0, 4.0) | zero()) >> step_sequencer(&notes) >> map(|f: &Frame<f32, U1>| midi_hz(f[0])) >> saw();
+/// ```
+pub fn step_sequencer(steps: &[f32]) -> An<StepSequencer> {
+    An(StepSequencer::new(steps))
+}
+
+/// Equal power wet/dry mix of `effect` with `mix` in 0...1 (0 is fully dry, 1 is fully wet).
+/// The dry path is delayed to match the latency of `effect`.
+/// The mix can be changed at runtime with `Setting::value`; other settings go to `effect`.
+/// Effect `effect` must have an equal number of inputs and outputs.
+/// - Setting: mix.
+/// - Input(s): signal.
+/// - Output(s): mixed signal.
+///
+/// ### Example: Half Wet Lowpass
+/// ```
+/// use fundsp::prelude::*;
+/// let node = wet_dry(0.5, lowpass_hz::<f32>(1000.0, 1.0));
+/// ```
+pub fn wet_dry<N, X>(mix: f32, effect: An<X>) -> An<WetDry<N, X>>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    An(WetDry::new(effect.0, mix, CrossfadeLaw::Power))
+}
+
+/// Parallel (New York) compression: mix heavily compressed `compressor` output
+/// with the dry signal at `blend` in 0...1. The blend is a linear crossfade
+/// with gains `1 - blend` for the dry signal and `blend` for the compressed signal,
+/// so the output level lies between the dry and compressed levels.
+/// The dry path is delayed to match the latency of `compressor`.
+/// The blend can be changed at runtime with `Setting::value`; other settings go to `compressor`.
+/// Node `compressor` must have an equal number of inputs and outputs.
+/// - Setting: blend.
+/// - Input(s): signal.
+/// - Output(s): blended signal.
+///
+/// ### Example: Drum Bus
+/// ```
+/// use fundsp::prelude::*;
+/// let node = parallel_compress(limiter_stereo(0.001, 0.1), 0.3);
+/// ```
+pub fn parallel_compress<N, X>(compressor: An<X>, blend: f32) -> An<WetDry<N, X>>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    An(WetDry::new(compressor.0, blend, CrossfadeLaw::Linear))
+}
+
+/// A nested allpass. The feedforward coefficient of the outer allpass
+/// is set from `coefficient`, which should have an absolute value smaller than one to prevent a blowup.
+/// The delay element of the outer allpass is replaced with `x`.
+/// The result is an allpass filter if `x` is allpass.
+/// If `x` is `pass()` then the result is a 1st order allpass.
+/// If `x` is a delay element then the result is a Schroeder allpass.
+/// If `x` is a 1st order allpass (`allpole`) then the result is a 2nd order nested allpass.
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+///
+/// ### Example: Schroeder Allpass
+/// ```
+/// use fundsp::prelude::*;
+/// allnest_c(0.5, delay(0.01));
+/// ```
+pub fn allnest_c<X>(coefficient: f32, x: An<X>) -> An<AllNest<U1, X>>
+where
+    X: AudioNode<Inputs = U1, Outputs = U1>,
+{
+    An(AllNest::new(coefficient, x.0))
+}
+
+/// A nested allpass. The feedforward coefficient of the outer allpass
+/// is set from the second input, which should have an absolute value smaller than one to prevent a blowup.
+/// The delay element of the outer allpass is replaced with `x`.
+/// The result is an allpass filter if `x` is allpass.
+/// If `x` is `pass()` then the result is a 1st order allpass.
+/// If `x` is a delay element then the result is a Schroeder allpass.
 /// If `x` is a 1st order allpass (`allpole`) then the result is a 2nd order nested allpass.
 /// - Input 0: input signal
 /// - Input 1: feedforward coefficient
@@ -1126,6 +1685,30 @@ where
     An(Map::new(f, Routing::Arbitrary(0.0)))
 }
 
+/// Transform channels with a function that the user promises is linear.
+/// Output `i` must equal input `i` multiplied by `gains[i]`; the gains can be a scalar, tuple,
+/// or a Frame. Unlike `map`, which is accounted as non-linear, the declared gains are used
+/// for signal flow analysis so that frequency responses propagate through.
+/// If `f` is not linear with the declared gains, then reported responses will be wrong.
+/// - Inputs: input signals
+/// - Outputs: mapped signals
+///
+/// ### Example: Declared Gain
+/// ```
+/// use fundsp::prelude::*;
+/// let mut node = lowpass_hz::<f32>(1000.0, 1.0) >> linear_map(2.0, |x: &Frame<f32, U1>| x[0] * 2.0);
+/// assert!(node.response(0, 100.0).is_some());
+/// ```
+pub fn linear_map<G, M, O>(gains: G, f: M) -> An<LinearMap<M, G::Size, O>>
+where
+    G: ConstantFrame<Sample = f32>,
+    G::Size: Size<f32>,
+    M: Fn(&Frame<f32, G::Size>) -> O + Clone + Send + Sync,
+    O: ConstantFrame<Sample = f32, Size = G::Size>,
+{
+    An(LinearMap::new(f, gains.frame()))
+}
+
 /// Keeps a signal zero centered.
 /// Filter `cutoff` (in Hz) is usually somewhere below the audible range.
 /// The default blocker cutoff is 10 Hz.
@@ -1154,6 +1737,23 @@ pub fn dcblock<F: Real>() -> An<DCBlock<F>> {
     An(DCBlock::new(F::new(10)))
 }
 
+/// Wrap generator `node` so that each of its outputs is kept zero centered
+/// with a DC blocking filter (cutoff 10 Hz). Oscillators that benefit
+/// include `pulse` and `poly_pulse` at extreme pulse widths, the chaotic
+/// generators `rossler`, `lorenz`, `logistic` and `henon`, and any source
+/// followed by an asymmetric waveshaper.
+/// - Input(s): from `node`.
+/// - Output(s): zero centered outputs of `node`.
+///
+/// ### Example: Zero Centered Lorenz Attractor
+/// ```
+/// use fundsp::prelude::*;
+/// dc_safe::<f32, _>(lorenz());
+/// ```
+pub fn dc_safe<F: Real, X: AudioNode>(node: An<X>) -> An<DCSafe<X, F>> {
+    An(DCSafe::new(node.0))
+}
+
 /// Apply 10 ms of fade-in to signal at time zero.
 /// - Input 0: input signal
 /// - Output 0: signal with fade-in
@@ -1175,6 +1775,25 @@ pub fn shape_fn<S: Fn(f32) -> f32 + Clone + Send + Sync>(f: S) -> An<Shaper<Shap
     An(Shaper::new(ShapeFn(f)))
 }
 
+/// Shape signal with a memoryless waveshaper given as a pair of closures:
+/// `f` processes a single sample and `v` a SIMD element of `F32x` samples.
+/// The closures must compute the same function; `v` is used in block processing.
+/// - Input 0: input signal
+/// - Output 0: shaped signal
+///
+/// ### Example: Soft Clipper
+/// ```
+/// use fundsp::prelude::*;
+/// shape_simd_fn(|x| x / (1.0 + abs(x)), |x| x / (F32x::ONE + x.abs()));
+/// ```
+pub fn shape_simd_fn<S, V>(f: S, v: V) -> An<Shaper<ShapeSimdFn<S, V>>>
+where
+    S: Fn(f32) -> f32 + Clone + Send + Sync,
+    V: Fn(F32x) -> F32x + Clone + Send + Sync,
+{
+    An(Shaper::new(ShapeSimdFn(f, v)))
+}
+
 /// Shape signal.
 /// - Input 0: input signal
 /// - Output 0: shaped signal
@@ -1231,6 +1850,188 @@ pub fn pan(pan: f32) -> An<Panner<U1>> {
     An(Panner::new(pan))
 }
 
+/// Binaural panner for headphone listening with fixed `azimuth` and `elevation` in radians.
+/// Azimuth is zero in front and positive to the right (`PI / 2` is hard right).
+/// Elevation is positive above the horizontal plane.
+/// Uses a parametric head model with interaural time and level differences
+/// and head shadow filtering. It is an approximation, not a measured HRTF.
+/// - Allocates: interaural delay line.
+/// - Input 0: mono signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Saw Wave 45 Degrees To The Left
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(220.0) >> binaural(-0.25 * f32::PI, 0.0);
+/// ```
+pub fn binaural(azimuth: f32, elevation: f32) -> An<Binaural<U1>> {
+    An(Binaural::new(azimuth, elevation))
+}
+
+/// Binaural panner for headphone listening with azimuth and elevation (in radians) as inputs,
+/// for moving sources. See `binaural` for a description of the model.
+/// - Allocates: interaural delay line.
+/// - Input 0: mono signal
+/// - Input 1: azimuth in radians (zero in front, positive to the right)
+/// - Input 2: elevation in radians (positive above the horizontal plane)
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Source Circling The Listener
+/// ```
+/// use fundsp::prelude::*;
+/// (saw_hz(220.0) | lfo(|t: f32| t) | dc(0.0)) >> binaural_in();
+/// ```
+pub fn binaural_in() -> An<Binaural<U3>> {
+    An(Binaural::new(0.0, 0.0))
+}
+
+/// First order ambisonic encoder. Encodes a mono source at `azimuth` and `elevation` (in radians)
+/// into AmbiX B-format: ACN channel order (W, Y, Z, X) with SN3D normalization.
+/// Azimuth is zero in front and positive to the right; elevation is positive above the horizontal plane.
+/// - Input 0: mono signal
+/// - Output 0: W (omnidirectional)
+/// - Output 1: Y (positive to the left)
+/// - Output 2: Z (positive up)
+/// - Output 3: X (positive to the front)
+///
+/// ### Example: Saw Wave 45 Degrees To The Left, Decoded To Stereo
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(220.0) >> ambisonic_encode(-0.25 * f32::PI, 0.0) >> ambisonic_decode_stereo();
+/// ```
+pub fn ambisonic_encode(azimuth: f32, elevation: f32) -> An<AmbisonicEncoder<U1>> {
+    An(AmbisonicEncoder::new(azimuth, elevation))
+}
+
+/// First order ambisonic encoder with azimuth and elevation (in radians) as inputs,
+/// for moving sources. See `ambisonic_encode` for the channel format.
+/// - Input 0: mono signal
+/// - Input 1: azimuth in radians (zero in front, positive to the right)
+/// - Input 2: elevation in radians (positive above the horizontal plane)
+/// - Output 0: W (omnidirectional)
+/// - Output 1: Y (positive to the left)
+/// - Output 2: Z (positive up)
+/// - Output 3: X (positive to the front)
+///
+/// ### Example: Source Circling The Listener
+/// ```
+/// use fundsp::prelude::*;
+/// (saw_hz(220.0) | lfo(|t: f32| t) | dc(0.0)) >> ambisonic_encode_in();
+/// ```
+pub fn ambisonic_encode_in() -> An<AmbisonicEncoder<U3>> {
+    An(AmbisonicEncoder::new(0.0, 0.0))
+}
+
+/// First order ambisonic decoder to stereo. Input is AmbiX B-format
+/// (ACN order, SN3D normalization). Left and right channels are virtual cardioid
+/// microphones pointing left and right. A source in front decodes with equal levels.
+/// - Input 0: W
+/// - Input 1: Y
+/// - Input 2: Z
+/// - Input 3: X
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(220.0) >> ambisonic_encode(0.0, 0.0) >> ambisonic_decode_stereo();
+/// ```
+pub fn ambisonic_decode_stereo() -> An<Mixer<U4, U2>> {
+    An(ambisonic_decoder(&Frame::from([
+        -0.5 * f32::PI,
+        0.5 * f32::PI,
+    ])))
+}
+
+/// First order ambisonic decoder to a square of four speakers at 45 degrees
+/// front left, front right, back left and back right. Input is AmbiX B-format
+/// (ACN order, SN3D normalization). Each speaker feed is a virtual cardioid
+/// microphone pointing at the speaker.
+/// - Input 0: W
+/// - Input 1: Y
+/// - Input 2: Z
+/// - Input 3: X
+/// - Output 0: front left
+/// - Output 1: front right
+/// - Output 2: back left
+/// - Output 3: back right
+///
+/// ### Example
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(220.0) >> ambisonic_encode(0.0, 0.0) >> ambisonic_decode_quad();
+/// ```
+pub fn ambisonic_decode_quad() -> An<Mixer<U4, U4>> {
+    An(ambisonic_decoder(&Frame::from([
+        -0.25 * f32::PI,
+        0.25 * f32::PI,
+        -0.75 * f32::PI,
+        0.75 * f32::PI,
+    ])))
+}
+
+/// Crossfade between mono signals A and B according to crossfade `law`.
+/// The equal power law (`CrossfadeLaw::Power`) applies gains `cos(x * pi / 2)` and `sin(x * pi / 2)`
+/// to A and B for mix value `x`, which keeps power constant when A and B are uncorrelated.
+/// The linear law (`CrossfadeLaw::Linear`) applies gains `1 - x` and `x`.
+/// - Input 0: signal A
+/// - Input 1: signal B
+/// - Input 2: mix value in 0...1 (0 = A, 1 = B)
+/// - Output 0: crossfaded signal
+///
+/// ### Example: Dry/Wet Mix
+/// ```
+/// use fundsp::prelude::*;
+/// ((pass() ^ (pass() >> lowpass_hz::<f32>(1000.0, 1.0))) | dc(0.3)) >> crossfade(CrossfadeLaw::Power);
+/// ```
+pub fn crossfade(law: CrossfadeLaw) -> An<Crossfade<U1>> {
+    An(Crossfade::new(law))
+}
+
+/// Crossfade between `N`-channel signals A and B according to crossfade `law`.
+/// See `crossfade` for the crossfade laws.
+/// - Inputs 0...N - 1: signal A
+/// - Inputs N...2 * N - 1: signal B
+/// - Input 2 * N: mix value in 0...1 (0 = A, 1 = B)
+/// - Outputs 0...N - 1: crossfaded signal
+///
+/// ### Example: Stereo Dry/Wet Mix
+/// ```
+/// use fundsp::prelude::*;
+/// (multipass::<U2>() ^ reverb_stereo(10.0, 2.0, 0.5) ^ (sink() | sink() | dc(0.5))) >> multicrossfade::<U2>(CrossfadeLaw::Power);
+/// ```
+pub fn multicrossfade<N>(law: CrossfadeLaw) -> An<Crossfade<N>>
+where
+    N: Size<f32> + Mul<U2>,
+    <N as Mul<U2>>::Output: Size<f32> + Add<U1>,
+    <<N as Mul<U2>>::Output as Add<U1>>::Output: Size<f32>,
+{
+    An(Crossfade::new(law))
+}
+
+/// Select one of `N` input channels. The selection index is rounded to the nearest integer
+/// and clamped to 0...N - 1. Switching channels crossfades smoothly over 10 ms to avoid clicks.
+/// For other crossfade times, use `An(Selector::new(fade_time))`.
+/// - Inputs 0...N - 1: signals
+/// - Input N: selection index
+/// - Output 0: selected signal
+///
+/// ### Example: A/B/C Switching
+/// ```
+/// use fundsp::prelude::*;
+/// (saw_hz(110.0) | square_hz(110.0) | triangle_hz(110.0) | dc(1.0)) >> selector::<U3>();
+/// ```
+pub fn selector<N>() -> An<Selector<N>>
+where
+    N: Size<f32> + Add<U1>,
+    <N as Add<U1>>::Output: Size<f32>,
+{
+    An(Selector::new(0.01))
+}
+
 /// Parameter follower filter with halfway response time in seconds.
 /// - Input 0: input signal
 /// - Output 0: smoothed signal
@@ -1260,6 +2061,95 @@ pub fn afollow<F: Real>(attack_time: F, release_time: F) -> An<AFollow<F>> {
     An(AFollow::new(attack_time, release_time))
 }
 
+/// Envelope follower. Outputs the smoothed level of the input as a signal,
+/// for example, to drive a filter cutoff in an auto-wah or a gain in a sidechain.
+/// `attack_time` and `release_time` are halfway response times in seconds for rising and falling levels.
+/// `detector` selects the level: `Detector::Peak` or `Detector::Rms`.
+/// - Input 0: signal
+/// - Output 0: level
+///
+/// ### Example: Auto-Wah
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0)
+///     >> ((pass() ^ envelope_follower(0.005, 0.1, Detector::Peak) * 4000.0 + 300.0) | dc(5.0))
+///     >> bandpass::<f32>();
+/// ```
+pub fn envelope_follower(
+    attack_time: f32,
+    release_time: f32,
+    detector: Detector,
+) -> An<impl AudioNode<Inputs = U1, Outputs = U1>> {
+    let rms = matches!(detector, Detector::Rms);
+    map(move |x: &Frame<f32, U1>| if rms { x[0] * x[0] } else { abs(x[0]) })
+        >> afollow(attack_time as f64, release_time as f64)
+        >> map(move |x: &Frame<f32, U1>| if rms { sqrt(max(x[0], 0.0)) } else { x[0] })
+}
+
+/// Auto-wah. A bandpass filter whose center frequency tracks the level of the input.
+/// Built from `envelope_follower` and `bandpass`: a peak envelope follower with
+/// a 5 ms attack and a 100 ms release drives the center frequency, which moves
+/// exponentially from `min_frequency` to `max_frequency` Hz as the level,
+/// multiplied by `sensitivity`, rises from 0 to 1.
+/// `q` is the Q of the bandpass filter (for example, 5.0).
+/// - Input 0: audio.
+/// - Output 0: filtered audio.
+///
+/// ### Example: Auto-Wah On A Saw Wave
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> autowah(2.0, 300.0, 3000.0, 5.0);
+/// ```
+pub fn autowah(
+    sensitivity: f32,
+    min_frequency: f32,
+    max_frequency: f32,
+    q: f32,
+) -> An<impl AudioNode<Inputs = U1, Outputs = U1>> {
+    ((pass()
+        ^ (envelope_follower(0.005, 0.1, Detector::Peak)
+            >> map(move |x: &Frame<f32, U1>| {
+                xerp(min_frequency, max_frequency, clamp01(x[0] * sensitivity))
+            })))
+        | dc(q))
+        >> bandpass::<f32>()
+}
+
+/// Dynamic EQ band. A bell filter at `frequency` Hz with Q `q` whose gain is driven
+/// by the level in that band, for de-essing and frequency-specific dynamics.
+/// The detector is a bandpass filter (2nd order) with the same center frequency
+/// and Q as the bell, so it measures the level in the band the bell acts upon.
+/// An RMS envelope follower with a 5 ms attack and a 100 ms release tracks the band level.
+/// Above `threshold_db` (in dB), the bell cuts by `(level - threshold) * (1 - 1 / ratio)` dB,
+/// which compresses the band with the given `ratio` (for example, 4.0).
+/// Below the threshold the bell is flat and the signal passes unchanged.
+/// - Input 0: audio.
+/// - Output 0: equalized audio.
+///
+/// ### Example: De-Esser
+/// ```
+/// use fundsp::prelude::*;
+/// pink::<f32>() >> dynamic_bell(6000.0, 2.0, -30.0, 4.0);
+/// ```
+pub fn dynamic_bell(
+    frequency: f32,
+    q: f32,
+    threshold_db: f32,
+    ratio: f32,
+) -> An<impl AudioNode<Inputs = U1, Outputs = U1>> {
+    assert!(ratio >= 1.0);
+    let slope = 1.0 - 1.0 / ratio;
+    (pass()
+        ^ (sink() | dc((frequency, q)))
+        ^ (bandpass_hz::<f32>(frequency, q)
+            >> envelope_follower(0.005, 0.1, Detector::Rms)
+            >> map(move |x: &Frame<f32, U1>| {
+                let over = max(amp_db(max(x[0], 1.0e-10)) - threshold_db, 0.0);
+                db_amp(-over * slope)
+            })))
+        >> bell::<f32>()
+}
+
 /// Look-ahead limiter with attack and release times in seconds.
 /// Look-ahead is equal to the attack time.
 /// - Allocates: look-ahead buffers.
@@ -1280,6 +2170,53 @@ pub fn limiter_stereo(attack_time: f32, release_time: f32) -> An<Limiter<U2>> {
     An(Limiter::new(DEFAULT_SR, attack_time, release_time))
 }
 
+/// Look-ahead limiter with true peak detection. Attack and release times are in seconds
+/// and the ceiling is in dB. Inter-sample peaks are estimated with 4x oversampling
+/// and the output true peak is kept under the ceiling.
+/// Latency is equal to the attack time plus 7 samples.
+/// - Allocates: look-ahead buffers.
+/// - Input 0: signal
+/// - Output 0: limited signal
+///
+/// ### Example: Limit To -1 dB True Peak
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(440.0) * 2.0 >> true_peak_limiter(0.005, 0.1, -1.0);
+/// ```
+pub fn true_peak_limiter(
+    attack_time: f32,
+    release_time: f32,
+    ceiling_db: f32,
+) -> An<TruePeakLimiter<U1>> {
+    An(TruePeakLimiter::new(
+        DEFAULT_SR,
+        attack_time,
+        release_time,
+        ceiling_db,
+    ))
+}
+
+/// Stereo look-ahead limiter with true peak detection. Attack and release times
+/// are in seconds and the ceiling is in dB. The channels are limited together.
+/// Latency is equal to the attack time plus 7 samples.
+/// - Allocates: look-ahead buffers.
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: limited left signal
+/// - Output 1: limited right signal
+pub fn true_peak_limiter_stereo(
+    attack_time: f32,
+    release_time: f32,
+    ceiling_db: f32,
+) -> An<TruePeakLimiter<U2>> {
+    An(TruePeakLimiter::new(
+        DEFAULT_SR,
+        attack_time,
+        release_time,
+        ceiling_db,
+    ))
+}
+
 /// Pinking filter.
 /// - Input 0: input signal
 /// - Output 0: filtered signal
@@ -1293,6 +2230,35 @@ pub fn pink<F: Float>() -> An<Pipe<Noise, Pinkpass<F>>> {
     white() >> pinkpass::<F>()
 }
 
+/// Spectral tilt filter with tilt in dB/octave as an input.
+/// The tilt ranges from -6 dB/octave (brown) through -3 dB/octave (pink),
+/// 0 dB/octave (white) and 3 dB/octave (blue) to 6 dB/octave (violet).
+/// The tilt is accurate from about 20 Hz to 15 kHz. Gain is unity at 1 kHz.
+/// - Input 0: input signal
+/// - Input 1: tilt in dB/octave (-6...6)
+/// - Output 0: filtered signal
+pub fn tiltpass<F: Float>() -> An<Tiltpass<F>> {
+    An(Tiltpass::new(0.0))
+}
+
+/// Noise with continuously variable spectral tilt in dB/octave.
+/// The tilt ranges from -6 dB/octave (brown) through -3 dB/octave (pink),
+/// 0 dB/octave (white) and 3 dB/octave (blue) to 6 dB/octave (violet).
+/// The tilt is accurate from about 20 Hz to 15 kHz.
+/// Gain is normalized at 1 kHz, so spectral density there matches white noise
+/// and the peak level grows with the magnitude of the tilt.
+/// - Input 0: tilt in dB/octave (-6...6)
+/// - Output 0: noise
+///
+/// ### Example: Sweep From Pink To Blue Noise
+/// ```
+/// use fundsp::prelude::*;
+/// lfo(|t: f32| -3.0 + 6.0 * t / 10.0) >> tilted_noise::<f64>();
+/// ```
+pub fn tilted_noise<F: Float>() -> An<Pipe<Stack<Noise, Pass>, Tiltpass<F>>> {
+    (white() | pass()) >> tiltpass::<F>()
+}
+
 /// Brown noise.
 /// - Output 0: brown noise
 pub fn brown<F: Real>() -> An<Pipe<Noise, Binop<FrameMul<U1>, Lowpole<F, U1>, Constant<U1>>>> {
@@ -1343,6 +2309,36 @@ where
     An(Feedback2::new(x.0, y.0, FrameHadamard::new()))
 }
 
+/// Feedback delay network with an integrated feedback delay of `delay` seconds
+/// (rounded to the nearest sample) in addition to the one sample delay of `fdn`.
+/// Mix delayed output of enclosed circuit `x` back to its input.
+/// The delayed output is diffused with a Hadamard matrix for feedback.
+/// Feedback circuit `x` must have an equal number of inputs and outputs.
+/// The number of inputs and outputs must be a power of two.
+///
+/// Unlike `fdn`, blocks up to `delay` in length are processed in one go
+/// with the Hadamard transform applied to the whole block, which is much faster.
+/// With 32 channels and a delay of at least 64 samples (the block size), it is about 1.5 times as fast as `fdn`
+/// (see the `fdn` and `fdn_delay` benchmarks).
+/// The delay lines in `x` can be shortened by `delay` to keep loop lengths the same:
+/// the output is then that of `fdn` delayed by `delay`. With zero `delay`, this is the same as `fdn`.
+/// - Allocates: feedback delay lines.
+/// - Inputs: input signal.
+/// - Outputs: `x` output signal.
+///
+/// *** Example: Mono Reverb
+/// ```
+/// use fundsp::prelude::*;
+/// split() >> fdn_delay::<U16, _>(0.01, stacki::<U16, _, _>(|i| { delay(lerp(0.0, 0.02, rnd1(i))) >> fir((0.2, 0.4, 0.2)) })) >> join();
+/// ```
+pub fn fdn_delay<N, X>(delay: f64, x: An<X>) -> An<DelayedFeedback<N, X, FrameHadamard<N>>>
+where
+    N: Size<f32>,
+    X: AudioNode<Inputs = N, Outputs = N>,
+{
+    An(DelayedFeedback::new(delay, x.0, FrameHadamard::new()))
+}
+
 /// Bus `x` and `y` together: same as `x & y`.
 ///
 /// - Input(s): from `x` and `y`.
@@ -1727,18 +2723,29 @@ pub fn reverb_stereo(
     let a = pow(db_amp(-60.0), 0.03 * room_size / 10.0 / time) as f32;
     let weights = fir3(1.0 - damping as f32).weights() * Frame::splat(a);
 
+    // Part of the shortest delay is shared by all lines. It is moved into the feedback delay,
+    // which lets the network process whole blocks at once. The shared delay is a multiple
+    // of 20 ms, which is a whole number of samples at sample rates that are multiples of 50 Hz,
+    // such as 32, 44.1, 48 and 96 kHz. There the loop lengths round the same as in a plain `fdn`.
+    // At other sample rates, such as 11025 Hz, a loop may differ from `fdn` by one sample.
+    let shortest = DELAYS.iter().fold(f64::INFINITY, |a, &b| a.min(b)) * room_size / 10.0;
+    let shared_delay = floor(shortest * 50.0) / 50.0;
+
     // Delay lines.
-    let line =
-        stacki::<U32, _, _>(|i| delay(DELAYS[i as usize] * room_size / 10.0) >> fir(weights));
+    let line = stacki::<U32, _, _>(|i| {
+        delay(DELAYS[i as usize] * room_size / 10.0 - shared_delay) >> fir(weights)
+    });
 
     // The feedback structure.
-    let reverb = fdn::<U32, _>(line);
+    let reverb = fdn_delay::<U32, _>(shared_delay, line);
 
     // Pan the channels with an S shape.
+    // The shared delay is restored at the output to keep the timing of the reverb.
     multisplit::<U2, U16>()
         >> reverb
         >> sumf::<U32, _, _, f32>(|x| pan(lerp(-1.0, 1.0, smooth9(x))))
             * dc((1.0 / 16.0, 1.0 / 16.0))
+        >> (delay(shared_delay) | delay(shared_delay))
 }
 
 /// Create a stereo reverb unit (32-channel hybrid FDN).
@@ -1843,6 +2850,45 @@ pub fn reverb3_stereo(
     An(super::reverb::Reverb::new(time, diffusion, filter.0))
 }
 
+/// Stereo plate reverb with the classic figure-8 tank topology of Jon Dattorro.
+/// `time` is approximate reverberation time to -60 dB in seconds.
+/// `damping` is high frequency damping in 0...1.
+/// `predelay` is in seconds.
+/// - Allocates: delay lines
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: reverberated left signal
+/// - Output 1: reverberated right signal
+/// - Setting: `Time(seconds)`, `Coefficient(damping)` and `Delay(predelay)`.
+///
+/// ### Example: Add 20% Plate Reverb
+/// ```
+/// use fundsp::prelude::*;
+/// multipass() & 0.2 * plate_reverb(2.0, 0.3, 0.02);
+/// ```
+pub fn plate_reverb(time: f64, damping: f64, predelay: f64) -> An<Plate> {
+    An(Plate::new(time as f32, damping as f32, predelay as f32))
+}
+
+/// Early reflection generator. The `pattern` consists of `(delay, gain, pan)` triplets,
+/// with delay in seconds and pan in -1...1. Each reflection is a delayed copy of the input
+/// scaled by gain and panned with an equal power pan law. There is no feedback,
+/// so the response is finite and can be mixed independently of a reverb tail.
+/// Preset patterns are `EARLY_SMALL_ROOM` and `EARLY_HALL`.
+/// - Allocates: delay line
+/// - Input 0: mono signal
+/// - Output 0: left reflections
+/// - Output 1: right reflections
+///
+/// ### Example: Small Room With Plate Tail
+/// ```
+/// use fundsp::prelude::*;
+/// split::<U2>() & 0.3 * early_reflections(&EARLY_SMALL_ROOM) & 0.2 * (split::<U2>() >> plate_reverb(1.0, 0.5, 0.03));
+/// ```
+pub fn early_reflections(pattern: &[(f32, f32, f32)]) -> An<EarlyReflections> {
+    An(EarlyReflections::new(pattern))
+}
+
 /// Stereo reverb with a slow fade-in envelope.
 /// `room_size` is in meters (at least 15 meters).
 /// `time` is approximate reverberation time to -60 dB in seconds.
@@ -1958,10 +3004,131 @@ pub fn dsf_square_r(roughness: f32) -> An<Dsf<U1>> {
     An(Dsf::new(2.0, roughness))
 }
 
-/// Karplus-Strong plucked string oscillator with `frequency` in Hz.
-/// High frequency damping is in 0...1.
-/// - Allocates: pluck buffer.
-/// - Input 0: string excitation
+/// Additive oscillator from `partials`. Each partial is a triple `(ratio, amplitude, phase)`,
+/// where `ratio` is the harmonic number relative to the fundamental and `phase` in 0...1
+/// is the phase offset of the partial. Partials at or above the Nyquist frequency are skipped.
+/// CPU cost is proportional to the number of partials.
+/// - Allocates: partial list.
+/// - Input 0: fundamental frequency in Hz
+/// - Output 0: additive wave
+///
+/// ### Example: Organ-Like Tone
+/// ```
+/// use fundsp::prelude::*;
+/// dc(220.0) >> additive(&[(1.0, 0.5, 0.0), (2.0, 0.25, 0.0), (4.0, 0.125, 0.0)]);
+/// ```
+pub fn additive(partials: &[(f32, f32, f32)]) -> An<Additive> {
+    An(Additive::new(partials))
+}
+
+/// Two operator FM oscillator: a sine carrier phase modulated by a sine modulator.
+/// The modulator runs at `ratio` times the carrier frequency. The modulation `index`
+/// is the peak phase deviation of the carrier in radians, which equals
+/// the peak frequency deviation divided by the modulator frequency.
+/// Larger indices spread energy into more sidebands, which are spaced
+/// at multiples of the modulator frequency around the carrier.
+/// The index is reduced as needed to keep significant sidebands below Nyquist.
+/// - Allocates: operator state.
+/// - Input 0: carrier frequency in Hz
+/// - Output 0: FM wave
+///
+/// ### Example: Bell-Like Tone
+/// ```
+/// use fundsp::prelude::*;
+/// dc(220.0) >> fm2(3.5, 2.0);
+/// ```
+pub fn fm2(ratio: f32, index: f32) -> An<FmStack<U1>> {
+    An(FmStack::new(
+        FmAlgorithm::stack(2),
+        &[FmOperator::new(1.0, 1.0), FmOperator::new(ratio, index)],
+    ))
+}
+
+/// Two operator FM oscillator with modulation index as an input.
+/// See `fm2` for the parameterization.
+/// - Allocates: operator state.
+/// - Input 0: carrier frequency in Hz
+/// - Input 1: modulation index in radians
+/// - Output 0: FM wave
+///
+/// ### Example: Decaying Index
+/// ```
+/// use fundsp::prelude::*;
+/// (dc(110.0) | lfo(|t: f32| 5.0 * exp(-t))) >> fm2_in(1.0);
+/// ```
+pub fn fm2_in(ratio: f32) -> An<FmStack<U2>> {
+    An(FmStack::new(
+        FmAlgorithm::stack(2),
+        &[FmOperator::new(1.0, 1.0), FmOperator::new(ratio, 1.0)],
+    ))
+}
+
+/// FM synthesizer with `operators` connected according to `algorithm`.
+/// Each operator has a frequency ratio, a level and a feedback index.
+/// Modulator levels are modulation indices in radians; carrier levels are output amplitudes.
+/// CPU cost is proportional to the number of operators.
+/// - Allocates: operator state.
+/// - Input 0: fundamental frequency in Hz
+/// - Output 0: FM wave
+///
+/// ### Example: Two Parallel Two Operator Stacks
+/// ```
+/// use fundsp::prelude::*;
+/// let algorithm = FmAlgorithm::new(4, &[(1, 0), (3, 2)], &[0, 2]);
+/// let operators = [
+///     FmOperator::new(1.0, 0.5),
+///     FmOperator::new(2.0, 1.5),
+///     FmOperator::new(3.0, 0.5),
+///     FmOperator::new(7.0, 0.5).with_feedback(0.5),
+/// ];
+/// dc(220.0) >> fm_stack(algorithm, &operators);
+/// ```
+pub fn fm_stack(algorithm: FmAlgorithm, operators: &[FmOperator]) -> An<FmStack<U1>> {
+    An(FmStack::new(algorithm, operators))
+}
+
+/// FM synthesizer with `operators` connected according to `algorithm`,
+/// with modulation depth as an input. The depth scales the levels of all modulators.
+/// See `fm_stack` for the parameterization.
+/// - Allocates: operator state.
+/// - Input 0: fundamental frequency in Hz
+/// - Input 1: modulation depth
+/// - Output 0: FM wave
+///
+/// ### Example
+/// ```
+/// use fundsp::prelude::*;
+/// let operators = [FmOperator::new(1.0, 1.0), FmOperator::new(1.0, 1.0), FmOperator::new(2.0, 1.0)];
+/// (dc(110.0) | dc(0.5)) >> fm_stack_in(FmAlgorithm::stack(3), &operators);
+/// ```
+pub fn fm_stack_in(algorithm: FmAlgorithm, operators: &[FmOperator]) -> An<FmStack<U2>> {
+    An(FmStack::new(algorithm, operators))
+}
+
+/// Phase distortion oscillator in the style of the Casio CZ series.
+/// Reads a cosine wave with its phase warped according to `shape`:
+/// `PdShape::Saw`, `PdShape::Square`, `PdShape::Pulse`,
+/// `PdShape::ResonantSaw` or `PdShape::ResonantTriangle`.
+/// At distortion amount 0 the saw, square and pulse shapes produce a cosine wave.
+/// Higher amounts add harmonics; the resonant shapes sweep a resonant peak upward.
+/// The output is not bandlimited.
+/// - Input 0: frequency in Hz
+/// - Input 1: distortion amount in 0...1
+/// - Output 0: phase distorted wave
+///
+/// ### Example: Sawtooth Sweep
+/// ```
+/// use fundsp::prelude::*;
+/// (dc(110.0) | lfo(|t: f32| 0.5 + 0.5 * sin_hz(0.2, t))) >> phase_distortion(PdShape::Saw);
+/// ```
+pub fn phase_distortion(shape: PdShape) -> An<PhaseDistortion> {
+    An(PhaseDistortion::new(shape))
+}
+
+/// Karplus-Strong plucked string oscillator with `frequency` in Hz.
+/// High frequency damping is in 0...1.
+/// - Allocates: pluck buffer.
+/// - Input 0: string excitation
 /// - Output 0: oscillator output
 ///
 /// ### Example
@@ -2633,7 +3800,12 @@ pub fn wavech_at(
     ))
 }
 
-/// Mono chorus, 5 voices. For stereo, stack two of these using different seed values.
+/// LFO seed of the left channel in `chorus_stereo`.
+const CHORUS_LEFT_SEED: u64 = 0x1234;
+/// LFO seed of the right channel in `chorus_stereo`.
+const CHORUS_RIGHT_SEED: u64 = 0xfeed_5678;
+
+/// Mono chorus, 5 voices. For stereo, see `chorus_stereo`.
 /// `seed`: LFO seed.
 /// `separation`: base voice separation in seconds (for example, 0.0).
 /// `variation`: delay variation in seconds (for example, 0.02).
@@ -2683,6 +3855,117 @@ pub fn chorus(
         * dc(0.2)
 }
 
+/// Stereo chorus, 5 voices per channel. Each channel has its own chorus
+/// with independent delay modulation, so the wet voices of the two channels are
+/// decorrelated even for a mono source: the chorus spreads across the stereo field
+/// while the dry signal stays in place. Larger `variation` makes the image wider.
+/// `separation`: base voice separation in seconds (for example, 0.0).
+/// `variation`: delay variation in seconds (for example, 0.02).
+/// `mod_frequency`: delay modulation frequency (for example, 0.3).
+/// - Input 0: left audio.
+/// - Input 1: right audio.
+/// - Output 0: chorused left audio, including original signal.
+/// - Output 1: chorused right audio, including original signal.
+///
+/// ### Example: Wide Saw Pad
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> split::<U2>() >> chorus_stereo(0.015, 0.005, 0.5);
+/// ```
+pub fn chorus_stereo(
+    separation: f32,
+    variation: f32,
+    mod_frequency: f32,
+) -> An<impl AudioNode<Inputs = U2, Outputs = U2>> {
+    chorus(CHORUS_LEFT_SEED, separation, variation, mod_frequency)
+        | chorus(CHORUS_RIGHT_SEED, separation, variation, mod_frequency)
+}
+
+/// Rotary speaker (Leslie) simulation at `speed` in 0...1 (0 = chorale, 1 = tremolo).
+/// Horn and drum rotors add Doppler pitch modulation and amplitude modulation
+/// and accelerate gradually when the speed changes.
+/// - Setting: speed.
+/// - Input 0: audio
+/// - Output 0: left audio
+/// - Output 1: right audio
+///
+/// ### Example: Organ Through Leslie
+/// ```
+/// use fundsp::prelude::*;
+/// let mut node = (square_hz(220.0) & square_hz(440.0) * 0.5) >> leslie(0.0);
+/// // Switch to tremolo.
+/// node.set(Setting::value(1.0));
+/// ```
+pub fn leslie(speed: f32) -> An<Leslie<U1>> {
+    An(Leslie::new(speed))
+}
+
+/// Rotary speaker (Leslie) simulation with speed input in 0...1 (0 = chorale, 1 = tremolo).
+/// - Input 0: audio
+/// - Input 1: speed
+/// - Output 0: left audio
+/// - Output 1: right audio
+pub fn leslie_in() -> An<Leslie<U2>> {
+    An(Leslie::new(0.0))
+}
+
+/// Artificial double tracking (ADT). Mixes the input with a single doubled copy
+/// delayed by about 25 milliseconds, with slowly wandering delay and subtle pitch wobble,
+/// to simulate a second performance of the same part.
+/// Unlike `chorus`, which spreads several regularly modulated voices at evenly spaced delays,
+/// ADT has one voice and randomized modulation, thickening a signal without an obvious
+/// chorus sound. For stereo, stack two of these using different seed values.
+/// `seed`: modulation seed.
+/// `delay_jitter`: delay variation in seconds (for example, 0.005).
+/// `pitch_jitter`: approximate pitch variation in cents (for example, 5.0).
+/// - Input 0: audio.
+/// - Output 0: doubled audio, including original signal.
+///
+/// ### Example: Doubled Saw Wave
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(220.0) >> adt(0, 0.005, 5.0);
+/// ```
+pub fn adt(
+    seed: u64,
+    delay_jitter: f32,
+    pitch_jitter: f32,
+) -> An<impl AudioNode<Inputs = U1, Outputs = U1>> {
+    let base = 0.025;
+    // Pitch deviates in proportion to the rate of change of delay.
+    let pitch_rate = 3.0;
+    let pitch_depth = pitch_jitter * (core::f32::consts::LN_2 / 1200.0) / (f32::TAU * pitch_rate);
+    let min_delay = max(0.0, base - delay_jitter - pitch_depth);
+    let max_delay = base + delay_jitter + pitch_depth;
+    (pass()
+        & (pass()
+            | lfo(move |t: f32| {
+                base + delay_jitter * spline_noise(seed, t * 0.25)
+                    + pitch_depth * spline_noise(hash1(seed), t * pitch_rate)
+            })
+            .interval(0.01))
+            >> tap(min_delay, max_delay))
+        * dc(0.5)
+}
+
+/// Tape emulation with wow, flutter, saturation and a gentle high frequency rolloff.
+/// Wow is a 0.5 Hz and flutter a 6.5 Hz plus 11.3 Hz delay modulation.
+/// `wow_depth`: wow depth in 0...1 (for example, 0.3). At full depth, pitch deviates by about 0.6%.
+/// `flutter_depth`: flutter depth in 0...1 (for example, 0.2). At full depth, pitch deviates by about 0.5%.
+/// `saturation`: saturation amount in 0...1 (for example, 0.3), where 1 is heavy distortion.
+/// - Allocates: the delay line.
+/// - Input 0: audio
+/// - Output 0: processed audio
+///
+/// ### Example: Lo-Fi Saw Wave
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(220.0) >> tape(0.3, 0.2, 0.3);
+/// ```
+pub fn tape(wow_depth: f32, flutter_depth: f32, saturation: f32) -> An<Tape> {
+    An(Tape::new(wow_depth, flutter_depth, saturation))
+}
+
 /// Mono flanger.
 /// `feedback_amount`: amount of feedback (for example, 0.9 or -0.9). Negative feedback inverts feedback phase.
 /// `minimum_delay`: minimum delay in seconds (for example, 0.005).
@@ -2744,6 +4027,34 @@ pub fn shared(value: f32) -> Shared {
     Shared::new(value)
 }
 
+/// Shared double precision float variable. Can be read from and written to from multiple threads.
+/// Only available on targets with native 64-bit atomics.
+///
+/// ### Example: Precise Frequency Control
+/// ```
+/// use fundsp::prelude::*;
+/// let frequency = shared_f64(440.0);
+/// var_f64(&frequency) >> sine::<f32>();
+/// ```
+#[cfg(target_has_atomic = "64")]
+pub fn shared_f64(value: f64) -> SharedF64 {
+    SharedF64::new(value)
+}
+
+/// Shared integer counter starting from zero. Can be read from and written to from multiple threads.
+/// The count saturates at `u32::MAX`.
+///
+/// ### Example: Count Clipped Samples
+/// ```
+/// use fundsp::prelude::*;
+/// let clipped = shared_counter();
+/// sine_hz::<f32>(110.0) * 2.0 >> clip_monitor(&clipped);
+/// assert_eq!(clipped.value(), 0);
+/// ```
+pub fn shared_counter() -> SharedCounter {
+    SharedCounter::new(0)
+}
+
 /// Outputs the value of the shared variable.
 ///
 /// - Output 0: value
@@ -2778,6 +4089,137 @@ where
     An(VarFn::new(shared, f))
 }
 
+/// Outputs the value of the shared variable smoothed with halfway response time `response_time` seconds.
+/// Equivalent to `var(&shared) >> follow(response_time)`.
+/// The first output sample is the current value of the variable.
+/// The response time is sample rate independent.
+/// - Setting: response time.
+/// - Output 0: smoothed value
+///
+/// ### Example: Smoothed Amplitude Control
+/// ```
+/// use fundsp::prelude::*;
+/// let amp = shared(1.0);
+/// noise() * var_smooth(&amp, 0.01);
+/// ```
+pub fn var_smooth(shared: &Shared, response_time: f32) -> An<VarSmooth> {
+    An(VarSmooth::new(shared, response_time, response_time))
+}
+
+/// Outputs the value of the shared variable smoothed with halfway response times in seconds.
+/// The attack time is used for rising segments while the release time is used for falling segments.
+/// Equivalent to `var(&shared) >> afollow(attack_time, release_time)`.
+/// The first output sample is the current value of the variable.
+/// The response times are sample rate independent.
+/// - Setting: attack and release times.
+/// - Output 0: smoothed value
+///
+/// ### Example: Smoothed Amplitude Control With Slower Release
+/// ```
+/// use fundsp::prelude::*;
+/// let amp = shared(1.0);
+/// noise() * var_asmooth(&amp, 0.01, 0.1);
+/// ```
+pub fn var_asmooth(shared: &Shared, attack_time: f32, release_time: f32) -> An<VarSmooth> {
+    An(VarSmooth::new(shared, attack_time, release_time))
+}
+
+/// Outputs the value of the shared double precision variable, rounded to single precision.
+/// Only available on targets with native 64-bit atomics.
+///
+/// - Output 0: value
+#[cfg(target_has_atomic = "64")]
+pub fn var_f64(shared: &SharedF64) -> An<VarF64> {
+    An(VarF64::new(shared))
+}
+
+/// Shared double precision variable mapped through a function.
+/// The function receives the full precision value.
+/// Outputs the value of the function, which may be scalar or tuple.
+/// Only available on targets with native 64-bit atomics.
+///
+/// - Outputs: value
+///
+/// ### Example: Control Pitch In Fractional MIDI Semitones
+/// ```
+/// use fundsp::prelude::*;
+/// let pitch = shared_f64(69.0);
+/// var_fn_f64(&pitch, |x| midi_hz(x) as f32) >> saw();
+/// ```
+#[cfg(target_has_atomic = "64")]
+pub fn var_fn_f64<F, R>(shared: &SharedF64, f: F) -> An<VarFnF64<F, R>>
+where
+    F: Clone + Fn(f64) -> R + Send + Sync,
+    R: ConstantFrame<Sample = f32>,
+    R::Size: Size<f32>,
+{
+    An(VarFnF64::new(shared, f))
+}
+
+/// Array of shared variables with initial values `values` (scalar, tuple or frame).
+/// Elements can be read from and written to from multiple threads.
+/// Each element is updated atomically but the array as a whole is not:
+/// use [`shared_snapshot`] if the elements must be read as a consistent group.
+///
+/// ### Example: Envelope Times
+/// ```
+/// use fundsp::prelude::*;
+/// let times = shared_array((0.01, 0.1, 0.5, 0.2));
+/// times.set(3, 0.4);
+/// assert_eq!(times.value(3), 0.4);
+/// ```
+pub fn shared_array<X: ConstantFrame<Sample = f32>>(values: X) -> SharedArray<X::Size>
+where
+    X::Size: Size<f32>,
+{
+    SharedArray::new(values.frame())
+}
+
+/// Outputs the values of a shared array, one element per channel.
+/// - Outputs: values
+///
+/// ### Example: Stereo Gain Control
+/// ```
+/// use fundsp::prelude::*;
+/// let gains = shared_array((1.0, 0.5));
+/// (noise() | noise()) * var_array(&gains);
+/// ```
+pub fn var_array<N: Size<f32>>(shared: &SharedArray<N>) -> An<VarArray<N>> {
+    An(VarArray::new(shared))
+}
+
+/// Array of shared variables with initial values `values` (scalar, tuple or frame)
+/// that are written and read as a consistent group.
+/// Updates are made with `set_values`.
+///
+/// ### Example: Filter Parameters
+/// ```
+/// use fundsp::prelude::*;
+/// let parameters = shared_snapshot((1000.0, 1.0));
+/// parameters.set_values(&Frame::from([2000.0, 2.0]));
+/// assert_eq!(parameters.values(), Frame::from([2000.0, 2.0]));
+/// ```
+pub fn shared_snapshot<X: ConstantFrame<Sample = f32>>(values: X) -> SharedSnapshot<X::Size>
+where
+    X::Size: Size<f32>,
+{
+    SharedSnapshot::new(values.frame())
+}
+
+/// Outputs a consistent snapshot of a shared snapshot array, one element per channel.
+/// If an update is in progress, the previous snapshot is output.
+/// - Outputs: values
+///
+/// ### Example: Consistent Filter Parameters
+/// ```
+/// use fundsp::prelude::*;
+/// let parameters = shared_snapshot((1000.0, 1.0));
+/// (pass() | var_snapshot(&parameters)) >> lowpass::<f32>();
+/// ```
+pub fn var_snapshot<N: Size<f32>>(shared: &SharedSnapshot<N>) -> An<VarSnapshot<N>> {
+    An(VarSnapshot::new(shared))
+}
+
 /// Timer node. A node with no inputs or outputs that maintains
 /// current stream time in a shared variable.
 /// It can be added to any node by stacking.
@@ -2802,6 +4244,57 @@ pub fn snoop(capacity: usize) -> (Snoop, An<SnoopBackend>) {
     (snoop, An(backend))
 }
 
+/// Snoop node with an oscilloscope trigger for sharing audio data with a frontend thread.
+/// Waveforms read with `triggered` start at a rising crossing of `trigger_level`,
+/// producing a still image of periodic signals. If no crossing is found,
+/// the latest samples are read instead (free-running mode).
+/// The latest samples buffer has room for at least `capacity` samples.
+/// Returns (frontend, backend).
+/// - Input 0: signal to snoop.
+/// - Output 0: signal passed through.
+///
+/// ### Example: Oscilloscope
+/// ```
+/// use fundsp::prelude::*;
+/// let (mut frontend, backend) = snoop_triggered(2048, 0.0);
+/// let mut node = saw_hz(110.0) >> backend;
+/// for _ in 0..2048 {
+///     node.get_mono();
+/// }
+/// frontend.update();
+/// let mut waveform = [0.0; 512];
+/// assert!(frontend.triggered(&mut waveform));
+/// ```
+pub fn snoop_triggered(capacity: usize, trigger_level: f32) -> (Snoop, An<SnoopBackend>) {
+    let (snoop, backend) = Snoop::new_triggered(capacity, trigger_level);
+    (snoop, An(backend))
+}
+
+/// Snoop node for sharing audio data and its magnitude spectrum with a frontend thread.
+/// The spectrum is updated once per window of `fft_size` samples,
+/// where `fft_size` is a power of two between 2 and 32768.
+/// The FFT is computed on the audio thread, which adds its cost to the backend.
+/// The latest samples buffer has room for at least `fft_size` samples.
+/// Returns (frontend, backend).
+/// - Input 0: signal to snoop.
+/// - Output 0: signal passed through.
+///
+/// ### Example: Spectrum Analyzer
+/// ```
+/// use fundsp::prelude::*;
+/// let (frontend, backend) = spectrum_snoop(1024);
+/// let mut node = sine_hz::<f32>(1000.0) >> backend;
+/// for _ in 0..1024 {
+///     node.get_mono();
+/// }
+/// let bin = round(1000.0 / frontend.bin_frequency(1)) as usize;
+/// assert!(frontend.magnitude(bin) > 0.5);
+/// ```
+pub fn spectrum_snoop(fft_size: usize) -> (SpectrumSnoop, An<SpectrumSnoopBackend>) {
+    let (snoop, backend) = SpectrumSnoop::new(fft_size);
+    (snoop, An(backend))
+}
+
 /// Frequency domain resynthesizer.
 /// The number of inputs is `I` and the number of outputs is `O`.
 /// The window length (in samples) must be a power of two and at least four.
@@ -2835,6 +4328,78 @@ where
     An(Resynth::new(window_length, processing))
 }
 
+/// Frequency domain resynthesizer with `overlaps` overlapping windows.
+/// See `resynth`, which uses four overlapping windows.
+/// Fewer overlaps smear transients less and cost less; more overlaps
+/// reduce artifacts from spectral modifications at a higher cost.
+/// The number of overlaps must be a power of two between 2 and `window_length`:
+/// with two overlaps the analysis and synthesis windows are sine windows
+/// and with four or more they are Hann windows, normalized so that
+/// the overlap-added windows sum to one (constant overlap-add).
+/// The processing function is called once every `window_length / overlaps` samples.
+/// The latency in samples is equal to window length.
+/// - Allocates: all needed buffers when created.
+/// - Input(s): `I` input signals.
+/// - Output(s): `O` processed signals.
+///
+/// ### Example: FFT Brickwall Lowpass Filter With 8x Overlap
+/// ```
+/// use fundsp::prelude::*;
+/// let cutoff = 1000.0;
+/// let synth = resynth_overlap::<U1, U1, _>(1024, 8, |fft|
+///     for i in 0..fft.bins() {
+///         if fft.frequency(i) <= cutoff {
+///             fft.set(0, i, fft.at(0, i));
+///         }
+///     });
+/// ```
+pub fn resynth_overlap<I, O, F>(
+    window_length: usize,
+    overlaps: usize,
+    processing: F,
+) -> An<Resynth<I, O, F>>
+where
+    I: Size<f32>,
+    O: Size<f32>,
+    F: FnMut(&mut FftWindow) + Clone + Send + Sync,
+{
+    An(Resynth::with_overlaps(window_length, overlaps, processing))
+}
+
+/// Frequency domain resynthesizer that runs in preallocated `scratch` storage
+/// without any heap allocations.
+/// The window length is `L` (in samples), which must be a power of two between 4 and 32768.
+/// The number of inputs is `I` and the number of outputs is `O`.
+/// See `resynth` for a description of the processing and `ResynthScratch` for the required storage size.
+/// - Input(s): `I` input signals.
+/// - Output(s): `O` processed signals.
+///
+/// ### Example: FFT Brickwall Lowpass Filter Without Heap
+/// ```
+/// use fundsp::prelude::*;
+/// let cutoff = 1000.0;
+/// let scratch = ResynthScratch::<typenum::U1024, U1, U1>::new();
+/// let synth = resynth_with(scratch, move |fft|
+///     for i in 0..fft.bins() {
+///         if fft.frequency(i) <= cutoff {
+///             fft.set(0, i, fft.at(0, i));
+///         }
+///     });
+/// ```
+pub fn resynth_with<L, I, O, F>(
+    scratch: ResynthScratch<L, I, O>,
+    processing: F,
+) -> An<Resynth<I, O, F, FftArrays<L, I, O>>>
+where
+    L: Size<f32> + core::ops::Add<U2>,
+    typenum::Sum<L, U2>: Size<f32>,
+    I: Size<f32>,
+    O: Size<f32>,
+    F: FnMut(&mut FftWindow<FftArrays<L, I, O>>) + Clone + Send + Sync,
+{
+    An(Resynth::with_scratch(scratch, processing))
+}
+
 /// `N`-channel impulse. The first sample on each channel is one and the rest are zero.
 /// - Output(s): impulse.
 pub fn impulse<N: Size<f32>>() -> An<Impulse<N>> {
@@ -2863,6 +4428,74 @@ pub fn rotate(angle: f32, gain: f32) -> An<Mixer<U2, U2>> {
     ))
 }
 
+/// Sum stereo to mono with gain law `law`. `StereoLaw::Minus6dB` never exceeds
+/// the peak input level, `StereoLaw::Minus3dB` preserves the power of uncorrelated channels
+/// and `StereoLaw::Unity` is a plain sum.
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: mono signal
+///
+/// ### Example (Mono Fold-Down)
+/// ```
+/// use fundsp::prelude::*;
+/// let mut node = to_mono(StereoLaw::Minus6dB);
+/// assert_eq!(node.tick(&[1.0, 1.0].into())[0], 1.0);
+/// ```
+pub fn to_mono(law: StereoLaw) -> An<Mixer<U2, U1>> {
+    let gain = law.gain();
+    An(Mixer::new([[gain, gain].into()].into()))
+}
+
+/// Spread mono to stereo with gain law `law`. `StereoLaw::Unity` is dual mono,
+/// `StereoLaw::Minus3dB` is an equal power center pan (like `pan(0.0)`)
+/// and `StereoLaw::Minus6dB` is a linear center pan.
+/// - Input 0: mono signal
+/// - Output 0: left signal
+/// - Output 1: right signal
+pub fn to_stereo(law: StereoLaw) -> An<Mixer<U1, U2>> {
+    let gain = law.gain();
+    An(Mixer::new([[gain].into(), [gain].into()].into()))
+}
+
+/// Mono-to-stereo widener with widening `amount` in 0...1.
+/// The input is decorrelated with an allpass network into a side signal
+/// that is added to the left channel and subtracted from the right channel,
+/// `L = (x + amount * A(x)) / 2` and `R = (x - amount * A(x)) / 2`.
+/// The result is mono compatible: `L + R` equals the input exactly.
+/// - Setting: amount.
+/// - Input 0: mono audio
+/// - Output 0: left output
+/// - Output 1: right output
+///
+/// ### Example: Widened Saw Pad
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> lowpass_hz(2000.0, 0.7) >> stereoize(0.8);
+/// ```
+pub fn stereoize(amount: f32) -> An<Stereoizer> {
+    An(Stereoizer::new(amount))
+}
+
+/// Mixing matrix with `M` inputs and `N` outputs. The `matrix` is indexed by output channel first,
+/// then input channel. Cells can be updated at runtime with `Setting::matrix(output, input, gain)`.
+/// - Inputs: `M` input channels
+/// - Outputs: `N` output channels
+///
+/// ### Example (Mix Three Channels To Stereo)
+/// ```
+/// use fundsp::prelude::*;
+/// let mut node = mixer_matrix::<U3, U2>([[1.0, 0.5, 0.0].into(), [0.0, 0.5, 1.0].into()].into());
+/// node.set(Setting::matrix(0, 1, 0.7));
+/// assert_eq!(node.gain(0, 1), 0.7);
+/// ```
+pub fn mixer_matrix<M, N>(matrix: Frame<Frame<f32, M>, N>) -> An<Mixer<M, N>>
+where
+    M: Size<f32>,
+    N: Size<f32> + Size<Frame<f32, M>>,
+{
+    An(Mixer::new(matrix))
+}
+
 /// Convert `AudioUnit` `unit` to an `AudioNode`.
 /// The number of inputs and outputs is chosen statically and must match the `AudioUnit`.
 /// - Input(s): from `unit`.
@@ -3124,9 +4757,217 @@ pub fn poly_pulse<F: Real>() -> An<PolyPulse<F>> {
     An(PolyPulse::new())
 }
 
+/// Bandlimited impulse train (BLIT) oscillator. Each impulse has unit area
+/// and contains the harmonics below Nyquist. Leaky integration derives other waveforms:
+/// the train minus its mean (frequency divided by sample rate) integrates to a sawtooth
+/// and the difference of two trains half a period apart integrates to a square wave.
+/// - Input 0: frequency (Hz)
+/// - Output 0: impulse train
+///
+/// ### Example: Leaky Integrated Square Wave
+/// ```
+/// use fundsp::prelude::*;
+/// dc(110.0) >> (blit::<f32>() ^ blit::<f32>().phase(0.5)) >> (pass() - pass()) >> feedback(pass() * 0.995);
+/// ```
+pub fn blit<F: Real>() -> An<Blit<F>> {
+    An(Blit::new())
+}
+
 /// PolyBLEP pulse wave oscillator at `f` Hz with pulse `width` in 0...1.
 /// A fast, fairly bandlimited pulse wave algorithm.
 /// - Output 0: pulse wave
 pub fn poly_pulse_hz<F: Real>(f: f32, width: f32) -> An<Pipe<Constant<U2>, PolyPulse<F>>> {
     dc((f, width)) >> poly_pulse()
 }
+
+/// SIMD accelerated biquad filter with independent settings for each channel.
+/// The number of channels is the number of lanes in SIMD type `F`, for example, 8 for `f32x8`.
+/// - Setting channel `i` coefficients: `Setting::biquad(a1, a2, b0, b1, b2).index(i)`.
+/// - Inputs: input signals.
+/// - Outputs: filtered signals.
+///
+/// ### Example: 4-Channel Bank
+/// ```
+/// use fundsp::prelude::*;
+/// use fundsp::wide::f64x4;
+/// let bank = biquad_bank::<f64x4>();
+/// assert_eq!(bank.inputs(), 4);
+/// ```
+pub fn biquad_bank<F: Float>() -> An<BiquadBank<F>> {
+    An(BiquadBank::new())
+}
+
+/// Bank of resonators tuned to a chord, excited by the input, for pitched resonant bodies.
+/// Resonances are at `frequencies` in Hz. Each resonator has a bandwidth of
+/// `bandwidth + RESONATOR_T60_BANDWIDTH / decay` Hz: with zero `bandwidth`,
+/// the resonances ring for `decay` seconds until they have decayed by 60 dB,
+/// and a resonance of bandwidth `B` Hz decays by 60 dB in about `2.2 / B` seconds.
+/// The resonators run in SIMD accelerated biquad banks.
+/// - Allocates: the filter banks.
+/// - Setting: decay time in seconds (`Setting::time`).
+/// - Input 0: excitation
+/// - Output 0: resonant body
+///
+/// ### Example: Noise Excited Minor Seventh Chord
+/// ```
+/// use fundsp::prelude::*;
+/// noise() * 0.1 >> resonator_chord(&[220.0, 261.63, 329.63, 392.0], 1.0, 2.0);
+/// ```
+pub fn resonator_chord(frequencies: &[f32], bandwidth: f32, decay: f32) -> An<ResonatorChord> {
+    An(ResonatorChord::new(frequencies, bandwidth, decay))
+}
+
+/// Channel vocoder with `bands` log-spaced bands (`bands` > 0) in 100 Hz...8 kHz.
+/// The envelope response time is 10 ms. It can be changed with `Setting::time`.
+/// - Allocates: filter banks and envelope followers.
+/// - Input 0: modulator (for example, voice)
+/// - Input 1: carrier (for example, synth)
+/// - Output 0: vocoded signal
+///
+/// ### Example: Vocoded Saw Wave
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | saw_hz(110.0)) >> vocoder(16);
+/// ```
+pub fn vocoder(bands: usize) -> An<Vocoder> {
+    An(Vocoder::new(bands, 0.01))
+}
+
+/// Ring modulator. Multiplies carrier and bipolar modulator.
+/// - Input 0: carrier
+/// - Input 1: modulator
+/// - Output 0: ring modulated signal
+///
+/// ### Example: Ring Modulated Saw Wave
+/// ```
+/// use fundsp::prelude::*;
+/// (saw_hz(220.0) | sine_hz::<f32>(30.0)) >> ring_mod();
+/// ```
+pub fn ring_mod() -> An<RingMod> {
+    An(RingMod::new())
+}
+
+/// Unipolar amplitude modulator with modulation `depth` in 0...1.
+/// The modulator in -1...1 is mapped to carrier gain in `1 - depth`...1.
+/// - Setting: depth (`Setting::value`).
+/// - Input 0: carrier
+/// - Input 1: modulator
+/// - Output 0: amplitude modulated signal
+///
+/// ### Example: Tremolo
+/// ```
+/// use fundsp::prelude::*;
+/// (saw_hz(220.0) | sine_hz::<f32>(5.0)) >> amp_mod(0.5);
+/// ```
+pub fn amp_mod(depth: f32) -> An<AmpMod> {
+    An(AmpMod::new(depth))
+}
+
+/// Bitcrusher. Quantizes amplitude to `bits` bits (`bits` > 0),
+/// with a step size of `2^(1 - bits)`. Fractional bit depths are allowed.
+/// - Setting: bit depth.
+/// - Input 0: input signal
+/// - Output 0: quantized signal
+///
+/// ### Example: Lo-Fi Saw
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> bitcrush(4.0);
+/// ```
+pub fn bitcrush(bits: f32) -> An<Bitcrush<U1>> {
+    An(Bitcrush::new(bits))
+}
+
+/// Bitcrusher with bit depth input.
+/// - Setting: bit depth.
+/// - Input 0: input signal
+/// - Input 1: bit depth (`bits` > 0)
+/// - Output 0: quantized signal
+pub fn bitcrush_in() -> An<Bitcrush<U2>> {
+    An(Bitcrush::new(16.0))
+}
+
+/// Sample rate reducer. Samples the input every `factor` samples (`factor` >= 1)
+/// and holds the value in between. Fractional factors are allowed.
+/// Aliasing is not suppressed: there is no anti-aliasing filter by design.
+/// - Setting: decimation factor.
+/// - Input 0: input signal
+/// - Output 0: decimated signal
+///
+/// ### Example: Aliased Sine
+/// ```
+/// use fundsp::prelude::*;
+/// sine_hz::<f32>(1000.0) >> downsample(8.0);
+/// ```
+pub fn downsample(factor: f32) -> An<Downsample<U1>> {
+    An(Downsample::new(factor))
+}
+
+/// Sample rate reducer with decimation factor input.
+/// Aliasing is not suppressed: there is no anti-aliasing filter by design.
+/// - Setting: decimation factor.
+/// - Input 0: input signal
+/// - Input 1: decimation factor (minimum 1)
+/// - Output 0: decimated signal
+pub fn downsample_in() -> An<Downsample<U2>> {
+    An(Downsample::new(1.0))
+}
+
+/// `tanh` saturation with drive `amount` (`amount` > 0) and automatic gain compensation:
+/// a full scale sine wave keeps its RMS level at any drive.
+/// - Setting: drive amount.
+/// - Input 0: input signal
+/// - Output 0: saturated signal
+///
+/// ### Example: Driven Saw
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> drive(5.0);
+/// ```
+pub fn drive(amount: f32) -> An<Drive<U1>> {
+    An(Drive::new(amount))
+}
+
+/// `tanh` saturation with drive amount input and automatic gain compensation:
+/// a full scale sine wave keeps its RMS level at any drive.
+/// - Setting: drive amount.
+/// - Input 0: input signal
+/// - Input 1: drive amount (minimum 0.001)
+/// - Output 0: saturated signal
+pub fn drive_in() -> An<Drive<U2>> {
+    An(Drive::new(1.0))
+}
+
+/// Harmonic exciter with crossover `frequency` in Hz and gain `amount` (`amount` >= 0).
+/// The signal above the crossover is saturated to generate odd and even harmonics,
+/// which are high-passed again and added to the dry signal. A `frequency` of 3 kHz
+/// is a good default for adding "air".
+/// - Setting: crossover frequency with `Setting::center`, amount with `Setting::value`.
+/// - Input 0: input signal
+/// - Output 0: excited signal
+///
+/// ### Example: Brighten A Pad
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> lowpass_hz::<f32>(2000.0, 1.0) >> exciter(3000.0, 0.5);
+/// ```
+pub fn exciter(frequency: f32, amount: f32) -> An<Exciter> {
+    An(Exciter::new(frequency, amount))
+}
+
+/// Shape signal at `factor` times the sample rate to reduce aliasing.
+/// The oversampling `factor` must be a power of two: 2, 4 or 8.
+/// Higher factors suppress more aliasing but cost more CPU:
+/// the shape is evaluated `factor` times per sample and every 2x stage
+/// adds filtering and a few samples of latency.
+/// - Input 0: input signal
+/// - Output 0: shaped signal
+///
+/// ### Example: Oversampled Hard Clipping
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(440.0) >> shape_oversampled(4, Clip(4.0));
+/// ```
+pub fn shape_oversampled<S: Shape>(factor: usize, shape: S) -> An<ShapeOversampler<S>> {
+    An(ShapeOversampler::new(factor, shape))
+}
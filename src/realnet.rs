@@ -9,6 +9,47 @@ use super::signal::*;
 use thingbuf::mpsc::{channel, Receiver, Sender};
 extern crate alloc;
 use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Number of pending automation events the backend can hold.
+/// Events scheduled beyond this are dropped.
+pub const AUTOMATION_CAPACITY: usize = 1024;
+
+/// Automation event pending in the backend.
+#[derive(Clone)]
+struct AutomationEvent {
+    /// Time in seconds.
+    time: f64,
+    /// Scheduling order of events at the same time.
+    order: u64,
+    setting: Setting,
+}
+
+impl Ord for AutomationEvent {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        // Reverse the order so the earliest event is at the top of the heap.
+        other
+            .time
+            .total_cmp(&self.time)
+            .then(other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for AutomationEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for AutomationEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == core::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for AutomationEvent {}
 
 /// Message from frontend to backend.
 #[derive(Default, Clone)]
@@ -17,6 +58,8 @@ pub(crate) enum NetMessage {
     Null,
     Net(Net),
     Setting(Setting),
+    /// Setting scheduled at an absolute time in seconds.
+    Automation(f64, Setting),
 }
 
 /// Message from backend to frontend.
@@ -34,6 +77,15 @@ pub struct NetBackend {
     /// For receiving new versions and settings from the frontend.
     receiver: Receiver<NetMessage>,
     net: Net,
+    /// Pending automation events, earliest first.
+    automation: BinaryHeap<AutomationEvent>,
+    /// Scheduling order of the next automation event.
+    automation_order: u64,
+    /// Index of the next sample to be processed.
+    time: u64,
+    /// Buffers for processing blocks one sample at a time.
+    tick_input: Vec<f32>,
+    tick_output: Vec<f32>,
 }
 
 impl Clone for NetBackend {
@@ -41,10 +93,17 @@ impl Clone for NetBackend {
         // Allocate a dummy channel.
         let (sender_net, _receiver_net) = channel(1);
         let (_sender_message, receiver_message) = channel(1);
+        let mut automation = BinaryHeap::with_capacity(AUTOMATION_CAPACITY);
+        automation.extend(self.automation.iter().cloned());
         Self {
             sender: Some(sender_net),
             receiver: receiver_message,
             net: self.net.clone(),
+            automation,
+            automation_order: self.automation_order,
+            time: self.time,
+            tick_input: self.tick_input.clone(),
+            tick_output: self.tick_output.clone(),
         }
     }
 }
@@ -55,7 +114,36 @@ impl NetBackend {
         Self {
             sender: Some(sender),
             receiver,
+            tick_input: vec![0.0; net.inputs()],
+            tick_output: vec![0.0; net.outputs()],
             net,
+            automation: BinaryHeap::with_capacity(AUTOMATION_CAPACITY),
+            automation_order: 0,
+            time: 0,
+        }
+    }
+
+    /// Sample index at which an automation event at `time` seconds is applied.
+    #[inline]
+    fn event_sample(&self, time: f64) -> u64 {
+        round(max(0.0, time) * self.net.sample_rate()) as u64
+    }
+
+    /// Sample index of the next automation event, if any.
+    #[inline]
+    fn next_event(&self) -> Option<u64> {
+        self.automation
+            .peek()
+            .map(|event| self.event_sample(event.time))
+    }
+
+    /// Apply automation events that are due at the current sample.
+    #[inline]
+    fn apply_automation(&mut self) {
+        while self.next_event().is_some_and(|sample| sample <= self.time) {
+            if let Some(event) = self.automation.pop() {
+                self.net.set(event.setting);
+            }
         }
     }
 
@@ -84,6 +172,17 @@ impl NetBackend {
                         NetMessage::Setting(setting) => {
                             self.net.set(setting);
                         }
+                        NetMessage::Automation(time, setting) => {
+                            // Drop the event if the queue is full, as growing it would allocate.
+                            if self.automation.len() < AUTOMATION_CAPACITY {
+                                self.automation.push(AutomationEvent {
+                                    time,
+                                    order: self.automation_order,
+                                    setting,
+                                });
+                                self.automation_order += 1;
+                            }
+                        }
                         NetMessage::Null => (),
                     }
                 }
@@ -118,6 +217,7 @@ impl AudioUnit for NetBackend {
 
     fn reset(&mut self) {
         self.net.reset();
+        self.time = 0;
         self.handle_messages();
     }
 
@@ -128,12 +228,34 @@ impl AudioUnit for NetBackend {
 
     fn tick(&mut self, input: &[f32], output: &mut [f32]) {
         self.handle_messages();
+        self.apply_automation();
         self.net.tick_2(input, output, &self.sender);
+        self.time += 1;
     }
 
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
         self.handle_messages();
-        self.net.process_2(size, input, output, &self.sender);
+        if self
+            .next_event()
+            .is_some_and(|sample| sample < self.time + size as u64)
+        {
+            // Process the block one sample at a time to apply automation sample-accurately.
+            for i in 0..size {
+                self.apply_automation();
+                for channel in 0..self.tick_input.len() {
+                    self.tick_input[channel] = input.at_f32(channel, i);
+                }
+                self.net
+                    .tick_2(&self.tick_input, &mut self.tick_output, &self.sender);
+                for channel in 0..self.tick_output.len() {
+                    output.set_f32(channel, i, self.tick_output[channel]);
+                }
+                self.time += 1;
+            }
+        } else {
+            self.net.process_2(size, input, output, &self.sender);
+            self.time += size as u64;
+        }
     }
 
     fn get_id(&self) -> u64 {
@@ -157,4 +279,11 @@ impl AudioUnit for NetBackend {
     fn allocate(&mut self) {
         self.net.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.net.memory_usage()
+            + self.automation.capacity() * core::mem::size_of::<AutomationEvent>()
+            + (self.tick_input.capacity() + self.tick_output.capacity())
+                * core::mem::size_of::<f32>()
+    }
 }
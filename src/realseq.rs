@@ -20,6 +20,8 @@ pub(crate) enum Message {
     Edit(EventId, Edit),
     /// Edit event in relative time.
     EditRelative(EventId, Edit),
+    /// Set or clear loop region.
+    Loop(Option<(f64, f64)>),
 }
 
 pub struct SequencerBackend {
@@ -78,6 +80,12 @@ impl SequencerBackend {
                     self.sequencer
                         .edit_relative(id, edit.end_time, edit.fade_out);
                 }
+                Message::Loop(Some((start_time, end_time))) => {
+                    self.sequencer.set_loop(start_time, end_time);
+                }
+                Message::Loop(None) => {
+                    self.sequencer.clear_loop();
+                }
             }
         }
     }
@@ -125,7 +133,7 @@ impl AudioUnit for SequencerBackend {
         self.handle_messages();
         self.sequencer.tick(input, output);
         // Tick and process are the only places where events may be pushed to the past vector.
-        if !self.sequencer.replay_events() {
+        if !self.sequencer.retain_past() {
             self.send_back_past();
         }
     }
@@ -134,7 +142,7 @@ impl AudioUnit for SequencerBackend {
         self.handle_messages();
         self.sequencer.process(size, input, output);
         // Tick and process are the only places where events may be pushed to the past vector.
-        if !self.sequencer.replay_events() {
+        if !self.sequencer.retain_past() {
             self.send_back_past();
         }
     }
@@ -160,4 +168,8 @@ impl AudioUnit for SequencerBackend {
     fn allocate(&mut self) {
         self.sequencer.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.sequencer.memory_usage()
+    }
 }
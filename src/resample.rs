@@ -1,11 +1,95 @@
-//! Cubic variable speed resampler.
+//! Variable speed resampler with selectable interpolation quality.
 
 use super::audionode::*;
 use super::math::*;
 use super::signal::*;
 use super::*;
 use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use once_cell::race::OnceBox;
 
+/// Half length of the windowed sinc kernel in samples.
+const SINC_HALF: usize = 16;
+/// Sinc kernel table resolution in points per sample.
+const SINC_RESOLUTION: usize = 64;
+/// Sinc lowpass cutoff relative to the Nyquist frequency.
+/// The cutoff is lowered further when speeding up.
+const SINC_CUTOFF: f32 = 0.9;
+
+/// Interpolation quality of `Resampler`.
+/// Higher quality costs more CPU.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ResampleQuality {
+    /// Linear interpolation. Cheapest, with audible aliasing and high frequency loss.
+    Linear,
+    /// Cubic spline interpolation. Moderate cost and quality.
+    #[default]
+    Cubic,
+    /// Windowed sinc interpolation with a fixed kernel of 32 samples.
+    /// The kernel is lowpassed when speeding up to suppress aliasing.
+    /// Costs roughly ten times as much as cubic interpolation
+    /// and attenuates the top 10% of the spectrum.
+    Sinc,
+}
+
+/// Tables for the windowed sinc kernel, shared between all resamplers.
+struct SincTable {
+    /// Sinc function sampled at `SINC_RESOLUTION` points per sample from 0 to `SINC_HALF`.
+    sinc: Vec<f32>,
+    /// Blackman window sampled at the same points.
+    window: Vec<f32>,
+}
+
+impl SincTable {
+    fn new() -> Self {
+        let length = SINC_HALF * SINC_RESOLUTION + 2;
+        let sinc = (0..length)
+            .map(|i| {
+                let x = i as f64 / SINC_RESOLUTION as f64 * f64::PI;
+                if i == 0 {
+                    1.0
+                } else {
+                    (sin(x) / x) as f32
+                }
+            })
+            .collect();
+        let window = (0..length)
+            .map(|i| {
+                let u = min(1.0, i as f64 / (SINC_HALF * SINC_RESOLUTION) as f64);
+                // Blackman window centered at zero.
+                (0.42 + 0.5 * cos(u * f64::PI) + 0.08 * cos(u * f64::TAU)) as f32
+            })
+            .collect();
+        Self { sinc, window }
+    }
+
+    /// Linearly interpolated lookup of `table` at `x` samples, which must be in 0...`SINC_HALF`.
+    #[inline]
+    fn lookup(table: &[f32], x: f32) -> f32 {
+        let p = x * SINC_RESOLUTION as f32;
+        let i = p as usize;
+        lerp(table[i], table[i + 1], p - i as f32)
+    }
+
+    /// Kernel weight at distance `x` samples with relative `cutoff` in 0...1.
+    #[inline]
+    fn weight(&self, x: f32, cutoff: f32) -> f32 {
+        let x = abs(x);
+        cutoff * Self::lookup(&self.sinc, x * cutoff) * Self::lookup(&self.window, x)
+    }
+}
+
+fn sinc_table() -> &'static SincTable {
+    static INSTANCE: OnceBox<SincTable> = OnceBox::new();
+    INSTANCE.get_or_init(|| Box::new(SincTable::new()))
+}
+
+/// Variable speed resampler of a generator node.
+/// The interpolation quality is cubic by default, see `ResampleQuality`.
+/// - Input 0: sampling speed, where 1 is the original speed.
+/// - Output(s): resampled outputs of enclosed generator.
 #[derive(Clone)]
 pub struct Resampler<X>
 where
@@ -16,6 +100,7 @@ where
     buffer: Frame<Frame<f32, U128>, X::Outputs>,
     consumer: f64,
     producer: usize,
+    quality: ResampleQuality,
 }
 
 impl<X> Resampler<X>
@@ -34,9 +119,22 @@ where
             buffer: Frame::default(),
             consumer: 1.0,
             producer: 0,
+            quality: ResampleQuality::default(),
         }
     }
 
+    /// Get current interpolation quality.
+    #[inline]
+    pub fn quality(&self) -> ResampleQuality {
+        self.quality
+    }
+
+    /// Set interpolation quality. This does not allocate.
+    #[inline]
+    pub fn set_quality(&mut self, quality: ResampleQuality) {
+        self.quality = quality;
+    }
+
     // Access enclosed node.
     #[inline]
     pub fn node(&self) -> &X {
@@ -73,26 +171,63 @@ where
 
     #[inline]
     fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
-        self.consumer += max(0.0, input[0]).to_f64();
+        let speed = max(0.0, input[0]);
+        self.consumer += speed.to_f64();
         let d = self.consumer - self.consumer.floor();
         let consumer_i = (self.consumer - d) as usize;
-        while consumer_i + 2 >= self.producer {
+        let d = d as f32;
+        // How many samples ahead of the consumer the interpolator reads.
+        let reach = match self.quality {
+            ResampleQuality::Linear => 1,
+            ResampleQuality::Cubic => 2,
+            ResampleQuality::Sinc => SINC_HALF,
+        };
+        while consumer_i + reach >= self.producer {
             let inner = self.x.tick(&Frame::default());
             for channel in 0..X::Outputs::USIZE {
                 self.buffer[channel][self.producer & 0x7f] = inner[channel];
             }
             self.producer += 1;
         }
-        let output: Frame<f32, Self::Outputs> = Frame::generate(|channel| {
-            spline(
-                self.buffer[channel][(consumer_i + 0x7f) & 0x7f],
-                self.buffer[channel][consumer_i & 0x7f],
-                self.buffer[channel][(consumer_i + 1) & 0x7f],
-                self.buffer[channel][(consumer_i + 2) & 0x7f],
-                d as f32,
-            )
-        });
-        output
+        match self.quality {
+            ResampleQuality::Linear => Frame::generate(|channel| {
+                lerp(
+                    self.buffer[channel][consumer_i & 0x7f],
+                    self.buffer[channel][(consumer_i + 1) & 0x7f],
+                    d,
+                )
+            }),
+            ResampleQuality::Cubic => Frame::generate(|channel| {
+                spline(
+                    self.buffer[channel][(consumer_i + 0x7f) & 0x7f],
+                    self.buffer[channel][consumer_i & 0x7f],
+                    self.buffer[channel][(consumer_i + 1) & 0x7f],
+                    self.buffer[channel][(consumer_i + 2) & 0x7f],
+                    d,
+                )
+            }),
+            ResampleQuality::Sinc => {
+                let table = sinc_table();
+                // Lower the cutoff when speeding up to keep content below the output Nyquist frequency.
+                let cutoff = SINC_CUTOFF * min(1.0, 1.0 / speed);
+                let mut weights = [0.0f32; SINC_HALF * 2];
+                let mut total = 0.0;
+                for (j, weight) in weights.iter_mut().enumerate() {
+                    *weight = table.weight(j as f32 + 1.0 - SINC_HALF as f32 - d, cutoff);
+                    total += *weight;
+                }
+                // Normalize for unity gain at DC.
+                let scale = 1.0 / total;
+                let start = consumer_i + 0x80 + 1 - SINC_HALF;
+                Frame::generate(|channel| {
+                    let mut sum = 0.0;
+                    for (j, weight) in weights.iter().enumerate() {
+                        sum += *weight * self.buffer[channel][(start + j) & 0x7f];
+                    }
+                    sum * scale
+                })
+            }
+        }
     }
 
     fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
@@ -106,4 +241,8 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
 }
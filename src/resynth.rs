@@ -8,25 +8,314 @@ use super::audionode::*;
 use super::math::*;
 use super::signal::*;
 use super::*;
+use core::ops::Add;
 use num_complex::Complex32;
+use numeric_array::ArrayLength;
+use typenum::{Sum, U2};
 extern crate alloc;
 use alloc::vec;
 use alloc::vec::Vec;
 
-/// Number of overlapping FFT windows.
+/// Default number of overlapping FFT windows.
 const WINDOWS: usize = 4;
 
-/// A single FFT window. Contains input and output
-/// values in the frequency domain.
+/// Storage for the channel buffers of an FFT window.
+/// Each input channel holds window length plus two samples
+/// and each output channel holds window length complex values.
+pub trait FftStorage: Clone + Send + Sync {
+    /// Storage for the window function, which holds window length samples.
+    type Window: AsRef<[f32]> + AsMut<[f32]> + Clone + Send + Sync;
+
+    /// Container for the overlapping FFT windows of a resynthesizer.
+    type Windows: AsRef<[FftWindow<Self>]> + AsMut<[FftWindow<Self>]> + Clone + Send + Sync;
+
+    /// Create storage for `inputs` input channels and `outputs` output channels
+    /// of window length `length`.
+    fn new(length: usize, inputs: usize, outputs: usize) -> Self;
+
+    /// Create storage for the window function of window length `length`.
+    fn new_window(length: usize) -> Self::Window;
+
+    /// Create `overlaps` FFT windows of window length `length`
+    /// with `inputs` input channels and `outputs` output channels.
+    fn new_windows(length: usize, overlaps: usize, inputs: usize, outputs: usize) -> Self::Windows;
+
+    /// Number of input channels.
+    fn inputs(&self) -> usize;
+
+    /// Number of output channels.
+    fn outputs(&self) -> usize;
+
+    /// Input channel `channel`.
+    fn input(&self, channel: usize) -> &[f32];
+
+    /// Mutable input channel `channel`.
+    fn input_mut(&mut self, channel: usize) -> &mut [f32];
+
+    /// Output channel `channel`.
+    fn output(&self, channel: usize) -> &[Complex32];
+
+    /// Mutable output channel `channel`.
+    fn output_mut(&mut self, channel: usize) -> &mut [Complex32];
+
+    /// Heap memory used by the storage in bytes.
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    /// Heap memory used by window function storage `window` in bytes.
+    #[allow(unused_variables)]
+    fn window_memory_usage(window: &Self::Window) -> usize {
+        0
+    }
+
+    /// Heap memory used by window container `windows` in bytes, excluding the windows themselves.
+    #[allow(unused_variables)]
+    fn windows_memory_usage(windows: &Self::Windows) -> usize {
+        0
+    }
+}
+
+/// Heap allocated FFT window storage. This is the default storage of `Resynth`.
 #[derive(Clone)]
-pub struct FftWindow {
-    /// Window length. Must be a power of two and at least four.
-    /// Equals the length of each input and output channel vector.
-    length: usize,
+pub struct FftVecs {
     /// Input samples for each input channel.
     input: Vec<Vec<f32>>,
     /// Output samples for each output channel.
     output: Vec<Vec<Complex32>>,
+}
+
+impl FftStorage for FftVecs {
+    type Window = Vec<f32>;
+    type Windows = Vec<FftWindow<FftVecs>>;
+
+    fn new(length: usize, inputs: usize, outputs: usize) -> Self {
+        Self {
+            input: vec![vec!(0.0; length + 2); inputs],
+            output: vec![vec!(Complex32::ZERO; length); outputs],
+        }
+    }
+
+    fn new_window(length: usize) -> Self::Window {
+        vec![0.0; length]
+    }
+
+    fn new_windows(length: usize, overlaps: usize, inputs: usize, outputs: usize) -> Self::Windows {
+        (0..overlaps)
+            .map(|_| FftWindow::new(length, 0, inputs, outputs))
+            .collect()
+    }
+
+    #[inline]
+    fn inputs(&self) -> usize {
+        self.input.len()
+    }
+
+    #[inline]
+    fn outputs(&self) -> usize {
+        self.output.len()
+    }
+
+    #[inline]
+    fn input(&self, channel: usize) -> &[f32] {
+        &self.input[channel]
+    }
+
+    #[inline]
+    fn input_mut(&mut self, channel: usize) -> &mut [f32] {
+        &mut self.input[channel]
+    }
+
+    #[inline]
+    fn output(&self, channel: usize) -> &[Complex32] {
+        &self.output[channel]
+    }
+
+    #[inline]
+    fn output_mut(&mut self, channel: usize) -> &mut [Complex32] {
+        &mut self.output[channel]
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.input.capacity() * core::mem::size_of::<Vec<f32>>()
+            + self
+                .input
+                .iter()
+                .map(|x| x.capacity() * core::mem::size_of::<f32>())
+                .sum::<usize>()
+            + self.output.capacity() * core::mem::size_of::<Vec<Complex32>>()
+            + self
+                .output
+                .iter()
+                .map(|x| x.capacity() * core::mem::size_of::<Complex32>())
+                .sum::<usize>()
+    }
+
+    fn window_memory_usage(window: &Self::Window) -> usize {
+        window.capacity() * core::mem::size_of::<f32>()
+    }
+
+    fn windows_memory_usage(windows: &Self::Windows) -> usize {
+        windows.capacity() * core::mem::size_of::<FftWindow<FftVecs>>()
+    }
+}
+
+/// Fixed size FFT window storage that does not allocate.
+/// `L` is the window length, `I` the number of inputs and `O` the number of outputs.
+/// Resynthesizers with fixed size storage always use four overlapping windows.
+/// The storage takes `4 * I * (L + 2) + 8 * O * L` bytes.
+#[derive(Clone)]
+pub struct FftArrays<L, I, O>
+where
+    L: ArrayLength + Add<U2>,
+    Sum<L, U2>: ArrayLength,
+    I: ArrayLength,
+    O: ArrayLength,
+{
+    /// Input samples for each input channel.
+    input: Frame<Frame<f32, Sum<L, U2>>, I>,
+    /// Output samples for each output channel.
+    output: Frame<Frame<Complex32, L>, O>,
+}
+
+impl<L, I, O> Default for FftArrays<L, I, O>
+where
+    L: ArrayLength + Add<U2>,
+    Sum<L, U2>: ArrayLength,
+    I: ArrayLength,
+    O: ArrayLength,
+{
+    fn default() -> Self {
+        Self {
+            input: Frame::generate(|_| Frame::default()),
+            output: Frame::generate(|_| Frame::generate(|_| Complex32::ZERO)),
+        }
+    }
+}
+
+impl<L, I, O> FftStorage for FftArrays<L, I, O>
+where
+    L: Size<f32> + Add<U2>,
+    Sum<L, U2>: Size<f32>,
+    I: Size<f32>,
+    O: Size<f32>,
+{
+    type Window = Frame<f32, L>;
+    type Windows = [FftWindow<Self>; WINDOWS];
+
+    fn new(length: usize, inputs: usize, outputs: usize) -> Self {
+        assert!(length == L::USIZE && inputs == I::USIZE && outputs == O::USIZE);
+        Self::default()
+    }
+
+    fn new_window(length: usize) -> Self::Window {
+        assert!(length == L::USIZE);
+        Frame::default()
+    }
+
+    fn new_windows(length: usize, overlaps: usize, inputs: usize, outputs: usize) -> Self::Windows {
+        assert_eq!(overlaps, WINDOWS);
+        core::array::from_fn(|_| FftWindow::new(length, 0, inputs, outputs))
+    }
+
+    #[inline]
+    fn inputs(&self) -> usize {
+        I::USIZE
+    }
+
+    #[inline]
+    fn outputs(&self) -> usize {
+        O::USIZE
+    }
+
+    #[inline]
+    fn input(&self, channel: usize) -> &[f32] {
+        &self.input[channel]
+    }
+
+    #[inline]
+    fn input_mut(&mut self, channel: usize) -> &mut [f32] {
+        &mut self.input[channel]
+    }
+
+    #[inline]
+    fn output(&self, channel: usize) -> &[Complex32] {
+        &self.output[channel]
+    }
+
+    #[inline]
+    fn output_mut(&mut self, channel: usize) -> &mut [Complex32] {
+        &mut self.output[channel]
+    }
+}
+
+/// Preallocated scratch storage for a resynthesizer with window length `L`,
+/// `I` inputs and `O` outputs. Use with `Resynth::with_scratch` to run
+/// a resynthesizer without any heap allocations.
+/// Window length must be a power of two between 4 and 32768.
+/// The scratch storage holds four overlapping windows and the window function,
+/// which take `16 * I * (L + 2) + 32 * O * L + 4 * L` bytes in total.
+/// For example, a mono window of length 1024 takes 53280 bytes.
+#[derive(Clone)]
+pub struct ResynthScratch<L, I, O>
+where
+    L: Size<f32> + Add<U2>,
+    Sum<L, U2>: Size<f32>,
+    I: Size<f32>,
+    O: Size<f32>,
+{
+    /// Buffers for each window.
+    window: [FftArrays<L, I, O>; WINDOWS],
+    /// Window function.
+    window_function: Frame<f32, L>,
+}
+
+impl<L, I, O> ResynthScratch<L, I, O>
+where
+    L: Size<f32> + Add<U2>,
+    Sum<L, U2>: Size<f32>,
+    I: Size<f32>,
+    O: Size<f32>,
+{
+    /// Create new scratch storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Size of the scratch storage in bytes.
+    pub const fn bytes() -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+impl<L, I, O> Default for ResynthScratch<L, I, O>
+where
+    L: Size<f32> + Add<U2>,
+    Sum<L, U2>: Size<f32>,
+    I: Size<f32>,
+    O: Size<f32>,
+{
+    fn default() -> Self {
+        Self {
+            window: Default::default(),
+            window_function: Frame::default(),
+        }
+    }
+}
+
+/// A single FFT window. Contains input and output
+/// values in the frequency domain.
+/// The channel buffers are held in storage `S`.
+#[derive(Clone)]
+pub struct FftWindow<S: FftStorage = FftVecs> {
+    /// Window length. Must be a power of two and at least four.
+    /// Equals the length of each output channel and two less than
+    /// the length of each input channel.
+    length: usize,
+    /// Input and output channels.
+    storage: S,
+    /// Number of overlapping windows.
+    overlaps: usize,
     /// Sample rate for convenience.
     sample_rate: f32,
     /// Current index into input and output vectors.
@@ -35,17 +324,17 @@ pub struct FftWindow {
     samples: u64,
 }
 
-impl FftWindow {
+impl<S: FftStorage> FftWindow<S> {
     /// Number of input channels.
     #[inline]
     pub fn inputs(&self) -> usize {
-        self.input.len()
+        self.storage.inputs()
     }
 
     /// Number of output channels.
     #[inline]
     pub fn outputs(&self) -> usize {
-        self.output.len()
+        self.storage.outputs()
     }
 
     /// Sample rate in Hz.
@@ -87,33 +376,71 @@ impl FftWindow {
     /// The window callback is invoked once for each window.
     #[inline]
     pub fn windows_per_second(&self) -> f64 {
-        WINDOWS as f64 * self.sample_rate as f64 / self.length as f64
+        self.overlaps as f64 * self.sample_rate as f64 / self.length as f64
     }
 
     /// Time between windows (in seconds) and also the time between two callback calls.
     #[inline]
     pub fn delta_time(&self) -> f64 {
-        self.length as f64 / (WINDOWS as f64 * self.sample_rate as f64)
+        self.length as f64 / (self.overlaps as f64 * self.sample_rate as f64)
     }
 
     /// Get forward vector for forward FFT.
     #[inline]
-    pub(crate) fn forward_vector(&mut self, channel: usize) -> &mut Vec<f32> {
-        &mut self.input[channel]
+    pub(crate) fn forward_vector(&mut self, channel: usize) -> &mut [f32] {
+        self.storage.input_mut(channel)
     }
 
     /// Get inverse vector for inverse FFT.
     #[inline]
-    pub(crate) fn inverse_vector(&mut self, channel: usize) -> &mut Vec<Complex32> {
-        &mut self.output[channel]
-    }
-
-    /// FFT window length. This is a power of two and at least four.
+    pub(crate) fn inverse_vector(&mut self, channel: usize) -> &mut [Complex32] {
+        self.storage.output_mut(channel)
+    }
+
+    /// FFT window length, that is, the total length of the window in samples.
+    /// This is a power of two and at least four.
+    ///
+    /// ### Example: Window Geometry
+    /// ```
+    /// use fundsp::hacker32::*;
+    /// let window = FftWindow::<FftVecs>::new(1024, 0, 1, 1);
+    /// assert_eq!(window.length(), 1024);
+    /// assert_eq!(window.hop_size(), 256);
+    /// assert_eq!(window.overlaps(), 4);
+    /// ```
     #[inline]
     pub fn length(&self) -> usize {
         self.length
     }
 
+    /// Hop size: the number of samples between the starts of successive windows.
+    /// Equals `length() / overlaps()`. The processing function is called once per hop.
+    ///
+    /// In phase vocoder processing, the expected phase advance of bin `i` between
+    /// successive windows is `TAU * i * hop_size() / length()` radians;
+    /// the deviation from it gives the true frequency of the bin.
+    #[inline]
+    pub fn hop_size(&self) -> usize {
+        self.length / self.overlaps
+    }
+
+    /// Number of windows overlapping at any sample. This is four by default
+    /// and can be configured with `resynth_overlap`.
+    ///
+    /// The window function is applied both before analysis and after synthesis,
+    /// so each output sample is a sum of `overlaps()` windows,
+    /// each weighted by the square of the window function.
+    /// With four or more overlaps, the window function is the Hann window,
+    /// and the squared Hann windows sum to `overlaps() * 3 / 8` at every sample
+    /// (1.5 with the default four overlaps). With two overlaps, the squared Hann window
+    /// does not sum to a constant, so the window function is the square root of the Hann window
+    /// (a sine window) and the squared windows, that is, Hann windows, sum to one.
+    /// The output is normalized by the reciprocal of the sum for perfect reconstruction.
+    #[inline]
+    pub fn overlaps(&self) -> usize {
+        self.overlaps
+    }
+
     /// Number of FFT bins.
     /// Equals the length of each frequency domain vector.
     /// The lowest bin is zero and the highest bin (corresponding to the Nyquist frequency) is `bins() - 1`.
@@ -132,19 +459,20 @@ impl FftWindow {
     /// Get input value at bin `i` of `channel`.
     #[inline]
     pub fn at(&self, channel: usize, i: usize) -> Complex32 {
-        Complex32::new(self.input[channel][i * 2], self.input[channel][i * 2 + 1])
+        let input = self.storage.input(channel);
+        Complex32::new(input[i * 2], input[i * 2 + 1])
     }
 
     /// Return output value for bin `i` of `channel`.
     #[inline]
     pub fn at_output(&self, channel: usize, i: usize) -> Complex32 {
-        self.output[channel][i]
+        self.storage.output(channel)[i]
     }
 
     /// Set output value for bin `i` of `channel`.
     #[inline]
     pub fn set(&mut self, channel: usize, i: usize, value: Complex32) {
-        self.output[channel][i] = value;
+        self.storage.output_mut(channel)[i] = value;
     }
 
     /// Forward `input` channel to `output` channel unchanged.
@@ -156,10 +484,15 @@ impl FftWindow {
 
     /// Create new window.
     pub fn new(length: usize, index: usize, inputs: usize, outputs: usize) -> Self {
+        Self::with_storage(length, index, S::new(length, inputs, outputs))
+    }
+
+    /// Create new window using preallocated `storage`.
+    fn with_storage(length: usize, index: usize, storage: S) -> Self {
         Self {
             length,
-            input: vec![vec!(0.0; length + 2); inputs],
-            output: vec![vec!(Complex32::ZERO; length); outputs],
+            storage,
+            overlaps: WINDOWS,
             sample_rate: DEFAULT_SR as f32,
             index,
             samples: 0,
@@ -175,20 +508,22 @@ impl FftWindow {
     #[inline]
     pub(crate) fn write<T: Float, N: Size<T>>(&mut self, input: &Frame<T, N>, window_value: f32) {
         for (channel, item) in input.iter().enumerate() {
-            self.input[channel][self.index] = item.to_f32() * window_value;
+            self.storage.input_mut(channel)[self.index] = item.to_f32() * window_value;
         }
     }
 
     /// Read output for current index.
     #[inline]
     pub(crate) fn read<T: Float, N: Size<T>>(&self, window_value: f32) -> Frame<T, N> {
-        Frame::generate(|channel| convert(self.output[channel][self.index].re * window_value))
+        Frame::generate(|channel| {
+            convert(self.storage.output(channel)[self.index].re * window_value)
+        })
     }
 
     /// Set FFT outputs to all zeros.
     pub fn clear_output(&mut self) {
         for i in 0..self.outputs() {
-            self.output[i].fill(Complex32::ZERO);
+            self.storage.output_mut(i).fill(Complex32::ZERO);
         }
     }
 
@@ -198,15 +533,20 @@ impl FftWindow {
         self.index
     }
 
+    /// Heap memory used by this window in bytes.
+    pub(crate) fn memory_usage(&self) -> usize {
+        self.storage.memory_usage()
+    }
+
     /// Reset the window to an empty state.
     pub(crate) fn reset(&mut self, start_index: usize) {
         self.samples = 0;
         self.index = start_index;
         for channel in 0..self.inputs() {
-            self.input[channel].fill(0.0);
+            self.storage.input_mut(channel).fill(0.0);
         }
         for channel in 0..self.outputs() {
-            self.output[channel].fill(Complex32::ZERO);
+            self.storage.output_mut(channel).fill(Complex32::ZERO);
         }
     }
 
@@ -224,27 +564,33 @@ impl FftWindow {
     }
 }
 
-/// Frequency domain resynthesizer. Processes windows of input samples with an overlap of four.
+/// Frequency domain resynthesizer. Processes windows of input samples with an overlap of four
+/// by default, or a configurable power of two overlap (see `with_overlaps`).
 /// Each window is Fourier transformed and then processed into output spectra
 /// by the user supplied processing function. The window output starts from an all zeros state.
 /// The output windows are finally inverse transformed into the outputs.
 /// The latency is equal to the window length.
 /// If any output is a copy of an input, then the input will be reconstructed exactly once
 /// the windows are all overlapping, which happens one window length beyond latency.
+/// Window buffers are held in storage `S`, which is heap allocated by default.
+/// See `ResynthScratch` for running without heap allocations.
 #[derive(Clone)]
-pub struct Resynth<I, O, F>
+pub struct Resynth<I, O, F, S = FftVecs>
 where
     I: Size<f32>,
     O: Size<f32>,
-    F: FnMut(&mut FftWindow) + Clone + Send + Sync,
+    F: FnMut(&mut FftWindow<S>) + Clone + Send + Sync,
+    S: FftStorage,
 {
     _marker: core::marker::PhantomData<(I, O)>,
     /// FFT windows.
-    window: [FftWindow; WINDOWS],
+    window: S::Windows,
     /// Window length.
     window_length: usize,
-    /// Hann window function.
-    window_function: Vec<f32>,
+    /// Number of overlapping windows.
+    overlaps: usize,
+    /// Hann window function, or its square root with two overlaps.
+    window_function: S::Window,
     /// Processing function.
     processing: F,
     /// Sample rate.
@@ -255,11 +601,12 @@ where
     z: f32,
 }
 
-impl<I, O, F> Resynth<I, O, F>
+impl<I, O, F, S> Resynth<I, O, F, S>
 where
     I: Size<f32>,
     O: Size<f32>,
-    F: FnMut(&mut FftWindow) + Clone + Send + Sync,
+    F: FnMut(&mut FftWindow<S>) + Clone + Send + Sync,
+    S: FftStorage,
 {
     /// Number of FFT bins. Equals the length of each frequency domain vector in FFT windows.
     #[inline]
@@ -273,50 +620,115 @@ where
         self.window_length
     }
 
-    /// Create new resynthesizer. Window length must be a power of two between 4 and 32768.
+    /// Number of overlapping windows.
+    #[inline]
+    pub fn overlaps(&self) -> usize {
+        self.overlaps
+    }
+
+    /// Hop size in samples: the number of samples between successive windows.
+    #[inline]
+    fn hop_size(&self) -> usize {
+        self.window_length / self.overlaps
+    }
+
+    /// Create new resynthesizer with four overlapping windows.
+    /// Window length must be a power of two between 4 and 32768.
     pub fn new(window_length: usize, processing: F) -> Self {
-        assert!(window_length >= 4 && window_length.is_power_of_two());
+        Self::with_overlaps(window_length, WINDOWS, processing)
+    }
+
+    /// Create new resynthesizer with `overlaps` overlapping windows.
+    /// Window length must be a power of two between 4 and 32768.
+    /// The number of overlaps must be a power of two between 2 and window length.
+    /// Fixed size storage supports only four overlaps.
+    pub fn with_overlaps(window_length: usize, overlaps: usize, processing: F) -> Self {
+        Self::from_parts(
+            window_length,
+            overlaps,
+            S::new_windows(window_length, overlaps, I::USIZE, O::USIZE),
+            S::new_window(window_length),
+            processing,
+        )
+    }
 
-        let mut window_function = Vec::with_capacity(window_length);
+    /// Create new resynthesizer from window storage.
+    fn from_parts(
+        window_length: usize,
+        overlaps: usize,
+        mut window: S::Windows,
+        mut window_function: S::Window,
+        processing: F,
+    ) -> Self {
+        assert!(window_length >= 4 && window_length.is_power_of_two());
+        assert!(overlaps >= 2 && overlaps <= window_length && overlaps.is_power_of_two());
+        assert_eq!(window.as_ref().len(), overlaps);
 
-        for i in 0..window_length {
+        for (i, x) in window_function.as_mut().iter_mut().enumerate() {
             let hann = 0.5
                 + 0.5
                     * cos((i as i32 - (window_length >> 1) as i32) as f32 * f32::TAU
                         / window_length as f32);
-            window_function.push(hann);
+            // With two overlaps, only the Hann window itself sums to a constant,
+            // so we split it between analysis and synthesis.
+            *x = if overlaps == 2 { sqrt(hann) } else { hann };
         }
 
-        let window = [
-            FftWindow::new(window_length, 0, I::USIZE, O::USIZE),
-            FftWindow::new(window_length, window_length >> 2, I::USIZE, O::USIZE),
-            FftWindow::new(window_length, window_length >> 1, I::USIZE, O::USIZE),
-            FftWindow::new(
-                window_length,
-                (window_length >> 1) + (window_length >> 2),
-                I::USIZE,
-                O::USIZE,
-            ),
-        ];
+        for window in window.as_mut().iter_mut() {
+            window.overlaps = overlaps;
+        }
 
-        Self {
+        let mut node = Self {
             _marker: core::marker::PhantomData,
             window,
             window_length,
+            overlaps,
             window_function,
             processing,
             sample_rate: DEFAULT_SR,
             samples: 0,
-            z: 2.0 / 3.0,
-        }
+            // Reciprocal of the sum of the overlapping squared window functions.
+            z: if overlaps == 2 {
+                1.0
+            } else {
+                8.0 / (3.0 * overlaps as f32)
+            },
+        };
+        node.reset();
+        node
     }
 }
 
-impl<I, O, F> AudioNode for Resynth<I, O, F>
+impl<L, I, O, F> Resynth<I, O, F, FftArrays<L, I, O>>
 where
+    L: Size<f32> + Add<U2>,
+    Sum<L, U2>: Size<f32>,
     I: Size<f32>,
     O: Size<f32>,
-    F: FnMut(&mut FftWindow) + Clone + Send + Sync,
+    F: FnMut(&mut FftWindow<FftArrays<L, I, O>>) + Clone + Send + Sync,
+{
+    /// Create new resynthesizer that uses preallocated `scratch` storage
+    /// and does not allocate. Window length is `L`, which must be
+    /// a power of two between 4 and 32768.
+    pub fn with_scratch(scratch: ResynthScratch<L, I, O>, processing: F) -> Self {
+        Self::from_parts(
+            L::USIZE,
+            WINDOWS,
+            scratch
+                .window
+                .map(|storage| FftWindow::with_storage(L::USIZE, 0, storage)),
+            scratch.window_function,
+            processing,
+        )
+    }
+}
+
+impl<I, O, F, S> AudioNode for Resynth<I, O, F, S>
+where
+    I: Size<f32>,
+    O: Size<f32>,
+    F: FnMut(&mut FftWindow<S>) + Clone + Send + Sync,
+    S: FftStorage,
 {
     const ID: u64 = 80;
     type Inputs = I;
@@ -324,46 +736,57 @@ where
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
         self.sample_rate = sample_rate;
-        for i in 0..WINDOWS {
-            self.window[i].set_sample_rate(sample_rate as f32);
+        for window in self.window.as_mut().iter_mut() {
+            window.set_sample_rate(sample_rate as f32);
         }
     }
 
     fn reset(&mut self) {
         self.samples = 0;
-        for i in 0..WINDOWS {
-            self.window[i].reset(i * (self.window_length >> 2));
+        let hop = self.hop_size();
+        for (i, window) in self.window.as_mut().iter_mut().enumerate() {
+            window.reset(i * hop);
         }
     }
 
+    fn memory_usage(&self) -> usize {
+        self.window
+            .as_ref()
+            .iter()
+            .map(|x| x.memory_usage())
+            .sum::<usize>()
+            + S::windows_memory_usage(&self.window)
+            + S::window_memory_usage(&self.window_function)
+    }
+
     fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
         let mut output = Frame::default();
 
-        for i in 0..WINDOWS {
-            let window_value = self.window_function[self.window[i].index()];
-            self.window[i].write(input, window_value);
-            output += self.window[i].read(window_value * self.z);
-            self.window[i].advance();
+        for window in self.window.as_mut().iter_mut() {
+            let window_value = self.window_function.as_ref()[window.index()];
+            window.write(input, window_value);
+            output += window.read(window_value * self.z);
+            window.advance();
         }
 
         self.samples += 1;
 
-        if self.samples & ((self.window_length as u64 >> 2) - 1) == 0 {
-            for i in 0..WINDOWS {
-                if self.window[i].is_fft_time() {
+        if self.samples & (self.hop_size() as u64 - 1) == 0 {
+            for window in self.window.as_mut().iter_mut() {
+                if window.is_fft_time() {
                     for channel in 0..I::USIZE {
-                        let input = self.window[i].forward_vector(channel);
+                        let input = window.forward_vector(channel);
                         let fft_length = input.len() - 2;
                         super::fft::real_fft(&mut input[..fft_length]);
                         super::fft::fix_nyquist(input);
                     }
 
-                    self.window[i].clear_output();
+                    window.clear_output();
 
-                    (self.processing)(&mut self.window[i]);
+                    (self.processing)(window);
 
                     for channel in 0..O::USIZE {
-                        let output = self.window[i].inverse_vector(channel);
+                        let output = window.inverse_vector(channel);
                         super::fft::fix_negative(output);
                         super::fft::inverse_fft(output);
                     }
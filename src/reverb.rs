@@ -4,6 +4,8 @@ use super::audionode::*;
 use super::combinator::*;
 use super::delay::{AllNest, Delay};
 use super::math::*;
+use super::pan::pan_weights;
+use super::setting::{Parameter, Setting};
 use super::signal::*;
 use super::wave::*;
 use super::*;
@@ -231,6 +233,30 @@ impl<F: AudioNode<Inputs = U1, Outputs = U1>> AudioNode for Reverb<F> {
         self.feedback = 0.0;
     }
 
+    fn memory_usage(&self) -> usize {
+        self.pre.iter().map(|x| x.memory_usage()).sum::<usize>()
+            + self.block.capacity() * core::mem::size_of::<ReverbBlock<F>>()
+            + self
+                .block
+                .iter()
+                .map(|block| {
+                    block
+                        .allpass0
+                        .iter()
+                        .map(|x| x.memory_usage())
+                        .sum::<usize>()
+                        + block
+                            .allpass1
+                            .iter()
+                            .map(|x| x.memory_usage())
+                            .sum::<usize>()
+                        + block.filter0.memory_usage()
+                        + block.filter1.memory_usage()
+                        + block.delay.memory_usage()
+                })
+                .sum::<usize>()
+    }
+
     fn set_sample_rate(&mut self, sample_rate: f64) {
         for block in self.block.iter_mut() {
             for x in block.allpass0.iter_mut() {
@@ -277,3 +303,463 @@ impl<F: AudioNode<Inputs = U1, Outputs = U1>> AudioNode for Reverb<F> {
         Routing::Arbitrary(0.0).route(input, 2)
     }
 }
+
+/// Sample rate of the original plate reverb design. Delay lengths are given at this rate.
+const PLATE_SR: f64 = 29761.0;
+
+/// Input diffuser delays in samples at `PLATE_SR`.
+const PLATE_DIFFUSER: [usize; 4] = [142, 107, 379, 277];
+
+/// Tank delays in samples at `PLATE_SR`, left half first. In order: modulated allpass,
+/// delay, allpass, delay.
+const PLATE_TANK: [[usize; 4]; 2] = [[672, 4453, 1800, 3720], [908, 4217, 2656, 3163]];
+
+/// Average time of one tank half in seconds. The decay gain is applied twice per half.
+const PLATE_HALF_TIME: f64 =
+    (672 + 4453 + 1800 + 3720 + 908 + 4217 + 2656 + 3163) as f64 / 2.0 / PLATE_SR;
+
+/// Modulation excursion of the first tank allpasses in samples at `PLATE_SR`.
+const PLATE_EXCURSION: f64 = 16.0;
+
+/// Output taps in samples at `PLATE_SR`: (tank half, line index, tap, sign).
+/// Line indices are 1 for the first delay, 2 for the second allpass and 3 for the second delay.
+const PLATE_TAPS: [[(usize, usize, usize, f32); 7]; 2] = [
+    [
+        (1, 1, 266, 1.0),
+        (1, 1, 2974, 1.0),
+        (1, 2, 1913, -1.0),
+        (1, 3, 1996, 1.0),
+        (0, 1, 1990, -1.0),
+        (0, 2, 187, -1.0),
+        (0, 3, 1066, -1.0),
+    ],
+    [
+        (0, 1, 353, 1.0),
+        (0, 1, 3627, 1.0),
+        (0, 2, 1228, -1.0),
+        (0, 3, 2673, 1.0),
+        (1, 1, 2111, -1.0),
+        (1, 2, 335, -1.0),
+        (1, 3, 121, -1.0),
+    ],
+];
+
+/// Minimum predelay capacity in seconds.
+const PLATE_MAX_PREDELAY: f64 = 0.5;
+
+/// Delay line with a power-of-two buffer.
+#[derive(Clone, Default)]
+struct ReverbLine {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl ReverbLine {
+    /// Allocate room for a delay of `samples` samples.
+    fn allocate(&mut self, samples: usize) {
+        let length = (samples + 2).next_power_of_two();
+        self.buffer.clear();
+        self.buffer.resize(length, 0.0);
+        self.index = 0;
+    }
+
+    fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.index = 0;
+    }
+
+    /// Read the sample written `delay` samples ago. Call before `write`.
+    #[inline]
+    fn tap(&self, delay: usize) -> f32 {
+        self.buffer[self.index.wrapping_sub(delay) & (self.buffer.len() - 1)]
+    }
+
+    /// Read with linear interpolation at fractional `delay`. Call before `write`.
+    #[inline]
+    fn tap_linear(&self, delay: f32) -> f32 {
+        let whole = delay as usize;
+        let fraction = delay - whole as f32;
+        lerp(self.tap(whole), self.tap(whole + 1), fraction)
+    }
+
+    #[inline]
+    fn write(&mut self, x: f32) {
+        self.buffer[self.index] = x;
+        self.index = (self.index + 1) & (self.buffer.len() - 1);
+    }
+
+    /// Process allpass filter with coefficient `g` and delay `delay`.
+    #[inline]
+    fn allpass(&mut self, g: f32, delay: f32, x: f32) -> f32 {
+        let v = self.tap_linear(delay);
+        let w = x + g * v;
+        self.write(w);
+        v - g * w
+    }
+
+    /// Process delay of `delay` samples.
+    #[inline]
+    fn delay(&mut self, delay: usize, x: f32) -> f32 {
+        let y = self.tap(delay);
+        self.write(x);
+        y
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.buffer.capacity() * core::mem::size_of::<f32>()
+    }
+}
+
+/// Plate reverb with the figure-8 topology of Jon Dattorro,
+/// "Effect Design, Part 1: Reverberator and Other Filters" (1997).
+///
+/// The stereo input is summed to mono, predelayed and bandwidth limited
+/// with a one-pole lowpass, then diffused with four allpasses of
+/// 142, 107, 379 and 277 samples. The diffused signal feeds a tank of two
+/// cross-coupled halves. The left half consists of a modulated allpass of 672 samples,
+/// a delay of 4453 samples, a damping lowpass, an allpass of 1800 samples
+/// and a delay of 3720 samples. The right half has lengths 908, 4217, 2656 and 3163.
+/// The decay gain is applied after each damping filter and tank half.
+/// Stereo outputs are formed from seven taps into the tank each.
+/// All lengths are given at the original design rate of 29761 Hz
+/// and are scaled to the current sample rate.
+#[derive(Clone)]
+pub struct Plate {
+    /// Reverberation time to -60 dB in seconds.
+    time: f32,
+    /// Damping in 0...1.
+    damping: f32,
+    /// Predelay in seconds.
+    predelay_time: f32,
+    sample_rate: f64,
+    predelay: ReverbLine,
+    predelay_samples: usize,
+    bandwidth: f32,
+    diffuser: [ReverbLine; 4],
+    diffuser_length: [f32; 4],
+    tank: [[ReverbLine; 4]; 2],
+    tank_length: [[usize; 4]; 2],
+    taps: [[(usize, usize, usize, f32); 7]; 2],
+    excursion: f32,
+    damping_state: [f32; 2],
+    phase: f64,
+    gain: f32,
+    diffusion2: f32,
+}
+
+impl Plate {
+    /// Create new plate reverb. `time` is reverberation time to -60 dB in seconds,
+    /// `damping` is high frequency damping in 0...1 and `predelay` is in seconds.
+    pub fn new(time: f32, damping: f32, predelay: f32) -> Self {
+        let mut node = Self {
+            time,
+            damping: clamp01(damping),
+            predelay_time: max(predelay, 0.0),
+            sample_rate: 0.0,
+            predelay: ReverbLine::default(),
+            predelay_samples: 0,
+            bandwidth: 0.0,
+            diffuser: Default::default(),
+            diffuser_length: [0.0; 4],
+            tank: Default::default(),
+            tank_length: [[0; 4]; 2],
+            taps: PLATE_TAPS,
+            excursion: 0.0,
+            damping_state: [0.0; 2],
+            phase: 0.0,
+            gain: 0.0,
+            diffusion2: 0.0,
+        };
+        node.set_time(time);
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Reverberation time to -60 dB in seconds.
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Set reverberation time to -60 dB in seconds.
+    pub fn set_time(&mut self, time: f32) {
+        self.time = max(time, 1.0e-3);
+        self.gain = min(
+            pow(db_amp(-60.0), PLATE_HALF_TIME / (2.0 * self.time as f64)),
+            0.99999,
+        ) as f32;
+        self.diffusion2 = clamp(0.25, 0.5, self.gain + 0.15);
+    }
+
+    /// High frequency damping in 0...1.
+    #[inline]
+    pub fn damping(&self) -> f32 {
+        self.damping
+    }
+
+    /// Set high frequency damping in 0...1.
+    pub fn set_damping(&mut self, damping: f32) {
+        self.damping = clamp01(damping);
+    }
+
+    /// Predelay in seconds.
+    #[inline]
+    pub fn predelay(&self) -> f32 {
+        self.predelay_time
+    }
+
+    /// Set predelay in seconds. Predelay is limited to the larger of
+    /// half a second and the initial predelay.
+    pub fn set_predelay(&mut self, predelay: f32) {
+        self.predelay_time = max(predelay, 0.0);
+        self.predelay_samples = min(
+            round(self.predelay_time as f64 * self.sample_rate) as usize,
+            self.predelay.buffer.len() - 2,
+        );
+    }
+
+    /// Scale delay length `samples` from the design rate to the current sample rate.
+    fn scale(&self, samples: f64) -> f64 {
+        samples * self.sample_rate / PLATE_SR
+    }
+}
+
+impl AudioNode for Plate {
+    const ID: u64 = 112;
+    type Inputs = U2;
+    type Outputs = U2;
+
+    fn reset(&mut self) {
+        self.predelay.reset();
+        for line in self.diffuser.iter_mut() {
+            line.reset();
+        }
+        for line in self.tank.iter_mut().flatten() {
+            line.reset();
+        }
+        self.bandwidth = 0.0;
+        self.damping_state = [0.0; 2];
+        self.phase = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            let predelay = max(PLATE_MAX_PREDELAY, self.predelay_time as f64);
+            self.predelay
+                .allocate(round(predelay * sample_rate) as usize);
+            for i in 0..4 {
+                let length = self.scale(PLATE_DIFFUSER[i] as f64);
+                self.diffuser_length[i] = length as f32;
+                self.diffuser[i].allocate(length as usize + 1);
+            }
+            self.excursion = self.scale(PLATE_EXCURSION) as f32;
+            for half in 0..2 {
+                for i in 0..4 {
+                    let length = max(1.0, round(self.scale(PLATE_TANK[half][i] as f64)));
+                    self.tank_length[half][i] = length as usize;
+                    let extra = if i == 0 {
+                        self.excursion as usize + 2
+                    } else {
+                        1
+                    };
+                    self.tank[half][i].allocate(length as usize + extra);
+                }
+            }
+            let ratio = self.scale(1.0);
+            for (taps, design) in self.taps.iter_mut().zip(PLATE_TAPS.iter()) {
+                for (tap, &(half, line, delay, sign)) in taps.iter_mut().zip(design.iter()) {
+                    let delay = min(
+                        round(delay as f64 * ratio) as usize,
+                        self.tank_length[half][line],
+                    );
+                    *tap = (half, line, delay, sign);
+                }
+            }
+            self.set_predelay(self.predelay_time);
+            self.reset();
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Time(time) => self.set_time(*time),
+            Parameter::Coefficient(damping) => self.set_damping(*damping),
+            Parameter::Delay(predelay) => self.set_predelay(*predelay),
+            _ => (),
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.predelay.memory_usage()
+            + self
+                .diffuser
+                .iter()
+                .chain(self.tank.iter().flatten())
+                .map(|line| line.memory_usage())
+                .sum::<usize>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let x = (input[0] + input[1]) * 0.5;
+        let x = if self.predelay_samples > 0 {
+            self.predelay.delay(self.predelay_samples, x)
+        } else {
+            x
+        };
+        self.bandwidth += 0.9995 * (x - self.bandwidth);
+        let mut x = self.bandwidth;
+        for (i, g) in [0.75, 0.75, 0.625, 0.625].into_iter().enumerate() {
+            x = self.diffuser[i].allpass(g, self.diffuser_length[i], x);
+        }
+
+        let feedback = [
+            self.tank[1][3].tap(self.tank_length[1][3]),
+            self.tank[0][3].tap(self.tank_length[0][3]),
+        ];
+        let phase = self.phase * f64::TAU;
+        let modulation = [sin(phase) as f32, cos(phase) as f32];
+        self.phase += 1.0 / self.sample_rate;
+        self.phase -= floor(self.phase);
+
+        for half in 0..2 {
+            let length = &self.tank_length[half];
+            let line = &mut self.tank[half];
+            let delay = length[0] as f32 + self.excursion * modulation[half];
+            let v = line[0].allpass(-0.7, delay, x + self.gain * feedback[half]);
+            let v = line[1].delay(length[1], v);
+            self.damping_state[half] = lerp(v, self.damping_state[half], self.damping);
+            let v = line[2].allpass(
+                self.diffusion2,
+                length[2] as f32,
+                self.damping_state[half] * self.gain,
+            );
+            line[3].write(v);
+        }
+
+        Frame::generate(|channel| {
+            self.taps[channel]
+                .iter()
+                .map(|&(half, line, delay, sign)| sign * self.tank[half][line].tap(delay))
+                .sum::<f32>()
+                * 0.6
+        })
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary(0.0).route(input, 2)
+    }
+}
+
+/// Early reflection pattern of a small room: `(delay, gain, pan)` triplets
+/// with delay in seconds and pan in -1...1.
+pub const EARLY_SMALL_ROOM: [(f32, f32, f32); 10] = [
+    (0.0043, 0.841, -0.5),
+    (0.0075, 0.504, 0.6),
+    (0.0107, 0.491, -0.3),
+    (0.0133, 0.379, 0.8),
+    (0.0171, 0.380, -0.8),
+    (0.0199, 0.346, 0.2),
+    (0.0223, 0.289, -0.6),
+    (0.0261, 0.272, 0.5),
+    (0.0293, 0.192, -0.1),
+    (0.0311, 0.193, 0.7),
+];
+
+/// Early reflection pattern of a concert hall: `(delay, gain, pan)` triplets
+/// with delay in seconds and pan in -1...1.
+pub const EARLY_HALL: [(f32, f32, f32); 12] = [
+    (0.0199, 0.700, -0.4),
+    (0.0271, 0.600, 0.5),
+    (0.0353, 0.550, -0.7),
+    (0.0419, 0.500, 0.3),
+    (0.0487, 0.450, 0.8),
+    (0.0557, 0.420, -0.2),
+    (0.0631, 0.380, -0.9),
+    (0.0709, 0.330, 0.6),
+    (0.0773, 0.300, -0.5),
+    (0.0829, 0.270, 0.1),
+    (0.0901, 0.240, 0.9),
+    (0.0967, 0.200, -0.3),
+];
+
+/// Early reflection generator. A tapped delay line with a gain and an equal power pan
+/// for each tap. There is no feedback: the output is a finite sum of delayed copies
+/// of the input, so the unit can be mixed independently of a diffuse reverb tail.
+#[derive(Clone)]
+pub struct EarlyReflections {
+    /// Reflection pattern as `(delay, gain, pan)` triplets.
+    pattern: Vec<(f32, f32, f32)>,
+    /// Taps as `(delay in samples, left gain, right gain)`.
+    taps: Vec<(usize, f32, f32)>,
+    line: ReverbLine,
+    sample_rate: f64,
+}
+
+impl EarlyReflections {
+    /// Create new early reflection generator from a pattern of `(delay, gain, pan)` triplets,
+    /// where delay is in seconds and pan is in -1...1.
+    pub fn new(pattern: &[(f32, f32, f32)]) -> Self {
+        let mut node = Self {
+            pattern: pattern.to_vec(),
+            taps: Vec::with_capacity(pattern.len()),
+            line: ReverbLine::default(),
+            sample_rate: 0.0,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Reflection pattern as `(delay, gain, pan)` triplets.
+    pub fn pattern(&self) -> &[(f32, f32, f32)] {
+        &self.pattern
+    }
+}
+
+impl AudioNode for EarlyReflections {
+    const ID: u64 = 113;
+    type Inputs = U1;
+    type Outputs = U2;
+
+    fn reset(&mut self) {
+        self.line.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.taps.clear();
+            for &(delay, gain, pan) in self.pattern.iter() {
+                let (left, right) = pan_weights(pan);
+                let delay = round(max(delay, 0.0) as f64 * sample_rate) as usize;
+                self.taps.push((delay, gain * left, gain * right));
+            }
+            let length = self.taps.iter().map(|tap| tap.0).max().unwrap_or(0);
+            self.line.allocate(length + 1);
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.line.memory_usage()
+            + self.pattern.capacity() * core::mem::size_of::<(f32, f32, f32)>()
+            + self.taps.capacity() * core::mem::size_of::<(usize, f32, f32)>()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.line.write(input[0]);
+        let mut left = 0.0;
+        let mut right = 0.0;
+        for &(delay, left_gain, right_gain) in self.taps.iter() {
+            let x = self.line.tap(delay + 1);
+            left += x * left_gain;
+            right += x * right_gain;
+        }
+        [left, right].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary(0.0).route(input, 2)
+    }
+}
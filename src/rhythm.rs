@@ -0,0 +1,460 @@
+//! Clock and rhythm nodes for sequencing.
+
+use super::audionode::*;
+use super::math::*;
+use super::setting::*;
+use super::signal::*;
+use super::*;
+use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Trigger threshold for trigger and reset inputs. A rising edge is detected
+/// when the input moves from at or below the threshold to above it.
+const TRIGGER_THRESHOLD: f32 = 0.5;
+
+/// Tempo clock. Emits a one-sample impulse on each tick, with `division` ticks per beat.
+/// Ticks are sample-accurate: an accumulator counts down the time to the next tick
+/// in samples, and a tick is emitted on the first sample at or after each exact tick time.
+/// After a tick, the (possibly fractional) period is added to the accumulator
+/// and the remainder is kept, so fractional tick periods alternate between
+/// the neighboring whole sample periods and the average period is exact without drift.
+/// When the tempo changes, the time to the next tick is scaled to the new tempo.
+/// The first tick is at time zero. A rising edge on the reset input (crossing 0.5 upward)
+/// restarts the clock, emitting a tick on the same sample.
+/// - Setting: tempo in BPM.
+/// - Input 0: reset
+/// - Output 0: trigger impulse (1 on a tick, 0 otherwise)
+#[derive(Clone)]
+pub struct Clock {
+    /// Tempo in beats per minute.
+    bpm: f32,
+    /// Ticks per beat.
+    division: f32,
+    /// Time to the next tick in samples.
+    remaining: f64,
+    /// Previous reset input.
+    previous: f32,
+    sample_rate: f64,
+}
+
+impl Clock {
+    /// Create a new clock with tempo `bpm` and `division` ticks per beat.
+    pub fn new(bpm: f32, division: f32) -> Self {
+        assert!(bpm > 0.0 && division > 0.0);
+        let mut node = Self {
+            bpm,
+            division,
+            remaining: 0.0,
+            previous: 0.0,
+            sample_rate: DEFAULT_SR,
+        };
+        node.reset();
+        node
+    }
+
+    /// Tempo in beats per minute.
+    #[inline]
+    pub fn bpm(&self) -> f32 {
+        self.bpm
+    }
+
+    /// Set tempo in beats per minute. The time to the next tick is scaled to the new tempo.
+    #[inline]
+    pub fn set_bpm(&mut self, bpm: f32) {
+        assert!(bpm > 0.0);
+        self.remaining *= self.bpm as f64 / bpm as f64;
+        self.bpm = bpm;
+    }
+
+    /// Ticks per beat.
+    #[inline]
+    pub fn division(&self) -> f32 {
+        self.division
+    }
+
+    /// Tick period in samples. May be fractional.
+    #[inline]
+    pub fn period(&self) -> f64 {
+        60.0 * self.sample_rate / (self.bpm as f64 * self.division as f64)
+    }
+}
+
+impl AudioNode for Clock {
+    const ID: u64 = 150;
+    type Inputs = U1;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        // The first tick is at time zero.
+        self.remaining = 0.0;
+        self.previous = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if self.previous <= TRIGGER_THRESHOLD && input[0] > TRIGGER_THRESHOLD {
+            self.remaining = 0.0;
+        }
+        self.previous = input[0];
+        // Allow for rounding error so ticks at whole sample times are not delayed.
+        let trigger = if self.remaining < 1.0e-6 {
+            self.remaining += self.period();
+            1.0
+        } else {
+            0.0
+        };
+        self.remaining -= 1.0;
+        [trigger].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Value(bpm) = setting.parameter() {
+            self.set_bpm(*bpm);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
+
+/// Maximum number of steps in a Euclidean rhythm.
+pub const EUCLID_MAX_STEPS: usize = 64;
+
+/// Distribute `pulses` onsets as evenly as possible over `steps` steps
+/// with Bjorklund's algorithm. The first step is always an onset (if there are any).
+pub fn bjorklund(pulses: usize, steps: usize) -> Vec<bool> {
+    let mut pattern = vec![false; steps];
+    bjorklund_fill(pulses, &mut pattern);
+    pattern
+}
+
+/// Write the Bjorklund rhythm of `pulses` onsets over `pattern.len()` steps into `pattern`
+/// without allocating. The algorithm keeps two kinds of groups, front groups and remainder groups,
+/// and repeatedly appends a remainder group to each front group until at most one remainder is left.
+/// All groups of a kind are identical, so one copy of each is kept at the start of `pattern`
+/// and the rhythm is expanded from them at the end.
+fn bjorklund_fill(pulses: usize, pattern: &mut [bool]) {
+    let steps = pattern.len();
+    let pulses = core::cmp::min(pulses, steps);
+    if pulses == 0 || pulses == steps {
+        pattern.fill(pulses > 0);
+        return;
+    }
+    // Start with one group per step: onsets followed by rests.
+    // The front group is at 0..a and the remainder group is at a..a + b.
+    pattern[0] = true;
+    pattern[1] = false;
+    let (mut a, mut b) = (1, 1);
+    let (mut a_count, mut b_count) = (pulses, steps - pulses);
+    while b_count > 1 {
+        let n = core::cmp::min(a_count, b_count);
+        // The new front group is the old front group followed by the remainder group,
+        // which is already in place. Copy the new remainder group after it.
+        if a_count > n {
+            pattern.copy_within(0..a, a + b);
+            (a, b) = (a + b, a);
+            (a_count, b_count) = (n, a_count - n);
+        } else {
+            if b_count > n {
+                pattern.copy_within(a..a + b, a + b);
+            }
+            a += b;
+            (a_count, b_count) = (n, b_count - n);
+        }
+    }
+    // Expand into front groups followed by remainder groups.
+    // The remainder groups are copied last first so the source is overwritten last.
+    for i in (0..b_count).rev() {
+        pattern.copy_within(a..a + b, a * a_count + i * b);
+    }
+    for i in 1..a_count {
+        pattern.copy_within(0..a, i * a);
+    }
+}
+
+/// Euclidean rhythm generator. Advances one step on each input trigger
+/// and passes the trigger through on steps that are onsets of the rhythm.
+/// The rhythm distributes `pulses` onsets as evenly as possible over `steps` steps,
+/// computed with Bjorklund's algorithm (see `bjorklund`): for example,
+/// 3 pulses over 8 steps is the tresillo `x..x..x.`.
+/// The pattern is rotated left by `rotation` steps, so rotation 1 starts the rhythm
+/// from its second step. The pattern can be changed at runtime with `set_rhythm`
+/// or `Setting::rhythm`; the step position is kept (modulo the new step count).
+/// There can be up to `EUCLID_MAX_STEPS` steps. The pattern is stored inline,
+/// so changing it does not allocate.
+/// A trigger is a rising edge of the input crossing 0.5 upward.
+/// - Setting: rhythm.
+/// - Input 0: clock trigger
+/// - Output 0: trigger impulse on onsets
+#[derive(Clone)]
+pub struct Euclid {
+    pulses: usize,
+    steps: usize,
+    rotation: usize,
+    /// Rotated pattern in the first `steps` items.
+    pattern: [bool; EUCLID_MAX_STEPS],
+    /// Index of the next step.
+    step: usize,
+    /// Previous trigger input.
+    previous: f32,
+}
+
+impl Euclid {
+    /// Create a new Euclidean rhythm with `pulses` onsets over `steps` steps
+    /// (`steps` in 1...`EUCLID_MAX_STEPS`), rotated left by `rotation` steps.
+    pub fn new(pulses: usize, steps: usize, rotation: usize) -> Self {
+        assert!(steps > 0 && steps <= EUCLID_MAX_STEPS);
+        let mut node = Self {
+            pulses: 0,
+            steps: 1,
+            rotation: 0,
+            pattern: [false; EUCLID_MAX_STEPS],
+            step: 0,
+            previous: 0.0,
+        };
+        node.set_rhythm(pulses, steps, rotation);
+        node
+    }
+
+    /// Set rhythm to `pulses` onsets over `steps` steps, rotated left by `rotation` steps.
+    /// If `steps` is not in 1...`EUCLID_MAX_STEPS`, the rhythm is not changed.
+    /// Does not allocate, so this is safe to call from the audio thread.
+    pub fn set_rhythm(&mut self, pulses: usize, steps: usize, rotation: usize) {
+        if steps == 0 || steps > EUCLID_MAX_STEPS {
+            return;
+        }
+        self.pulses = core::cmp::min(pulses, steps);
+        self.steps = steps;
+        self.rotation = rotation % steps;
+        let pattern = &mut self.pattern[..steps];
+        bjorklund_fill(pulses, pattern);
+        pattern.rotate_left(self.rotation);
+        self.step %= steps;
+    }
+
+    /// Number of onsets.
+    #[inline]
+    pub fn pulses(&self) -> usize {
+        self.pulses
+    }
+
+    /// Number of steps.
+    #[inline]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Rotation in steps.
+    #[inline]
+    pub fn rotation(&self) -> usize {
+        self.rotation
+    }
+
+    /// The rotated pattern: `true` for onsets and `false` for rests.
+    #[inline]
+    pub fn pattern(&self) -> &[bool] {
+        &self.pattern[..self.steps]
+    }
+}
+
+impl AudioNode for Euclid {
+    const ID: u64 = 151;
+    type Inputs = U1;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.step = 0;
+        self.previous = 0.0;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let edge = self.previous <= TRIGGER_THRESHOLD && input[0] > TRIGGER_THRESHOLD;
+        self.previous = input[0];
+        if !edge {
+            return [0.0].into();
+        }
+        let onset = self.pattern[self.step];
+        self.step = (self.step + 1) % self.steps;
+        [if onset { 1.0 } else { 0.0 }].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Rhythm(pulses, steps, rotation) = setting.parameter() {
+            self.set_rhythm(*pulses, *steps, *rotation);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
+
+/// Step sequencer for control values. Outputs the value of the current step,
+/// held until the next step. A trigger on the advance input moves to the next step,
+/// wrapping around after the last step, and a trigger on the reset input returns to the first step.
+/// Triggers are rising edges crossing 0.5 upward. If both inputs trigger on the same sample,
+/// the reset wins and the sequencer stays on the first step. The sequencer starts on the first step.
+///
+/// With a nonzero glide time, the output ramps linearly from its current value
+/// to the new step value over the glide time, starting on the sample of the step change.
+/// A step change during a glide starts a new glide from the current output.
+/// With zero glide (the default), the output jumps to the new value on the sample of the step change.
+/// - Allocates: the step values.
+/// - Setting: `Setting::step(index, value)` sets step values and `Setting::time(glide)` sets glide time in seconds.
+/// - Input 0: advance trigger
+/// - Input 1: reset trigger
+/// - Output 0: current step value
+#[derive(Clone)]
+pub struct StepSequencer {
+    steps: Vec<f32>,
+    /// Current step.
+    index: usize,
+    /// Glide time in seconds.
+    glide: f32,
+    /// Current output value.
+    value: f32,
+    /// Output increment per sample during a glide.
+    delta: f32,
+    /// Samples left in the current glide.
+    glide_left: usize,
+    /// Previous advance and reset inputs.
+    previous: (f32, f32),
+    sample_rate: f32,
+}
+
+impl StepSequencer {
+    /// Create a new step sequencer with step values `steps` (at least one step).
+    pub fn new(steps: &[f32]) -> Self {
+        assert!(!steps.is_empty());
+        let mut node = Self {
+            steps: steps.to_vec(),
+            index: 0,
+            glide: 0.0,
+            value: 0.0,
+            delta: 0.0,
+            glide_left: 0,
+            previous: (0.0, 0.0),
+            sample_rate: DEFAULT_SR as f32,
+        };
+        node.reset();
+        node
+    }
+
+    /// Step values.
+    #[inline]
+    pub fn steps(&self) -> &[f32] {
+        &self.steps
+    }
+
+    /// Set the value of step `index`. If it is the current step, the output moves to the new value
+    /// (gliding if glide is enabled).
+    pub fn set_step(&mut self, index: usize, value: f32) {
+        if index < self.steps.len() {
+            self.steps[index] = value;
+            if index == self.index {
+                self.start_glide();
+            }
+        }
+    }
+
+    /// Index of the current step.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Glide time in seconds.
+    #[inline]
+    pub fn glide(&self) -> f32 {
+        self.glide
+    }
+
+    /// Set glide time in seconds. Zero disables glide.
+    #[inline]
+    pub fn set_glide(&mut self, glide: f32) {
+        self.glide = max(0.0, glide);
+    }
+
+    /// Move the output toward the value of the current step.
+    fn start_glide(&mut self) {
+        let target = self.steps[self.index];
+        let samples = round(self.glide * self.sample_rate) as usize;
+        if samples == 0 {
+            self.value = target;
+            self.glide_left = 0;
+        } else {
+            self.delta = (target - self.value) / samples as f32;
+            self.glide_left = samples;
+        }
+    }
+}
+
+impl AudioNode for StepSequencer {
+    const ID: u64 = 152;
+    type Inputs = U2;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.index = 0;
+        self.value = self.steps[0];
+        self.glide_left = 0;
+        self.previous = (0.0, 0.0);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate as f32;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let advance = self.previous.0 <= TRIGGER_THRESHOLD && input[0] > TRIGGER_THRESHOLD;
+        let reset = self.previous.1 <= TRIGGER_THRESHOLD && input[1] > TRIGGER_THRESHOLD;
+        self.previous = (input[0], input[1]);
+        if reset {
+            self.index = 0;
+            self.start_glide();
+        } else if advance {
+            self.index = (self.index + 1) % self.steps.len();
+            self.start_glide();
+        }
+        if self.glide_left > 0 {
+            self.glide_left -= 1;
+            self.value = if self.glide_left == 0 {
+                self.steps[self.index]
+            } else {
+                self.value + self.delta
+            };
+        }
+        [self.value].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Step(index, value) => self.set_step(*index, *value),
+            Parameter::Time(glide) => self.set_glide(*glide),
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).combine_nonlinear(input.at(1), 0.0));
+        output
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.steps.capacity() * core::mem::size_of::<f32>()
+    }
+}
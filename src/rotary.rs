@@ -0,0 +1,257 @@
+//! Rotary speaker simulation.
+
+use super::audionode::*;
+use super::biquad::*;
+use super::math::*;
+use super::setting::*;
+use super::signal::*;
+use super::*;
+use core::marker::PhantomData;
+use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Crossover frequency between the drum and horn rotors in Hz.
+pub const LESLIE_CROSSOVER_HZ: f32 = 800.0;
+
+/// Horn rotation frequency at chorale (slow) speed in Hz.
+pub const HORN_CHORALE_HZ: f32 = 0.83;
+
+/// Horn rotation frequency at tremolo (fast) speed in Hz.
+pub const HORN_TREMOLO_HZ: f32 = 6.7;
+
+/// Drum rotation frequency at chorale (slow) speed in Hz.
+pub const DRUM_CHORALE_HZ: f32 = 0.67;
+
+/// Drum rotation frequency at tremolo (fast) speed in Hz.
+pub const DRUM_TREMOLO_HZ: f32 = 5.8;
+
+/// Time constant of horn acceleration and deceleration in seconds.
+pub const HORN_INERTIA: f32 = 0.4;
+
+/// Time constant of drum acceleration and deceleration in seconds.
+pub const DRUM_INERTIA: f32 = 2.0;
+
+/// A rotor with a Doppler delay line and amplitude modulation
+/// seen from two microphones on opposite sides.
+#[derive(Clone)]
+struct Rotor {
+    chorale: f32,
+    tremolo: f32,
+    /// Time constant of rotation speed changes in seconds.
+    inertia: f32,
+    /// Rotation frequency in Hz.
+    rate: f32,
+    /// Rotation phase in 0...1.
+    phase: f64,
+    /// Peak delay variation in seconds.
+    depth: f32,
+    /// Amplitude modulation depth in 0...1.
+    am: f32,
+    /// Rate smoothing coefficient per sample.
+    smoothing: f32,
+    line: Vec<f32>,
+    index: usize,
+    sample_rate: f32,
+}
+
+impl Rotor {
+    fn new(chorale: f32, tremolo: f32, inertia: f32, depth: f32, am: f32) -> Self {
+        Self {
+            chorale,
+            tremolo,
+            inertia,
+            rate: chorale,
+            phase: 0.0,
+            depth,
+            am,
+            smoothing: 0.0,
+            line: Vec::new(),
+            index: 0,
+            sample_rate: DEFAULT_SR as f32,
+        }
+    }
+
+    /// Target rotation frequency at `speed` in 0...1.
+    #[inline]
+    fn target(&self, speed: f32) -> f32 {
+        lerp(self.chorale, self.tremolo, clamp01(speed))
+    }
+
+    fn reset(&mut self, speed: f32) {
+        self.rate = self.target(speed);
+        self.phase = 0.0;
+        self.line.fill(0.0);
+        self.index = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate as f32;
+        self.smoothing = 1.0 - exp(-1.0 / (self.inertia * self.sample_rate));
+        // The delay line holds twice the peak variation plus room for interpolation.
+        let length = (2.0 * self.depth * self.sample_rate).ceil() as usize + 4;
+        self.line = vec![0.0; length.next_power_of_two()];
+        self.index = 0;
+    }
+
+    /// Read the delay line `delay` samples back with linear interpolation.
+    #[inline]
+    fn read(&self, delay: f32) -> f32 {
+        let mask = self.line.len() - 1;
+        let position = delay.floor();
+        let fraction = delay - position;
+        let i = self.index.wrapping_sub(position as usize) & mask;
+        let j = i.wrapping_sub(1) & mask;
+        lerp(self.line[i], self.line[j], fraction)
+    }
+
+    /// Process one sample at `speed` in 0...1. Returns left and right microphone signals.
+    #[inline]
+    fn tick(&mut self, x: f32, speed: f32) -> (f32, f32) {
+        self.rate += (self.target(speed) - self.rate) * self.smoothing;
+        self.phase += self.rate as f64 / self.sample_rate as f64;
+        self.phase -= self.phase.floor();
+        let mask = self.line.len() - 1;
+        self.index = (self.index + 1) & mask;
+        self.line[self.index] = x;
+        // Distance to each microphone follows the cosine of the rotor angle.
+        // The rotor is closest to, and loudest at, the microphone it faces.
+        let facing = cos(self.phase * f64::TAU) as f32;
+        let base = self.depth * self.sample_rate + 1.0;
+        let swing = self.depth * self.sample_rate;
+        let left = self.read(base - swing * facing) * (1.0 + self.am * facing);
+        let right = self.read(base + swing * facing) * (1.0 - self.am * facing);
+        (left, right)
+    }
+}
+
+/// Rotary speaker (Leslie) simulation. The input is split with a 2nd order
+/// crossover at `LESLIE_CROSSOVER_HZ` into a drum rotor (low band) and a horn rotor
+/// (high band). Each rotor is heard from two microphones on opposite sides, left and right:
+/// as the rotor turns, a delay line modulated by its distance to each microphone
+/// produces Doppler pitch modulation and its directivity produces amplitude modulation,
+/// both at the rotation frequency.
+///
+/// Speed 0 is chorale (slow) and speed 1 is tremolo (fast); values in between
+/// interpolate the rotation frequencies. The horn turns at `HORN_CHORALE_HZ` to
+/// `HORN_TREMOLO_HZ` and the drum at `DRUM_CHORALE_HZ` to `DRUM_TREMOLO_HZ`.
+/// When the speed changes, each rotor approaches its new rotation frequency exponentially
+/// with time constant `HORN_INERTIA` (horn) or `DRUM_INERTIA` (heavier drum),
+/// so the horn spins up and down faster than the drum. After a reset, the rotors
+/// turn at the current speed. Number of inputs is `N`, either `U1` or `U2`.
+/// - Setting: speed.
+/// - Input 0: audio
+/// - Input 1 (optional): speed in 0...1
+/// - Output 0: left audio
+/// - Output 1: right audio
+#[derive(Clone)]
+pub struct Leslie<N: Size<f32>> {
+    _marker: PhantomData<N>,
+    speed: f32,
+    horn: Rotor,
+    drum: Rotor,
+    low: Biquad<f32>,
+    high: Biquad<f32>,
+}
+
+impl<N: Size<f32>> Leslie<N> {
+    /// Create a new rotary speaker at `speed` in 0...1 (0 = chorale, 1 = tremolo).
+    pub fn new(speed: f32) -> Self {
+        let mut node = Self {
+            _marker: PhantomData,
+            speed: clamp01(speed),
+            horn: Rotor::new(HORN_CHORALE_HZ, HORN_TREMOLO_HZ, HORN_INERTIA, 0.0004, 0.5),
+            drum: Rotor::new(DRUM_CHORALE_HZ, DRUM_TREMOLO_HZ, DRUM_INERTIA, 0.0002, 0.25),
+            low: Biquad::new(),
+            high: Biquad::new(),
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node.reset();
+        node
+    }
+
+    /// Target speed in 0...1.
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set target speed in 0...1 (0 = chorale, 1 = tremolo).
+    /// The rotors accelerate or decelerate toward the new speed.
+    #[inline]
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = clamp01(speed);
+    }
+
+    /// Current rotation frequency of the horn in Hz.
+    #[inline]
+    pub fn horn_rate(&self) -> f32 {
+        self.horn.rate
+    }
+
+    /// Current rotation frequency of the drum in Hz.
+    #[inline]
+    pub fn drum_rate(&self) -> f32 {
+        self.drum.rate
+    }
+}
+
+impl<N: Size<f32>> AudioNode for Leslie<N> {
+    const ID: u64 = 148;
+    type Inputs = N;
+    type Outputs = U2;
+
+    fn reset(&mut self) {
+        self.horn.reset(self.speed);
+        self.drum.reset(self.speed);
+        self.low.reset();
+        self.high.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.horn.set_sample_rate(sample_rate);
+        self.drum.set_sample_rate(sample_rate);
+        let q = core::f32::consts::FRAC_1_SQRT_2;
+        self.low.set_coefs(BiquadCoefs::lowpass(
+            sample_rate as f32,
+            LESLIE_CROSSOVER_HZ,
+            q,
+        ));
+        self.high.set_coefs(BiquadCoefs::highpass(
+            sample_rate as f32,
+            LESLIE_CROSSOVER_HZ,
+            q,
+        ));
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 {
+            self.speed = clamp01(input[1]);
+        }
+        let x = Frame::from([input[0]]);
+        let low = self.low.tick(&x)[0];
+        let high = self.high.tick(&x)[0];
+        let (drum_left, drum_right) = self.drum.tick(low, self.speed);
+        let (horn_left, horn_right) = self.horn.tick(high, self.speed);
+        [drum_left + horn_left, drum_right + horn_right].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Value(speed) = setting.parameter() {
+            self.set_speed(*speed);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output.set(1, input.at(0).distort(0.0));
+        output
+    }
+
+    fn memory_usage(&self) -> usize {
+        (self.horn.line.capacity() + self.drum.line.capacity()) * core::mem::size_of::<f32>()
+    }
+}
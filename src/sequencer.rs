@@ -6,6 +6,7 @@ use super::math::*;
 use super::realseq::*;
 use super::shared::IdGenerator;
 use super::signal::*;
+use super::wave::*;
 use super::*;
 use core::cmp::{Eq, Ord, Ordering};
 extern crate alloc;
@@ -56,6 +57,35 @@ impl Fade {
     }
 }
 
+/// Convert musical time `beat` (in beats, starting from zero) to seconds at tempo `bpm`
+/// beats per minute. To address bars, set `beat` to bar number times beats per bar
+/// plus beat within the bar.
+///
+/// Without swing (`swing` = 0), beat `n` starts at `n * 60 / bpm` seconds.
+/// Swing delays the off-beat: within each beat, the half-beat point is moved from 0.5
+/// to `0.5 + swing / 6`, and positions in between are mapped linearly.
+/// At `swing` = 1 the off-beat falls on the last triplet of the beat.
+/// Swing is in 0...1.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// assert_eq!(beat_time(120.0, 4.0, 0.0), 2.0);
+/// assert!((beat_time(60.0, 0.5, 1.0) - 2.0 / 3.0).abs() < 1.0e-12);
+/// ```
+pub fn beat_time(bpm: f64, beat: f64, swing: f64) -> f64 {
+    assert!(bpm > 0.0);
+    let whole = floor(beat);
+    let fraction = beat - whole;
+    let pivot = 0.5 + clamp01(swing) / 6.0;
+    let swung = if fraction < 0.5 {
+        fraction * 2.0 * pivot
+    } else {
+        pivot + (fraction - 0.5) * 2.0 * (1.0 - pivot)
+    };
+    (whole + swung) * 60.0 / bpm
+}
+
 #[derive(Clone)]
 pub(crate) struct Event {
     pub unit: Box<dyn AudioUnit>,
@@ -240,6 +270,10 @@ pub struct Sequencer {
     front: Option<(Sender<Message>, Receiver<Option<Event>>)>,
     /// Whether we replay existing events after a call to `reset`.
     replay_events: bool,
+    /// Optional loop region (start time, end time) in seconds.
+    loop_region: Option<(f64, f64)>,
+    /// Intermediate output frame for blocks that contain a loop boundary.
+    loop_frame: Vec<f32>,
 }
 
 impl Clone for Sequencer {
@@ -262,6 +296,8 @@ impl Clone for Sequencer {
             tick_buffer: self.tick_buffer.clone(),
             front: None,
             replay_events: self.replay_events,
+            loop_region: self.loop_region,
+            loop_frame: self.loop_frame.clone(),
         }
     }
 }
@@ -292,6 +328,8 @@ impl Sequencer {
             tick_buffer: vec![0.0; outputs],
             front: None,
             replay_events,
+            loop_region: None,
+            loop_frame: vec![0.0; outputs],
         }
     }
 
@@ -422,6 +460,46 @@ impl Sequencer {
         )
     }
 
+    /// Add an event at musical time `beat` lasting `duration_beats` beats at tempo `bpm`.
+    /// Beats are converted to seconds with [`beat_time`] without swing:
+    /// beat `n` starts at `n * 60 / bpm` seconds.
+    /// The event has no fades. Returns the ID of the event.
+    ///
+    /// ### Example: Four On The Floor
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer::new(false, 1);
+    /// for beat in 0..4 {
+    ///     sequencer.push_at_beat(120.0, beat as f64, 0.25, Box::new(sine_hz(55.0)));
+    /// }
+    /// ```
+    pub fn push_at_beat(
+        &mut self,
+        bpm: f64,
+        beat: f64,
+        duration_beats: f64,
+        unit: Box<dyn AudioUnit>,
+    ) -> EventId {
+        self.push_at_beat_swing(bpm, 0.0, beat, duration_beats, unit)
+    }
+
+    /// Add an event at musical time `beat` lasting `duration_beats` beats at tempo `bpm`
+    /// with `swing` (in 0...1) applied to off-beats.
+    /// Start and end times are converted to seconds with [`beat_time`].
+    /// The event has no fades. Returns the ID of the event.
+    pub fn push_at_beat_swing(
+        &mut self,
+        bpm: f64,
+        swing: f64,
+        beat: f64,
+        duration_beats: f64,
+        unit: Box<dyn AudioUnit>,
+    ) -> EventId {
+        let start_time = beat_time(bpm, beat, swing);
+        let end_time = beat_time(bpm, beat + duration_beats, swing);
+        self.push(start_time, end_time, Fade::Smooth, 0.0, 0.0, unit)
+    }
+
     /// Make a change to an existing event. Only the end time and fade out time
     /// of the event may be changed. The new end time can only be used to shorten events.
     /// Edits are intended to be used with events where we do not know ahead of time
@@ -548,11 +626,119 @@ impl Sequencer {
         self.front.is_some()
     }
 
+    /// Render the arrangement offline into a wave of length `duration` seconds at `sample_rate` Hz.
+    /// Scheduled events are processed in blocks and mixed together.
+    /// Rendering starts from the current time, which is zero for a sequencer that has not been played yet.
+    /// To render again from the start, call `reset` first (this requires `replay_events`).
+    /// Sets the sample rate of the sequencer. The sequencer must not have a backend.
+    ///
+    /// Rendering is deterministic: the same schedule yields the same output,
+    /// provided the scheduled units are deterministic, for example, noise generators
+    /// have fixed seeds.
+    ///
+    /// ### Example: Render Two Notes
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer::new(false, 1);
+    /// sequencer.push(0.0, 0.5, Fade::Smooth, 0.01, 0.01, Box::new(sine_hz(440.0)));
+    /// sequencer.push(0.5, 1.0, Fade::Smooth, 0.01, 0.01, Box::new(sine_hz(660.0)));
+    /// let wave = sequencer.render(1.0, 44100.0);
+    /// assert!(wave.channels() == 1 && wave.len() == 44100);
+    /// ```
+    pub fn render(&mut self, duration: f64, sample_rate: f64) -> Wave {
+        assert!(!self.has_backend());
+        Wave::render(sample_rate, duration, self)
+    }
+
     /// Returns whether we retain past events and replay them after a reset.
     pub fn replay_events(&self) -> bool {
         self.replay_events
     }
 
+    /// Loop the time region from `start_time` to `end_time` seconds.
+    /// When playback reaches `end_time`, it jumps back to `start_time`
+    /// and all events that overlap the region are reset and replayed.
+    /// Past events are retained while looping, regardless of `replay_events`.
+    ///
+    /// Events still playing at the loop end, including any release tails
+    /// or fade outs extending past it, are cut off at the loop end.
+    /// Events that started before the loop start and are still playing
+    /// at the loop start are restarted from the beginning there.
+    /// To avoid clicks, keep events inside the region
+    /// or fade them out before the loop end.
+    /// Blocks that contain the loop end are rendered sample by sample.
+    ///
+    /// ### Example: Loop A One Second Beat
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer::new(false, 1);
+    /// sequencer.push(0.0, 0.1, Fade::Smooth, 0.01, 0.01, Box::new(sine_hz(110.0)));
+    /// sequencer.push(0.5, 0.6, Fade::Smooth, 0.01, 0.01, Box::new(sine_hz(220.0)));
+    /// sequencer.set_loop(0.0, 1.0);
+    /// let wave = sequencer.render(4.0, 44100.0);
+    /// assert!(wave.at(0, 44100 + 2205) != 0.0);
+    /// ```
+    pub fn set_loop(&mut self, start_time: f64, end_time: f64) {
+        assert!(start_time < end_time);
+        if let Some((sender, receiver)) = &mut self.front {
+            // Deallocate all past events.
+            while receiver.try_recv().is_ok() {}
+            if sender
+                .try_send(Message::Loop(Some((start_time, end_time))))
+                .is_ok()
+            {}
+        } else {
+            self.loop_region = Some((start_time, end_time));
+        }
+    }
+
+    /// Stop looping. Playback continues forward from the current time.
+    pub fn clear_loop(&mut self) {
+        if let Some((sender, receiver)) = &mut self.front {
+            // Deallocate all past events.
+            while receiver.try_recv().is_ok() {}
+            if sender.try_send(Message::Loop(None)).is_ok() {}
+        } else {
+            self.loop_region = None;
+        }
+    }
+
+    /// Returns the loop region (start time, end time), if any.
+    /// This method is not applicable to frontends.
+    pub fn loop_region(&self) -> Option<(f64, f64)> {
+        self.loop_region
+    }
+
+    /// Returns whether past events are retained. This is an internal method.
+    pub(crate) fn retain_past(&self) -> bool {
+        self.replay_events || self.loop_region.is_some()
+    }
+
+    /// Jump back to the start of the loop region if we have reached its end.
+    fn wrap_loop(&mut self) {
+        if let Some((start_time, end_time)) = self.loop_region {
+            if self.time < end_time - 0.5 * self.sample_duration {
+                return;
+            }
+            while let Some(active) = self.active.pop() {
+                self.past.push(active);
+            }
+            self.active_map.clear();
+            let mut i = 0;
+            while i < self.past.len() {
+                if self.past[i].end_time > start_time && self.past[i].start_time < end_time {
+                    let mut event = self.past.swap_remove(i);
+                    event.unit.reset();
+                    self.ready.push(event);
+                } else {
+                    i += 1;
+                }
+            }
+            self.time = start_time;
+            self.active_threshold = -f64::INFINITY;
+        }
+    }
+
     /// Get past events. This is an internal method.
     pub(crate) fn get_past_event(&mut self) -> Option<Event> {
         self.past.pop()
@@ -631,9 +817,10 @@ impl AudioUnit for Sequencer {
 
     #[inline]
     fn tick(&mut self, input: &[f32], output: &mut [f32]) {
-        if !self.replay_events {
+        if !self.retain_past() {
             while let Some(_past) = self.past.pop() {}
         }
+        self.wrap_loop();
         for channel in 0..self.outputs {
             output[channel] = 0.0;
         }
@@ -702,9 +889,24 @@ impl AudioUnit for Sequencer {
     }
 
     fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
-        if !self.replay_events {
+        if !self.retain_past() {
             while let Some(_past) = self.past.pop() {}
         }
+        self.wrap_loop();
+        if let Some((_start_time, end_time)) = self.loop_region {
+            if self.time + self.sample_duration * (size as f64 - 0.5) >= end_time {
+                // The loop end falls inside this block: render it sample by sample.
+                let mut frame = core::mem::take(&mut self.loop_frame);
+                for i in 0..size {
+                    self.tick(&[], &mut frame);
+                    for channel in 0..self.outputs {
+                        output.set_f32(channel, i, frame[channel]);
+                    }
+                }
+                self.loop_frame = frame;
+                return;
+            }
+        }
         for channel in 0..self.outputs {
             output.channel_mut(channel)[..simd_items(size)].fill(F32x::ZERO);
         }
@@ -810,6 +1012,19 @@ impl AudioUnit for Sequencer {
         self.past.reserve(DEFAULT_CAPACITY);
         self.edit_map.reserve(DEFAULT_CAPACITY);
     }
+
+    fn memory_usage(&self) -> usize {
+        // Hash maps are not counted.
+        let event_memory = |event: &Event| event.unit.footprint() + event.unit.memory_usage();
+        (self.active.capacity() + self.ready.capacity() + self.past.capacity())
+            * core::mem::size_of::<Event>()
+            + self.active.iter().map(event_memory).sum::<usize>()
+            + self.ready.iter().map(event_memory).sum::<usize>()
+            + self.past.iter().map(event_memory).sum::<usize>()
+            + self.buffer.memory_usage()
+            + (self.tick_buffer.capacity() + self.loop_frame.capacity())
+                * core::mem::size_of::<f32>()
+    }
 }
 
 #[cfg(test)]
@@ -50,6 +50,12 @@ pub enum Parameter {
     /// Subsampling period of non-audio inputs (frequency, Q and gain) in filters:
     /// the non-audio inputs are read every this many samples.
     Subsample(u32),
+    /// Set mixing matrix gain from an input channel (second field) to an output channel (first field).
+    Matrix(usize, usize, f32),
+    /// Set value of a sequencer step (first field) to the second field.
+    Step(usize, f32),
+    /// Set Euclidean rhythm `(pulses, steps, rotation)`.
+    Rhythm(usize, usize, usize),
 }
 
 /// Address specifies location to apply setting in a graph.
@@ -71,10 +77,21 @@ pub enum Address {
 /// Settings are node parameters with no dedicated inputs.
 /// Nodes inside nodes can be accessed in the setting system by including an address
 /// in the setting. Up to four levels of address are supported.
+///
+/// A setting can also carry a ramp time, in which case supporting nodes
+/// interpolate to the new value over the ramp instead of jumping to it.
+/// Ramps are supported by `Constant` (`dc`, `constant`, and the constant
+/// in arithmetic opcodes such as `mul` and `add`) for `Parameter::Value`
+/// by fixed parameter SVF filters (`lowpass_hz`, `bell_hz` etc.)
+/// for the center, Q and gain parameters,
+/// by `wet_dry` and `parallel_compress` for the mix,
+/// and by `biquad` for the coefficients.
+/// Other nodes ignore the ramp time and apply the setting instantaneously.
 #[derive(Clone, Default)]
 pub struct Setting {
     parameter: Parameter,
     address: ArrayVec<[Address; 4]>,
+    ramp: f32,
 }
 
 impl Setting {
@@ -83,6 +100,7 @@ impl Setting {
         Self {
             parameter: Parameter::Center(center),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for center and Q parameters.
@@ -90,6 +108,7 @@ impl Setting {
         Self {
             parameter: Parameter::CenterQ(center, q),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for center, Q and gain parameters.
@@ -97,6 +116,7 @@ impl Setting {
         Self {
             parameter: Parameter::CenterQGain(center, q, gain),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for constant values.
@@ -104,6 +124,15 @@ impl Setting {
         Self {
             parameter: Parameter::Value(value),
             address: ArrayVec::new(),
+            ramp: 0.0,
+        }
+    }
+    /// Create setting for a filter coefficient.
+    pub fn coefficient(coefficient: f32) -> Self {
+        Self {
+            parameter: Parameter::Coefficient(coefficient),
+            address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for biquad filter coefficients.
@@ -111,6 +140,7 @@ impl Setting {
         Self {
             parameter: Parameter::Biquad(a1, a2, b0, b1, b2),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for delay.
@@ -118,6 +148,7 @@ impl Setting {
         Self {
             parameter: Parameter::Delay(delay),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for response time.
@@ -125,6 +156,7 @@ impl Setting {
         Self {
             parameter: Parameter::Time(time),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for roughness in 0...1.
@@ -132,6 +164,7 @@ impl Setting {
         Self {
             parameter: Parameter::Roughness(roughness),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for sample-and-hold variability in 0...1.
@@ -139,6 +172,7 @@ impl Setting {
         Self {
             parameter: Parameter::Variability(variability),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for pan in -1...1.
@@ -146,6 +180,7 @@ impl Setting {
         Self {
             parameter: Parameter::Pan(pan),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for attack and release times in seconds.
@@ -153,6 +188,7 @@ impl Setting {
         Self {
             parameter: Parameter::AttackRelease(attack, release),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for oscillator initial phase in 0...1.
@@ -160,6 +196,7 @@ impl Setting {
         Self {
             parameter: Parameter::Phase(phase),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for generator seed.
@@ -167,6 +204,7 @@ impl Setting {
         Self {
             parameter: Parameter::Seed(seed),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for envelope sampling interval in seconds.
@@ -174,6 +212,7 @@ impl Setting {
         Self {
             parameter: Parameter::Interval(time),
             address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Create setting for subsampling `period` of non-audio inputs (frequency, Q and gain) for filters.
@@ -182,6 +221,32 @@ impl Setting {
         Self {
             parameter: Parameter::Subsample(period),
             address: ArrayVec::new(),
+            ramp: 0.0,
+        }
+    }
+    /// Create setting for mixing matrix gain from channel `input` to channel `output`.
+    pub fn matrix(output: usize, input: usize, gain: f32) -> Self {
+        Self {
+            parameter: Parameter::Matrix(output, input, gain),
+            address: ArrayVec::new(),
+            ramp: 0.0,
+        }
+    }
+    /// Create setting for the value of sequencer step `index`.
+    pub fn step(index: usize, value: f32) -> Self {
+        Self {
+            parameter: Parameter::Step(index, value),
+            address: ArrayVec::new(),
+            ramp: 0.0,
+        }
+    }
+    /// Create setting for a Euclidean rhythm of `pulses` onsets over `steps` steps,
+    /// rotated left by `rotation` steps.
+    pub fn rhythm(pulses: usize, steps: usize, rotation: usize) -> Self {
+        Self {
+            parameter: Parameter::Rhythm(pulses, steps, rotation),
+            address: ArrayVec::new(),
+            ramp: 0.0,
         }
     }
     /// Add indexed address to setting.
@@ -204,10 +269,20 @@ impl Setting {
         self.address.push(Address::Right);
         self
     }
+    /// Set ramp time in seconds. Supporting nodes interpolate
+    /// to the new value over the ramp; others ignore it.
+    pub fn ramp(mut self, ramp_time: f32) -> Self {
+        self.ramp = ramp_time;
+        self
+    }
     /// Access parameter.
     pub fn parameter(&self) -> &Parameter {
         &self.parameter
     }
+    /// Ramp time in seconds. Zero means the setting is applied instantaneously.
+    pub fn ramp_time(&self) -> f32 {
+        self.ramp
+    }
     /// Get the next level of address. This is used by structural nodes.
     pub fn direction(&self) -> Address {
         if self.address.is_empty() {
@@ -314,4 +389,7 @@ impl<X: AudioNode> AudioNode for SettingListener<X> {
         self.receive_settings();
         self.x.route(input, frequency)
     }
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
 }
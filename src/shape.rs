@@ -1,13 +1,42 @@
 //! Waveshaping components.
 
 use super::audionode::*;
+use super::biquad::*;
 use super::buffer::*;
 use super::math::*;
+use super::setting::*;
 use super::signal::*;
 use super::*;
+use core::marker::PhantomData;
 use numeric_array::typenum::*;
 
 /// A waveshaper: some kind of nonlinearity. It may have a state.
+///
+/// Implementing `Shape` is the easiest way to write a custom mono processor:
+/// wrapped in `shape`, it gets block processing and frequency response routing for free.
+/// Only `shape` is required. The default `simd` calls `shape` for each lane in order,
+/// so stateful shapes see samples in sequence; override `simd` with a vectorized version
+/// if the shape is stateless. Any state must be restored in `reset`.
+///
+/// ### Example: Custom Saturator
+/// ```
+/// use fundsp::hacker32::*;
+///
+/// #[derive(Clone)]
+/// struct Saturate(f32);
+///
+/// impl Shape for Saturate {
+///     fn shape(&mut self, input: f32) -> f32 {
+///         input / (1.0 + abs(input * self.0))
+///     }
+///     fn simd(&mut self, input: F32x) -> F32x {
+///         input / (F32x::ONE + (input * self.0).abs())
+///     }
+/// }
+///
+/// let mut node = shape(Saturate(2.0));
+/// assert_eq!(node.filter_mono(1.0), 1.0 / 3.0);
+/// ```
 pub trait Shape: Clone + Sync + Send {
     /// Process a single sample.
     fn shape(&mut self, input: f32) -> f32;
@@ -43,6 +72,30 @@ impl<S: Fn(f32) -> f32 + Clone + Sync + Send> Shape for ShapeFn<S> {
     }
 }
 
+/// Memoryless waveshaper from a pair of closures:
+/// the first processes a single sample and the second a SIMD element.
+/// The two closures must compute the same function.
+#[derive(Clone)]
+pub struct ShapeSimdFn<S, V>(pub S, pub V)
+where
+    S: Fn(f32) -> f32 + Clone + Sync + Send,
+    V: Fn(F32x) -> F32x + Clone + Sync + Send;
+
+impl<S, V> Shape for ShapeSimdFn<S, V>
+where
+    S: Fn(f32) -> f32 + Clone + Sync + Send,
+    V: Fn(F32x) -> F32x + Clone + Sync + Send,
+{
+    #[inline]
+    fn shape(&mut self, input: f32) -> f32 {
+        self.0(input)
+    }
+    #[inline]
+    fn simd(&mut self, input: F32x) -> F32x {
+        self.1(input)
+    }
+}
+
 /// Clamp signal multiplied by the hardness parameter to -1...1.
 #[derive(Clone)]
 pub struct Clip(pub f32);
@@ -155,6 +208,42 @@ impl Shape for SoftCrush {
     }
 }
 
+/// Triangle wavefolder. The signal is multiplied by the gain parameter
+/// and then reflected back into -1...1 whenever it exceeds the range.
+/// The shape is odd symmetric and generates odd harmonics.
+#[derive(Clone)]
+pub struct Fold(pub f32);
+
+impl Shape for Fold {
+    #[inline]
+    fn shape(&mut self, input: f32) -> f32 {
+        let x = (input * self.0 + 1.0) * 0.25;
+        4.0 * abs(x - floor(x + 0.5)) - 1.0
+    }
+    #[inline]
+    fn simd(&mut self, input: F32x) -> F32x {
+        let x = (input * self.0 + F32x::ONE) * 0.25;
+        (x - (x + 0.5).floor()).abs() * 4.0 - F32x::ONE
+    }
+}
+
+/// Asymmetric `tanh` distortion with separate hardness values
+/// for the positive (first argument) and negative (second argument) halves.
+/// Unequal hardness values give the tube-like even harmonics of an asymmetric curve.
+#[derive(Clone)]
+pub struct Asymmetric(pub f32, pub f32);
+
+impl Shape for Asymmetric {
+    #[inline]
+    fn shape(&mut self, input: f32) -> f32 {
+        if input >= 0.0 {
+            (input * self.0).tanh()
+        } else {
+            (input * self.1).tanh()
+        }
+    }
+}
+
 /// Adaptive normalizing distortion with smoothing timescale and inner shape as parameters.
 /// Smoothing timescale is specified in seconds.
 /// It is the time it takes for level estimation to move halfway to a new level.
@@ -247,3 +336,364 @@ impl<S: Shape> AudioNode for Shaper<S> {
         output
     }
 }
+
+/// Bitcrusher. Quantizes amplitude to integer multiples of the step size `2^(1 - bits)`,
+/// which gives `2^bits + 1` levels in -1...1. Fractional bit depths are allowed.
+/// Number of inputs is `N`, either `U1` or `U2`.
+/// - Setting: bit depth.
+/// - Input 0: input signal
+/// - Input 1 (optional): bit depth
+/// - Output 0: quantized signal
+#[derive(Clone)]
+pub struct Bitcrush<N: Size<f32>> {
+    _marker: PhantomData<N>,
+    bits: f32,
+    step: f32,
+}
+
+impl<N: Size<f32>> Bitcrush<N> {
+    /// Create new bitcrusher with bit depth `bits` (`bits` > 0).
+    pub fn new(bits: f32) -> Self {
+        let mut node = Self {
+            _marker: PhantomData,
+            bits: 0.0,
+            step: 0.0,
+        };
+        node.set_bits(bits);
+        node
+    }
+
+    /// Bit depth.
+    pub fn bits(&self) -> f32 {
+        self.bits
+    }
+
+    /// Set bit depth (`bits` > 0).
+    pub fn set_bits(&mut self, bits: f32) {
+        self.bits = bits;
+        self.step = exp2(1.0 - bits);
+    }
+
+    /// Quantization step size.
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+}
+
+impl<N: Size<f32>> AudioNode for Bitcrush<N> {
+    const ID: u64 = 102;
+    type Inputs = N;
+    type Outputs = U1;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 && input[1] != self.bits {
+            self.set_bits(input[1]);
+        }
+        [round(input[0] / self.step) * self.step].into()
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        if N::USIZE > 1 {
+            for i in 0..size {
+                if input.at_f32(1, i) != self.bits {
+                    self.set_bits(input.at_f32(1, i));
+                }
+                output.set_f32(0, i, round(input.at_f32(0, i) / self.step) * self.step);
+            }
+        } else {
+            let step = F32x::splat(self.step);
+            for i in 0..full_simd_items(size) {
+                output.set(0, i, (input.at(0, i) / step).round() * step);
+            }
+            self.process_remainder(size, input, output);
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Value(bits) = setting.parameter() {
+            self.set_bits(*bits);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
+
+/// Sample rate reducer. Samples the input every `factor` samples
+/// and holds the value in between. Fractional factors are allowed.
+/// No anti-aliasing is applied by design: frequencies above half the reduced
+/// sample rate fold back into the audible range, which is the point of the effect.
+/// Number of inputs is `N`, either `U1` or `U2`.
+/// - Setting: decimation factor.
+/// - Input 0: input signal
+/// - Input 1 (optional): decimation factor (minimum 1)
+/// - Output 0: decimated signal
+#[derive(Clone)]
+pub struct Downsample<N: Size<f32>> {
+    _marker: PhantomData<N>,
+    factor: f32,
+    phase: f32,
+    value: f32,
+}
+
+impl<N: Size<f32>> Downsample<N> {
+    /// Create new sample rate reducer with decimation `factor` (`factor` >= 1).
+    pub fn new(factor: f32) -> Self {
+        let mut node = Self {
+            _marker: PhantomData,
+            factor: 1.0,
+            phase: 0.0,
+            value: 0.0,
+        };
+        node.set_factor(factor);
+        node.reset();
+        node
+    }
+
+    /// Decimation factor.
+    pub fn factor(&self) -> f32 {
+        self.factor
+    }
+
+    /// Set decimation factor. Factors below 1 are clamped to 1.
+    pub fn set_factor(&mut self, factor: f32) {
+        self.factor = max(factor, 1.0);
+    }
+}
+
+impl<N: Size<f32>> AudioNode for Downsample<N> {
+    const ID: u64 = 103;
+    type Inputs = N;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        // Sample the first input immediately.
+        self.phase = 1.0;
+        self.value = 0.0;
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 && input[1] != self.factor {
+            self.set_factor(input[1]);
+        }
+        if self.phase >= 1.0 {
+            self.value = input[0];
+            self.phase -= floor(self.phase);
+        }
+        self.phase += 1.0 / self.factor;
+        [self.value].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Value(factor) = setting.parameter() {
+            self.set_factor(*factor);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
+
+/// `tanh` saturation with automatic gain compensation.
+/// The input is multiplied by the drive amount before `tanh` and the output
+/// is multiplied by a compensation gain. The gain is chosen so that a full scale
+/// sine wave (amplitude 1) keeps its RMS level at any drive:
+/// `gain = sqrt(1/2) / rms(tanh(amount * sin(t)))`.
+/// At low drive the gain approaches `1 / amount`, so the node is nearly transparent,
+/// and at high drive it approaches `sqrt(1/2)` as the output becomes a square wave.
+/// Quieter inputs come out quieter than they went in, as with any saturator.
+/// The gain is computed numerically whenever the drive amount changes.
+/// Number of inputs is `N`, either `U1` or `U2`.
+/// - Setting: drive amount.
+/// - Input 0: input signal
+/// - Input 1 (optional): drive amount (minimum 0.001)
+/// - Output 0: saturated signal
+#[derive(Clone)]
+pub struct Drive<N: Size<f32>> {
+    _marker: PhantomData<N>,
+    amount: f32,
+    gain: f32,
+}
+
+impl<N: Size<f32>> Drive<N> {
+    /// Create new compensated `tanh` drive with drive `amount` (`amount` > 0).
+    pub fn new(amount: f32) -> Self {
+        let mut node = Self {
+            _marker: PhantomData,
+            amount: 0.0,
+            gain: 0.0,
+        };
+        node.set_amount(amount);
+        node
+    }
+
+    /// Drive amount.
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    /// Set drive amount. Amounts below 0.001 are clamped to 0.001.
+    pub fn set_amount(&mut self, amount: f32) {
+        self.amount = max(amount, 0.001);
+        // Mean square of tanh(amount * sin(t)) over a quarter period, using the midpoint rule.
+        const POINTS: usize = 32;
+        let amount = self.amount as f64;
+        let mut sum = 0.0;
+        for i in 0..POINTS {
+            let t = (i as f64 + 0.5) / POINTS as f64 * f64::PI * 0.5;
+            sum += squared(tanh(amount * sin(t)));
+        }
+        self.gain = sqrt(0.5 / (sum / POINTS as f64)) as f32;
+    }
+
+    /// Output compensation gain.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+}
+
+impl<N: Size<f32>> AudioNode for Drive<N> {
+    const ID: u64 = 138;
+    type Inputs = N;
+    type Outputs = U1;
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        if N::USIZE > 1 && input[1] != self.amount {
+            self.set_amount(input[1]);
+        }
+        [tanh(input[0] * self.amount) * self.gain].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Value(amount) = setting.parameter() {
+            self.set_amount(*amount);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
+
+/// Saturation drive of the exciter harmonic generator.
+const EXCITER_DRIVE: f32 = 4.0;
+
+/// Harmonic exciter. The input is high-passed at the crossover frequency
+/// and the high band is saturated to generate harmonics: `tanh` adds odd harmonics
+/// and its square adds even harmonics. The result is high-passed again at the crossover
+/// frequency, which removes DC and low intermodulation products, and added to the dry
+/// signal with gain `amount`. Both highpass filters are 2nd order Butterworth.
+/// Content below the crossover frequency passes through unchanged.
+/// Crossover frequencies between 2 kHz and 6 kHz add "air"; 3 kHz is a good default.
+/// - Setting: crossover frequency with `Setting::center`, amount with `Setting::value`.
+/// - Input 0: input signal
+/// - Output 0: excited signal
+#[derive(Clone)]
+pub struct Exciter {
+    /// Crossover frequency in Hz.
+    frequency: f32,
+    /// Amount of generated harmonics mixed into the output.
+    amount: f32,
+    /// Sidechain highpass before the saturator.
+    pre: Biquad<f32>,
+    /// Highpass after the saturator.
+    post: Biquad<f32>,
+    sample_rate: f64,
+}
+
+impl Exciter {
+    /// Create a new exciter with crossover `frequency` in Hz and gain `amount` (`amount` >= 0).
+    pub fn new(frequency: f32, amount: f32) -> Self {
+        let mut node = Self {
+            frequency,
+            amount,
+            pre: Biquad::new(),
+            post: Biquad::new(),
+            sample_rate: DEFAULT_SR,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Crossover frequency in Hz.
+    #[inline]
+    pub fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    /// Set crossover frequency in Hz.
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+        let coefs = BiquadCoefs::highpass(
+            self.sample_rate as f32,
+            frequency,
+            core::f32::consts::FRAC_1_SQRT_2,
+        );
+        self.pre.set_coefs(coefs);
+        self.post.set_coefs(coefs);
+    }
+
+    /// Amount of generated harmonics.
+    #[inline]
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    /// Set amount of generated harmonics (`amount` >= 0).
+    #[inline]
+    pub fn set_amount(&mut self, amount: f32) {
+        self.amount = amount;
+    }
+}
+
+impl AudioNode for Exciter {
+    const ID: u64 = 147;
+    type Inputs = U1;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.pre.reset();
+        self.post.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.pre.set_sample_rate(sample_rate);
+        self.post.set_sample_rate(sample_rate);
+        self.set_frequency(self.frequency);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let high = self.pre.tick(input)[0];
+        let saturated = tanh(high * EXCITER_DRIVE);
+        let harmonics = (saturated + 0.5 * saturated * saturated) / EXCITER_DRIVE;
+        let harmonics = self.post.tick(&[harmonics].into())[0];
+        [input[0] + harmonics * self.amount].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Center(frequency) => self.set_frequency(*frequency),
+            Parameter::Value(amount) => self.set_amount(*amount),
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).distort(0.0));
+        output
+    }
+}
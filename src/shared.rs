@@ -3,9 +3,14 @@
 use super::audionode::*;
 use super::buffer::*;
 use super::combinator::*;
+use super::follow::*;
+use super::setting::*;
 use super::signal::*;
 use super::*;
-use core::sync::atomic::{AtomicU32, Ordering};
+use core::marker::PhantomData;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::{fence, AtomicU32, Ordering};
 
 use numeric_array::typenum::*;
 extern crate alloc;
@@ -41,6 +46,27 @@ impl Atomic for f32 {
     }
 }
 
+#[cfg(target_has_atomic = "64")]
+impl Atomic for f64 {
+    type Storage = AtomicU64;
+
+    #[inline]
+    fn storage(t: Self) -> Self::Storage {
+        AtomicU64::from(t.to_bits())
+    }
+
+    #[inline]
+    fn store(stored: &Self::Storage, t: Self) {
+        stored.store(t.to_bits(), Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn get_stored(stored: &Self::Storage) -> Self {
+        let u = stored.load(Ordering::Relaxed);
+        f64::from_bits(u)
+    }
+}
+
 /// A shared float variable that can be accessed from multiple threads.
 #[derive(Default, Clone)]
 pub struct Shared {
@@ -80,6 +106,56 @@ impl Shared {
     }
 }
 
+/// A shared integer counter that can be accessed from multiple threads.
+/// The count saturates at `u32::MAX`.
+#[derive(Default, Clone)]
+pub struct SharedCounter {
+    value: Arc<AtomicU32>,
+}
+
+impl SharedCounter {
+    #[inline]
+    pub fn new(value: u32) -> Self {
+        Self {
+            value: Arc::new(AtomicU32::new(value)),
+        }
+    }
+
+    /// Get reference to underlying atomic.
+    #[inline]
+    pub fn get_shared(&self) -> &Arc<AtomicU32> {
+        &self.value
+    }
+
+    /// Add `count` to the counter, saturating at `u32::MAX`.
+    #[inline]
+    pub fn add(&self, count: u32) {
+        let _ = self
+            .value
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |value| {
+                Some(value.saturating_add(count))
+            });
+    }
+
+    /// Set the count. Synonymous with `set`.
+    #[inline]
+    pub fn set_value(&self, value: u32) {
+        self.value.store(value, Ordering::Relaxed)
+    }
+
+    /// Set the count. Synonymous with `set_value`.
+    #[inline]
+    pub fn set(&self, value: u32) {
+        self.value.store(value, Ordering::Relaxed)
+    }
+
+    /// Get the count.
+    #[inline]
+    pub fn value(&self) -> u32 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
 /// Outputs the value of a shared variable.
 #[derive(Default, Clone)]
 pub struct Var {
@@ -187,6 +263,467 @@ where
     }
 }
 
+/// Outputs the value of a shared variable smoothed on the audio thread.
+/// Response times are halfway response times in seconds,
+/// with separate times for rising and falling segments.
+/// The first output sample is the current value of the variable.
+/// Setting: response time or attack/release times.
+#[derive(Clone)]
+pub struct VarSmooth {
+    value: Arc<AtomicU32>,
+    follow: AFollow<f64>,
+}
+
+impl VarSmooth {
+    pub fn new(shared: &Shared, attack_time: f32, release_time: f32) -> Self {
+        Self {
+            value: Arc::clone(shared.get_shared()),
+            follow: AFollow::new(attack_time as f64, release_time as f64),
+        }
+    }
+
+    /// Attack time in seconds.
+    pub fn attack_time(&self) -> f32 {
+        self.follow.attack_time() as f32
+    }
+
+    /// Release time in seconds.
+    pub fn release_time(&self) -> f32 {
+        self.follow.release_time() as f32
+    }
+}
+
+impl AudioNode for VarSmooth {
+    const ID: u64 = 110;
+
+    type Inputs = U0;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        self.follow.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.follow.set_sample_rate(sample_rate);
+    }
+
+    #[inline]
+    fn tick(&mut self, _input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.follow.tick(&[f32::get_stored(&self.value)].into())
+    }
+
+    fn process(&mut self, size: usize, _input: &BufferRef, output: &mut BufferMut) {
+        let target = [f32::get_stored(&self.value)].into();
+        for i in 0..size {
+            output.set_f32(0, i, self.follow.tick(&target)[0]);
+        }
+    }
+
+    fn set(&mut self, setting: Setting) {
+        match setting.parameter() {
+            Parameter::Time(time) => self.follow.set_time(*time as f64, *time as f64),
+            Parameter::AttackRelease(attack, release) => {
+                self.follow.set_time(*attack as f64, *release as f64)
+            }
+            _ => (),
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Generator(0.0).route(input, self.outputs())
+    }
+}
+
+/// A shared double precision float variable that can be accessed from multiple threads.
+/// Only available on targets with native 64-bit atomics (`target_has_atomic = "64"`).
+/// On other targets, use `Shared` or wrap an `f64` in a lock
+/// or in an atomic from the `portable-atomic` crate.
+#[cfg(target_has_atomic = "64")]
+#[derive(Default, Clone)]
+pub struct SharedF64 {
+    value: Arc<AtomicU64>,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl SharedF64 {
+    #[inline]
+    pub fn new(value: f64) -> Self {
+        Self {
+            value: Arc::new(f64::storage(value)),
+        }
+    }
+
+    /// Get reference to underlying atomic.
+    #[inline]
+    pub fn get_shared(&self) -> &Arc<AtomicU64> {
+        &self.value
+    }
+
+    /// Set the value of this variable. Synonymous with `set`.
+    #[inline]
+    pub fn set_value(&self, value: f64) {
+        f64::store(&self.value, value)
+    }
+
+    /// Set the value of this variable. Synonymous with `set_value`.
+    #[inline]
+    pub fn set(&self, value: f64) {
+        f64::store(&self.value, value)
+    }
+
+    /// Get the value of this variable.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        f64::get_stored(&self.value)
+    }
+}
+
+/// Outputs the value of a shared double precision variable.
+/// The output is rounded to single precision.
+#[cfg(target_has_atomic = "64")]
+#[derive(Default, Clone)]
+pub struct VarF64 {
+    value: Arc<AtomicU64>,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl VarF64 {
+    #[inline]
+    pub fn new(shared: &SharedF64) -> Self {
+        Self {
+            value: Arc::clone(shared.get_shared()),
+        }
+    }
+
+    /// Set the value of this variable.
+    #[inline]
+    pub fn set_value(&self, value: f64) {
+        f64::store(&self.value, value)
+    }
+
+    /// Get the value of this variable.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        f64::get_stored(&self.value)
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl AudioNode for VarF64 {
+    const ID: u64 = 106;
+
+    type Inputs = U0;
+    type Outputs = U1;
+
+    #[inline]
+    fn tick(&mut self, _: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let sample = self.value() as f32;
+        [sample].into()
+    }
+
+    fn process(&mut self, size: usize, _input: &BufferRef, output: &mut BufferMut) {
+        let sample = self.value() as f32;
+        output.channel_mut(0)[..simd_items(size)].fill(F32x::splat(sample));
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut signal = SignalFrame::new(self.outputs());
+        signal.set(0, Signal::Value(self.value()));
+        signal
+    }
+}
+
+/// Outputs the value of a shared double precision variable mapped through a function.
+/// The function receives the full precision value.
+#[cfg(target_has_atomic = "64")]
+#[derive(Default, Clone)]
+pub struct VarFnF64<F, R>
+where
+    F: Clone + Fn(f64) -> R + Send + Sync,
+    R: ConstantFrame<Sample = f32>,
+    R::Size: Size<f32>,
+{
+    value: Arc<AtomicU64>,
+    f: F,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl<F, R> VarFnF64<F, R>
+where
+    F: Clone + Fn(f64) -> R + Send + Sync,
+    R: ConstantFrame<Sample = f32>,
+    R::Size: Size<f32>,
+{
+    pub fn new(shared: &SharedF64, f: F) -> Self {
+        Self {
+            value: Arc::clone(shared.get_shared()),
+            f,
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl<F, R> AudioNode for VarFnF64<F, R>
+where
+    F: Clone + Fn(f64) -> R + Send + Sync,
+    R: ConstantFrame<Sample = f32>,
+    R::Size: Size<f32>,
+{
+    const ID: u64 = 107;
+
+    type Inputs = U0;
+    type Outputs = R::Size;
+
+    #[inline]
+    fn tick(&mut self, _input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        (self.f)(f64::get_stored(&self.value)).frame()
+    }
+
+    fn process(&mut self, size: usize, _input: &BufferRef, output: &mut BufferMut) {
+        let frame = (self.f)(f64::get_stored(&self.value)).frame();
+        for channel in 0..self.outputs() {
+            output.channel_mut(channel)[..simd_items(size)].fill(F32x::splat(frame[channel]));
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        super::signal::Routing::Generator(0.0).route(input, self.outputs())
+    }
+}
+
+/// An array of `N` shared float variables that can be accessed from multiple threads.
+/// Each element is atomic on its own: a reader may observe some elements
+/// of a concurrent update but not others. If the elements must be read
+/// as a consistent group, use `SharedSnapshot` instead.
+#[derive(Clone)]
+pub struct SharedArray<N: Size<f32>> {
+    values: Arc<Vec<AtomicU32>>,
+    _marker: PhantomData<N>,
+}
+
+impl<N: Size<f32>> SharedArray<N> {
+    pub fn new(values: Frame<f32, N>) -> Self {
+        Self {
+            values: Arc::new(values.iter().map(|x| f32::storage(*x)).collect()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get reference to underlying atomics.
+    #[inline]
+    pub fn get_shared(&self) -> &Arc<Vec<AtomicU32>> {
+        &self.values
+    }
+
+    /// Set the value of element `i`.
+    #[inline]
+    pub fn set(&self, i: usize, value: f32) {
+        f32::store(&self.values[i], value)
+    }
+
+    /// Get the value of element `i`.
+    #[inline]
+    pub fn value(&self, i: usize) -> f32 {
+        f32::get_stored(&self.values[i])
+    }
+
+    /// Set the values of all elements. Elements are stored one at a time.
+    #[inline]
+    pub fn set_values(&self, values: &Frame<f32, N>) {
+        for (stored, x) in self.values.iter().zip(values.iter()) {
+            f32::store(stored, *x);
+        }
+    }
+
+    /// Get the values of all elements. Elements are loaded one at a time.
+    #[inline]
+    pub fn values(&self) -> Frame<f32, N> {
+        Frame::generate(|i| f32::get_stored(&self.values[i]))
+    }
+}
+
+/// Outputs the values of a shared array, one element per channel.
+#[derive(Clone)]
+pub struct VarArray<N: Size<f32>> {
+    shared: SharedArray<N>,
+}
+
+impl<N: Size<f32>> VarArray<N> {
+    pub fn new(shared: &SharedArray<N>) -> Self {
+        Self {
+            shared: shared.clone(),
+        }
+    }
+}
+
+impl<N: Size<f32>> AudioNode for VarArray<N> {
+    const ID: u64 = 108;
+
+    type Inputs = U0;
+    type Outputs = N;
+
+    #[inline]
+    fn tick(&mut self, _input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.shared.values()
+    }
+
+    fn process(&mut self, size: usize, _input: &BufferRef, output: &mut BufferMut) {
+        for channel in 0..self.outputs() {
+            output.channel_mut(channel)[..simd_items(size)]
+                .fill(F32x::splat(self.shared.value(channel)));
+        }
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut signal = SignalFrame::new(self.outputs());
+        for channel in 0..self.outputs() {
+            signal.set(channel, Signal::Value(self.shared.value(channel).to_f64()));
+        }
+        signal
+    }
+}
+
+struct SnapshotStorage {
+    /// Sequence number. It is odd while an update is in progress.
+    sequence: AtomicU32,
+    values: Vec<AtomicU32>,
+}
+
+/// An array of `N` shared float variables that are updated and read as a group.
+/// Consistency is guaranteed with a sequence lock: writers are serialized,
+/// and a read that overlaps an update is detected and retried.
+#[derive(Clone)]
+pub struct SharedSnapshot<N: Size<f32>> {
+    storage: Arc<SnapshotStorage>,
+    _marker: PhantomData<N>,
+}
+
+impl<N: Size<f32>> SharedSnapshot<N> {
+    pub fn new(values: Frame<f32, N>) -> Self {
+        Self {
+            storage: Arc::new(SnapshotStorage {
+                sequence: AtomicU32::new(0),
+                values: values.iter().map(|x| f32::storage(*x)).collect(),
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the values of all elements. Readers see either all or none of the new values.
+    /// Waits for any concurrent writer to finish first.
+    pub fn set_values(&self, values: &Frame<f32, N>) {
+        let storage = &*self.storage;
+        let mut sequence = storage.sequence.load(Ordering::Relaxed);
+        loop {
+            if sequence & 1 == 0 {
+                match storage.sequence.compare_exchange_weak(
+                    sequence,
+                    sequence.wrapping_add(1),
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => sequence = current,
+                }
+            } else {
+                core::hint::spin_loop();
+                sequence = storage.sequence.load(Ordering::Relaxed);
+            }
+        }
+        fence(Ordering::Release);
+        for (stored, x) in storage.values.iter().zip(values.iter()) {
+            f32::store(stored, *x);
+        }
+        storage
+            .sequence
+            .store(sequence.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Attempt to read a consistent snapshot of all elements.
+    /// Returns `None` if an update was in progress. Never blocks.
+    pub fn try_values(&self) -> Option<Frame<f32, N>> {
+        let storage = &*self.storage;
+        let before = storage.sequence.load(Ordering::Acquire);
+        if before & 1 == 1 {
+            return None;
+        }
+        let values = Frame::generate(|i| f32::get_stored(&storage.values[i]));
+        fence(Ordering::Acquire);
+        let after = storage.sequence.load(Ordering::Relaxed);
+        if before == after {
+            Some(values)
+        } else {
+            None
+        }
+    }
+
+    /// Read a consistent snapshot of all elements, waiting for any update in progress.
+    /// Prefer `try_values` in real-time contexts.
+    pub fn values(&self) -> Frame<f32, N> {
+        loop {
+            if let Some(values) = self.try_values() {
+                return values;
+            }
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Outputs a consistent snapshot of a shared snapshot array, one element per channel.
+/// If an update is in progress, the previous snapshot is output instead,
+/// so the audio thread never waits.
+#[derive(Clone)]
+pub struct VarSnapshot<N: Size<f32>> {
+    shared: SharedSnapshot<N>,
+    values: Frame<f32, N>,
+}
+
+impl<N: Size<f32>> VarSnapshot<N> {
+    pub fn new(shared: &SharedSnapshot<N>) -> Self {
+        Self {
+            shared: shared.clone(),
+            values: shared.values(),
+        }
+    }
+
+    /// Fetch the latest consistent snapshot, if available.
+    #[inline]
+    fn update(&mut self) {
+        if let Some(values) = self.shared.try_values() {
+            self.values = values;
+        }
+    }
+}
+
+impl<N: Size<f32>> AudioNode for VarSnapshot<N> {
+    const ID: u64 = 109;
+
+    type Inputs = U0;
+    type Outputs = N;
+
+    #[inline]
+    fn tick(&mut self, _input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.update();
+        self.values.clone()
+    }
+
+    fn process(&mut self, size: usize, _input: &BufferRef, output: &mut BufferMut) {
+        self.update();
+        for channel in 0..self.outputs() {
+            output.channel_mut(channel)[..simd_items(size)].fill(F32x::splat(self.values[channel]));
+        }
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        self.update();
+        let mut signal = SignalFrame::new(self.outputs());
+        for channel in 0..self.outputs() {
+            signal.set(channel, Signal::Value(self.values[channel].to_f64()));
+        }
+        signal
+    }
+}
+
 /// Store present stream time to a shared variable.
 #[derive(Clone)]
 pub struct Timer {
@@ -21,6 +21,14 @@ pub enum Signal {
 }
 
 impl Signal {
+    /// Latency of the signal in samples, if known.
+    pub fn latency(&self) -> Option<f64> {
+        match self {
+            Signal::Latency(latency) | Signal::Response(_, latency) => Some(*latency),
+            _ => None,
+        }
+    }
+
     /// Filter signal using the frequency response function `filter`
     /// while adding extra `latency`. Latency is measured in samples.
     pub fn filter(&self, latency: f64, filter: impl Fn(Complex64) -> Complex64) -> Signal {
@@ -0,0 +1,35 @@
+//! Type-level channel counts shared by all preludes.
+//!
+//! The aliases `U0`...`U128` are re-exported from `typenum` here, in one place,
+//! and the preludes re-export this module. For larger counts,
+//! use the generic alias `U<N>` or the [`channels!`](crate::channels) macro,
+//! which work for any count.
+
+pub use numeric_array::typenum::U;
+pub use numeric_array::typenum::{
+    U0, U1, U10, U100, U101, U102, U103, U104, U105, U106, U107, U108, U109, U11, U110, U111, U112,
+    U113, U114, U115, U116, U117, U118, U119, U12, U120, U121, U122, U123, U124, U125, U126, U127,
+    U128, U13, U14, U15, U16, U17, U18, U19, U2, U20, U21, U22, U23, U24, U25, U26, U27, U28, U29,
+    U3, U30, U31, U32, U33, U34, U35, U36, U37, U38, U39, U4, U40, U41, U42, U43, U44, U45, U46,
+    U47, U48, U49, U5, U50, U51, U52, U53, U54, U55, U56, U57, U58, U59, U6, U60, U61, U62, U63,
+    U64, U65, U66, U67, U68, U69, U7, U70, U71, U72, U73, U74, U75, U76, U77, U78, U79, U8, U80,
+    U81, U82, U83, U84, U85, U86, U87, U88, U89, U9, U90, U91, U92, U93, U94, U95, U96, U97, U98,
+    U99,
+};
+
+/// Type-level channel count from an integer literal.
+/// `channels!(N)` is the same type as `UN`, for example, `channels!(90)` is `U90`.
+/// Unlike the `UN` aliases, it is not limited to 128.
+///
+/// ### Example: 200-Channel Multipass
+/// ```
+/// use fundsp::hacker::*;
+/// let node = multipass::<fundsp::channels!(200)>();
+/// assert_eq!(node.inputs(), 200);
+/// ```
+#[macro_export]
+macro_rules! channels {
+    ($n:literal) => {
+        $crate::typenum::U<$n>
+    };
+}
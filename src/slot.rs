@@ -3,6 +3,7 @@
 use super::audiounit::*;
 use super::buffer::*;
 use super::math::*;
+use super::net::Net;
 use super::sequencer::*;
 use super::signal::*;
 use super::*;
@@ -89,6 +90,51 @@ impl Slot {
     }
 }
 
+/// Frontend for morphing between whole networks, for example, for scene changes in live performance.
+/// The backend is a [`SlotBackend`]. Each change equal power crossfades
+/// from the current network to the new one, after which the old network is dropped.
+/// While a crossfade is in progress both networks are processed, which doubles
+/// their CPU load for the duration of the fade.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// let (mut crossfader, mut backend) = NetCrossfader::new(Net::wrap(Box::new(sine_hz(110.0))));
+/// crossfader.crossfade_to(Net::wrap(Box::new(saw_hz(110.0))), 2.0);
+/// backend.get_mono();
+/// ```
+pub struct NetCrossfader {
+    slot: Slot,
+}
+
+impl NetCrossfader {
+    /// Create a new crossfader starting from network `net`.
+    /// The number of inputs and outputs is taken from the initial network.
+    /// Returns (frontend, backend) pair.
+    pub fn new(net: Net) -> (NetCrossfader, SlotBackend) {
+        let (slot, backend) = Slot::new(Box::new(net));
+        (NetCrossfader { slot }, backend)
+    }
+
+    /// Crossfade to network `net` over `fade_time` seconds using an equal power fade.
+    /// The new network must have the same number of inputs and outputs.
+    /// If a crossfade is already in progress, then the latest network
+    /// starts fading in after it completes.
+    pub fn crossfade_to(&mut self, net: Net, fade_time: f64) {
+        self.slot.set(Fade::Power, fade_time, Box::new(net));
+    }
+
+    /// Number of inputs.
+    pub fn inputs(&self) -> usize {
+        self.slot.inputs()
+    }
+
+    /// Number of outputs.
+    pub fn outputs(&self) -> usize {
+        self.slot.outputs()
+    }
+}
+
 pub struct SlotBackend {
     inputs: usize,
     outputs: usize,
@@ -319,4 +365,13 @@ impl AudioUnit for SlotBackend {
             latest.allocate();
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        let unit_memory = |unit: &dyn AudioUnit| unit.footprint() + unit.memory_usage();
+        unit_memory(self.current.as_ref())
+            + self.next.as_deref().map_or(0, unit_memory)
+            + self.latest.as_deref().map_or(0, unit_memory)
+            + self.buffer.memory_usage()
+            + self.tick.capacity() * core::mem::size_of::<f32>()
+    }
 }
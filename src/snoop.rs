@@ -1,12 +1,17 @@
-//! The snoop node shares audio data with a frontend thread.
+//! The snoop nodes share audio data and spectra with a frontend thread.
 
 use super::audionode::*;
 use super::buffer::*;
+use super::fft::*;
+use super::math::*;
+use super::shared::*;
 use super::signal::*;
 use super::*;
+use core::sync::atomic::{AtomicU32, Ordering};
 use numeric_array::*;
 use thingbuf::mpsc::{channel, Receiver, Sender};
 extern crate alloc;
+use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
 
@@ -50,11 +55,23 @@ pub struct Snoop {
     index: usize,
     total: u64,
     latest: Vec<f32>,
+    trigger: Option<f32>,
 }
 
 impl Snoop {
     /// Create a new snoop node. Returns a (frontend, backend) pair.
     pub fn new(capacity: usize) -> (Snoop, SnoopBackend) {
+        Self::create(capacity, None)
+    }
+
+    /// Create a new snoop node with an oscilloscope trigger at `trigger_level`.
+    /// Waveforms read with `triggered` start at a rising crossing of the level.
+    /// Returns a (frontend, backend) pair.
+    pub fn new_triggered(capacity: usize, trigger_level: f32) -> (Snoop, SnoopBackend) {
+        Self::create(capacity, Some(trigger_level))
+    }
+
+    fn create(capacity: usize, trigger: Option<f32>) -> (Snoop, SnoopBackend) {
         let capacity = capacity.next_power_of_two();
         let (sender, receiver) = channel(1024);
         let snoop = Snoop {
@@ -62,6 +79,7 @@ impl Snoop {
             index: 0,
             total: 0,
             latest: vec![0.0; capacity],
+            trigger,
         };
         let snoop_backend = SnoopBackend {
             index: 0,
@@ -86,6 +104,41 @@ impl Snoop {
         self.total
     }
 
+    /// Trigger level, if this is a triggered snoop.
+    pub fn trigger_level(&self) -> Option<f32> {
+        self.trigger
+    }
+
+    /// Copy a waveform of `output.len()` samples into `output`, in chronological order.
+    /// For a triggered snoop, the waveform starts at the latest rising crossing of the trigger level
+    /// that is followed by enough samples, which keeps periodic waveforms still on display.
+    /// Returns whether a trigger was found. If not, or if the snoop is not triggered,
+    /// the latest samples are copied instead (free-running mode).
+    /// The length of `output` must be less than `capacity`.
+    pub fn triggered(&self, output: &mut [f32]) -> bool {
+        let length = output.len();
+        assert!(length < self.capacity());
+        if length == 0 {
+            return false;
+        }
+        let mut start = length - 1;
+        let mut found = false;
+        if let Some(level) = self.trigger {
+            // Search backwards in time for a rising crossing.
+            for index in length - 1..self.capacity() - 1 {
+                if self.at(index + 1) < level && self.at(index) >= level {
+                    start = index;
+                    found = true;
+                    break;
+                }
+            }
+        }
+        for (i, x) in output.iter_mut().enumerate() {
+            *x = self.at(start - i);
+        }
+        found
+    }
+
     /// Get the next buffer of data, if available.
     /// Either this method or `update` should be polled repeatedly.
     pub fn get(&mut self) -> Option<SnoopBuffer> {
@@ -154,3 +207,208 @@ impl AudioNode for SnoopBackend {
         input.clone()
     }
 }
+
+/// Spectrum data shared between the spectrum snoop backend and frontend.
+struct SpectrumShared {
+    /// Magnitude of each bin.
+    magnitudes: Vec<AtomicU32>,
+    /// Number of windows analyzed so far (wraps around).
+    windows: AtomicU32,
+    /// Sample rate of the backend.
+    sample_rate: AtomicU32,
+}
+
+/// Receiver for snooped audio data and its magnitude spectrum.
+/// The spectrum is updated once per window of `fft_size` samples
+/// from a Hann windowed FFT computed in the backend.
+pub struct SpectrumSnoop {
+    snoop: Snoop,
+    shared: Arc<SpectrumShared>,
+    fft_size: usize,
+}
+
+impl SpectrumSnoop {
+    /// Create a new spectrum snoop node. Returns a (frontend, backend) pair.
+    /// The FFT size must be a power of two between 2 and 32768.
+    /// The latest samples buffer has room for at least `fft_size` samples.
+    pub fn new(fft_size: usize) -> (SpectrumSnoop, SpectrumSnoopBackend) {
+        assert!(fft_size.is_power_of_two() && (2..=32768).contains(&fft_size));
+        let (snoop, snoop_backend) = Snoop::new(fft_size);
+        let shared = Arc::new(SpectrumShared {
+            magnitudes: (0..fft_size / 2 + 1).map(|_| f32::storage(0.0)).collect(),
+            windows: AtomicU32::new(0),
+            sample_rate: f32::storage(DEFAULT_SR as f32),
+        });
+        // Hann window scaled so that a full scale sine wave has unit magnitude.
+        let scale = 2.0 / (fft_size as f32 * 0.5);
+        let window = (0..fft_size)
+            .map(|i| (0.5 - 0.5 * cos(i as f32 * f32::TAU / fft_size as f32)) * scale)
+            .collect();
+        let frontend = SpectrumSnoop {
+            snoop,
+            shared: shared.clone(),
+            fft_size,
+        };
+        let backend = SpectrumSnoopBackend {
+            snoop: snoop_backend,
+            shared,
+            window,
+            input: vec![0.0; fft_size],
+            scratch: vec![0.0; fft_size],
+            index: 0,
+        };
+        (frontend, backend)
+    }
+
+    /// Return sample where `index` is a reverse index: the latest sample is at index 0.
+    pub fn at(&self, index: usize) -> f32 {
+        self.snoop.at(index)
+    }
+
+    /// Capacity of the latest sample buffer.
+    pub fn capacity(&self) -> usize {
+        self.snoop.capacity()
+    }
+
+    /// Total number of samples received so far.
+    pub fn total(&self) -> u64 {
+        self.snoop.total()
+    }
+
+    /// Get the next buffer of data, if available.
+    /// Either this method or `update` should be polled repeatedly
+    /// to keep the latest samples current. The spectrum is available without polling.
+    pub fn get(&mut self) -> Option<SnoopBuffer> {
+        self.snoop.get()
+    }
+
+    /// Receive latest data.
+    /// Either this method or `get` should be polled repeatedly
+    /// to keep the latest samples current. The spectrum is available without polling.
+    pub fn update(&mut self) {
+        self.snoop.update()
+    }
+
+    /// FFT size in samples.
+    pub fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Number of frequency bins in the spectrum, from DC to Nyquist inclusive.
+    pub fn bins(&self) -> usize {
+        self.fft_size / 2 + 1
+    }
+
+    /// Magnitude of frequency bin `bin` from the latest window.
+    /// A full scale sine wave centered on a bin has unit magnitude.
+    pub fn magnitude(&self, bin: usize) -> f32 {
+        f32::get_stored(&self.shared.magnitudes[bin])
+    }
+
+    /// Copy magnitudes of the latest window into `spectrum`, which must have room for `bins()` items.
+    /// Bins are read one at a time: if a window finishes while copying,
+    /// the copy may contain bins from two consecutive windows.
+    pub fn spectrum(&self, spectrum: &mut [f32]) {
+        for (x, y) in spectrum.iter_mut().zip(self.shared.magnitudes.iter()) {
+            *x = f32::get_stored(y);
+        }
+    }
+
+    /// Number of windows analyzed so far. Wraps around.
+    /// The spectrum is new when this number has changed.
+    pub fn windows(&self) -> u32 {
+        self.shared.windows.load(Ordering::Acquire)
+    }
+
+    /// Sample rate of the backend in Hz.
+    pub fn sample_rate(&self) -> f32 {
+        f32::get_stored(&self.shared.sample_rate)
+    }
+
+    /// Center frequency of bin `bin` in Hz.
+    pub fn bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.sample_rate() / self.fft_size as f32
+    }
+}
+
+/// The spectrum snoop backend node passes through audio data while sending it
+/// to the spectrum snoop frontend. Computes one FFT per window of input.
+#[derive(Clone)]
+pub struct SpectrumSnoopBackend {
+    snoop: SnoopBackend,
+    shared: Arc<SpectrumShared>,
+    window: Vec<f32>,
+    input: Vec<f32>,
+    scratch: Vec<f32>,
+    index: usize,
+}
+
+impl SpectrumSnoopBackend {
+    /// Analyze the latest window and publish the spectrum.
+    fn analyze(&mut self) {
+        for ((x, y), w) in self
+            .scratch
+            .iter_mut()
+            .zip(self.input.iter())
+            .zip(self.window.iter())
+        {
+            *x = *y * *w;
+        }
+        let bins = self.shared.magnitudes.len();
+        let spectrum = real_fft(&mut self.scratch);
+        // The Nyquist bin is packed into the imaginary part of DC.
+        f32::store(&self.shared.magnitudes[0], abs(spectrum[0].re) * 0.5);
+        f32::store(&self.shared.magnitudes[bins - 1], abs(spectrum[0].im) * 0.5);
+        for (i, x) in spectrum.iter().enumerate().skip(1) {
+            f32::store(&self.shared.magnitudes[i], x.norm());
+        }
+        self.shared.windows.fetch_add(1, Ordering::Release);
+    }
+
+    #[inline]
+    fn push(&mut self, x: f32) {
+        self.input[self.index] = x;
+        self.index += 1;
+        if self.index == self.input.len() {
+            self.analyze();
+            self.index = 0;
+        }
+    }
+}
+
+impl AudioNode for SpectrumSnoopBackend {
+    const ID: u64 = 111;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+
+    fn reset(&mut self) {
+        self.snoop.reset();
+        self.index = 0;
+    }
+
+    fn memory_usage(&self) -> usize {
+        (self.window.capacity() + self.input.capacity() + self.scratch.capacity())
+            * core::mem::size_of::<f32>()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        f32::store(&self.shared.sample_rate, sample_rate as f32);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.push(input[0]);
+        self.snoop.tick(input)
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        for i in 0..size {
+            self.push(input.at_f32(0, i));
+        }
+        self.snoop.process(size, input, output);
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        input.clone()
+    }
+}
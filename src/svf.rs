@@ -13,17 +13,56 @@ use num_complex::Complex64;
 use numeric_array::typenum::*;
 
 /// State variable filter coefficients, generic formulation.
+///
+/// The filter is a topology-preserving transform (TPT), or zero-delay feedback,
+/// discretization of the analog state variable filter with trapezoidal integrators.
+/// `g = tan(PI * cutoff / sample_rate)` is the prewarped integrator gain and
+/// `k = 1 / Q` is the damping. The filter computes bandpass output `v1` and lowpass output `v2`
+/// from input `v0` using the derived coefficients `a1 = 1 / (1 + g * (g + k))`,
+/// `a2 = g * a1` and `a3 = g * a2`. The output is the mix `m0 * v0 + m1 * v1 + m2 * v2`.
+/// Equalizing modes (bell and shelves) modify `g`, `k` and the mixing coefficients
+/// according to gain.
+///
+/// The coefficients can be computed independently of a filter, for example,
+/// to precompute coefficient tables or to plot frequency responses with `response`.
+///
+/// ### Example: Lowpass Response At Cutoff
+/// ```
+/// use fundsp::hacker::*;
+/// let coefs = SvfCoefs::lowpass(44100.0, 1000.0, 1.0);
+/// assert!((coefs.response(44100.0, 1000.0).norm() - 1.0).abs() < 1.0e-9);
+/// ```
 #[derive(Clone, Default)]
 pub struct SvfCoefs<F: Real> {
+    /// Prewarped integrator gain.
+    pub g: F,
+    /// Damping, the inverse of Q.
+    pub k: F,
     pub a1: F,
     pub a2: F,
     pub a3: F,
+    /// Mixing coefficient for the input.
     pub m0: F,
+    /// Mixing coefficient for the bandpass output.
     pub m1: F,
+    /// Mixing coefficient for the lowpass output.
     pub m2: F,
 }
 
 impl<F: Real> SvfCoefs<F> {
+    /// Evaluate the complex frequency response at `frequency` Hz
+    /// given the `sample_rate` in Hz.
+    pub fn response(&self, sample_rate: f64, frequency: f64) -> Complex64 {
+        let g = self.g.to_f64();
+        let k = self.k.to_f64();
+        let z = Complex64::from_polar(1.0, frequency * f64::TAU / sample_rate);
+        let denominator =
+            (z - 1.0) * (z - 1.0) + g * k * (z * z - 1.0) + g * g * (z + 1.0) * (z + 1.0);
+        let bandpass = g * (z * z - 1.0) / denominator;
+        let lowpass = g * g * (z + 1.0) * (z + 1.0) / denominator;
+        self.m0.to_f64() + self.m1.to_f64() * bandpass + self.m2.to_f64() * lowpass
+    }
+
     /// Calculate coefficients for a lowpass filter.
     pub fn lowpass(sample_rate: F, cutoff: F, q: F) -> Self {
         let g = tan(F::from_f64(f64::PI) * cutoff / sample_rate);
@@ -36,6 +75,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = F::one();
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -57,6 +98,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = -F::one();
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -78,6 +121,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = F::zero();
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -99,6 +144,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = F::zero();
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -120,6 +167,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = F::new(-2);
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -141,6 +190,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = F::zero();
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -164,6 +215,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = F::zero();
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -187,6 +240,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = a * a - F::one();
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -210,6 +265,8 @@ impl<F: Real> SvfCoefs<F> {
         let m2 = F::one() - a * a;
 
         SvfCoefs {
+            g,
+            k,
             a1,
             a2,
             a3,
@@ -871,6 +928,7 @@ where
 }
 
 /// Simper SVF with fixed parameters.
+/// Setting: center, Q and gain. Supports ramped settings.
 /// - Input 0: audio
 /// - Output 0: filtered audio
 #[derive(Default, Clone)]
@@ -884,6 +942,16 @@ where
     coefs: SvfCoefs<F>,
     ic1eq: F,
     ic2eq: F,
+    /// Target parameters of the ongoing ramp.
+    target: SvfParams<F>,
+    /// Per sample cutoff multiplier of the ongoing ramp.
+    cutoff_factor: F,
+    /// Per sample Q increment of the ongoing ramp.
+    q_increment: F,
+    /// Per sample gain increment of the ongoing ramp.
+    gain_increment: F,
+    /// Number of samples left in the ongoing ramp.
+    ramp_samples: usize,
 }
 
 impl<F, M> FixedSvf<F, M>
@@ -898,10 +966,15 @@ where
         mode.update(&params, &mut coefs);
         FixedSvf {
             mode,
-            params,
+            params: params.clone(),
             coefs,
             ic1eq: F::zero(),
             ic2eq: F::zero(),
+            target: params,
+            cutoff_factor: F::one(),
+            q_increment: F::zero(),
+            gain_increment: F::zero(),
+            ramp_samples: 0,
         }
     }
 
@@ -936,6 +1009,7 @@ where
     pub fn set_cutoff(&mut self, cutoff: F) {
         self.params.cutoff = cutoff;
         self.mode.update_frequency(&self.params, &mut self.coefs);
+        self.ramp_samples = 0;
     }
 
     /// Set filter center in Hz. Synonymous with `set_cutoff`.
@@ -950,6 +1024,7 @@ where
         self.params.cutoff = cutoff;
         self.params.q = q;
         self.mode.update_frequency(&self.params, &mut self.coefs);
+        self.ramp_samples = 0;
     }
 
     /// Set filter center in Hz and Q. Synonymous with `set_cutoff_q`.
@@ -963,6 +1038,7 @@ where
     pub fn set_q(&mut self, q: F) {
         self.params.q = q;
         self.mode.update_q(&self.params, &mut self.coefs);
+        self.ramp_samples = 0;
     }
 
     /// Set filter gain. Only equalizing modes support gain. Other modes ignore it.
@@ -970,6 +1046,7 @@ where
     pub fn set_gain(&mut self, gain: F) {
         self.params.gain = gain;
         self.mode.update_gain(&self.params, &mut self.coefs);
+        self.ramp_samples = 0;
     }
 
     /// Set filter cutoff in Hz, Q and gain. Synonymous with `set_center_q_gain`.
@@ -979,6 +1056,7 @@ where
         self.params.q = q;
         self.params.gain = gain;
         self.mode.update(&self.params, &mut self.coefs);
+        self.ramp_samples = 0;
     }
 
     /// Set filter center in Hz, Q and gain. Synonymous with `set_cutoff_q_gain`.
@@ -986,6 +1064,54 @@ where
     pub fn set_center_q_gain(&mut self, center: F, q: F, gain: F) {
         self.set_cutoff_q_gain(center, q, gain);
     }
+
+    /// Interpolate to new cutoff in Hz, Q and gain over `ramp_time` seconds.
+    /// Cutoff is interpolated exponentially while Q and gain are interpolated linearly.
+    pub fn ramp_cutoff_q_gain(&mut self, cutoff: F, q: F, gain: F, ramp_time: f32) {
+        let samples = round(ramp_time as f64 * self.params.sample_rate.to_f64()) as usize;
+        if samples == 0 {
+            self.set_cutoff_q_gain(cutoff, q, gain);
+        } else {
+            let scale = F::one() / F::new(samples as i64);
+            self.cutoff_factor = pow(cutoff / self.params.cutoff, scale);
+            self.q_increment = (q - self.params.q) * scale;
+            self.gain_increment = (gain - self.params.gain) * scale;
+            self.target.cutoff = cutoff;
+            self.target.q = q;
+            self.target.gain = gain;
+            self.ramp_samples = samples;
+        }
+    }
+
+    /// Filter one sample.
+    #[inline]
+    fn filter(&mut self, v0: F) -> F {
+        if self.ramp_samples > 0 {
+            self.advance();
+        }
+        let v3 = v0 - self.ic2eq;
+        let v1 = self.coefs.a1 * self.ic1eq + self.coefs.a2 * v3;
+        let v2 = self.ic2eq + self.coefs.a2 * self.ic1eq + self.coefs.a3 * v3;
+        self.ic1eq = F::new(2) * v1 - self.ic1eq;
+        self.ic2eq = F::new(2) * v2 - self.ic2eq;
+        self.coefs.m0 * v0 + self.coefs.m1 * v1 + self.coefs.m2 * v2
+    }
+
+    /// Advance the ongoing ramp by one sample.
+    #[inline]
+    fn advance(&mut self) {
+        self.ramp_samples -= 1;
+        if self.ramp_samples == 0 {
+            self.params.cutoff = self.target.cutoff;
+            self.params.q = self.target.q;
+            self.params.gain = self.target.gain;
+        } else {
+            self.params.cutoff *= self.cutoff_factor;
+            self.params.q += self.q_increment;
+            self.params.gain += self.gain_increment;
+        }
+        self.mode.update(&self.params, &mut self.coefs);
+    }
 }
 
 impl<F, M> AudioNode for FixedSvf<F, M>
@@ -1000,6 +1126,11 @@ where
     fn reset(&mut self) {
         self.ic1eq = F::zero();
         self.ic2eq = F::zero();
+        // Finish any ongoing ramp.
+        if self.ramp_samples > 0 {
+            self.ramp_samples = 1;
+            self.advance();
+        }
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
@@ -1009,27 +1140,35 @@ where
 
     #[inline]
     fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
-        let v0 = convert(input[0]);
-        let v3 = v0 - self.ic2eq;
-        let v1 = self.coefs.a1 * self.ic1eq + self.coefs.a2 * v3;
-        let v2 = self.ic2eq + self.coefs.a2 * self.ic1eq + self.coefs.a3 * v3;
-        self.ic1eq = F::new(2) * v1 - self.ic1eq;
-        self.ic2eq = F::new(2) * v2 - self.ic2eq;
-        [convert(
-            self.coefs.m0 * v0 + self.coefs.m1 * v1 + self.coefs.m2 * v2,
-        )]
-        .into()
+        [convert(self.filter(convert(input[0])))].into()
+    }
+
+    #[inline]
+    fn tick64(&mut self, input: &Frame<f64, Self::Inputs>) -> Frame<f64, Self::Outputs> {
+        [convert(self.filter(convert(input[0])))].into()
     }
 
     fn set(&mut self, setting: Setting) {
+        // Settings are relative to the target of any ongoing ramp.
+        let (q, gain) = if self.ramp_samples > 0 {
+            (self.target.q, self.target.gain)
+        } else {
+            (self.params.q, self.params.gain)
+        };
+        let ramp_time = setting.ramp_time();
         match setting.parameter() {
-            Parameter::Center(center) => self.set_center(F::from_f32(*center)),
-            Parameter::CenterQ(center, q) => {
-                self.set_center_q(F::from_f32(*center), F::from_f32(*q))
+            Parameter::Center(center) => {
+                self.ramp_cutoff_q_gain(F::from_f32(*center), q, gain, ramp_time)
             }
-            Parameter::CenterQGain(center, q, gain) => {
-                self.set_center_q_gain(F::from_f32(*center), F::from_f32(*q), F::from_f32(*gain))
+            Parameter::CenterQ(center, q) => {
+                self.ramp_cutoff_q_gain(F::from_f32(*center), F::from_f32(*q), gain, ramp_time)
             }
+            Parameter::CenterQGain(center, q, gain) => self.ramp_cutoff_q_gain(
+                F::from_f32(*center),
+                F::from_f32(*q),
+                F::from_f32(*gain),
+                ramp_time,
+            ),
             _ => (),
         }
     }
@@ -5,6 +5,7 @@ use super::buffer::*;
 use super::combinator::*;
 use super::math::*;
 use super::setting::*;
+use super::shared::*;
 use super::signal::*;
 use super::*;
 
@@ -95,4 +96,121 @@ impl<X: AudioNode, F: FnMut(f32, f32, &mut X) + Clone + Sync + Send> AudioNode f
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
+}
+
+/// A dynamical system that reports a control value. The update function
+/// `f(t, dt, samples, x)` is called with current time `t`, time `dt` elapsed since
+/// the previous update, number of `samples` processed since the last reset,
+/// and the enclosed node `x`. Its return value is written to a shared variable.
+///
+/// The update function runs in the processing thread, before the enclosed node processes
+/// the sample (or block) at which the update falls, so changes it makes to the node
+/// take effect immediately. The shared variable is written right after each update;
+/// other threads see the latest value. In block processing, updates are done
+/// at block boundaries only, so intervals are rounded up to a whole block.
+#[derive(Clone)]
+pub struct SystemOut<X: AudioNode, F: FnMut(f32, f32, u64, &mut X) -> f32 + Clone + Send + Sync> {
+    x: X,
+    f: F,
+    shared: Shared,
+    /// Sample count at the previous update.
+    last_update: u64,
+    samples: u64,
+    update_interval: f32,
+    sample_rate: f32,
+}
+
+impl<X: AudioNode, F: FnMut(f32, f32, u64, &mut X) -> f32 + Clone + Send + Sync> SystemOut<X, F> {
+    /// Create a new dynamical system that writes the return value of update function `f`
+    /// into `shared`. `dt` is the approximate target time between updates.
+    pub fn new(x: An<X>, dt: f32, shared: &Shared, f: F) -> Self {
+        let mut node = SystemOut {
+            x: x.0,
+            f,
+            shared: shared.clone(),
+            last_update: 0,
+            samples: 0,
+            update_interval: dt,
+            sample_rate: DEFAULT_SR as f32,
+        };
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node
+    }
+
+    /// Number of samples processed since the last reset.
+    #[inline]
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Call the update function if it is time and advance time by `size` samples.
+    #[inline]
+    fn advance(&mut self, size: usize) {
+        // Time is derived from sample counts so it does not drift.
+        let sample_rate = self.sample_rate as f64;
+        let delta_time = ((self.samples - self.last_update) as f64 / sample_rate) as f32;
+        // The first update is always done at time zero.
+        if delta_time >= self.update_interval || (self.samples == 0 && size > 0) {
+            let time = (self.samples as f64 / sample_rate) as f32;
+            let value = (self.f)(time, delta_time, self.samples, &mut self.x);
+            self.shared.set_value(value);
+            self.last_update = self.samples;
+        }
+        self.samples += size as u64;
+    }
+}
+
+impl<X: AudioNode, F: FnMut(f32, f32, u64, &mut X) -> f32 + Clone + Sync + Send> AudioNode
+    for SystemOut<X, F>
+{
+    const ID: u64 = 145;
+    type Inputs = X::Inputs;
+    type Outputs = X::Outputs;
+
+    fn reset(&mut self) {
+        self.x.reset();
+        self.last_update = 0;
+        self.samples = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.x.set_sample_rate(sample_rate);
+        self.sample_rate = convert(sample_rate);
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        self.advance(1);
+        self.x.tick(input)
+    }
+
+    fn process(&mut self, size: usize, input: &BufferRef, output: &mut BufferMut) {
+        self.advance(size);
+        self.x.process(size, input, output);
+    }
+
+    fn set(&mut self, setting: Setting) {
+        self.x.set(setting);
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        self.x.route(input, frequency)
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.x.memory_usage()
+    }
 }
@@ -12,6 +12,60 @@ use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
 
+/// Delay line compensating for the latency difference between a vertex input
+/// and the latest input of the same vertex. An empty delay line passes input through.
+#[derive(Clone, Default)]
+pub(crate) struct LatencyDelay {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl LatencyDelay {
+    /// Create a delay line of `length` samples.
+    pub fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length],
+            index: 0,
+        }
+    }
+
+    /// Delay length in samples.
+    #[inline]
+    pub fn length(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.index = 0;
+    }
+
+    #[inline]
+    pub fn tick(&mut self, x: f32) -> f32 {
+        if self.buffer.is_empty() {
+            return x;
+        }
+        let y = self.buffer[self.index];
+        self.buffer[self.index] = x;
+        self.index += 1;
+        if self.index == self.buffer.len() {
+            self.index = 0;
+        }
+        y
+    }
+
+    /// Delay `data` in place.
+    #[inline]
+    pub fn process(&mut self, data: &mut [f32]) {
+        if !self.buffer.is_empty() {
+            for x in data.iter_mut() {
+                *x = self.tick(*x);
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 /// Individual AudioUnits are vertices in the graph.
 pub(crate) struct Vertex {
@@ -25,6 +79,10 @@ pub(crate) struct Vertex {
     pub output: BufferVec,
     /// Temporary output buffers. The number of channels is equal to the number of outputs.
     pub output_tmp: BufferVec,
+    /// Latency compensation delays for inputs. The length is equal to the number of inputs.
+    pub compensation: Vec<LatencyDelay>,
+    /// Whether any input has a latency compensation delay.
+    pub compensated: bool,
     /// Input for tick iteration. The length is equal to the number of inputs.
     pub tick_input: Vec<f32>,
     /// Output for tick iteration. The length is equal to the number of outputs.
@@ -60,6 +118,8 @@ impl Vertex {
             input: BufferVec::new(inputs),
             output: BufferVec::new(outputs),
             output_tmp: BufferVec::new(outputs),
+            compensation: vec![LatencyDelay::default(); inputs],
+            compensated: false,
             tick_input: vec![0.0; inputs],
             tick_output: vec![0.0; outputs],
             tick_output_tmp: vec![0.0; outputs],
@@ -95,10 +155,31 @@ impl Vertex {
         self.unit.allocate();
     }
 
+    /// Heap memory used by this vertex, including the boxed unit, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.unit.footprint()
+            + self.unit.memory_usage()
+            + self.source.capacity() * core::mem::size_of::<Edge>()
+            + self.input.memory_usage()
+            + self.output.memory_usage()
+            + self.output_tmp.memory_usage()
+            + self.compensation.capacity() * core::mem::size_of::<LatencyDelay>()
+            + self
+                .compensation
+                .iter()
+                .map(|delay| delay.length() * core::mem::size_of::<f32>())
+                .sum::<usize>()
+            + (self.tick_input.capacity()
+                + self.tick_output.capacity()
+                + self.tick_output_tmp.capacity())
+                * core::mem::size_of::<f32>()
+    }
+
     /// Calculate source vertex and source port.
     pub fn update_source_vertex(&mut self) {
         self.source_vertex = None;
-        if self.inputs() == 0 {
+        // Compensated inputs are delayed in the input buffers.
+        if self.inputs() == 0 || self.compensated {
             return;
         }
         let mut source_node = 0;
@@ -0,0 +1,196 @@
+//! Channel vocoder.
+
+use super::audionode::*;
+use super::biquad::*;
+use super::biquad_bank::*;
+use super::follow::*;
+use super::math::*;
+use super::setting::*;
+use super::signal::*;
+use super::*;
+use numeric_array::typenum::*;
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Lowest band center frequency of the vocoder in Hz.
+pub const VOCODER_MIN_HZ: f32 = 100.0;
+
+/// Highest band center frequency of the vocoder in Hz.
+pub const VOCODER_MAX_HZ: f32 = 8000.0;
+
+/// Classic channel vocoder. The modulator and carrier are split into
+/// log-spaced bands in `VOCODER_MIN_HZ`...`VOCODER_MAX_HZ` with SIMD accelerated
+/// constant-gain bandpass filter banks. The rectified modulator bands are smoothed
+/// with followers and the resulting envelopes are applied to the carrier bands.
+/// - Allocates: filter banks and followers.
+/// - Setting: envelope response time.
+/// - Input 0: modulator (for example, voice)
+/// - Input 1: carrier (for example, synth)
+/// - Output 0: vocoded signal
+#[derive(Clone)]
+pub struct Vocoder {
+    bands: usize,
+    response_time: f32,
+    modulator: Vec<BiquadBank<F32x>>,
+    carrier: Vec<BiquadBank<F32x>>,
+    followers: Vec<Follow<f32>>,
+    sample_rate: f64,
+}
+
+impl Vocoder {
+    /// Create new vocoder with `bands` bands (`bands` > 0) and
+    /// envelope halfway `response_time` in seconds.
+    pub fn new(bands: usize, response_time: f32) -> Self {
+        assert!(bands > 0);
+        let banks = simd_items(bands);
+        let mut node = Self {
+            bands,
+            response_time,
+            modulator: vec![BiquadBank::new(); banks],
+            carrier: vec![BiquadBank::new(); banks],
+            followers: vec![Follow::new(response_time); bands],
+            sample_rate: DEFAULT_SR,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Number of bands.
+    pub fn bands(&self) -> usize {
+        self.bands
+    }
+
+    /// Center frequency of `band` in Hz.
+    pub fn frequency(&self, band: usize) -> f32 {
+        if self.bands == 1 {
+            xerp(VOCODER_MIN_HZ, VOCODER_MAX_HZ, 0.5)
+        } else {
+            xerp(
+                VOCODER_MIN_HZ,
+                VOCODER_MAX_HZ,
+                band as f32 / (self.bands - 1) as f32,
+            )
+        }
+    }
+
+    /// Envelope halfway response time in seconds.
+    pub fn response_time(&self) -> f32 {
+        self.response_time
+    }
+
+    /// Set envelope halfway response time in seconds.
+    pub fn set_response_time(&mut self, response_time: f32) {
+        self.response_time = response_time;
+        for follower in self.followers.iter_mut() {
+            follower.set_response_time(response_time);
+        }
+    }
+
+    /// Q of the bandpass filters. Adjacent bands meet at their half-power points.
+    fn q(&self) -> f32 {
+        if self.bands == 1 {
+            1.0
+        } else {
+            let ratio = pow(
+                VOCODER_MAX_HZ / VOCODER_MIN_HZ,
+                1.0 / (self.bands - 1) as f32,
+            );
+            sqrt(ratio) / (ratio - 1.0)
+        }
+    }
+
+    fn calculate_coefs(&mut self) {
+        let q = self.q();
+        for j in 0..self.modulator.len() {
+            // Unused lanes stay silent.
+            let mut coefs = BiquadCoefs::<F32x>::default();
+            for k in 0..min(SIMD_N, self.bands - j * SIMD_N) {
+                let c = BiquadCoefs::resonator(
+                    self.sample_rate as f32,
+                    self.frequency(j * SIMD_N + k),
+                    q,
+                );
+                coefs.a1.set(k, c.a1);
+                coefs.a2.set(k, c.a2);
+                coefs.b0.set(k, c.b0);
+                coefs.b1.set(k, c.b1);
+                coefs.b2.set(k, c.b2);
+            }
+            self.modulator[j].set_coefs(coefs);
+            self.carrier[j].set_coefs(coefs);
+        }
+    }
+}
+
+impl AudioNode for Vocoder {
+    const ID: u64 = 99;
+    type Inputs = U2;
+    type Outputs = U1;
+
+    fn reset(&mut self) {
+        for bank in self.modulator.iter_mut() {
+            bank.reset();
+        }
+        for bank in self.carrier.iter_mut() {
+            bank.reset();
+        }
+        for follower in self.followers.iter_mut() {
+            follower.reset();
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        (self.modulator.capacity() + self.carrier.capacity())
+            * core::mem::size_of::<BiquadBank<F32x>>()
+            + self.followers.capacity() * core::mem::size_of::<Follow<f32>>()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        for bank in self.modulator.iter_mut() {
+            bank.set_sample_rate(sample_rate);
+        }
+        for bank in self.carrier.iter_mut() {
+            bank.set_sample_rate(sample_rate);
+        }
+        for follower in self.followers.iter_mut() {
+            follower.set_sample_rate(sample_rate);
+        }
+        self.calculate_coefs();
+    }
+
+    #[inline]
+    fn tick(&mut self, input: &Frame<f32, Self::Inputs>) -> Frame<f32, Self::Outputs> {
+        let modulator_input = Frame::splat(input[0]);
+        let carrier_input = Frame::splat(input[1]);
+        let mut output = 0.0;
+        for (j, (modulator, carrier)) in self
+            .modulator
+            .iter_mut()
+            .zip(self.carrier.iter_mut())
+            .enumerate()
+        {
+            let modulator_bands = modulator.tick(&modulator_input);
+            let carrier_bands = carrier.tick(&carrier_input);
+            let lanes = min(SIMD_N, self.bands - j * SIMD_N);
+            for k in 0..lanes {
+                let envelope = self.followers[j * SIMD_N + k].filter_mono(abs(modulator_bands[k]));
+                output += envelope * carrier_bands[k];
+            }
+        }
+        [output].into()
+    }
+
+    fn set(&mut self, setting: Setting) {
+        if let Parameter::Time(time) = setting.parameter() {
+            self.set_response_time(*time);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = SignalFrame::new(self.outputs());
+        output.set(0, input.at(0).combine_nonlinear(input.at(1), 0.0));
+        output
+    }
+}
@@ -14,6 +14,9 @@ extern crate alloc;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
+/// Number of samples rendered between progress reports in `Wave::render_with_progress`.
+pub const PROGRESS_INTERVAL: usize = 8192;
+
 /// Multichannel wave in 32-bit float precision.
 /// Requires memory allocation via `Vec`.
 /// Each channel is stored in its own vector of samples.
@@ -181,6 +184,64 @@ impl Wave {
         self.vec.remove(channel)
     }
 
+    /// Append all channels of the `source` wave as new channels of this wave.
+    /// Panics if the lengths of the waves differ, unless this wave has no channels,
+    /// in which case the length of this wave becomes the length of `source`.
+    /// Any sample rate differences are ignored.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut wave = Wave::render(44100.0, 1.0, &mut (sine_hz(110.0)));
+    /// wave.append_channels(&Wave::render(44100.0, 1.0, &mut (sine_hz(220.0) | sine_hz(330.0))));
+    /// assert!(wave.channels() == 3 && wave.duration() == 1.0);
+    /// ```
+    pub fn append_channels(&mut self, source: &Wave) {
+        assert!(self.channels() == 0 || source.channels() == 0 || self.len() == source.len());
+        for channel in 0..source.channels() {
+            self.push_channel(source.channel(channel));
+        }
+    }
+
+    /// Return a new mono wave containing a copy of channel `channel`.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let wave = Wave::render(44100.0, 1.0, &mut (sine_hz(110.0) | dc(0.5)));
+    /// let right = wave.extract_channel(1);
+    /// assert!(right.channels() == 1 && right.amplitude() == 0.5);
+    /// ```
+    pub fn extract_channel(&self, channel: usize) -> Wave {
+        Wave::from_samples(self.sample_rate(), self.channel(channel))
+    }
+
+    /// Return a new mono wave that is the average of all channels of this wave.
+    /// Averaging keeps the level of identical channels unchanged.
+    /// The wave must have a non-zero number of channels.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let wave = Wave::render(44100.0, 1.0, &mut (dc(0.25) | dc(0.75)));
+    /// let mono = wave.mix_to_mono();
+    /// assert!(mono.channels() == 1 && mono.at(0, 0) == 0.5);
+    /// ```
+    pub fn mix_to_mono(&self) -> Wave {
+        assert!(self.channels() > 0);
+        let gain = 1.0 / self.channels() as f32;
+        let mut samples = self.channel(0).clone();
+        for channel in 1..self.channels() {
+            for (x, y) in samples.iter_mut().zip(self.channel(channel).iter()) {
+                *x += *y;
+            }
+        }
+        for x in samples.iter_mut() {
+            *x *= gain;
+        }
+        Wave::from_samples(self.sample_rate(), &samples)
+    }
+
     /// Append the contents of the `source` wave to the end of this wave.
     /// The number of channels in `source` and this wave must match.
     /// Any sample rate differences are ignored.
@@ -317,7 +378,7 @@ impl Wave {
         self.resize(i1 - i0);
     }
 
-    /// Peak amplitude of the wave. An empty wave has zero amplitude.
+    /// Peak amplitude of the wave over all channels. An empty wave has zero amplitude.
     ///
     /// ### Example
     /// ```
@@ -367,7 +428,71 @@ impl Wave {
         }
     }
 
-    /// Applies a smooth fade-in envelope to the wave with a duration of `time` seconds.
+    /// Root mean square level of the wave computed over all channels.
+    /// An empty wave has zero RMS level.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let wave = Wave::render(44100.0, 1.0, &mut (sine_hz(60.0) | dc(0.5)));
+    /// assert!((wave.rms() - (0.5f32 * 0.5 + 0.25 * 0.5).sqrt()).abs() < 1.0e-4);
+    /// ```
+    pub fn rms(&self) -> f32 {
+        let samples = self.channels() * self.len();
+        if samples == 0 {
+            return 0.0;
+        }
+        let mut sum = 0.0;
+        for channel in 0..self.channels() {
+            for i in 0..self.len() {
+                sum += squared(self.at(channel, i) as f64);
+            }
+        }
+        sqrt(sum / samples as f64) as f32
+    }
+
+    /// Scales the wave so that its peak amplitude is `target_db` decibels.
+    /// The reference level of 0 dB is amplitude 1 (full scale).
+    /// The same gain is applied to all channels, preserving balance.
+    /// Does nothing if the wave is silent.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut wave = Wave::render(44100.0, 1.0, &mut (sine_hz(60.0)));
+    /// wave.normalize_peak(-6.0);
+    /// assert!((wave.amplitude() - db_amp(-6.0)).abs() < 1.0e-6);
+    /// ```
+    pub fn normalize_peak(&mut self, target_db: f32) {
+        let a = self.amplitude();
+        if a == 0.0 {
+            return;
+        }
+        self.amplify(db_amp(target_db) / a);
+    }
+
+    /// Scales the wave so that its RMS level over all channels is `target_db` decibels.
+    /// The reference level of 0 dB is an RMS level of 1, which is the level of a full scale
+    /// square wave; a full scale sine wave is at about -3 dB.
+    /// The same gain is applied to all channels, preserving balance.
+    /// The resulting peak amplitude may exceed 1. Does nothing if the wave is silent.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut wave = Wave::render(44100.0, 1.0, &mut (noise() | noise()));
+    /// wave.normalize_rms(-20.0);
+    /// assert!((wave.rms() - db_amp(-20.0)).abs() < 1.0e-5);
+    /// ```
+    pub fn normalize_rms(&mut self, target_db: f32) {
+        let level = self.rms();
+        if level == 0.0 {
+            return;
+        }
+        self.amplify(db_amp(target_db) / level);
+    }
+
+    /// Applies a smooth fade-in envelope to the wave in-place with a duration of `time` seconds.
     /// If `time` is greater than the duration of the wave, then it will be set to the duration of the wave.
     ///
     /// ### Example
@@ -388,7 +513,7 @@ impl Wave {
         }
     }
 
-    /// Applies a smooth fade-out envelope to the wave with a duration of `time` seconds.
+    /// Applies a smooth fade-out envelope to the wave in-place with a duration of `time` seconds.
     /// If `time` is greater than the duration of the wave, then it will be set to the duration of the wave.
     ///
     /// ### Example
@@ -411,7 +536,7 @@ impl Wave {
         }
     }
 
-    /// Applies both fade-in and fade-out to the wave with a duration of `time` seconds.
+    /// Applies both fade-in and fade-out to the wave in-place with a duration of `time` seconds.
     /// If `time` is greater than the duration of the wave, then it will be set to the duration of the wave.
     ///
     /// ### Example
@@ -426,6 +551,121 @@ impl Wave {
         self.fade_out(time);
     }
 
+    /// Prepares the wave for seamless looping in-place by crossfading the last `time` seconds
+    /// into the start of the wave and then removing them. The wave becomes `time` seconds shorter.
+    /// The crossfade uses equal power curves (sine and cosine), which keep the level
+    /// of uncorrelated material constant. When looped, the last sample of the result
+    /// is followed by a sample that continues the original wave, so the loop point is click-free.
+    /// If `time` is greater than half the duration of the wave, then it will be set to half the duration.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut wave = Wave::render(44100.0, 4.0, &mut(pink() | pink()));
+    /// wave.crossfade_loop(0.5);
+    /// assert!(wave.duration() == 3.5);
+    /// ```
+    pub fn crossfade_loop(&mut self, time: f64) {
+        let time = min(time, self.duration() * 0.5);
+        let fade_n = round(time * self.sample_rate()) as usize;
+        if fade_n == 0 {
+            return;
+        }
+        let start = self.len() - fade_n;
+        for i in 0..fade_n {
+            let angle = i as f64 / fade_n as f64 * f64::PI * 0.5;
+            let (fade_in, fade_out) = (sin(angle) as f32, cos(angle) as f32);
+            for channel in 0..self.channels() {
+                self.set(
+                    channel,
+                    i,
+                    self.at(channel, i) * fade_in + self.at(channel, start + i) * fade_out,
+                );
+            }
+        }
+        self.resize(start);
+    }
+
+    /// Resample the wave to `sample_rate` Hz, returning a new wave with the same number of channels.
+    /// The duration of the wave is preserved and the length is rounded to the nearest sample.
+    /// This is an offline, allocating operation using a Kaiser windowed sinc filter
+    /// with 32 zero crossings per side, evaluated by linear interpolation from a table of 512 phases
+    /// per zero crossing. The passband extends to 94% of the lower of the two Nyquist frequencies
+    /// and the stopband is attenuated by about 80 dB.
+    ///
+    /// ### Example: Resample From 48 kHz To 44.1 kHz
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let wave = Wave::render(48000.0, 1.0, &mut (sine_hz(440.0)));
+    /// let resampled = wave.resample(44100.0);
+    /// assert!(resampled.sample_rate() == 44100.0 && resampled.len() == 44100);
+    /// ```
+    pub fn resample(&self, sample_rate: f64) -> Wave {
+        assert!(sample_rate > 0.0);
+        const ZEROS: usize = 32;
+        const PHASES: usize = 512;
+        const BETA: f64 = 8.0;
+        // Bessel function of the first kind of order zero.
+        let bessel_i0 = |x: f64| {
+            let mut sum = 1.0;
+            let mut term = 1.0;
+            let mut k = 1.0;
+            while term > sum * 1.0e-12 {
+                term *= squared(x / (2.0 * k));
+                sum += term;
+                k += 1.0;
+            }
+            sum
+        };
+        let table: Vec<f64> = (0..=ZEROS * PHASES + 1)
+            .map(|i| {
+                let x = i as f64 / PHASES as f64;
+                let sinc = if i == 0 {
+                    1.0
+                } else {
+                    sin(f64::PI * x) / (f64::PI * x)
+                };
+                let w = x / ZEROS as f64;
+                let window = if w < 1.0 {
+                    bessel_i0(BETA * sqrt(1.0 - w * w)) / bessel_i0(BETA)
+                } else {
+                    0.0
+                };
+                sinc * window
+            })
+            .collect();
+        let ratio = sample_rate / self.sample_rate();
+        let cutoff = min(1.0, ratio) * 0.94;
+        let length = round(self.len() as f64 * ratio) as usize;
+        let half_width = ZEROS as f64 / cutoff;
+        let mut wave = Wave::new(0, sample_rate);
+        let mut output = Vec::with_capacity(length);
+        for channel in 0..self.channels() {
+            output.clear();
+            let input = self.channel(channel);
+            for n in 0..length {
+                let t = n as f64 / ratio;
+                let k0 = max(0.0, ceil(t - half_width)) as usize;
+                let k1 = min(self.len() as f64 - 1.0, floor(t + half_width));
+                let mut value = 0.0;
+                if k1 >= 0.0 {
+                    for k in k0..=k1 as usize {
+                        let x = abs(t - k as f64) * cutoff * PHASES as f64;
+                        let i = x as usize;
+                        if i < ZEROS * PHASES {
+                            let h = lerp(table[i], table[i + 1], x - i as f64);
+                            value += input[k] as f64 * h;
+                        }
+                    }
+                }
+                output.push((value * cutoff) as f32);
+            }
+            wave.push_channel(&output);
+        }
+        wave
+    }
+
     /// Render wave with length `duration` seconds from generator `node`.
     /// Sets the sample rate of `node`.
     /// Does not discard pre-delay.
@@ -437,6 +677,33 @@ impl Wave {
     /// assert!(wave.sample_rate() == 44100.0 && wave.channels() == 2 && wave.duration() == 10.0);
     /// ```
     pub fn render(sample_rate: f64, duration: f64, node: &mut dyn AudioUnit) -> Self {
+        Self::render_with_progress(sample_rate, duration, node, |_| true)
+    }
+
+    /// Render wave with length `duration` seconds from generator `node`,
+    /// reporting progress to `callback`. Sets the sample rate of `node`.
+    /// Does not discard pre-delay.
+    ///
+    /// The callback is invoked on the calling thread after every `PROGRESS_INTERVAL` samples
+    /// and once more at the end with the fraction of the wave rendered so far, in 0...1.
+    /// Rendering is cancelled if the callback returns `false`:
+    /// the returned wave then contains the samples rendered before cancellation.
+    ///
+    /// ### Example: Render Brown Noise With Progress
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let wave = Wave::render_with_progress(44100.0, 1.0, &mut brown(), |progress| {
+    ///     println!("{:.0}%", progress * 100.0);
+    ///     true
+    /// });
+    /// assert!(wave.duration() == 1.0);
+    /// ```
+    pub fn render_with_progress(
+        sample_rate: f64,
+        duration: f64,
+        node: &mut dyn AudioUnit,
+        mut callback: impl FnMut(f64) -> bool,
+    ) -> Self {
         assert_eq!(node.inputs(), 0);
         assert!(node.outputs() > 0);
         assert!(duration >= 0.0);
@@ -445,8 +712,8 @@ impl Wave {
         let mut wave = Self::with_capacity(node.outputs(), sample_rate, length);
         let mut buffer = BufferVec::new(node.outputs());
         let mut buffer_mut = buffer.buffer_mut();
-        wave.len = length;
         let mut i = 0;
+        let mut next_report = PROGRESS_INTERVAL;
         while i < length {
             let n = Num::min(length - i, MAX_BUFFER_SIZE);
             node.process(n, &BufferRef::new(&[]), &mut buffer_mut);
@@ -459,7 +726,15 @@ impl Wave {
                 }
             }
             i += n;
+            wave.len = i;
+            if i >= next_report && i < length {
+                next_report += PROGRESS_INTERVAL;
+                if !callback(i as f64 / length as f64) {
+                    return wave;
+                }
+            }
         }
+        callback(1.0);
         wave
     }
 
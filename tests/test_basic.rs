@@ -284,6 +284,13 @@ fn test_basic() {
     net.check();
     check_wave(net);
 
+    check_wave(
+        noise() >> transistor_ladder_hz(1500.0, 0.8) | noise() >> diode_ladder_hz(500.0, 0.4),
+    );
+    check_wave(
+        noise() >> sallen_key_lowpass_hz(1500.0, 0.8) | noise() >> ota_lowpass_hz(500.0, 0.4),
+    );
+
     let mut net = Net::new(0, 2);
     net.chain(Box::new(noise()));
     net.chain(Box::new(lowpole_hz(1000.0) ^ lowpole_hz(500.0)));
@@ -334,12 +341,32 @@ fn test_basic() {
         &input,
         resonator_hz(440.0, 110.0) | resonator_hz(880.0, 110.0),
     );
+    check_wave_filter(&input, bitcrush(3.5) | downsample(2.5));
     let tap_node = (pass() | lfo(|t| (abs(spline_noise(4, t)), abs(spline_noise(5, t)))))
         >> multitap::<U2>(0.0, 1.0);
     check_wave_filter(&input, tap_node.clone() | tap_node.clone());
     let tap_node = (pass() | lfo(|t| (abs(spline_noise(6, t)), abs(spline_noise(7, t)))))
         >> multitap_linear::<U2>(0.0, 1.0);
     check_wave_filter(&input, tap_node.clone() | tap_node.clone());
+    // Fixed delays take the contiguous SIMD path in `process`.
+    check_wave_filter(&input, delay(0.0) | delay(1.0 / 44100.0));
+    check_wave_filter(&input, delay(3.0 / 44100.0) | delay(0.1));
+    check_wave_filter(&input, join::<U2>() >> haas(0.01, HaasSide::Left));
+    check_wave_filter(&input, join::<U2>() >> haas(0.02, HaasSide::Right));
+    check_wave_filter(
+        &input,
+        (pass() | dc(0.01)) >> tap(0.0, 0.01) | (pass() | dc(0.0)) >> tap(0.0, 0.01),
+    );
+    check_wave_filter(
+        &input,
+        (pass() | dc((0.0031, 0.5))) >> multitap::<U2>(0.0, 0.5)
+            | (pass() | dc((0.5, 0.00001))) >> multitap_linear::<U2>(0.0, 0.5),
+    );
+    check_wave_filter(
+        &input,
+        (pass() | dc(0.01)) >> tap_linear(0.0, 0.01)
+            | (pass() | dc(3.5 / 44100.0)) >> tap_linear(0.0, 0.01),
+    );
 
     // Check cycle.
     let mut cycle = Net::new(2, 1);
@@ -671,3 +698,1772 @@ fn test_resynth() {
         );
     }
 }
+
+#[test]
+/// Test pass-through resynthesizers with different overlaps.
+fn test_resynth_overlap() {
+    let window = 64;
+    let duration = 1024.0 / DEFAULT_SR;
+    let input = Wave::render(DEFAULT_SR, duration, &mut noise());
+    for overlaps in [2, 4, 8, 16] {
+        let mut synth = resynth_overlap::<U1, U1, _>(window, overlaps, |fft| {
+            assert_eq!(fft.overlaps() * fft.hop_size(), fft.length());
+            for i in 0..fft.bins() {
+                fft.set(0, i, fft.at(0, i));
+            }
+        });
+        assert_eq!(synth.overlaps(), overlaps);
+        let output = input.filter_latency(duration, &mut synth);
+        // Inputs are reconstructed once all windows are overlapping.
+        for i in window..input.length() {
+            assert!((input.at(0, i) - output.at(0, i)).abs() <= 1.0e-5);
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+#[test]
+fn test_shared_f64() {
+    // Double precision values survive the round trip.
+    let x = 1.0 + 1.0e-12;
+    let shared = shared_f64(x);
+    assert_eq!(shared.value(), x);
+    shared.set(-x);
+    assert_eq!(shared.value(), -x);
+
+    // The function of `var_fn_f64` receives the full precision value.
+    let mut node = var_fn_f64(&shared, |x| ((x + 1.0) * 1.0e12) as f32);
+    assert!(abs(node.get_mono() + 1.0) < 1.0e-3);
+
+    let mut node = var_f64(&shared);
+    assert_eq!(node.get_mono(), -1.0);
+    shared.set_value(0.5);
+    assert_eq!(node.get_mono(), 0.5);
+}
+
+#[test]
+fn test_shared_array() {
+    // Written values can be read back from the array and from the node.
+    let times = shared_array((0.01, 0.1, 0.5, 0.2));
+    let mut node = var_array(&times);
+    assert_eq!(node.inputs(), 0);
+    assert_eq!(node.outputs(), 4);
+    assert_eq!(
+        node.tick(&Frame::default()),
+        Frame::from([0.01, 0.1, 0.5, 0.2])
+    );
+    times.set(2, 0.25);
+    assert_eq!(times.value(2), 0.25);
+    times.set_values(&Frame::from([1.0, 2.0, 3.0, 4.0]));
+    assert_eq!(times.values(), Frame::from([1.0, 2.0, 3.0, 4.0]));
+    assert_eq!(
+        node.tick(&Frame::default()),
+        Frame::from([1.0, 2.0, 3.0, 4.0])
+    );
+
+    // Snapshots written from another thread are always read consistently.
+    let parameters = shared_snapshot((0.0, 0.0, 0.0, 0.0));
+    let mut node = var_snapshot(&parameters);
+    let writer = parameters.clone();
+    let thread = std::thread::spawn(move || {
+        for i in 1..=10000 {
+            let x = i as f32;
+            writer.set_values(&Frame::from([x, -x, x, -x]));
+        }
+    });
+    while !thread.is_finished() {
+        let y = node.tick(&Frame::default());
+        assert!(y[0] == y[2] && y[1] == y[3] && y[0] == -y[1]);
+    }
+    thread.join().unwrap();
+    assert_eq!(
+        parameters.values(),
+        Frame::from([10000.0, -10000.0, 10000.0, -10000.0])
+    );
+    assert_eq!(node.tick(&Frame::default()), parameters.values());
+}
+
+#[test]
+fn test_var_smooth() {
+    // Smoothing inside the node matches the `var >> follow` idiom.
+    let amp = shared(0.5);
+    let mut x = var_smooth(&amp, 0.01);
+    let mut y = var(&amp) >> follow(0.01);
+    x.set_sample_rate(48000.0);
+    y.set_sample_rate(48000.0);
+    // The first sample jumps straight to the current value.
+    assert_eq!(x.get_mono(), 0.5);
+    y.get_mono();
+    amp.set(1.0);
+    for _ in 0..1000 {
+        assert!(abs(x.get_mono() - y.get_mono()) < 1.0e-6);
+    }
+
+    // Separate attack and release times.
+    let mut x = var_asmooth(&amp, 0.001, 0.1);
+    let mut y = var(&amp) >> afollow(0.001, 0.1);
+    let mut output = BufferVec::new(1);
+    for i in 0..100 {
+        amp.set(if i % 50 < 25 { 1.0 } else { 0.0 });
+        x.process(64, &BufferRef::empty(), &mut output.buffer_mut());
+        for j in 0..64 {
+            assert!(abs(output.at_f32(0, j) - y.get_mono()) < 1.0e-6);
+        }
+    }
+}
+
+#[test]
+fn test_smooth_pole() {
+    // Step response reaches 1 - 1/e in one time constant.
+    let mut node = smooth_pole_samples(100.0);
+    let mut y = 0.0;
+    for _ in 0..100 {
+        y = node.filter_mono(1.0);
+    }
+    assert!(abs(y - (1.0 - exp(-1.0))) < 1.0e-3);
+
+    // The time constant in samples does not depend on the sample rate.
+    node.set_sample_rate(96000.0);
+    node.reset();
+    for _ in 0..100 {
+        y = node.filter_mono(1.0);
+    }
+    assert!(abs(y - (1.0 - exp(-1.0))) < 1.0e-3);
+
+    // Cutoff in Hz: time constant is 1 / (2 pi f) seconds.
+    let sample_rate = 48000.0;
+    let cutoff = 10.0;
+    let mut node = smooth_pole(cutoff);
+    node.set_sample_rate(sample_rate);
+    let tau = (sample_rate / (f64::TAU * cutoff as f64)).round() as usize;
+    for _ in 0..tau {
+        y = node.filter_mono(1.0);
+    }
+    assert!(abs(y - (1.0 - exp(-1.0))) < 1.0e-3);
+
+    // Change the cutoff with a setting.
+    node.set(Setting::center(100.0));
+    node.reset();
+    for _ in 0..tau / 10 {
+        y = node.filter_mono(1.0);
+    }
+    assert!(abs(y - (1.0 - exp(-1.0))) < 2.0e-3);
+}
+
+#[test]
+fn test_ramped_setting() {
+    // A ramped constant moves from the old value to the new one without discontinuities.
+    let mut node = dc(0.0);
+    node.set_sample_rate(1000.0);
+    node.set_ramped(Setting::value(1.0), 0.1);
+    let mut previous = 0.0;
+    for i in 1..=100 {
+        let x = node.get_mono();
+        assert!(abs(x - previous - 0.01) < 1.0e-5);
+        assert!(abs(x - i as f32 * 0.01) < 1.0e-4);
+        previous = x;
+    }
+    // The ramp ends exactly at the target.
+    assert_eq!(node.get_mono(), 1.0);
+
+    // Block processing follows the same ramp.
+    let mut x = dc(0.0);
+    let mut y = dc(0.0);
+    x.set(Setting::value(-1.0).ramp(0.005));
+    y.set(Setting::value(-1.0).ramp(0.005));
+    let mut output = BufferVec::new(1);
+    for _ in 0..10 {
+        x.process(64, &BufferRef::empty(), &mut output.buffer_mut());
+        for i in 0..64 {
+            assert_eq!(output.at_f32(0, i), y.get_mono());
+        }
+    }
+
+    // Filter cutoff is interpolated exponentially.
+    let mut filter = lowpass_hz(1000.0, 1.0);
+    filter.set_sample_rate(1000.0);
+    filter.set_ramped(Setting::center(4000.0), 0.01);
+    for _ in 0..5 {
+        filter.filter_mono(0.0);
+    }
+    assert!(abs(filter.cutoff() - 2000.0) < 0.1);
+    for _ in 0..5 {
+        filter.filter_mono(0.0);
+    }
+    assert_eq!(filter.cutoff(), 4000.0);
+
+    // Ramped settings reach nested nodes through addresses.
+    let mut node = pass() * dc(1.0) >> lowpass_hz(1000.0, 0.5);
+    for _ in 0..1000 {
+        node.filter_mono(1.0);
+    }
+    node.set_ramped(Setting::value(0.0).left().right(), 0.01);
+    let mut previous = 1.0;
+    for _ in 0..1000 {
+        let x = node.filter_mono(1.0);
+        assert!(x <= previous + 1.0e-3);
+        previous = x;
+    }
+    assert!(abs(previous) < 1.0e-3);
+
+    // Without a ramp, settings apply instantaneously.
+    let mut node = dc(0.0);
+    node.set(Setting::value(1.0));
+    assert_eq!(node.get_mono(), 1.0);
+}
+
+#[test]
+fn test_spectrum_snoop() {
+    let (mut frontend, backend) = spectrum_snoop(1024);
+    assert_eq!(frontend.bins(), 513);
+    let mut node = sine_hz(3000.0) * 0.5 >> backend;
+    node.set_sample_rate(48000.0);
+    assert_eq!(frontend.bin_frequency(64), 3000.0);
+    assert_eq!(frontend.windows(), 0);
+
+    // The spectrum is updated once per window.
+    for _ in 0..1023 {
+        node.get_mono();
+    }
+    assert_eq!(frontend.windows(), 0);
+    let x = node.get_mono();
+    assert_eq!(frontend.windows(), 1);
+
+    // A sine wave centered on a bin shows up with its amplitude there and nowhere else.
+    assert!(abs(frontend.magnitude(64) - 0.5) < 1.0e-3);
+    let mut spectrum = vec![0.0; frontend.bins()];
+    frontend.spectrum(&mut spectrum);
+    for (i, magnitude) in spectrum.iter().enumerate() {
+        if i < 63 || i > 65 {
+            assert!(*magnitude < 1.0e-3);
+        }
+    }
+
+    // Time domain data is available as with `snoop`.
+    frontend.update();
+    assert_eq!(frontend.total(), 1024);
+    assert_eq!(frontend.at(0), x);
+}
+
+#[test]
+fn test_snoop_triggered() {
+    let (mut frontend, mut backend) = snoop_triggered(1024, 0.0);
+    assert_eq!(frontend.trigger_level(), Some(0.0));
+    let mut waveform = [0.0; 256];
+    // Silence has no rising crossings: fall back to free-running mode.
+    for _ in 0..1024 {
+        backend.filter_mono(-0.5);
+    }
+    frontend.update();
+    assert!(!frontend.triggered(&mut waveform));
+    assert!(waveform.iter().all(|x| *x == -0.5));
+
+    // Triggered waveforms start at the same phase no matter when they are read.
+    let mut node = sine_hz(100.0) >> backend;
+    let mut previous: Option<[f32; 256]> = None;
+    for _ in 0..10 {
+        for _ in 0..1000 {
+            node.get_mono();
+        }
+        frontend.update();
+        assert!(frontend.triggered(&mut waveform));
+        assert!(waveform[0] >= 0.0 && waveform[0] < 0.02);
+        assert!(waveform[110] > 0.99);
+        if let Some(previous) = previous {
+            for (x, y) in waveform.iter().zip(previous.iter()) {
+                assert!(abs(x - y) < 0.02);
+            }
+        }
+        previous = Some(waveform);
+    }
+
+    // A snoop without a trigger is always free-running.
+    let (mut frontend, mut backend) = snoop(1024);
+    for i in 0..1024 {
+        backend.filter_mono(i as f32);
+    }
+    frontend.update();
+    assert!(!frontend.triggered(&mut waveform));
+    assert_eq!(waveform[255], 1023.0);
+    assert_eq!(waveform[0], 768.0);
+}
+
+#[test]
+fn test_sequencer_render() {
+    let sample_rate = 44100.0;
+    let mut sequencer = Sequencer::new(true, 1);
+    sequencer.push(0.1, 0.2, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    sequencer.push(0.5, 0.6, Fade::Smooth, 0.0, 0.0, Box::new(dc(2.0)));
+    let wave = sequencer.render(1.0, sample_rate);
+    assert_eq!(wave.channels(), 1);
+    assert_eq!(wave.len(), 44100);
+    // Both notes appear at their scheduled times with sample accuracy.
+    for i in 0..wave.len() {
+        let t = i as f64 / sample_rate;
+        let expected = if (0.1..0.2).contains(&t) {
+            1.0
+        } else if (0.5..0.6).contains(&t) {
+            2.0
+        } else {
+            0.0
+        };
+        assert!(
+            wave.at(0, i) == expected
+                || (t - 0.1).abs() < 2.0 / sample_rate
+                || (t - 0.2).abs() < 2.0 / sample_rate
+                || (t - 0.5).abs() < 2.0 / sample_rate
+                || (t - 0.6).abs() < 2.0 / sample_rate
+        );
+    }
+
+    // Rendering again from the start gives the same result.
+    sequencer.reset();
+    let wave2 = sequencer.render(1.0, sample_rate);
+    assert!(wave.channel(0) == wave2.channel(0));
+}
+
+#[test]
+fn test_sequencer_loop() {
+    let sample_rate = 44100.0;
+    let mut sequencer = Sequencer::new(false, 1);
+    sequencer.push(0.0, 0.1, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    sequencer.push(1.5, 1.6, Fade::Smooth, 0.0, 0.0, Box::new(dc(2.0)));
+    sequencer.set_loop(0.0, 1.0);
+    let wave = sequencer.render(2.5, sample_rate);
+    // The event at time 0 fires again at 1 s and 2 s.
+    for i in [0, 2000, 44100, 46100, 88200, 90200] {
+        assert_eq!(wave.at(0, i), 1.0);
+    }
+    // The event outside the loop region never plays.
+    for i in [10000, 50000, 66150, 95000] {
+        assert_eq!(wave.at(0, i), 0.0);
+    }
+    assert!(sequencer.time() < 1.0);
+
+    // Sample by sample processing wraps at the same point.
+    sequencer.reset();
+    sequencer.push(0.0, 0.1, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    let mut output = [0.0];
+    for i in 0..88200 {
+        sequencer.tick(&[], &mut output);
+        assert_eq!(output[0], if i % 44100 < 4410 { 1.0 } else { 0.0 });
+    }
+
+    // After clearing the loop, time moves forward again.
+    sequencer.clear_loop();
+    for _ in 0..88200 {
+        sequencer.tick(&[], &mut output);
+    }
+    assert!(sequencer.time() > 1.5);
+}
+
+#[test]
+fn test_sequencer_beat() {
+    // At 120 BPM a beat lasts half a second.
+    assert_eq!(beat_time(120.0, 0.0, 0.0), 0.0);
+    assert_eq!(beat_time(120.0, 3.0, 0.0), 1.5);
+    assert_eq!(beat_time(120.0, 2.5, 0.0), 1.25);
+    // Swing delays only the off-beat.
+    assert_eq!(beat_time(120.0, 3.0, 0.5), 1.5);
+    assert!(beat_time(120.0, 2.5, 0.5) > 1.25);
+    assert!((beat_time(120.0, 2.5, 1.0) - (1.0 + 1.0 / 3.0)).abs() < 1.0e-12);
+
+    let sample_rate = 44100.0;
+    let mut sequencer = Sequencer::new(false, 1);
+    sequencer.push_at_beat(120.0, 3.0, 1.0, Box::new(dc(1.0)));
+    let wave = sequencer.render(3.0, sample_rate);
+    // The event occupies 1.5...2.0 seconds.
+    assert_eq!(wave.at(0, 66149), 0.0);
+    assert_eq!(wave.at(0, 66150), 1.0);
+    assert_eq!(wave.at(0, 88199), 1.0);
+    assert_eq!(wave.at(0, 88200), 0.0);
+}
+
+#[cfg(feature = "midi")]
+#[test]
+fn test_sequencer_midi() {
+    // Format 0 file, 96 ticks per beat, tempo 60 BPM.
+    let bytes: [u8; 57] = [
+        b'M', b'T', b'h', b'd', 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x01, 0x00, 0x60, b'M',
+        b'T', b'r', b'k', 0x00, 0x00, 0x00, 0x23, // track header
+        0x00, 0xff, 0x51, 0x03, 0x0f, 0x42, 0x40, // tempo 1000000 us per beat
+        0x00, 0x90, 0x3c, 0x64, // note on 60
+        0x60, 0x80, 0x3c, 0x40, // note off 60
+        0x00, 0x90, 0x40, 0x64, // note on 64
+        0x00, 0x90, 0x43, 0x64, // note on 67
+        0x60, 0x90, 0x40, 0x00, // note on 64 with zero velocity
+        0x00, 0x80, 0x43, 0x00, // note off 67
+        0x00, 0xff, 0x2f, 0x00, // end of track
+    ];
+    let notes = fundsp::midi::midi_notes(&bytes).unwrap();
+    assert_eq!(notes.len(), 3);
+    assert_eq!(notes[0].key, 60);
+    assert_eq!(notes[0].velocity, 100);
+    assert!(notes[0].start_time == 0.0 && notes[0].end_time == 1.0);
+    assert!(notes[1].start_time == 1.0 && notes[1].end_time == 2.0);
+    assert!(notes[2].start_time == 1.0 && notes[2].end_time == 2.0);
+
+    let path = std::env::temp_dir().join("fundsp_test_sequencer_midi.mid");
+    std::fs::write(&path, bytes).unwrap();
+    let mut sequencer = Sequencer::new(false, 1);
+    let events = sequencer
+        .load_midi(&path, |note| Box::new(dc(note.key as f32)))
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(events, 3);
+    let wave = sequencer.render(2.5, 1000.0);
+    assert_eq!(wave.at(0, 500), 60.0);
+    assert_eq!(wave.at(0, 1500), 64.0 + 67.0);
+    assert_eq!(wave.at(0, 2200), 0.0);
+
+    assert!(sequencer
+        .load_midi_slice(b"not a MIDI file", |_| Box::new(zero()))
+        .is_err());
+}
+
+#[cfg(feature = "midi")]
+#[test]
+fn test_midi_control() {
+    use fundsp::midi::*;
+    let mut control = MidiControl::new(2);
+    let cutoff = shared(0.0);
+    control.map_control(74, &cutoff, 100.0, 1100.0);
+
+    // Two notes occupy both voices.
+    assert!(control.handle(&[0x90, 69, 127]));
+    assert!(control.handle(&[0x91, 57, 64]));
+    assert_eq!(control.voice(0).frequency.value(), 440.0);
+    assert_eq!(control.voice(0).velocity.value(), 1.0);
+    assert_eq!(control.voice(1).frequency.value(), 220.0);
+    assert_eq!(control.voice(1).key(), Some(57));
+
+    // A third note steals the oldest voice.
+    assert!(control.handle(&[0x90, 81, 100]));
+    assert_eq!(control.voice(0).key(), Some(81));
+    assert_eq!(control.voice(0).frequency.value(), 880.0);
+
+    // Note-off and note-on with zero velocity release voices.
+    assert!(control.handle(&[0x80, 81, 0]));
+    assert_eq!(control.voice(0).gate.value(), 0.0);
+    assert!(control.handle(&[0x91, 57, 0]));
+    assert_eq!(control.voice(1).gate.value(), 0.0);
+    assert!(!control.handle(&[0x80, 60, 0]));
+
+    // The voice released longest ago is reused first.
+    control.handle(&[0x90, 60, 100]);
+    assert_eq!(control.voice(0).key(), Some(60));
+
+    // Pitch bend to the maximum is close to two semitones up.
+    assert!(control.handle(&[0xe0, 0x7f, 0x7f]));
+    assert!((control.pitch_bend().value() - semitone_ratio(2.0)).abs() < 1.0e-3);
+    control.handle(&[0xe0, 0x00, 0x40]);
+    assert_eq!(control.pitch_bend().value(), 1.0);
+
+    // Controllers.
+    assert!(control.handle(&[0xb0, 1, 127]));
+    assert_eq!(control.mod_wheel().value(), 1.0);
+    assert!(control.handle(&[0xb0, 74, 127]));
+    assert_eq!(cutoff.value(), 1100.0);
+    assert!(!control.handle(&[0xb0, 75, 127]));
+
+    // Channel filtering.
+    control.set_channel(Some(2));
+    assert!(!control.handle(&[0x90, 62, 100]));
+    assert!(control.handle(&[0x92, 62, 100]));
+}
+
+/// Render `length` samples from `unit` in blocks of `block` samples.
+#[cfg(feature = "scalar")]
+fn render_blocks(unit: &mut dyn AudioUnit, length: usize, block: usize) -> Vec<f32> {
+    let mut output = BufferVec::new(unit.outputs());
+    let mut samples = Vec::new();
+    let mut i = 0;
+    while i < length {
+        let size = min(block, length - i);
+        unit.process(size, &BufferRef::empty(), &mut output.buffer_mut());
+        for j in 0..size {
+            for channel in 0..unit.outputs() {
+                samples.push(output.at_f32(channel, j));
+            }
+        }
+        i += size;
+    }
+    samples
+}
+
+#[cfg(feature = "scalar")]
+#[test]
+fn test_scalar_process() {
+    // With the `scalar` feature, block processing is bit-identical to `tick`
+    // regardless of block size.
+    let mut net = Net::wrap(Box::new(
+        (noise().seed(1) >> lowpass_hz(2000.0, 1.0) | saw_hz(110.0) * 0.5 | sine_hz(220.0))
+            >> (pass() | mul(0.7) | shape(Clip(2.0)))
+            >> join::<U3>()
+            >> shape(Tanh(2.0))
+            >> split::<U2>()
+            >> (delay(0.001) | highpass_hz(200.0, 0.5)),
+    ));
+    net = net >> (Net::wrap(Box::new(pan(0.2) + pan(-0.3))) >> multipass::<U2>() * 0.5);
+    net.set_sample_rate(48000.0);
+    let length = 1000;
+
+    let mut reference = Vec::new();
+    net.reset();
+    let mut output = [0.0; 2];
+    for _ in 0..length {
+        net.tick(&[], &mut output);
+        reference.extend_from_slice(&output);
+    }
+
+    for block in [1, 7, 64] {
+        net.reset();
+        assert_eq!(render_blocks(&mut net, length, block), reference);
+    }
+}
+
+#[test]
+fn test_memory_usage() {
+    // Stateless nodes allocate nothing.
+    assert_eq!(sine_hz(110.0).memory_usage(), 0);
+    assert_eq!((pass() * 0.5).memory_usage(), 0);
+
+    // A one second delay line holds at least one second of samples.
+    let mut node = delay(1.0);
+    node.set_sample_rate(48000.0);
+    let line = node.memory_usage();
+    assert!(line >= 48000 * core::mem::size_of::<f32>());
+
+    // Combinators sum their children.
+    let mut node = delay(1.0) >> split::<U2>() >> (pass() | delay(1.0)) >> join::<U2>();
+    node.set_sample_rate(48000.0);
+    assert_eq!(node.memory_usage(), 2 * line);
+
+    // Networks include their own buffers in addition to their nodes.
+    let mut net = Net::new(1, 1);
+    let id = net.push(Box::new(delay(1.0)));
+    net.pipe_input(id);
+    net.pipe_output(id);
+    net.set_sample_rate(48000.0);
+    net.allocate();
+    assert!(net.memory_usage() > line);
+}
+
+#[test]
+fn test_resynth_with() {
+    // Scratch storage size matches the documented formula.
+    assert_eq!(
+        ResynthScratch::<typenum::U1024, U1, U1>::bytes(),
+        16 * 1026 + 32 * 1024 + 4 * 1024
+    );
+
+    // Fixed storage resynthesis matches heap allocated resynthesis.
+    fn process<S: FftStorage>(fft: &mut FftWindow<S>) {
+        for i in 0..fft.bins() {
+            if fft.frequency(i) <= 2000.0 {
+                fft.set(0, i, fft.at(0, i) * 0.5);
+            }
+        }
+    }
+    let mut heap = resynth::<U1, U1, _>(64, process);
+    let mut fixed = resynth_with(ResynthScratch::<U64, U1, U1>::new(), process);
+    assert!(heap.memory_usage() > 0);
+    assert_eq!(fixed.memory_usage(), 0);
+    let mut rnd = funutd::Rnd::from_u64(1);
+    for _ in 0..1000 {
+        let x = rnd.f32() * 2.0 - 1.0;
+        assert_eq!(heap.filter_mono(x), fixed.filter_mono(x));
+    }
+}
+
+#[test]
+fn test_binaural() {
+    let sample_rate = 48000.0;
+    // Hard left: the right channel is delayed and attenuated.
+    let mut node = binaural(-0.5 * f32::PI, 0.0);
+    node.set_sample_rate(sample_rate);
+    let response: Vec<Frame<f32, U2>> = (0..256)
+        .map(|i| node.tick(&Frame::from([if i == 0 { 1.0 } else { 0.0 }])))
+        .collect();
+    let onset = |channel: usize| {
+        response
+            .iter()
+            .position(|x| abs(x[channel]) > 1.0e-3)
+            .unwrap()
+    };
+    let energy = |channel: usize| {
+        response
+            .iter()
+            .map(|x| x[channel] * x[channel])
+            .sum::<f32>()
+    };
+    // Woodworth interaural time difference for a lateral source is about 0.66 ms.
+    let itd = onset(1) - onset(0);
+    assert!(itd >= 30 && itd <= 33);
+    assert!(energy(1) < energy(0) * 0.5);
+
+    // Straight ahead, the channels are identical.
+    let mut node = binaural(0.0, 0.0);
+    let mut input = noise().seed(1);
+    for _ in 0..1000 {
+        let output = node.tick(&Frame::from([input.get_mono()]));
+        assert_eq!(output[0], output[1]);
+    }
+
+    // Direction can be modulated via inputs.
+    let mut moving = binaural_in();
+    let mut fixed = binaural(0.3, 0.2);
+    let mut input = noise().seed(2);
+    for _ in 0..1000 {
+        let x = input.get_mono();
+        assert_eq!(
+            moving.tick(&Frame::from([x, 0.3, 0.2])),
+            fixed.tick(&Frame::from([x]))
+        );
+    }
+}
+
+#[test]
+fn test_crossfade() {
+    // Equal power crossfade keeps the power of uncorrelated signals constant.
+    let length = 100000;
+    for mix in [0.0, 0.25, 0.5, 0.75, 1.0] {
+        let mut node =
+            (noise().seed(1) | noise().seed(2) | dc(mix)) >> crossfade(CrossfadeLaw::Power);
+        let power = (0..length)
+            .map(|_| squared(node.get_mono() as f64))
+            .sum::<f64>()
+            / length as f64;
+        // The power of uniform noise in -1...1 is 1/3.
+        assert!(abs(power - 1.0 / 3.0) < 0.01);
+    }
+
+    // Linear crossfade is a weighted sum.
+    let mut node = crossfade(CrossfadeLaw::Linear);
+    assert_eq!(node.tick(&Frame::from([1.0, 2.0, 0.25]))[0], 1.25);
+    assert_eq!(node.tick(&Frame::from([1.0, 2.0, -1.0]))[0], 1.0);
+    assert_eq!(node.tick(&Frame::from([1.0, 2.0, 2.0]))[0], 2.0);
+
+    // The equal power midpoint is -3 dB.
+    let mut node = crossfade(CrossfadeLaw::Power);
+    assert!(abs(node.tick(&Frame::from([1.0, 0.0, 0.5]))[0] - f32::SQRT_2 * 0.5) < 1.0e-6);
+
+    // Block processing matches tick.
+    check_wave(
+        (noise().seed(1)
+            | noise().seed(2)
+            | noise().seed(3)
+            | noise().seed(4)
+            | (sine_hz(10.0) * 0.5 + 0.5))
+            >> multicrossfade::<U2>(CrossfadeLaw::Power),
+    );
+}
+
+#[test]
+fn test_selector() {
+    let sample_rate = 1000.0;
+    let mut node = selector::<U3>();
+    node.set_sample_rate(sample_rate);
+    let channels = [1.0, 2.0, 3.0];
+    let mut select = |index: f32| node.tick(&Frame::from([1.0, 2.0, 3.0, index]))[0];
+
+    // The first selection takes effect immediately.
+    assert_eq!(select(1.2), channels[1]);
+    assert_eq!(select(0.8), channels[1]);
+
+    // After a switch, the output crossfades to the new channel in 10 ms.
+    let faded: Vec<f32> = (0..10).map(|_| select(2.0)).collect();
+    assert!(faded[0] > channels[1] && faded[0] < channels[2]);
+    assert!(faded.windows(2).all(|x| x[0] <= x[1]));
+    assert_eq!(faded[9], channels[2]);
+    assert_eq!(select(2.0), channels[2]);
+
+    // Index is clamped to the available channels.
+    for _ in 0..10 {
+        select(-5.0);
+    }
+    assert_eq!(select(-5.0), channels[0]);
+    for _ in 0..10 {
+        select(10.0);
+    }
+    assert_eq!(select(10.0), channels[2]);
+}
+
+#[test]
+fn test_align_latency() {
+    let sample_rate = 48000.0;
+    let mut limited = limiter(0.001, 0.01);
+    limited.set_sample_rate(sample_rate);
+    let latency = round(limited.latency().unwrap()) as usize;
+    assert!(latency > 0);
+
+    // The pass-through branch is delayed to match the limiter.
+    let mut node = align_latency(limiter(0.001, 0.01), pass());
+    node.set_sample_rate(sample_rate);
+    assert_eq!(node.compensation(0), 0);
+    assert_eq!(node.compensation(1), latency);
+    let response: Vec<Frame<f32, U2>> = (0..latency * 2)
+        .map(|i| {
+            let x = if i == 0 { 0.5 } else { 0.0 };
+            node.tick(&Frame::from([x, x]))
+        })
+        .collect();
+    let onset = |channel: usize| {
+        response
+            .iter()
+            .position(|x| abs(x[channel]) > 1.0e-6)
+            .unwrap()
+    };
+    assert_eq!(onset(0), latency);
+    assert_eq!(onset(1), latency);
+    assert_eq!(response[latency][1], 0.5);
+
+    // A single sample delay is not latency, so nothing is compensated.
+    let node = align_latency(tick(), pass());
+    assert_eq!(node.compensation(0), 0);
+    assert_eq!(node.compensation(1), 0);
+
+    // Block processing delays the pass-through branch by the same amount.
+    let mut node = align_latency(limiter(0.001, 0.01), pass());
+    let mut reference = limiter(0.001, 0.01);
+    let latency = node.compensation(1);
+    let mut input = noise().seed(1);
+    let mut input_buffer = BufferVec::new(2);
+    let mut output_buffer = BufferVec::new(2);
+    let mut reference_buffer = BufferVec::new(1);
+    let mut history = Vec::new();
+    for _ in 0..8 {
+        for i in 0..MAX_BUFFER_SIZE {
+            let x = input.get_mono();
+            input_buffer.set_f32(0, i, x);
+            input_buffer.set_f32(1, i, x);
+            history.push(x);
+        }
+        node.process(
+            MAX_BUFFER_SIZE,
+            &input_buffer.buffer_ref(),
+            &mut output_buffer.buffer_mut(),
+        );
+        reference.process(
+            MAX_BUFFER_SIZE,
+            &input_buffer.buffer_ref().subset(0, 1),
+            &mut reference_buffer.buffer_mut(),
+        );
+        let start = history.len() - MAX_BUFFER_SIZE;
+        for i in 0..MAX_BUFFER_SIZE {
+            assert_eq!(output_buffer.at_f32(0, i), reference_buffer.at_f32(0, i));
+            let delayed = if start + i >= latency {
+                history[start + i - latency]
+            } else {
+                0.0
+            };
+            assert_eq!(output_buffer.at_f32(1, i), delayed);
+        }
+    }
+}
+
+#[test]
+fn test_pitch_detector() {
+    let frequency = shared(0.0);
+    let confidence = shared(0.0);
+    let mut node = sine_hz(220.0) >> pitch_detector(1024, &frequency, &confidence);
+    for _ in 0..8192 {
+        node.get_mono();
+    }
+    // The estimate is within a few cents.
+    assert!(abs(1200.0 * log2(frequency.value() / 220.0)) < 3.0);
+    assert!(confidence.value() > 0.9);
+
+    // Noise is not periodic.
+    let mut node = noise().seed(1) >> pitch_detector(1024, &frequency, &confidence);
+    for _ in 0..8192 {
+        node.get_mono();
+    }
+    assert!(confidence.value() < 0.5);
+}
+
+#[test]
+fn test_onset_detector() {
+    // Click track: one click every 0.25 seconds over quiet noise.
+    let period = 11025;
+    let clicks = 8;
+    let mut node = onset_detector(0.5);
+    let mut background = noise().seed(1) * 0.001;
+    let mut triggers = Vec::new();
+    for i in 0..period * clicks {
+        let x = background.get_mono() + if i % period == 100 { 1.0 } else { 0.0 };
+        let output = node.tick(&Frame::from([x]));
+        assert_eq!(output[0], x);
+        if output[1] > 0.0 {
+            triggers.push(i);
+        }
+    }
+    assert_eq!(triggers.len(), clicks);
+    for (k, i) in triggers.iter().enumerate() {
+        // Each click is reported within the latency bound.
+        let click = k * period + 100;
+        assert!(*i > click && *i <= click + 512);
+    }
+}
+
+#[test]
+fn test_downcast() {
+    let mut unit: Box<dyn AudioUnit> = Box::new(lowpass_hz(1000.0, 1.0));
+    assert!(unit
+        .as_any()
+        .downcast_ref::<An<FixedSvf<f64, LowpassMode<f64>>>>()
+        .is_some());
+    assert!(unit.as_any().downcast_ref::<Net>().is_none());
+    let node = unit
+        .as_any_mut()
+        .downcast_mut::<An<FixedSvf<f64, LowpassMode<f64>>>>()
+        .unwrap();
+    node.set_cutoff(2000.0);
+    assert_eq!(node.cutoff(), 2000.0);
+
+    let mut unit: Box<dyn AudioUnit> = Box::new(Net::wrap(Box::new(sine_hz(440.0))));
+    let net = unit.as_any_mut().downcast_mut::<Net>().unwrap();
+    assert_eq!(net.size(), 1);
+}
+
+#[test]
+fn test_mixer_matrix() {
+    let mut node =
+        mixer_matrix::<U4, U2>([[1.0, 0.5, 0.0, 0.25].into(), [0.0, 0.5, 1.0, 0.25].into()].into());
+    let input = Frame::from([1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(node.tick(&input), Frame::from([3.0, 5.0]));
+    node.set(Setting::matrix(1, 3, -1.0));
+    assert_eq!(node.tick(&input), Frame::from([3.0, 0.0]));
+    // Out of range cells are ignored.
+    node.set(Setting::matrix(2, 0, 1.0));
+    assert_eq!(node.gain(1, 3), -1.0);
+
+    // The mixer is linear, so responses from all inputs are summed.
+    let mut node =
+        (lowpass_hz(1000.0, 1.0) | pass()) >> mixer_matrix::<U2, U1>([[0.5, 0.5].into()].into());
+    let f = 1000.0;
+    let response = node.response(0, f).unwrap();
+    let expected = (lowpass_hz(1000.0, 1.0).response(0, f).unwrap() + 1.0) * 0.5;
+    assert!((response - expected).norm() < 1.0e-6);
+}
+
+#[test]
+fn test_stereo_law() {
+    // Identical channels sum to their own level at -6 dB and do not clip.
+    let mut node = to_mono(StereoLaw::Minus6dB);
+    assert_eq!(node.tick(&Frame::from([1.0, 1.0]))[0], 1.0);
+    assert_eq!(node.tick(&Frame::from([1.0, -1.0]))[0], 0.0);
+    // A plain sum doubles the level.
+    let mut node = to_mono(StereoLaw::Unity);
+    assert_eq!(node.tick(&Frame::from([1.0, 1.0]))[0], 2.0);
+
+    // At -3 dB, the power of uncorrelated channels is preserved.
+    let mut node = (noise().seed(1) | noise().seed(2)) >> to_mono(StereoLaw::Minus3dB);
+    let mut reference = noise().seed(1);
+    let (mut power, mut reference_power) = (0.0, 0.0);
+    for _ in 0..100000 {
+        power += squared(node.get_mono());
+        reference_power += squared(reference.get_mono());
+    }
+    assert!((power / reference_power - 1.0).abs() < 0.02);
+
+    // A mono signal spread at -3 dB matches a center pan.
+    let mut node = to_stereo(StereoLaw::Minus3dB);
+    let mut center = pan(0.0);
+    let input = Frame::from([0.5]);
+    let output = node.tick(&input);
+    let expected = center.tick(&input);
+    assert!((output[0] - expected[0]).abs() < 1.0e-6 && (output[1] - expected[1]).abs() < 1.0e-6);
+    // Dual mono copies the signal and a -6 dB spread sums back to it.
+    let mut node = to_stereo(StereoLaw::Unity);
+    assert_eq!(node.tick(&Frame::from([0.5])), Frame::from([0.5, 0.5]));
+    let mut node = to_stereo(StereoLaw::Minus6dB) >> to_mono(StereoLaw::Unity);
+    assert_eq!(node.filter_mono(0.5), 0.5);
+}
+
+#[test]
+fn test_stereoize() {
+    let mut node = stereoize(1.0);
+    let mut rnd = funutd::Rnd::from_u64(1);
+    let (mut left_power, mut right_power, mut cross) = (0.0, 0.0, 0.0);
+    let mut differ = 0;
+    for _ in 0..44100 {
+        let x = rnd.f32() * 2.0 - 1.0;
+        let output = node.tick(&Frame::from([x]));
+        // Mono compatible: the channels sum back to the input.
+        assert!((output[0] + output[1] - x).abs() < 1.0e-5);
+        if (output[0] - output[1]).abs() > 1.0e-3 {
+            differ += 1;
+        }
+        left_power += squared(output[0]);
+        right_power += squared(output[1]);
+        cross += output[0] * output[1];
+    }
+    assert!(differ > 40000);
+    // The channels are decorrelated.
+    assert!(abs(cross / sqrt(left_power * right_power)) < 0.2);
+
+    // With zero amount, both channels are half the input.
+    let mut node = stereoize(0.0);
+    assert_eq!(node.tick(&Frame::from([1.0])), Frame::from([0.5, 0.5]));
+    // The frequency responses are complementary.
+    let mut node = stereoize(0.7);
+    for f in [50.0, 440.0, 3000.0] {
+        let sum = node.response(0, f).unwrap() + node.response(1, f).unwrap();
+        assert!((sum.re - 1.0).abs() < 1.0e-6 && sum.im.abs() < 1.0e-6);
+        assert!((node.response(0, f).unwrap() - node.response(1, f).unwrap()).norm() > 0.1);
+    }
+}
+
+#[test]
+fn test_dc_safe() {
+    // DC biased sources are zero centered after wrapping.
+    let mut node = dc_safe((dc(0.5) + sine_hz(440.0)) | (dc(-0.3) + sine_hz(110.0)));
+    for _ in 0..88200 {
+        node.get_stereo();
+    }
+    let (mut left, mut right) = (0.0, 0.0);
+    for _ in 0..44100 {
+        let (l, r) = node.get_stereo();
+        left += l;
+        right += r;
+    }
+    assert!(abs(left / 44100.0) < 1.0e-3);
+    assert!(abs(right / 44100.0) < 1.0e-3);
+
+    // The response blocks DC and passes audio frequencies.
+    let mut node = dc_safe(pass());
+    assert!(node.response(0, 0.0).unwrap().norm() < 1.0e-6);
+    assert!((node.response(0, 1000.0).unwrap().norm() - 1.0).abs() < 1.0e-3);
+
+    check_wave(dc_safe((dc((110.0, 0.9)) >> pulse()) | (dc(0.2) + noise())));
+    check_wave(dc_safe((dc(440.0) >> lorenz()) | (dc(440.0) >> rossler())));
+}
+
+#[test]
+fn test_ambisonics() {
+    // A source in front decodes with equal left and right levels.
+    let mut node = ambisonic_encode(0.0, 0.0) >> ambisonic_decode_stereo();
+    let output = node.tick(&Frame::from([1.0]));
+    assert!(output[0] > 0.0 && (output[0] - output[1]).abs() < 1.0e-6);
+
+    // B-format channels follow the ACN order with SN3D normalization.
+    let mut node = ambisonic_encode(-0.5 * f32::PI, 0.0);
+    let output = node.tick(&Frame::from([1.0]));
+    assert!((output[0] - 1.0).abs() < 1.0e-6);
+    assert!((output[1] - 1.0).abs() < 1.0e-6);
+    assert!(output[2].abs() < 1.0e-6 && output[3].abs() < 1.0e-6);
+
+    // A source on the left is louder in the left channel.
+    let mut node = ambisonic_encode(-0.5 * f32::PI, 0.0) >> ambisonic_decode_stereo();
+    let output = node.tick(&Frame::from([1.0]));
+    assert!(output[0] > 0.99 && output[1].abs() < 1.0e-6);
+
+    // A source in front is louder in the front speakers.
+    let mut node = ambisonic_encode(0.0, 0.0) >> ambisonic_decode_quad();
+    let output = node.tick(&Frame::from([1.0]));
+    assert!((output[0] - output[1]).abs() < 1.0e-6);
+    assert!((output[2] - output[3]).abs() < 1.0e-6);
+    assert!(output[0] > output[2]);
+
+    check_wave(noise() >> ambisonic_encode(0.3, 0.2) >> ambisonic_decode_stereo());
+    check_wave(
+        (noise() | lfo(|t: f64| t) | dc(0.1)) >> ambisonic_encode_in() >> ambisonic_decode_stereo(),
+    );
+}
+
+#[test]
+fn test_additive() {
+    // Bin aligned fundamental with a third harmonic at half amplitude.
+    let length = 4096;
+    let bin = 16;
+    let frequency = DEFAULT_SR / length as f64 * bin as f64;
+    let mut node = dc(frequency as f32) >> additive(&[(1.0, 1.0, 0.0), (3.0, 0.5, 0.25)]);
+    let mut data: Vec<f32> = (0..length).map(|_| node.get_mono()).collect();
+    let spectrum = fundsp::fft::real_fft(&mut data);
+    let h: [f32; 4] = core::array::from_fn(|k| spectrum[bin * (k + 1)].norm());
+    assert!((h[2] / h[0] - 0.5).abs() < 1.0e-3);
+    assert!(h[1] < 1.0e-3 * h[0] && h[3] < 1.0e-3 * h[0]);
+    let total: f32 = spectrum.iter().map(|x| x.norm_sqr()).sum();
+    assert!(h[0] * h[0] + h[2] * h[2] > 0.9999 * total);
+
+    // Partials above Nyquist are skipped.
+    let mut node = additive(&[(1.0, 1.0, 0.0), (3.0, 1.0, 0.0)]).phase(0.0);
+    let mut sine = sine().phase(0.0);
+    let input = Frame::from([10000.0]);
+    for _ in 0..1000 {
+        assert!((node.tick(&input)[0] - sine.tick(&input)[0]).abs() < 1.0e-4);
+    }
+}
+
+#[test]
+fn test_fm() {
+    // Return the spectral spread (in harmonics) of an FM tone with modulation `index`.
+    let spread = |index: f32| {
+        let length = 4096;
+        let bin = 16;
+        let frequency = DEFAULT_SR / length as f64 * bin as f64;
+        let mut node = dc(frequency as f32) >> fm2(1.0, index);
+        let mut data: Vec<f32> = (0..length).map(|_| node.get_mono()).collect();
+        let spectrum = fundsp::fft::real_fft(&mut data);
+        let mut total = 0.0;
+        let mut moment = 0.0;
+        for (i, x) in spectrum.iter().enumerate() {
+            total += x.norm_sqr();
+            moment += x.norm_sqr() * squared(i as f32 / bin as f32);
+        }
+        sqrt(moment / total)
+    };
+    // At zero index the tone is a pure sine at the fundamental.
+    assert!((spread(0.0) - 1.0).abs() < 1.0e-3);
+    // Increasing the index broadens the spectrum.
+    let s1 = spread(0.5);
+    let s2 = spread(2.0);
+    let s3 = spread(5.0);
+    assert!(s1 > 1.01 && s2 > s1 && s3 > s2);
+
+    // A parallel algorithm is additive synthesis.
+    let operators = [FmOperator::new(1.0, 0.5), FmOperator::new(2.0, 0.25)];
+    let mut fm = dc(440.0) >> fm_stack(FmAlgorithm::parallel(2), &operators);
+    let mut additive = dc(440.0) >> additive(&[(1.0, 0.5, 0.0), (2.0, 0.25, 0.0)]).phase(0.0);
+    for _ in 0..1000 {
+        assert!((fm.get_mono() - additive.get_mono()).abs() < 1.0e-4);
+    }
+
+    // The depth input scales modulation indices.
+    let mut a = (dc(440.0) | dc(2.0)) >> fm2_in(1.5);
+    let mut b = dc(440.0) >> fm2(1.5, 2.0);
+    for _ in 0..1000 {
+        assert!((a.get_mono() - b.get_mono()).abs() < 1.0e-4);
+    }
+}
+
+#[test]
+fn test_blit() {
+    // Bin aligned impulse train whose period is not a whole number of samples.
+    let length = 4096;
+    let bin = 47;
+    let frequency = DEFAULT_SR / length as f64 * bin as f64;
+    let mut node = dc(frequency as f32) >> blit().phase(0.0);
+    let mut data: Vec<f32> = (0..length).map(|_| node.get_mono()).collect();
+    let mean = data.iter().sum::<f32>() / length as f32;
+    assert!((mean as f64 / (frequency / DEFAULT_SR) - 1.0).abs() < 1.0e-3);
+    let spectrum = fundsp::fft::real_fft(&mut data);
+    // All harmonics up to Nyquist are present with equal magnitude.
+    let harmonics = (DEFAULT_SR * 0.5 / frequency) as usize;
+    let h0 = spectrum[bin].norm();
+    for k in 1..=harmonics {
+        assert!((spectrum[bin * k].norm() / h0 - 1.0).abs() < 0.01);
+    }
+    // There is minimal aliasing between the harmonics.
+    let aliasing: f32 = (1..spectrum.len())
+        .filter(|i| i % bin != 0)
+        .map(|i| spectrum[i].norm_sqr())
+        .sum();
+    assert!(aliasing < 1.0e-6 * h0 * h0 * harmonics as f32);
+
+    check_wave((dc(440.0) >> blit()) | (dc(1234.5) >> blit().phase(0.5)));
+}
+
+#[test]
+fn test_pulse_antialias() {
+    // Return the fraction of energy outside the harmonics and PWM sidebands
+    // of a bin aligned pulse wave during a sinusoidal pulse width sweep.
+    let aliasing = |antialias: PulseAntialias| -> f32 {
+        let length = 4096;
+        let (carrier, modulator) = (331i32, 13i32);
+        let bin_hz = DEFAULT_SR / length as f64;
+        let mut osc = pulse().phase(0.0);
+        osc.set_antialias(antialias);
+        let mut node = (dc(carrier as f32 * bin_hz as f32)
+            | (sine_hz(modulator as f32 * bin_hz as f32) * 0.4 + 0.5))
+            >> osc;
+        for _ in 0..length {
+            node.get_mono();
+        }
+        let mut data: Vec<f32> = (0..length).map(|_| node.get_mono()).collect();
+        let spectrum = fundsp::fft::real_fft(&mut data);
+        let mut legit = vec![false; spectrum.len()];
+        for k in 0..8 {
+            for j in -60..=60 {
+                let i = k * carrier + j * modulator;
+                if i >= 0 && (i as usize) < legit.len() {
+                    legit[i as usize] = true;
+                }
+            }
+        }
+        let mut total = 0.0;
+        let mut inharmonic = 0.0;
+        for (i, x) in spectrum.iter().enumerate().skip(1) {
+            total += x.norm_sqr();
+            if !legit[i] {
+                inharmonic += x.norm_sqr();
+            }
+        }
+        inharmonic / total
+    };
+    let wavetable = aliasing(PulseAntialias::Wavetable);
+    let polyblep = aliasing(PulseAntialias::PolyBlep);
+    let naive = aliasing(PulseAntialias::Off);
+    assert!(wavetable < 1.0e-6);
+    // PolyBLEP correction suppresses most of the aliasing of the naive pulse.
+    assert!(naive > 1.0e-3 && polyblep < 0.01 * naive);
+
+    // The cheaper methods follow the bandlimited waveform.
+    for antialias in [PulseAntialias::PolyBlep, PulseAntialias::Off] {
+        let mut osc = pulse().phase(0.0);
+        osc.set_antialias(antialias);
+        assert_eq!(osc.antialias(), antialias);
+        let mut clean = (dc(50.0) | dc(0.3)) >> pulse().phase(0.0);
+        let mut cheap = (dc(50.0) | dc(0.3)) >> osc;
+        let mut close = 0;
+        for _ in 0..882 {
+            if (clean.get_mono() - cheap.get_mono()).abs() < 0.05 {
+                close += 1;
+            }
+        }
+        assert!(close > 850);
+
+        let mut osc = pulse();
+        osc.set_antialias(antialias);
+        let node = (dc(440.0) | lfo(|t| 0.5 + 0.3 * sin_hz(5.0, t))) >> osc;
+        check_wave(node.clone() | node);
+    }
+}
+
+#[test]
+fn test_phase_distortion() {
+    // Return magnitudes of the first eight harmonics of a bin aligned tone.
+    let harmonics = |shape: PdShape, amount: f32| -> [f32; 8] {
+        let length = 4096;
+        let bin = 16;
+        let frequency = DEFAULT_SR / length as f64 * bin as f64;
+        let mut node = (dc(frequency as f32) | dc(amount)) >> phase_distortion(shape).phase(0.0);
+        let mut data: Vec<f32> = (0..length).map(|_| node.get_mono()).collect();
+        let spectrum = fundsp::fft::real_fft(&mut data);
+        core::array::from_fn(|k| spectrum[bin * (k + 1)].norm())
+    };
+    // Without distortion the output is a pure cosine.
+    for shape in [PdShape::Saw, PdShape::Square, PdShape::Pulse] {
+        let h = harmonics(shape, 0.0);
+        assert!(h[1..].iter().all(|x| *x < 1.0e-3 * h[0]));
+    }
+    // Distortion amount increases harmonic content.
+    let brightness = |h: [f32; 8]| h[1..].iter().sum::<f32>() / h[0];
+    let b1 = brightness(harmonics(PdShape::Saw, 0.3));
+    let b2 = brightness(harmonics(PdShape::Saw, 0.9));
+    assert!(b1 > 0.1 && b2 > b1);
+    // Square is half-wave symmetric, so it has odd harmonics only.
+    let h = harmonics(PdShape::Square, 0.8);
+    assert!(h[2] > 0.1 * h[0]);
+    assert!(h[1] < 1.0e-3 * h[0] && h[3] < 1.0e-3 * h[0]);
+    // The resonant peak moves up with the amount.
+    let h = harmonics(PdShape::ResonantSaw, 0.2);
+    assert!(h[3] > h[0] && h[3] > h[6]);
+}
+
+#[test]
+fn test_tilted_noise() {
+    // Return the average spectral density in dB of `node` around `frequency` (one octave band).
+    let density = |node: &mut dyn AudioUnit, frequency: f64| {
+        let length = 2048;
+        let blocks = 64;
+        let window: Vec<f32> = (0..length)
+            .map(|i| 0.5 - 0.5 * cos(i as f32 * f32::TAU / length as f32))
+            .collect();
+        let bin_width = DEFAULT_SR / length as f64;
+        let low = (frequency / sqrt(2.0) / bin_width).round() as usize;
+        let high = (frequency * sqrt(2.0) / bin_width).round() as usize;
+        let mut power = 0.0;
+        for _ in 0..blocks {
+            let mut data: Vec<f32> = window.iter().map(|w| node.get_mono() * w).collect();
+            let spectrum = fundsp::fft::real_fft(&mut data);
+            for x in spectrum[low..high].iter() {
+                power += x.norm_sqr() as f64;
+            }
+        }
+        10.0 * log10(power / (high - low) as f64)
+    };
+    for tilt in [-6.0, -3.0, 0.0, 3.0, 6.0] {
+        let mut node = dc(tilt) >> tilted_noise();
+        // Let the filter settle.
+        for _ in 0..44100 {
+            node.get_mono();
+        }
+        let low = density(&mut node, 250.0);
+        let high = density(&mut node, 4000.0);
+        // The bands are four octaves apart.
+        let slope = (high - low) / 4.0;
+        assert!((slope - tilt as f64).abs() < 0.5);
+    }
+
+    // The filter response matches the tilt and is normalized to unity at 1 kHz.
+    let mut filter = tiltpass();
+    filter.tick(&Frame::from([0.0, -3.0]));
+    let f1 = filter.response_db(0, 1000.0).unwrap();
+    let f2 = filter.response_db(0, 2000.0).unwrap();
+    assert!(f1.abs() < 1.0e-6);
+    assert!((f2 - f1 + 3.0).abs() < 0.5);
+}
+
+#[test]
+fn test_chaos() {
+    // Return `length` samples from `node` clocked at the sample rate.
+    let render = |node: &mut dyn AudioUnit, length: usize| -> Vec<f32> {
+        (0..length)
+            .map(|_| {
+                let mut output = [0.0];
+                node.tick(&[DEFAULT_SR as f32], &mut output);
+                output[0]
+            })
+            .collect()
+    };
+    // Check that `data` does not repeat with any period up to half its length.
+    let aperiodic = |data: &[f32]| {
+        (1..=data.len() / 2).all(|period| (period..data.len()).any(|i| data[i] != data[i - period]))
+    };
+
+    let data = render(&mut logistic(4.0), 2000);
+    assert!(data.iter().all(|x| *x >= 0.0 && *x <= 1.0));
+    assert!(aperiodic(&data));
+
+    let data = render(&mut henon(1.4, 0.3), 2000);
+    assert!(data.iter().all(|x| x.abs() < 1.3));
+    assert!(aperiodic(&data));
+
+    let data = render(&mut voss().seed(1), 2000);
+    assert!(data.iter().all(|x| x.abs() <= 1.0));
+    assert!(aperiodic(&data));
+
+    // A periodic regime of the logistic map is detected as periodic.
+    let mut node = logistic(3.2);
+    render(&mut node, 1000);
+    assert!(!aperiodic(&render(&mut node, 100)));
+
+    // The clock rate holds values between iterations.
+    let mut node = dc(DEFAULT_SR as f32 / 4.0) >> logistic(4.0);
+    let data: Vec<f32> = (0..16).map(|_| node.get_mono()).collect();
+    assert!(data.windows(2).filter(|w| w[0] != w[1]).count() <= 4);
+}
+
+#[test]
+fn test_fdn_delay() {
+    // The integrated delay of D samples equals a single sample feedback loop
+    // with D samples of extra delay, with the output advanced by D samples.
+    let d = 100;
+    let line = |i: u64| delay(0.001 * (i + 1) as f32) >> fir((0.2, 0.4, 0.2));
+    let mut reference = fdn::<U8, _>(stacki::<U8, _, _>(move |i| {
+        line(i) >> delay(d as f32 / DEFAULT_SR as f32)
+    }));
+    let mut node = fdn_delay::<U8, _>(d as f32 / DEFAULT_SR as f32, stacki::<U8, _, _>(line));
+    assert_eq!(node.delay_samples(), d + 1);
+
+    let input = Wave::render(DEFAULT_SR, 0.5, &mut (noise() >> split::<U8>()));
+    let mut tick_node = node.clone();
+    // Block processing takes the fast path.
+    let output = input.filter(0.5, &mut node);
+    let expected = input.filter(0.5 + d as f64 / DEFAULT_SR, &mut reference);
+    for i in 0..output.length() {
+        let frame = Frame::generate(|channel| input.at(channel, i));
+        let tick_output = tick_node.tick(&frame);
+        for channel in 0..8 {
+            assert!((output.at(channel, i) - expected.at(channel, i + d)).abs() < 1.0e-6);
+            assert!((output.at(channel, i) - tick_output[channel]).abs() < 1.0e-6);
+        }
+    }
+
+    // With zero delay, the network is the same as `fdn`.
+    let mut node = fdn_delay::<U8, _>(0.0, stacki::<U8, _, _>(line));
+    let mut reference = fdn::<U8, _>(stacki::<U8, _, _>(line));
+    assert_eq!(node.delay_samples(), 1);
+    let output = input.filter(0.5, &mut node);
+    let expected = input.filter(0.5, &mut reference);
+    for channel in 0..8 {
+        assert_eq!(output.channel(channel), expected.channel(channel));
+    }
+}
+
+#[test]
+fn test_reverb_stereo_fdn() {
+    // The reverb renders the same as the plain `fdn` topology it replaced.
+    fn reference(
+        room_size: f64,
+        time: f64,
+        damping: f64,
+    ) -> An<impl AudioNode<Inputs = U2, Outputs = U2>> {
+        const DELAYS: [f64; 32] = [
+            0.073904, 0.052918, 0.066238, 0.066387, 0.037783, 0.080073, 0.050961, 0.075900,
+            0.043646, 0.072095, 0.056194, 0.045961, 0.058934, 0.068016, 0.047529, 0.058156,
+            0.072972, 0.036084, 0.062715, 0.076377, 0.044339, 0.076725, 0.077884, 0.046126,
+            0.067741, 0.049800, 0.051709, 0.082923, 0.070121, 0.079315, 0.055039, 0.081859,
+        ];
+        let a = pow(db_amp(-60.0), 0.03 * room_size / 10.0 / time) as f32;
+        let weights = fir3(1.0 - damping as f32).weights() * Frame::splat(a);
+        let line = stacki::<U32, _, _>(|i| {
+            An(Delay::new(DELAYS[i as usize] * room_size / 10.0)) >> fir(weights)
+        });
+        multisplit::<U2, U16>()
+            >> fdn::<U32, _>(line)
+            >> sumf::<U32, _, _>(|x| pan(lerp(-1.0, 1.0, smooth9(x))))
+                * dc((1.0 / 16.0, 1.0 / 16.0))
+    }
+    let input = Wave::render(DEFAULT_SR, 0.5, &mut (noise() | noise().seed(1)));
+    // At sample rates that are multiples of 50 Hz, the loop lengths are the same.
+    for sample_rate in [32000.0, 44100.0, 48000.0, 96000.0] {
+        for room_size in [10.0, 15.0, 20.0, 30.0] {
+            let mut input = input.clone();
+            input.set_sample_rate(sample_rate);
+            let mut verb = reverb_stereo(room_size as f32, 2.0, 0.5);
+            let mut reference = reference(room_size, 2.0, 0.5);
+            let output = input.filter(input.duration(), &mut verb);
+            let expected = input.filter(input.duration(), &mut reference);
+            for channel in 0..2 {
+                assert_eq!(output.channel(channel), expected.channel(channel));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_biquad_smoothing() {
+    // Sweep a biquad lowpass in coarse steps once per block and measure
+    // the largest jump between consecutive output samples.
+    fn max_jump(smoothing: f32) -> f64 {
+        let mut filter = biquad(0.0, 0.0, 1.0, 0.0, 0.0);
+        filter.set_smoothing(smoothing);
+        assert_eq!(filter.smoothing(), smoothing);
+        let mut source = sine_hz(2000.0);
+        let mut previous = 0.0;
+        let mut jump = 0.0f64;
+        for block in 0..200 {
+            let cutoff = if block % 2 == 0 { 200.0 } else { 8000.0 };
+            let c = BiquadCoefs::butter_lowpass(DEFAULT_SR as f32, cutoff);
+            filter.set(Setting::biquad(c.a1, c.a2, c.b0, c.b1, c.b2));
+            for _ in 0..64 {
+                let y = filter.filter_mono(source.get_mono()) as f64;
+                if block > 0 {
+                    jump = jump.max((y - previous).abs());
+                }
+                previous = y;
+            }
+        }
+        jump
+    }
+    // A 2 kHz sine of unit amplitude moves at most 2 pi 2000 / 44100 per sample.
+    let limit = f64::TAU * 2000.0 / DEFAULT_SR;
+    // Unsmoothed steps cause large discontinuities; smoothed steps stay near the limit.
+    assert!(max_jump(0.0) > limit * 5.0);
+    assert!(max_jump(0.01) < limit * 1.2);
+
+    // Ramped settings end at the target coefficients.
+    let mut filter = biquad(0.0, 0.0, 1.0, 0.0, 0.0);
+    filter.set(Setting::biquad(0.1, 0.2, 0.3, 0.4, 0.5).ramp(0.001));
+    for _ in 0..100 {
+        filter.filter_mono(0.0);
+    }
+    let c = filter.coefs();
+    assert!((c.a1 - 0.1).abs() < 1.0e-6 && (c.b2 - 0.5).abs() < 1.0e-6);
+}
+
+#[test]
+fn test_net_tap() {
+    // Meter the RMS level of a sine between two gain stages.
+    let mut net = Net::new(0, 1);
+    let source = net.chain(Box::new(sine_hz(100.0)));
+    let gain = net.chain(Box::new(mul(0.5)));
+    net.chain(Box::new(mul(0.1)));
+    let (tap, level) = net.tap(gain, 0, Meter::Rms(0.1));
+    assert_eq!(net.size(), 4);
+    net.check();
+    // The output is unaffected by the tap.
+    let mut sum = 0.0;
+    for _ in 0..44100 {
+        let x = net.get_mono();
+        sum += x * x;
+    }
+    assert!((sqrt(sum / 44100.0) - 0.05 / sqrt(2.0)).abs() < 0.001);
+    assert!((level.value() - 0.5 / sqrt(2.0)).abs() < 0.01);
+
+    // Taps can be moved to other nodes.
+    net.connect(source, 0, tap, 0);
+    for _ in 0..44100 {
+        net.get_mono();
+    }
+    assert!((level.value() - 1.0 / sqrt(2.0)).abs() < 0.01);
+
+    // Removing the tap leaves the network as it was.
+    net.remove(tap);
+    assert_eq!(net.size(), 3);
+    net.check();
+}
+
+#[test]
+fn test_net_crossfader() {
+    let scene_a = Net::wrap(Box::new(dc((1.0, 0.0))));
+    let scene_b = Net::wrap(Box::new(dc((0.0, 1.0))));
+    let (mut crossfader, mut backend) = NetCrossfader::new(scene_a);
+    assert_eq!(crossfader.outputs(), 2);
+    assert_eq!(backend.get_stereo(), (1.0, 0.0));
+
+    // Crossfade over 1000 samples.
+    crossfader.crossfade_to(scene_b, 1000.0 / DEFAULT_SR);
+    for i in 0..1000 {
+        let (a, b) = backend.get_stereo();
+        // Equal power.
+        assert!((a * a + b * b - 1.0).abs() < 0.01);
+        if i == 500 {
+            // Both scenes contribute equally at the midpoint.
+            assert!((a - 0.5f32.sqrt()).abs() < 0.01);
+            assert!((b - 0.5f32.sqrt()).abs() < 0.01);
+        }
+    }
+    // The old scene has been dropped.
+    assert_eq!(backend.get_stereo(), (0.0, 1.0));
+
+    // Block processing follows the same fade.
+    crossfader.crossfade_to(Net::wrap(Box::new(dc((1.0, 1.0)))), 256.0 / DEFAULT_SR);
+    let input = BufferVec::new(0);
+    let mut output = BufferVec::new(2);
+    backend.process(64, &input.buffer_ref(), &mut output.buffer_mut());
+    assert!((output.at_f32(0, 32) - sine_ease(0.125f32)).abs() < 0.01);
+    assert!((output.at_f32(1, 32) - sine_ease(0.875f32) - sine_ease(0.125f32)).abs() < 0.01);
+    for _ in 0..4 {
+        backend.process(64, &input.buffer_ref(), &mut output.buffer_mut());
+    }
+    assert_eq!(output.at_f32(0, 0), 1.0);
+}
+
+#[test]
+fn test_process64() {
+    // Double precision is maintained through native nodes and operators.
+    let mut node = pass()
+        >> butterpass_hz(1000.0)
+        >> (lowpass_hz(2000.0, 0.5) ^ lowpole_hz(3000.0))
+        >> (dcblock_hz(1.0) | highpole_hz(1.0));
+    let mut unit: Box<dyn AudioUnit> = Box::new(node.clone());
+    let size = 1000;
+    let low = vec![1.0f64; size];
+    let high = vec![1.0 + 1.0e-10; size];
+    let mut output_low = vec![vec![0.0f64; size]; 2];
+    let mut output_high = vec![vec![0.0f64; size]; 2];
+    {
+        let mut output: Vec<&mut [f64]> = output_low.iter_mut().map(|x| &mut x[..]).collect();
+        node.process64(size, &[&low], &mut output);
+    }
+    {
+        let mut output: Vec<&mut [f64]> = output_high.iter_mut().map(|x| &mut x[..]).collect();
+        unit.process64(size, &[&high], &mut output);
+    }
+    // By linearity, the tiny input offset shows up in proportion to the output.
+    for channel in 0..2 {
+        let y = output_low[channel][size - 1];
+        let difference = (output_high[channel][size - 1] - y) / 1.0e-10;
+        assert!(y > 0.5);
+        assert!((difference / y - 1.0).abs() < 0.01);
+    }
+
+    // Networks process in blocks through their own buffers and match single precision ticks.
+    let mut net = Net::wrap(Box::new(lowpass_hz(1000.0, 1.0) | sine_hz(110.0)));
+    let mut other = net.clone();
+    let input: Vec<f64> = (0..size).map(|i| (i as f64 * 0.1).sin()).collect();
+    let mut output = vec![vec![0.0f64; size]; 2];
+    {
+        let mut output: Vec<&mut [f64]> = output.iter_mut().map(|x| &mut x[..]).collect();
+        net.process64(size, &[&input], &mut output);
+    }
+    for i in 0..size {
+        let mut frame = [0.0f32; 2];
+        other.tick(&[input[i] as f32], &mut frame);
+        assert!((output[0][i] - frame[0] as f64).abs() < 1.0e-4);
+        assert!((output[1][i] - frame[1] as f64).abs() < 1.0e-4);
+    }
+
+    // Nodes without native support round to single precision.
+    let mut node = shape(Clip(1.0));
+    let mut output = [0.0f64; 1];
+    node.process64(1, &[&[1.0 + 1.0e-10]], &mut [&mut output]);
+    assert_eq!(output[0], 1.0);
+
+    // Double precision ticks match single precision ticks for single precision input.
+    let mut node = lowpass_hz(1000.0, 1.0);
+    let mut other = node.clone();
+    for i in 0..100 {
+        let x = (i as f32 * 0.1).sin();
+        let y = node.tick64(&Frame::from([x as f64]))[0];
+        let z = other.tick(&Frame::from([x]))[0];
+        assert_eq!(y as f32, z);
+    }
+}
+
+#[test]
+fn test_oscillator_phase() {
+    // Setting phase to zero restarts each oscillator at the start of its cycle.
+    // `An::phase` is the initial phase builder, so we access the node directly.
+    let mut node = sine();
+    node.filter_mono(440.0);
+    node.set_phase(0.0);
+    assert_eq!(node.0.phase(), 0.0);
+    assert_eq!(node.filter_mono(440.0), 0.0);
+    assert!(abs(node.0.phase() - 440.0 / DEFAULT_SR) < 1.0e-9);
+    node.set_phase(0.25);
+    assert!(abs(node.filter_mono(440.0) - 1.0) < 1.0e-6);
+
+    let mut node = saw();
+    for _ in 0..10 {
+        node.filter_mono(440.0);
+    }
+    node.set_phase(0.0);
+    assert_eq!(node.0.phase(), 0.0);
+    // Wavetable oscillators advance the phase before each output sample.
+    let expected = saw_table()
+        .read(0, 440.0, 440.0 * (1.0 / DEFAULT_SR as f32))
+        .0;
+    assert_eq!(node.filter_mono(440.0), expected);
+    // The phase output agrees.
+    let mut node = An(WaveSynth::<U2>::new(saw_table()));
+    node.set_phase(0.75);
+    let phase = node.tick(&Frame::from([440.0]))[1];
+    assert_eq!(phase, node.0.phase());
+    assert!(abs(phase - (0.75 + 440.0 / DEFAULT_SR as f32)) < 1.0e-6);
+
+    // Synced pulse oscillators restart identically.
+    let mut node1 = pulse();
+    let mut node2 = pulse();
+    node1.tick(&Frame::from([300.0, 0.3]));
+    for _ in 0..13 {
+        node2.tick(&Frame::from([300.0, 0.3]));
+    }
+    node1.set_phase(0.0);
+    node2.set_phase(0.0);
+    assert_eq!(node1.0.phase(), 0.0);
+    for _ in 0..100 {
+        let x = node1.tick(&Frame::from([300.0, 0.3]));
+        let y = node2.tick(&Frame::from([300.0, 0.3]));
+        assert!(abs(x[0] - y[0]) < 1.0e-4);
+    }
+
+    // Block processing continues from the set phase.
+    let mut node = saw();
+    let mut reference = node.clone();
+    node.set_phase(0.5);
+    reference.set_phase(0.5);
+    let input = BufferVec::new(1);
+    let mut frequency = BufferVec::new(1);
+    let mut output = BufferVec::new(1);
+    for i in 0..64 {
+        frequency.set_f32(0, i, 440.0);
+    }
+    node.process(64, &frequency.buffer_ref(), &mut output.buffer_mut());
+    let _ = input;
+    for i in 0..64 {
+        assert!(abs(output.at_f32(0, i) - reference.filter_mono(440.0)) < 1.0e-6);
+    }
+}
+
+#[test]
+fn test_update_out() {
+    // The update function runs about every `dt` seconds and reports into the shared variable.
+    let updates = shared(0.0);
+    let mut node = update_out(dc(1.0), 0.01, &updates, |_t, _dt, samples, _x| {
+        samples as f32
+    });
+    let mut previous = 0.0;
+    let mut intervals = Vec::new();
+    for _ in 0..44100 {
+        node.get_mono();
+        let samples = updates.value();
+        if samples != previous {
+            intervals.push(samples - previous);
+            previous = samples;
+        }
+    }
+    assert!(intervals.len() >= 98 && intervals.len() <= 101);
+    for interval in intervals {
+        assert!(interval >= 441.0 && interval <= 442.0);
+    }
+
+    // Time and sample count agree in block processing, where updates fall on block boundaries.
+    let report = shared(0.0);
+    let mut node = update_out(dc(1.0), 0.01, &report, |t, dt, samples, _x| {
+        assert!((t * DEFAULT_SR as f32 - samples as f32).abs() < 1.0);
+        assert!(samples == 0 || dt >= 0.01);
+        t
+    });
+    let mut output = BufferVec::new(1);
+    for _ in 0..1000 {
+        node.process(64, &BufferRef::empty(), &mut output.buffer_mut());
+    }
+    assert!(report.value() > 1.4);
+    node.reset();
+    node.process(64, &BufferRef::empty(), &mut output.buffer_mut());
+    assert_eq!(report.value(), 0.0);
+}
+
+#[test]
+fn test_envelope_hz() {
+    // Maximum error of an envelope node tracking a fast exponential attack.
+    fn attack_error(mut node: impl AudioUnit) -> f64 {
+        let mut error = 0.0f64;
+        for i in 0..4410 {
+            let t = i as f64 / DEFAULT_SR;
+            let y = node.get_mono() as f64;
+            error = error.max((y - (1.0 - exp(-t * 1000.0))).abs());
+        }
+        error
+    }
+    let coarse = attack_error(envelope(|t| 1.0 - exp(-t * 1000.0)));
+    let fine = attack_error(envelope_hz(20000.0, |t| 1.0 - exp(-t * 1000.0)));
+    assert!(fine < 0.02);
+    assert!(fine * 10.0 < coarse);
+    // The rate is equivalent to the interval builder.
+    let a = attack_error(lfo_hz(20000.0, |t| 1.0 - exp(-t * 1000.0)));
+    let b = attack_error(lfo(|t| 1.0 - exp(-t * 1000.0)).interval(1.0 / 20000.0));
+    assert!((a - b).abs() < 1.0e-6);
+}
+
+#[test]
+fn test_envelope_exact() {
+    // The exact envelope matches the closed form function at every sample.
+    let f = |t: f64| sin(t * 37.0) * exp(-t);
+    let mut node = envelope_exact(f);
+    for i in 0..10000 {
+        let t = i as f64 / DEFAULT_SR;
+        assert_eq!(node.get_mono(), f(t) as f32);
+    }
+    // Block processing and resets give the same samples.
+    node.reset();
+    let mut output = BufferVec::new(1);
+    node.process(64, &BufferRef::empty(), &mut output.buffer_mut());
+    for i in 0..64 {
+        assert_eq!(output.at_f32(0, i), f(i as f64 / DEFAULT_SR) as f32);
+    }
+    // Sample rate changes the time step.
+    let mut node = lfo_exact(|t| (t, -t));
+    node.set_sample_rate(1000.0);
+    node.get_stereo();
+    assert_eq!(node.get_stereo(), (0.001, -0.001));
+}
+
+#[test]
+fn test_net_automation() {
+    // A gain change scheduled at 10 ms takes effect exactly at sample 441.
+    let mut net = Net::new(1, 1);
+    let gain_id = net.chain(Box::new(mul(1.0)));
+    net.set_sample_rate(44100.0);
+    let mut backend = net.backend();
+    net.set_at(0.01, Setting::value(0.5).node(gain_id).right());
+    for i in 0..1000 {
+        let expected = if i < 441 { 1.0 } else { 0.5 };
+        assert_eq!(backend.filter_mono(1.0), expected);
+    }
+
+    // Block processing is sample-accurate too, and events apply in time order.
+    backend.reset();
+    net.set_at(0.02, Setting::value(3.0).node(gain_id).right());
+    net.set_at(0.01, Setting::value(2.0).node(gain_id).right());
+    let mut input = BufferVec::new(1);
+    for i in 0..MAX_BUFFER_SIZE {
+        input.set_f32(0, i, 1.0);
+    }
+    let mut output = BufferVec::new(1);
+    let mut values = Vec::new();
+    for _ in 0..16 {
+        backend.process(
+            MAX_BUFFER_SIZE,
+            &input.buffer_ref(),
+            &mut output.buffer_mut(),
+        );
+        for i in 0..MAX_BUFFER_SIZE {
+            values.push(output.at_f32(0, i));
+        }
+    }
+    for (i, value) in values.iter().enumerate() {
+        let expected = if i < 441 {
+            0.5
+        } else if i < 882 {
+            2.0
+        } else {
+            3.0
+        };
+        assert_eq!(*value, expected);
+    }
+
+    // Events at the same time apply in scheduling order.
+    backend.reset();
+    net.set_at(0.001, Setting::value(4.0).node(gain_id).right());
+    net.set_at(0.001, Setting::value(5.0).node(gain_id).right());
+    for _ in 0..100 {
+        backend.filter_mono(1.0);
+    }
+    assert_eq!(backend.filter_mono(1.0), 5.0);
+
+    // When the backend queue is full, further events are dropped instead of allocating.
+    backend.reset();
+    let memory = backend.memory_usage();
+    for _ in 0..AUTOMATION_CAPACITY / 256 {
+        for _ in 0..256 {
+            net.set_at(1.0, Setting::value(6.0).node(gain_id).right());
+        }
+        backend.filter_mono(1.0);
+    }
+    net.set_at(0.001, Setting::value(7.0).node(gain_id).right());
+    for _ in 0..100 {
+        assert_eq!(backend.filter_mono(1.0), 5.0);
+    }
+    assert_eq!(backend.memory_usage(), memory);
+}
+
+#[test]
+fn test_net_latency_compensation() {
+    // Mix an FFT path with a window length of latency with a dry path.
+    let window = 256;
+    let identity = |fft: &mut FftWindow| {
+        for i in 0..fft.bins() {
+            fft.set(0, i, fft.at(0, i));
+        }
+    };
+    let mut net =
+        Net::wrap(Box::new(resynth::<U1, U1, _>(window, identity))) & Net::wrap(Box::new(pass()));
+    assert!(net.latency_compensation());
+    assert_eq!(net.latency(), Some(window as f64));
+
+    // The dry path is delayed by the window length, so once all windows overlap,
+    // the mix is exactly twice the delayed input, both when ticking and processing.
+    let mut rnd = Rnd::from_u64(1);
+    let input: Vec<f32> = (0..window * 8).map(|_| rnd.f32() * 2.0 - 1.0).collect();
+    for block in [false, true] {
+        net.reset();
+        let output: Vec<f32> = if block {
+            let mut output = Vec::new();
+            let mut input_buffer = BufferVec::new(1);
+            let mut output_buffer = BufferVec::new(1);
+            for chunk in input.chunks(MAX_BUFFER_SIZE) {
+                for (i, x) in chunk.iter().enumerate() {
+                    input_buffer.set_f32(0, i, *x);
+                }
+                net.process(
+                    chunk.len(),
+                    &input_buffer.buffer_ref(),
+                    &mut output_buffer.buffer_mut(),
+                );
+                output.extend((0..chunk.len()).map(|i| output_buffer.at_f32(0, i)));
+            }
+            output
+        } else {
+            input.iter().map(|x| net.filter_mono(*x)).collect()
+        };
+        for i in window * 2..input.len() {
+            assert!((output[i] - 2.0 * input[i - window]).abs() < 1.0e-4);
+        }
+    }
+
+    // Without compensation, the paths are misaligned.
+    net.set_latency_compensation(false);
+    assert_eq!(net.latency(), Some(0.0));
+    net.reset();
+    let output: Vec<f32> = input.iter().map(|x| net.filter_mono(*x)).collect();
+    let misaligned = (window * 2..input.len())
+        .filter(|&i| (output[i] - 2.0 * input[i - window]).abs() > 1.0e-2)
+        .count();
+    assert!(misaligned > window);
+}
@@ -72,3 +72,174 @@ fn test_dynamics() {
         assert_eq!(x2, s1.value());
     }
 }
+
+/// Estimate true peak of `x` with 4x oversampling, skipping the edges.
+fn true_peak(x: &[f32]) -> f32 {
+    let half = 32;
+    let mut peak = 0.0;
+    for n in half..x.len() as i32 - half {
+        peak = max(peak, abs(x[n as usize]));
+        for phase in [0.25, 0.5, 0.75] {
+            let mut y = 0.0;
+            for k in 1 - half..=half {
+                let u = (k as f64 - phase) * f64::PI;
+                let window = 0.5 + 0.5 * cos(u / half as f64);
+                y += x[(n + k) as usize] as f64 * sin(u) / u * window;
+            }
+            peak = max(peak, abs(y as f32));
+        }
+    }
+    peak
+}
+
+#[test]
+fn test_true_peak_limiter() {
+    let ceiling = db_amp(-1.0);
+    // A sine wave at a quarter of the sample rate with a 45 degree phase offset:
+    // sample peaks are at -3 dB while the true peak is at 0 dB.
+    let fs4: Vec<f32> = (0..4000)
+        .map(|n| {
+            if n >= 100 {
+                sin(n as f32 * f32::PI * 0.5 + f32::PI * 0.25)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    // A +20 dB burst.
+    let burst: Vec<f32> = (0..4000)
+        .map(|n| {
+            let amplitude = if n >= 1000 && n < 2000 { 3.0 } else { 0.3 };
+            amplitude * sin(n as f32 * 0.23 * f32::TAU)
+        })
+        .collect();
+    assert!(fs4.iter().all(|&x| abs(x) < ceiling));
+    assert!(true_peak(&fs4) > ceiling);
+
+    for (sample_rate, attack_time) in [(44100.0, 0.005), (48000.0, 0.001)] {
+        for input in [&fs4, &burst] {
+            let mut x = true_peak_limiter(attack_time, 0.05, -1.0);
+            x.set_sample_rate(sample_rate);
+            let output: Vec<f32> = input.iter().map(|&v| x.filter_mono(v)).collect();
+            let peak = true_peak(&output);
+            assert!(peak <= ceiling);
+            // The limiter should not reduce gain excessively.
+            assert!(peak >= db_amp(-1.5));
+        }
+    }
+}
+
+#[test]
+fn test_envelope_follower() {
+    // RMS of a sine wave with amplitude 0.5 is 0.5 / sqrt(2).
+    let mut node = (sine_hz(1000.0) * 0.5) >> envelope_follower(0.05, 0.05, Detector::Rms);
+    for _ in 0..44100 {
+        node.get_mono();
+    }
+    for _ in 0..1000 {
+        let level = node.get_mono();
+        assert!(abs(level - 0.5 / f32::SQRT_2) < 0.005);
+    }
+
+    // A peak follower with fast attack and slow release tracks the amplitude.
+    let mut node = (sine_hz(1000.0) * 0.5) >> envelope_follower(0.0001, 0.5, Detector::Peak);
+    for _ in 0..44100 {
+        node.get_mono();
+    }
+    for _ in 0..1000 {
+        let level = node.get_mono();
+        assert!(level > 0.48 && level <= 0.5);
+    }
+}
+
+#[test]
+fn test_clip_monitor() {
+    let mut rnd = Rnd::from_u64(1);
+    let input: Vec<f32> = (0..1000).map(|_| rnd.f32_in(-1.5, 1.5)).collect();
+    let expected = input.iter().filter(|x| x.abs() > 1.0).count();
+
+    // Test tick.
+    let clipped = shared_counter();
+    let mut node = clip_monitor(&clipped);
+    for x in input.iter() {
+        assert_eq!(node.filter_mono(*x), *x);
+    }
+    assert_eq!(clipped.value(), expected as u32);
+
+    // Test process.
+    let clipped = shared_counter();
+    let mut node = clip_monitor(&clipped);
+    let mut input_buffer = BufferVec::new(1);
+    let mut output_buffer = BufferVec::new(1);
+    let mut processed = 0;
+    for block in input.chunks(MAX_BUFFER_SIZE) {
+        for (i, x) in block.iter().enumerate() {
+            input_buffer.set_f32(0, i, *x);
+        }
+        node.process(
+            block.len(),
+            &input_buffer.buffer_ref(),
+            &mut output_buffer.buffer_mut(),
+        );
+        for (i, x) in block.iter().enumerate() {
+            assert_eq!(output_buffer.at_f32(0, i), *x);
+        }
+        processed += block.len();
+    }
+    assert_eq!(processed, input.len());
+    assert_eq!(clipped.value(), expected as u32);
+
+    // The count is exact beyond single precision range and saturates at the maximum.
+    clipped.set(1 << 24);
+    node.filter_mono(2.0);
+    assert_eq!(clipped.value(), (1 << 24) + 1);
+    clipped.set(u32::MAX - 1);
+    for _ in 0..3 {
+        node.filter_mono(-2.0);
+    }
+    assert_eq!(clipped.value(), u32::MAX);
+}
+
+#[test]
+fn test_dc_meter() {
+    // A sine wave with a DC bias of 0.25: the reported offset converges to the bias.
+    let offset = shared(0.0);
+    let mut node = (sine_hz(100.0) + 0.25) >> monitor(&offset, Meter::Dc(0.1));
+    for _ in 0..44100 {
+        node.get_mono();
+    }
+    assert!(abs(offset.value() - 0.25) < 0.01);
+
+    // The offset moves halfway to a new level in the smoothing timescale.
+    let mut node = meter(Meter::Dc(0.1));
+    for _ in 0..4410 {
+        node.filter_mono(1.0);
+    }
+    assert!(abs(node.filter_mono(1.0) - 0.5) < 0.001);
+}
+
+#[test]
+fn test_dynamic_bell() {
+    let sample_rate = 44100.0;
+    // Measure output RMS of a sine at `hz` with amplitude `amp` after settling.
+    let rms = |hz: f32, amp: f32| {
+        let mut node = (sine_hz(hz) * amp) >> dynamic_bell(5000.0, 2.0, -30.0, 4.0);
+        node.set_sample_rate(sample_rate);
+        for _ in 0..22050 {
+            node.get_mono();
+        }
+        let mut sum = 0.0;
+        for _ in 0..22050 {
+            sum += squared(node.get_mono() as f64);
+        }
+        sqrt(sum / 22050.0) as f32
+    };
+    let input = |amp: f32| amp * core::f32::consts::FRAC_1_SQRT_2;
+
+    // A loud signal in the band is attenuated.
+    assert!(rms(5000.0, 1.0) < input(1.0) * db_amp(-12.0));
+    // A quiet signal in the band passes.
+    assert!(abs(amp_db(rms(5000.0, 0.001) / input(0.001))) < 0.1);
+    // A loud signal outside the band passes.
+    assert!(abs(amp_db(rms(200.0, 1.0) / input(1.0))) < 0.5);
+}
@@ -0,0 +1,840 @@
+//! Effect component tests.
+
+#![allow(clippy::manual_range_contains)]
+
+use fundsp::fft::*;
+use fundsp::hacker::*;
+
+#[test]
+fn test_vocoder() {
+    // A silent modulator yields silence.
+    let mut node = vocoder(16);
+    assert_eq!(node.bands(), 16);
+    let mut carrier = saw_hz(110.0);
+    for _ in 0..10000 {
+        assert_eq!(node.tick(&Frame::from([0.0, carrier.get_mono()]))[0], 0.0);
+    }
+
+    // An active modulator lets the carrier through.
+    let mut node = vocoder(16);
+    let mut modulator = noise().seed(1);
+    let mut energy = 0.0;
+    for _ in 0..10000 {
+        let x = node.tick(&Frame::from([modulator.get_mono(), carrier.get_mono()]))[0];
+        energy += x * x;
+    }
+    assert!(energy > 1.0);
+}
+
+#[test]
+fn test_bitcrush() {
+    // Quantization step is 2^(1 - bits).
+    for (bits, step) in [(1.0, 1.0), (2.0, 0.5), (4.0, 0.125), (8.0, 1.0 / 128.0)] {
+        let mut node = bitcrush(bits);
+        assert_eq!(node.step(), step);
+        let mut rnd = funutd::Rnd::from_u64(bits as u64);
+        for _ in 0..1000 {
+            let x = rnd.f32() * 2.0 - 1.0;
+            let y = node.filter_mono(x);
+            // Output is a multiple of the step within half a step of the input.
+            assert_eq!(round(y / step) * step, y);
+            assert!(abs(y - x) <= step * 0.5);
+        }
+    }
+
+    // Bit depth can be modulated via the second input.
+    let mut node = bitcrush_in();
+    assert_eq!(node.tick(&Frame::from([0.3, 1.0]))[0], 0.0);
+    assert_eq!(node.tick(&Frame::from([0.3, 3.0]))[0], 0.25);
+}
+
+#[test]
+fn test_downsample() {
+    // Integer factors hold each input sample for `factor` samples.
+    let mut node = downsample(4.0);
+    for i in 0..100 {
+        assert_eq!(node.filter_mono(i as f32), (i / 4 * 4) as f32);
+    }
+
+    // Factor 1 is a passthrough.
+    let mut node = downsample(1.0);
+    for i in 0..100 {
+        assert_eq!(node.filter_mono(i as f32), i as f32);
+    }
+
+    // Factor can be modulated via the second input.
+    let mut node = downsample_in();
+    assert_eq!(node.tick(&Frame::from([1.0, 2.0]))[0], 1.0);
+    assert_eq!(node.tick(&Frame::from([2.0, 2.0]))[0], 1.0);
+    assert_eq!(node.tick(&Frame::from([3.0, 2.0]))[0], 3.0);
+}
+
+/// Return magnitudes of the first four harmonics of a bin aligned sine wave passed through `node`.
+fn harmonics(mut node: impl AudioUnit) -> [f32; 4] {
+    let length = 4096;
+    let bin = 16;
+    let mut data: Vec<f32> = (0..length)
+        .map(|i| {
+            let x = sin(i as f32 / length as f32 * bin as f32 * f32::TAU) * 0.9;
+            let mut y = [0.0];
+            node.tick(&[x], &mut y);
+            y[0]
+        })
+        .collect();
+    let spectrum = real_fft(&mut data);
+    core::array::from_fn(|k| spectrum[bin * (k + 1)].norm())
+}
+
+#[test]
+fn test_fold_asymmetric() {
+    // Folding is odd symmetric: it generates odd harmonics only.
+    let h = harmonics(shape(Fold(3.0)));
+    assert!(h[2] > 0.05 * h[0]);
+    assert!(h[1] < 1.0e-4 * h[0] && h[3] < 1.0e-4 * h[0]);
+
+    // Folding stays within -1...1.
+    let mut fold = Fold(5.0);
+    for i in -1000..=1000 {
+        let y = fold.shape(i as f32 * 0.001);
+        assert!(y >= -1.0 && y <= 1.0);
+    }
+    // Folding is the identity within -1...1 at unity gain.
+    assert!(abs(Fold(1.0).shape(0.5) - 0.5) < 1.0e-6);
+    // Signal is reflected back at the limits.
+    assert!(abs(Fold(1.0).shape(1.5) - 0.5) < 1.0e-6);
+    assert!(abs(Fold(1.0).shape(-1.5) + 0.5) < 1.0e-6);
+
+    // Asymmetric curves generate even harmonics.
+    let h = harmonics(shape(Asymmetric(0.5, 2.0)));
+    assert!(h[1] > 0.05 * h[0]);
+
+    // Symmetric settings do not.
+    let h = harmonics(shape(Asymmetric(2.0, 2.0)));
+    assert!(h[1] + h[3] < 1.0e-4 * h[0]);
+}
+
+#[test]
+fn test_shape_simd_fn() {
+    // Block processing uses the SIMD closure and matches sample by sample processing.
+    let mut node = shape_simd_fn(|x| x / (1.0 + abs(x)), |x| x / (F32x::ONE + x.abs()));
+    let mut reference = node.clone();
+    let mut input = BufferVec::new(1);
+    for i in 0..MAX_BUFFER_SIZE {
+        input.set_f32(0, i, (i as f32 - 32.0) * 0.1);
+    }
+    let mut output = BufferVec::new(1);
+    node.process(61, &input.buffer_ref(), &mut output.buffer_mut());
+    for i in 0..61 {
+        let y = reference.filter_mono(input.at_f32(0, i));
+        assert!((output.at_f32(0, i) - y).abs() < 1.0e-6);
+    }
+}
+
+/// Return the fraction of energy in inharmonic bins when a hard clipped,
+/// bin aligned sine wave is passed through `node`.
+fn aliasing(mut node: impl AudioUnit) -> f32 {
+    let length = 4096;
+    let bin = 331;
+    let mut sine = (0..length * 2).map(|i| sin(i as f32 / length as f32 * bin as f32 * f32::TAU));
+    // Settle the filters first.
+    for x in sine.by_ref().take(length) {
+        node.filter_mono(x);
+    }
+    let mut data: Vec<f32> = sine.map(|x| node.filter_mono(x)).collect();
+    let spectrum = real_fft(&mut data);
+    let mut total = 0.0;
+    let mut inharmonic = 0.0;
+    for (i, x) in spectrum.iter().enumerate().skip(1) {
+        total += x.norm_sqr();
+        if i % bin != 0 {
+            inharmonic += x.norm_sqr();
+        }
+    }
+    inharmonic / total
+}
+
+#[test]
+fn test_shape_oversampled() {
+    let plain = aliasing(shape(Clip(4.0)));
+    let x2 = aliasing(shape_oversampled(2, Clip(4.0)));
+    let x4 = aliasing(shape_oversampled(4, Clip(4.0)));
+    let x8 = aliasing(shape_oversampled(8, Clip(4.0)));
+    // Each doubling of the factor suppresses aliasing further.
+    assert!(x2 < plain * 0.1);
+    assert!(x4 < x2 * 0.5);
+    assert!(x8 < x4 * 0.5);
+}
+
+#[test]
+fn test_oversample_n() {
+    let plain = aliasing(shape(Clip(4.0)));
+    let x2 = aliasing(oversample_n::<U2, _>(shape(Clip(4.0))));
+    let x4 = aliasing(oversample_n::<U4, _>(shape(Clip(4.0))));
+    let x8 = aliasing(oversample_n::<U8, _>(shape(Clip(4.0))));
+    // Each doubling of the factor suppresses aliasing further.
+    assert!(x2 < plain * 0.1);
+    assert!(x4 < x2 * 0.5);
+    assert!(x8 < x4 * 0.5);
+
+    // Multichannel nodes are oversampled channel by channel.
+    let mut node = oversample_n::<U4, _>(pass() | (pass() * 0.5));
+    let mut output = Frame::default();
+    for _ in 0..100 {
+        output = node.tick(&Frame::from([1.0, 1.0]));
+    }
+    assert!((output[0] - 1.0).abs() < 1.0e-2 && (output[1] - 0.5).abs() < 1.0e-2);
+}
+
+#[test]
+fn test_upsample_downsample() {
+    // A sine wave passes through upsampling and downsampling at unity gain.
+    for factor in [2, 4, 8] {
+        let mut node: Box<dyn AudioUnit> = match factor {
+            2 => Box::new(sine_hz(1000.0) >> upsample::<U2>() >> downsample_aa::<U2>()),
+            4 => Box::new(sine_hz(1000.0) >> upsample::<U4>() >> downsample_aa::<U4>()),
+            _ => Box::new(sine_hz(1000.0) >> upsample::<U8>() >> downsample_aa::<U8>()),
+        };
+        for _ in 0..1000 {
+            node.get_mono();
+        }
+        let mut power = 0.0;
+        for _ in 0..44100 {
+            power += squared(node.get_mono());
+        }
+        let rms = sqrt(power / 44100.0);
+        assert!((rms - sqrt(0.5)).abs() < 0.01);
+    }
+
+    // Upsampled channels are consecutive samples of the interpolated signal.
+    let mut node = sine_hz(1000.0) >> upsample::<U4>();
+    let mut samples = Vec::new();
+    for _ in 0..1000 {
+        samples.extend_from_slice(node.tick(&Frame::default()).as_slice());
+    }
+    let peak = samples[400..].iter().fold(0.0f32, |a, x| a.max(x.abs()));
+    assert!((peak - 1.0).abs() < 0.01);
+    // Successive samples at 4x rate change by at most the sine slope.
+    let slope = f32::TAU * 1000.0 / (44100.0 * 4.0);
+    assert!(samples[400..]
+        .windows(2)
+        .all(|w| (w[1] - w[0]).abs() < slope * 1.05));
+
+    // Up and down equals oversampling a pass-through node.
+    let mut node =
+        noise() >> ((upsample::<U4>() >> downsample_aa::<U4>()) ^ oversample_n::<U4, _>(pass()));
+    for _ in 0..1000 {
+        let (a, b) = node.get_stereo();
+        assert_eq!(a, b);
+    }
+}
+
+/// Resample a sine wave at input `bin` (of 4096) with constant `speed` and `quality`.
+/// Return the spectrum of the result.
+fn resampled_spectrum(bin: usize, speed: f32, quality: ResampleQuality) -> Vec<f32> {
+    let length = 4096;
+    let hz = bin as f64 / length as f64 * DEFAULT_SR;
+    let mut node = dc(speed) >> resample_quality(quality, sine_hz(hz as f32));
+    for _ in 0..length {
+        node.get_mono();
+    }
+    let mut data: Vec<f32> = (0..length).map(|_| node.get_mono()).collect();
+    real_fft(&mut data).iter().map(|x| x.norm_sqr()).collect()
+}
+
+#[test]
+fn test_resample_quality() {
+    // Slowing down a bright sine: everything except the transposed sine is imaging.
+    let imaging = |quality| {
+        let spectrum = resampled_spectrum(1400, 0.5, quality);
+        let total: f32 = spectrum.iter().sum();
+        (total - spectrum[700]) / total
+    };
+    let linear = imaging(ResampleQuality::Linear);
+    let cubic = imaging(ResampleQuality::Cubic);
+    let sinc = imaging(ResampleQuality::Sinc);
+    assert!(cubic < linear);
+    assert!(sinc < cubic * 0.01);
+
+    // Speeding up a bright sine transposes it above Nyquist: all output is aliasing.
+    let aliasing = |quality| resampled_spectrum(1400, 2.0, quality).iter().sum::<f32>();
+    let cubic = aliasing(ResampleQuality::Cubic);
+    let sinc = aliasing(ResampleQuality::Sinc);
+    assert!(sinc < cubic * 0.01);
+
+    // All qualities play back at the original speed at unity gain.
+    for quality in [
+        ResampleQuality::Linear,
+        ResampleQuality::Cubic,
+        ResampleQuality::Sinc,
+    ] {
+        let spectrum = resampled_spectrum(100, 1.0, quality);
+        let total: f32 = spectrum.iter().sum();
+        assert!(spectrum[100] > total * 0.999);
+        let unity = resampled_spectrum(100, 1.0, ResampleQuality::Cubic)[100];
+        assert!((spectrum[100] / unity - 1.0).abs() < 0.01);
+    }
+}
+
+/// Intervals in samples between upward zero crossings, located with linear interpolation.
+fn zero_crossing_intervals(samples: &[f32]) -> Vec<f32> {
+    let mut crossings = Vec::new();
+    for i in 1..samples.len() {
+        if samples[i - 1] < 0.0 && samples[i] >= 0.0 {
+            let d = samples[i] / (samples[i] - samples[i - 1]);
+            crossings.push(i as f64 - d as f64);
+        }
+    }
+    crossings.windows(2).map(|w| (w[1] - w[0]) as f32).collect()
+}
+
+#[test]
+fn test_tape() {
+    let intervals = |mut node: An<Tape>| {
+        let mut sine = sine_hz(1000.0);
+        let samples: Vec<f32> = (0..DEFAULT_SR as usize * 4)
+            .map(|_| node.filter_mono(sine.get_mono()))
+            .collect();
+        zero_crossing_intervals(&samples[1000..])
+    };
+    let range = |x: &[f32]| {
+        x.iter().fold(f32::MIN, |a, &b| a.max(b)) - x.iter().fold(f32::MAX, |a, &b| a.min(b))
+    };
+
+    // Without modulation, the pitch is steady.
+    let steady = intervals(tape(0.0, 0.0, 0.0));
+    assert!(range(&steady) < 0.02);
+
+    // Wow modulates the period by about 0.6%, or 0.26 samples at 1 kHz.
+    let wow = intervals(tape(1.0, 0.0, 0.0));
+    assert!(range(&wow) > 0.4);
+    // The modulation is slow: successive periods are nearly equal.
+    assert!(wow.windows(2).all(|w| (w[1] - w[0]).abs() < 0.02));
+
+    // Flutter modulates faster and less deeply.
+    let flutter = intervals(tape(0.0, 1.0, 0.0));
+    assert!(range(&flutter) > 0.2 && range(&flutter) < range(&wow));
+
+    // Saturation limits the level.
+    let mut node = tape(0.0, 0.0, 1.0);
+    for _ in 0..1000 {
+        assert!(node.filter_mono(10.0).abs() <= 0.1 + 1.0e-6);
+    }
+}
+
+/// Return the time in seconds for the backward integrated energy
+/// of the impulse response of `node` to decay by 60 dB.
+fn decay_time(node: &mut An<Plate>, length: f64) -> f64 {
+    let sample_rate = 44100.0;
+    node.set_sample_rate(sample_rate);
+    let mut energy: Vec<f64> = (0..(length * sample_rate) as usize)
+        .map(|i| {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            let y = node.tick(&Frame::from([x, x]));
+            (y[0] * y[0] + y[1] * y[1]) as f64
+        })
+        .collect();
+    for i in (0..energy.len() - 1).rev() {
+        energy[i] += energy[i + 1];
+    }
+    let threshold = energy[0] * 1.0e-6;
+    energy.iter().position(|&x| x < threshold).unwrap() as f64 / sample_rate
+}
+
+#[test]
+fn test_plate_reverb() {
+    for time in [1.0, 2.0, 4.0] {
+        let mut node = plate_reverb(time, 0.0, 0.0);
+        let measured = decay_time(&mut node, time as f64 * 2.0);
+        assert!(measured > time as f64 * 0.7 && measured < time as f64 * 1.3);
+    }
+
+    // Damping removes high frequencies from the tail.
+    let brightness = |damping: f32| {
+        let mut node = plate_reverb(2.0, damping, 0.0);
+        let mut previous = 0.0;
+        let mut energy = 0.0;
+        for i in 0..44100 {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            let y = node.tick(&Frame::from([x, x]))[0];
+            if i >= 22050 {
+                energy += (y - previous) * (y - previous);
+            }
+            previous = y;
+        }
+        energy
+    };
+    assert!(brightness(0.7) < brightness(0.0) * 0.1);
+
+    // Nothing comes out before the predelay has elapsed.
+    let mut node = plate_reverb(1.0, 0.0, 0.1);
+    node.set_sample_rate(44100.0);
+    let first = (0..10000)
+        .position(|i| {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            node.tick(&Frame::from([x, x]))[0] != 0.0
+        })
+        .unwrap();
+    assert!(first >= 4410);
+
+    // Parameters can be changed with settings.
+    node.set(Setting::time(3.0));
+    node.set(Setting::coefficient(0.5));
+    node.set(Setting::delay(0.05));
+    assert_eq!(node.time(), 3.0);
+    assert_eq!(node.damping(), 0.5);
+    assert_eq!(node.predelay(), 0.05);
+}
+
+#[test]
+fn test_early_reflections() {
+    let pattern = [(0.001, 0.5, -1.0), (0.002, -0.25, 1.0), (0.003, 1.0, 0.0)];
+    let mut node = early_reflections(&pattern);
+    node.set_sample_rate(1000.0);
+    let response: Vec<Frame<f32, U2>> = (0..10)
+        .map(|i| node.tick(&Frame::from([if i == 0 { 1.0 } else { 0.0 }])))
+        .collect();
+    assert_eq!(response[0], Frame::from([0.0, 0.0]));
+    assert!(abs(response[1][0] - 0.5) < 1.0e-6 && abs(response[1][1]) < 1.0e-6);
+    assert!(abs(response[2][0]) < 1.0e-6 && abs(response[2][1] + 0.25) < 1.0e-6);
+    assert!(abs(response[3][0] - f32::SQRT_2 * 0.5) < 1.0e-6);
+    assert!(abs(response[3][1] - f32::SQRT_2 * 0.5) < 1.0e-6);
+    // There is no feedback, so the response ends after the longest delay.
+    for frame in &response[4..] {
+        assert_eq!(*frame, Frame::from([0.0, 0.0]));
+    }
+
+    // Preset patterns fit within 0.1 seconds.
+    for pattern in [&EARLY_SMALL_ROOM[..], &EARLY_HALL[..]] {
+        let mut node = early_reflections(pattern);
+        let response: Vec<f32> = (0..10000)
+            .map(|i| node.tick(&Frame::from([if i == 0 { 1.0 } else { 0.0 }]))[0])
+            .collect();
+        let tail = response.iter().rposition(|&x| x != 0.0).unwrap();
+        assert!(tail < 4410);
+    }
+}
+
+#[test]
+fn test_haas() {
+    // The delayed channel lags the other by the delay time in samples.
+    for (side, delayed) in [(HaasSide::Left, 0), (HaasSide::Right, 1)] {
+        let mut node = haas(0.01, side);
+        assert!((node.delay() - 0.01).abs() < 1.0e-6);
+        assert_eq!(node.side(), side);
+        node.set_sample_rate(1000.0);
+        let mut input = noise().seed(1);
+        let output: Vec<Frame<f32, U2>> = (0..100)
+            .map(|_| node.tick(&Frame::from([input.get_mono()])))
+            .collect();
+        for i in 0..100 {
+            let expected = if i >= 10 {
+                output[i - 10][1 - delayed]
+            } else {
+                0.0
+            };
+            assert_eq!(output[i][delayed], expected);
+        }
+    }
+}
+
+/// Return the frequency of the output of `node` between 0.5 and 1.5 seconds,
+/// estimated from upward zero crossings.
+fn doppler_frequency(mut node: impl AudioUnit) -> f64 {
+    let sample_rate = 44100.0;
+    node.set_sample_rate(sample_rate);
+    let mut previous = 0.0;
+    let mut crossings = 0;
+    for i in 0..(1.5 * sample_rate) as usize {
+        let y = node.get_mono();
+        if i >= (0.5 * sample_rate) as usize && previous < 0.0 && y >= 0.0 {
+            crossings += 1;
+        }
+        previous = y;
+    }
+    crossings as f64
+}
+
+#[test]
+fn test_doppler() {
+    // A source moving at a tenth of the speed of sound shifts pitch by about 10%.
+    let approaching =
+        doppler_frequency((sine_hz(1000.0) | lfo(|t| 100.0 - 34.3 * t)) >> doppler(343.0, 200.0));
+    let receding =
+        doppler_frequency((sine_hz(1000.0) | lfo(|t| 10.0 + 34.3 * t)) >> doppler(343.0, 200.0));
+    let stationary = doppler_frequency((sine_hz(1000.0) | dc(50.0)) >> doppler(343.0, 200.0));
+    assert!(approaching > 1090.0 && approaching < 1110.0);
+    assert!(receding > 890.0 && receding < 910.0);
+    assert!(stationary > 995.0 && stationary < 1005.0);
+
+    // Amplitude follows the inverse of distance.
+    let mut node = (dc(1.0) | dc(4.0)) >> doppler(343.0, 10.0);
+    for _ in 0..1000 {
+        node.get_mono();
+    }
+    assert!(abs(node.get_mono() - 0.25) < 1.0e-6);
+}
+
+#[test]
+fn test_adt() {
+    // Without jitter, the double is the input delayed by 25 ms, mixed at equal level.
+    let mut node = adt(0, 0.0, 0.0);
+    node.set_sample_rate(1000.0);
+    let mut input = noise().seed(1);
+    let mut history = Vec::new();
+    for i in 0..200 {
+        let x = input.get_mono();
+        history.push(x);
+        let y = node.filter_mono(x);
+        let delayed = if i >= 25 { history[i - 25] } else { 0.0 };
+        assert!(abs(y - 0.5 * (x + delayed)) < 1.0e-6);
+    }
+
+    // Jitter makes the double deviate from a fixed delay, and the seed selects the modulation.
+    let render = |seed: u64| {
+        let mut node = adt(seed, 0.005, 10.0);
+        let mut input = noise().seed(1);
+        (0..44100)
+            .map(|_| node.filter_mono(input.get_mono()))
+            .collect::<Vec<f32>>()
+    };
+    let mut fixed = adt(0, 0.0, 0.0);
+    let mut input = noise().seed(1);
+    let reference: Vec<f32> = (0..44100)
+        .map(|_| fixed.filter_mono(input.get_mono()))
+        .collect();
+    assert_eq!(render(1), render(1));
+    assert!(render(1) != render(2));
+    assert!(render(1) != reference);
+}
+
+#[test]
+fn test_chorus_stereo() {
+    // Correlation coefficient of the output channels of `node` for a mono noise source.
+    fn correlation(mut node: impl AudioUnit) -> f32 {
+        let mut source = noise().seed(1);
+        let (mut lr, mut ll, mut rr) = (0.0, 0.0, 0.0);
+        for i in 0..44100 {
+            let x = source.get_mono();
+            let (l, r) = node.filter_stereo(x, x);
+            if i >= 4410 {
+                lr += l * r;
+                ll += l * l;
+                rr += r * r;
+            }
+        }
+        lr / sqrt(ll * rr)
+    }
+    // Identical mono choruses are fully correlated.
+    let same = correlation(chorus(1, 0.015, 0.005, 0.5) | chorus(1, 0.015, 0.005, 0.5));
+    assert!(same > 0.999);
+    // The stereo chorus decorrelates the wet voices; only the dry signal is shared.
+    let stereo = correlation(chorus_stereo(0.015, 0.005, 0.5));
+    assert!(stereo < 0.5);
+}
+
+#[test]
+fn test_leslie() {
+    // At tremolo speed, a tone in the horn band is modulated at the horn rotation frequency.
+    let mut node = sine_hz(3000.0) >> leslie(1.0);
+    let mut left = Vec::new();
+    for _ in 0..88200 {
+        left.push(node.get_stereo().0);
+    }
+
+    // Amplitude modulation: count crossings of the mean by the RMS envelope in 5 ms windows.
+    let envelope: Vec<f32> = left
+        .chunks(220)
+        .map(|chunk| sqrt(chunk.iter().map(|x| x * x).sum::<f32>() / 220.0))
+        .collect();
+    let mean = envelope.iter().sum::<f32>() / envelope.len() as f32;
+    let crossings = envelope
+        .windows(2)
+        .filter(|w| w[0] < mean && w[1] >= mean)
+        .count();
+    // The envelope crosses its mean upward once per rotation, 13 times in 2 seconds.
+    assert!(crossings >= 12 && crossings <= 14);
+
+    // Pitch modulation: periods between upward zero crossings vary by the Doppler shift.
+    let mut periods = Vec::new();
+    let mut previous: Option<f32> = None;
+    for i in 1..left.len() {
+        if left[i - 1] < 0.0 && left[i] >= 0.0 {
+            let t = (i - 1) as f32 + left[i - 1] / (left[i - 1] - left[i]);
+            if let Some(p) = previous {
+                periods.push(t - p);
+            }
+            previous = Some(t);
+        }
+    }
+    let shortest = periods.iter().fold(f32::MAX, |a, &b| a.min(b));
+    let longest = periods.iter().fold(0.0f32, |a, &b| a.max(b));
+    assert!(longest / shortest > 1.02 && longest / shortest < 1.1);
+
+    // Rotors accelerate gradually and the drum is slower to respond than the horn.
+    let mut node = leslie(0.0);
+    assert_eq!(node.horn_rate(), HORN_CHORALE_HZ);
+    node.set(Setting::value(1.0));
+    for _ in 0..4410 {
+        node.tick(&Frame::from([0.0]));
+    }
+    let horn = (node.horn_rate() - HORN_CHORALE_HZ) / (HORN_TREMOLO_HZ - HORN_CHORALE_HZ);
+    let drum = (node.drum_rate() - DRUM_CHORALE_HZ) / (DRUM_TREMOLO_HZ - DRUM_CHORALE_HZ);
+    assert!(horn > 0.2 && horn < 0.3);
+    assert!(drum > 0.03 && drum < 0.07);
+}
+
+#[test]
+fn test_autowah() {
+    // Return output to input amplitude ratio for a 2500 Hz sine wave of given amplitude.
+    let ratio = |amplitude: f32| {
+        let mut node = (sine_hz(2500.0) * amplitude) >> autowah(1.0, 300.0, 3000.0, 3.0);
+        let mut peak = 0.0;
+        for i in 0..44100 {
+            let y = node.get_mono();
+            if i >= 22050 {
+                peak = max(peak, abs(y));
+            }
+        }
+        peak / amplitude
+    };
+    // A loud input opens the filter toward the high end of the range.
+    assert!(ratio(1.0) > ratio(0.01) * 3.0);
+}
+
+#[test]
+fn test_bypass() {
+    let flag = shared(0.0);
+    let mut node = bypass(&flag, lowpass_hz(100.0, 1.0));
+    let mut reference = lowpass_hz(100.0, 1.0);
+    let mut source = noise().seed(1);
+
+    // Engaged effect matches the plain lowpass.
+    for _ in 0..1000 {
+        let x = source.get_mono();
+        assert_eq!(node.filter_mono(x), reference.filter_mono(x));
+    }
+
+    // Bypass reaches the dry signal after the crossfade.
+    flag.set(1.0);
+    for i in 0..1000 {
+        let x = source.get_mono();
+        let y = node.filter_mono(x);
+        reference.filter_mono(x);
+        if i >= 500 {
+            assert_eq!(y, x);
+        }
+    }
+
+    // The lowpass kept running, so it matches the reference when re-enabled.
+    flag.set(0.0);
+    for i in 0..1000 {
+        let x = source.get_mono();
+        let y = node.filter_mono(x);
+        let z = reference.filter_mono(x);
+        if i >= 500 {
+            assert!((y - z).abs() < 1.0e-6);
+        }
+    }
+
+    // Block processing follows the flag.
+    flag.set(1.0);
+    node.reset();
+    let input = Wave::render(DEFAULT_SR, 0.1, &mut (noise().seed(2)));
+    let output = input.filter(0.1, &mut node);
+    assert_eq!(input.channel(0), output.channel(0));
+
+    // In freeze mode, the lowpass is paused while bypassed.
+    let flag = shared(0.0);
+    let mut node = bypass_freeze(&flag, lowpass_hz(100.0, 1.0));
+    assert!(node.freeze());
+    node.filter_mono(1.0);
+    flag.set(1.0);
+    node.reset();
+    for _ in 0..1000 {
+        assert_eq!(node.filter_mono(1.0), 1.0);
+    }
+    flag.set(0.0);
+    // Freshly reset and frozen lowpass starts from zero state.
+    assert!(node.filter_mono(0.0).abs() < 1.0e-6);
+}
+
+#[test]
+fn test_wet_dry() {
+    let mut source = noise().seed(1);
+
+    // Mix 0 is the dry input.
+    let mut node = wet_dry(0.0, lowpass_hz(100.0, 1.0));
+    for _ in 0..1000 {
+        let x = source.get_mono();
+        assert_eq!(node.filter_mono(x), x);
+    }
+
+    // Mix 1 is the effect.
+    let mut node = wet_dry(1.0, lowpass_hz(100.0, 1.0));
+    let mut reference = lowpass_hz(100.0, 1.0);
+    for _ in 0..1000 {
+        let x = source.get_mono();
+        assert!((node.filter_mono(x) - reference.filter_mono(x)).abs() < 1.0e-6);
+    }
+
+    // Mix can be set at runtime. Half mix has equal gains.
+    node.set(Setting::value(0.5));
+    assert!((node.mix() - 0.5).abs() < 1.0e-6);
+    let mut node = wet_dry(0.5, pass() * 0.0);
+    let y = node.filter_mono(1.0);
+    assert!((y - core::f32::consts::FRAC_1_SQRT_2).abs() < 1.0e-6);
+    let mut node = wet_dry(0.5, pass());
+    assert!((node.filter_mono(1.0) - core::f32::consts::SQRT_2).abs() < 1.0e-6);
+
+    // The dry path is delayed by the latency of the effect.
+    let mut node = wet_dry(0.0, limiter(0.01, 0.01));
+    assert_eq!(node.dry_delay(), 441);
+    for _ in 0..441 {
+        assert_eq!(node.filter_mono(1.0), 0.0);
+    }
+    assert_eq!(node.filter_mono(1.0), 1.0);
+}
+
+#[test]
+fn test_triggered() {
+    let mut node = triggered(envelope(|t| exp(-t * 10.0)));
+    assert_eq!(node.inputs(), 1);
+    assert!((node.filter_mono(0.0) - 1.0).abs() < 1.0e-6);
+    for _ in 0..10000 {
+        node.filter_mono(0.0);
+    }
+    assert!(node.filter_mono(0.0) < 0.2);
+
+    // A rising edge restarts the decay.
+    assert!((node.filter_mono(1.0) - 1.0).abs() < 1.0e-6);
+    // A held gate does not retrigger.
+    for _ in 0..10000 {
+        node.filter_mono(1.0);
+    }
+    assert!(node.filter_mono(1.0) < 0.2);
+    // Neither does a value at the threshold.
+    node.filter_mono(0.0);
+    assert!(node.filter_mono(0.5) < 0.2);
+    assert!((node.filter_mono(0.6) - 1.0).abs() < 1.0e-6);
+
+    // Block processing retriggers at the edge.
+    node.reset();
+    let mut input = BufferVec::new(1);
+    input.set_f32(0, 40, 1.0);
+    let mut output = BufferVec::new(1);
+    for _ in 0..200 {
+        node.process(64, &BufferVec::new(1).buffer_ref(), &mut output.buffer_mut());
+    }
+    assert!(output.at_f32(0, 39) < 0.2);
+    node.process(64, &input.buffer_ref(), &mut output.buffer_mut());
+    assert!(output.at_f32(0, 39) < 0.2);
+    assert!((output.at_f32(0, 40) - 1.0).abs() < 1.0e-6);
+
+    // The wrapper reports the latency of the wrapped node.
+    let mut node = triggered(limiter(0.01, 0.1));
+    assert_eq!(node.latency(), limiter(0.01, 0.1).latency());
+    assert!(node.latency().unwrap() > 0.0);
+}
+
+#[test]
+fn test_parallel_compress() {
+    // Measure the RMS level of a loud sine through `node`, skipping the onset.
+    fn level(node: &mut dyn AudioUnit) -> f32 {
+        let mut source = sine_hz(110.0) * 4.0;
+        let mut sum = 0.0;
+        for i in 0..20000 {
+            let y = node.filter_mono(source.get_mono());
+            if i >= 10000 {
+                sum += y * y;
+            }
+        }
+        sqrt(sum / 10000.0)
+    }
+    let dry = level(&mut pass());
+    let compressed = level(&mut limiter(0.001, 0.1));
+    assert!(compressed < dry * 0.5);
+    for blend in [0.25, 0.5, 0.75] {
+        let blended = level(&mut parallel_compress(limiter(0.001, 0.1), blend));
+        assert!(blended < dry && blended > compressed);
+    }
+    // The compressed path is aligned with the dry path.
+    let node = parallel_compress(limiter(0.001, 0.1), 0.5);
+    assert_eq!(node.dry_delay(), 44);
+    assert_eq!(node.law(), CrossfadeLaw::Linear);
+}
+
+#[test]
+fn test_drive() {
+    // Measure the RMS level of a full scale sine through `node`.
+    fn level(node: &mut dyn AudioUnit) -> f32 {
+        let mut source = sine_hz(110.0);
+        let mut sum = 0.0;
+        for _ in 0..44100 {
+            let y = node.filter_mono(source.get_mono());
+            sum += y * y;
+        }
+        sqrt(sum / 44100.0)
+    }
+    let dry = level(&mut pass());
+    for amount in [0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 50.0] {
+        let driven = level(&mut drive(amount));
+        assert!(abs(amp_db(driven / dry)) < 0.1);
+    }
+
+    // Low drive is nearly transparent.
+    let mut node = drive(0.01);
+    assert!(abs(node.filter_mono(0.5) - 0.5) < 1.0e-4);
+
+    // Drive amount can be set at runtime and from the input.
+    let mut node = drive(1.0);
+    node.set(Setting::value(8.0));
+    assert_eq!(node.amount(), 8.0);
+    let mut node = drive_in();
+    node.tick(&Frame::from([0.0, 4.0]));
+    assert_eq!(node.amount(), 4.0);
+    assert_eq!(node.gain(), drive(4.0).gain());
+}
+
+#[test]
+fn test_exciter() {
+    // Measure energy above 4 kHz of a 1 kHz tone passed through `node`.
+    fn high_energy(node: &mut dyn AudioUnit) -> f32 {
+        let mut source = sine_hz(1000.0) * 0.5;
+        let mut measure = highpass_hz(4000.0, 0.7) >> highpass_hz(4000.0, 0.7);
+        let mut sum = 0.0;
+        for i in 0..44100 {
+            let y = measure.filter_mono(node.filter_mono(source.get_mono()));
+            if i >= 4410 {
+                sum += y * y;
+            }
+        }
+        sum
+    }
+    let dry = high_energy(&mut pass());
+    let excited = high_energy(&mut exciter(800.0, 0.5));
+    assert!(excited > dry * 10.0);
+
+    // Zero amount is transparent.
+    let mut node = exciter(2000.0, 0.0);
+    let mut source = noise().seed(1);
+    for _ in 0..1000 {
+        let x = source.get_mono();
+        assert_eq!(node.filter_mono(x), x);
+    }
+
+    // Content well below the crossover is nearly unchanged.
+    let mut node = exciter(5000.0, 1.0);
+    let mut source = sine_hz(100.0);
+    for i in 0..10000 {
+        let x = source.get_mono();
+        let y = node.filter_mono(x);
+        // Skip the onset transient.
+        if i >= 100 {
+            assert!((y - x).abs() < 0.01);
+        }
+    }
+    node.set(Setting::center(3000.0));
+    node.set(Setting::value(0.25));
+    assert_eq!(node.frequency(), 3000.0);
+    assert_eq!(node.amount(), 0.25);
+}
@@ -98,6 +98,8 @@ fn test_responses() {
     test_response(delay(0.0));
     test_response(delay(0.0001));
     test_response(delay(0.0001) >> delay(0.0002));
+    test_response((pass() | dc(0.5)) >> ring_mod());
+    test_response((pass() | dc(0.0)) >> amp_mod(0.5));
     test_response(dcblock());
     test_response(dcblock_hz(100.0) & follow(0.001));
     test_response(lowpole_hz(1000.0));
@@ -281,3 +283,382 @@ fn test_allpass() {
         }
     }
 }
+
+/// Test that modulators are linear only with a constant modulator.
+#[test]
+fn test_modulators() {
+    let mut node = (pass() | dc(2.0)) >> ring_mod();
+    assert!(node.response_db(0, 440.0).unwrap() > 6.0);
+    let mut node = (pass() | sine_hz(5.0)) >> ring_mod();
+    assert!(node.response(0, 440.0).is_none());
+    assert_eq!(node.latency(), Some(0.0));
+    let mut node = (pass() | dc(-1.0)) >> amp_mod(0.5);
+    assert!((node.response(0, 440.0).unwrap().re - 0.5).abs() < 1.0e-6);
+    let mut node = (pass() | sine_hz(5.0)) >> amp_mod(0.5);
+    assert!(node.response(0, 440.0).is_none());
+}
+
+/// Compare the analytic response of `coefs` to the measured response of `filter`.
+fn test_coefs_response(coefs: SvfCoefs<f64>, mut filter: impl AudioUnit) {
+    let length = 0x8000;
+    let sample_rate = DEFAULT_SR;
+    filter.set_sample_rate(sample_rate);
+    let mut buffer: Vec<f32> = (0..length)
+        .map(|i| filter.filter_mono(if i == 0 { 1.0 } else { 0.0 }))
+        .collect();
+    let spectrum = real_fft(&mut buffer);
+    for i in (1..length / 2).step_by(37) {
+        let f = i as f64 / length as f64 * sample_rate;
+        let reported = coefs.response(sample_rate, f);
+        let reported = Complex32::new(reported.re as f32, reported.im as f32);
+        assert!(is_equal_response(reported, spectrum[i]));
+    }
+}
+
+#[test]
+fn test_svf_coefs() {
+    let sr = DEFAULT_SR;
+    test_coefs_response(SvfCoefs::lowpass(sr, 1000.0, 2.0), lowpass_hz(1000.0, 2.0));
+    test_coefs_response(SvfCoefs::highpass(sr, 500.0, 0.7), highpass_hz(500.0, 0.7));
+    test_coefs_response(
+        SvfCoefs::bandpass(sr, 2000.0, 3.0),
+        bandpass_hz(2000.0, 3.0),
+    );
+    test_coefs_response(SvfCoefs::notch(sr, 3000.0, 1.0), notch_hz(3000.0, 1.0));
+    test_coefs_response(SvfCoefs::peak(sr, 800.0, 1.5), peak_hz(800.0, 1.5));
+    test_coefs_response(SvfCoefs::allpass(sr, 1500.0, 0.5), allpass_hz(1500.0, 0.5));
+    test_coefs_response(
+        SvfCoefs::bell(sr, 1000.0, 1.0, 2.0),
+        bell_hz(1000.0, 1.0, 2.0),
+    );
+    test_coefs_response(
+        SvfCoefs::lowshelf(sr, 300.0, 0.7, 0.5),
+        lowshelf_hz(300.0, 0.7, 0.5),
+    );
+    test_coefs_response(
+        SvfCoefs::highshelf(sr, 5000.0, 0.7, 3.0),
+        highshelf_hz(5000.0, 0.7, 3.0),
+    );
+
+    // The integrator gain and damping are exposed.
+    let coefs = SvfCoefs::lowpass(sr, 1000.0, 2.0);
+    assert_eq!(coefs.g, tan(f64::PI * 1000.0 / sr));
+    assert_eq!(coefs.k, 0.5);
+}
+
+#[test]
+fn test_thiran_delay() {
+    let length = 0x8000;
+    for order in [1, 2, 4, 8] {
+        for delay in [order as f32 - 0.5, order as f32 + 0.3, 10.7] {
+            // Reported response matches measured response.
+            test_response((pass() | dc(delay)) >> thiran_delay(16.0, order));
+
+            // Magnitude response is flat across the band.
+            let mut node = (pass() | dc(delay)) >> thiran_delay(16.0, order);
+            let mut data: Vec<f32> = (0..length)
+                .map(|i| node.filter_mono(if i == 0 { 1.0 } else { 0.0 }))
+                .collect();
+            let spectrum = real_fft(&mut data);
+            for s in &spectrum[1..] {
+                assert!(abs(s.norm() - 1.0) < 1.0e-4);
+            }
+
+            // Phase delay is accurate at low frequencies.
+            let response = node.response(0, 100.0).unwrap();
+            let phase_delay = -response.arg() / (f64::TAU * 100.0 / DEFAULT_SR);
+            assert!(abs(phase_delay - delay as f64) < 1.0e-3);
+        }
+    }
+}
+
+#[test]
+fn test_linear_map() {
+    // Response propagates through a linear map that doubles the signal.
+    let mut node = lowpass_hz(1000.0, 1.0) >> linear_map(2.0, |x: &Frame<f32, U1>| x[0] * 2.0);
+    let mut reference = lowpass_hz(1000.0, 1.0);
+    for f in [10.0, 100.0, 1000.0, 10000.0] {
+        let response = node.response(0, f).unwrap();
+        let expected = reference.response(0, f).unwrap() * 2.0;
+        assert!((response - expected).norm() < 1.0e-9);
+    }
+    assert!(abs(node.response_db(0, 10.0).unwrap() - amp_db(2.0)) < 1.0e-3);
+    test_response(node);
+
+    // An ordinary map is accounted as non-linear.
+    let mut node = lowpass_hz(1000.0, 1.0) >> map(|x: &Frame<f32, U1>| x[0] * 2.0);
+    assert!(node.response(0, 100.0).is_none());
+
+    // Multichannel gains.
+    let mut node =
+        (pass() | pass()) >> linear_map((0.5, -1.0), |x: &Frame<f32, U2>| (x[0] * 0.5, -x[1]));
+    assert!(abs(node.response(0, 100.0).unwrap().re - 0.5) < 1.0e-9);
+    assert!(abs(node.response(1, 100.0).unwrap().re + 1.0) < 1.0e-9);
+}
+
+#[test]
+fn test_ladder_oscillation() {
+    // Count the frequency of a self-oscillating ladder from upward zero crossings.
+    fn oscillation(node: &mut dyn AudioUnit) -> (f64, f32) {
+        node.set_sample_rate(DEFAULT_SR);
+        for _ in 0..44100 {
+            node.filter_mono(0.0);
+        }
+        let mut crossings = 0;
+        let mut peak = 0.0f32;
+        let mut previous = node.filter_mono(0.0);
+        for _ in 0..44100 {
+            let y = node.filter_mono(0.0);
+            if previous < 0.0 && y >= 0.0 {
+                crossings += 1;
+            }
+            peak = max(peak, abs(y));
+            previous = y;
+        }
+        (crossings as f64, peak)
+    }
+    for cutoff in [200.0, 1000.0, 4000.0] {
+        for node in [
+            &mut transistor_ladder_hz(cutoff, 1.0) as &mut dyn AudioUnit,
+            &mut diode_ladder_hz(cutoff, 1.0),
+        ] {
+            let (frequency, peak) = oscillation(node);
+            assert!(peak > 0.02);
+            assert!(abs(frequency / cutoff as f64 - 1.0) < 0.05);
+        }
+    }
+
+    // Below the threshold, the ladders stay silent without input.
+    for node in [
+        &mut transistor_ladder_hz(1000.0, 0.8) as &mut dyn AudioUnit,
+        &mut diode_ladder_hz(1000.0, 0.8),
+    ] {
+        let (_, peak) = oscillation(node);
+        assert!(peak < 1.0e-6);
+    }
+
+    // The ladders are stable with cutoff near Nyquist.
+    let mut node = noise() >> (pass() | dc((20000.0, 1.0))) >> diode_ladder();
+    let mut other = noise() >> (pass() | dc((20000.0, 1.0))) >> transistor_ladder();
+    for _ in 0..44100 {
+        assert!(abs(node.get_mono()) < 10.0);
+        assert!(abs(other.get_mono()) < 10.0);
+    }
+}
+
+#[test]
+fn test_analog_lowpass() {
+    // Measure small signal gain of `node` at frequency `f` Hz.
+    fn gain(node: &mut dyn AudioUnit, f: f32) -> f32 {
+        node.reset();
+        let mut source = sine_hz(f);
+        let mut peak = 0.0f32;
+        for i in 0..22050 {
+            let y = node.filter_mono(source.get_mono() * 1.0e-3);
+            if i >= 11025 {
+                peak = max(peak, abs(y));
+            }
+        }
+        peak * 1.0e3
+    }
+    // Find the resonant peak by scanning around the cutoff.
+    fn peak(node: &mut dyn AudioUnit, cutoff: f32) -> (f32, f32) {
+        let mut best = (0.0, 0.0);
+        for i in 0..=40 {
+            let f = cutoff * (0.9 + 0.005 * i as f32);
+            let g = gain(node, f);
+            if g > best.1 {
+                best = (f, g);
+            }
+        }
+        best
+    }
+    for cutoff in [300.0, 1000.0, 3000.0] {
+        // Sallen-Key passband gain rises with resonance.
+        let mut node = sallen_key_lowpass_hz(cutoff, 0.8);
+        let q = node.q();
+        let k = node.gain();
+        assert!(abs(gain(&mut node, 10.0) - k) < 0.01 * k);
+        let (f, g) = peak(&mut node, cutoff);
+        let expected_f = cutoff * sqrt(1.0 - 0.5 / (q * q));
+        let expected_g = k * q / sqrt(1.0 - 0.25 / (q * q));
+        assert!(abs(f / expected_f - 1.0) < 0.01);
+        assert!(abs(amp_db(g / expected_g)) < 0.5);
+
+        // OTA lowpass has unity passband gain.
+        let mut node = ota_lowpass_hz(cutoff, 0.8);
+        let q = node.q();
+        assert!(abs(gain(&mut node, 10.0) - 1.0) < 0.01);
+        let (f, g) = peak(&mut node, cutoff);
+        let expected_f = cutoff * sqrt(1.0 - 0.5 / (q * q));
+        let expected_g = q / sqrt(1.0 - 0.25 / (q * q));
+        assert!(abs(f / expected_f - 1.0) < 0.01);
+        assert!(abs(amp_db(g / expected_g)) < 0.5);
+    }
+
+    // At full resonance, the Sallen-Key filter rings on indefinitely
+    // while the OTA filter decays.
+    let mut sallen_key = sallen_key_lowpass_hz(1000.0, 1.0);
+    let mut ota = ota_lowpass_hz(1000.0, 1.0);
+    sallen_key.filter_mono(1.0);
+    ota.filter_mono(1.0);
+    let mut sallen_key_peak = 0.0f32;
+    let mut ota_peak = 0.0f32;
+    for i in 0..44100 {
+        let x = sallen_key.filter_mono(0.0);
+        let y = ota.filter_mono(0.0);
+        if i >= 22050 {
+            sallen_key_peak = max(sallen_key_peak, abs(x));
+            ota_peak = max(ota_peak, abs(y));
+        }
+    }
+    assert!(sallen_key_peak > 0.25 && sallen_key_peak <= 1.0);
+    assert!(ota_peak < 1.0e-6);
+}
+
+#[test]
+fn test_response_curve() {
+    let frequencies = [20.0, 200.0, 2000.0, 20000.0];
+    let mut node = bell_hz(1000.0, 2.0, 4.0) >> highpass_hz(100.0, 1.0);
+    let mut net = Net::wrap(Box::new(node.clone()));
+    let curve = node.response_curve(0, &frequencies);
+    let net_curve = net.response_curve_db(0, &frequencies);
+    for (i, f) in frequencies.iter().enumerate() {
+        let response = node.response(0, *f).unwrap();
+        assert_eq!(curve[i], Some(response));
+        let (db, phase) = net_curve[i].unwrap();
+        assert!(abs(db - node.response_db(0, *f).unwrap()) < 1.0e-9);
+        assert!(abs(phase - response.arg()) < 1.0e-9);
+    }
+}
+
+#[test]
+fn test_group_delay() {
+    // A single sample delay delays all frequencies by one sample.
+    let mut node = tick();
+    for f in [0.0, 10.0, 1000.0, 20000.0] {
+        let delay = node.group_delay(0, f).unwrap() * DEFAULT_SR;
+        assert!(abs(delay - 1.0) < 1.0e-6);
+    }
+
+    // Longer delays wrap the phase many times over.
+    let mut node = delay(0.5);
+    let delay = node.group_delay(0, 15000.0).unwrap();
+    assert!(abs(delay - 0.5) < 1.0e-6);
+
+    // A symmetric FIR filter has linear phase.
+    let mut node = 0.25 * pass() & 0.5 * tick() & 0.25 * (tick() >> tick());
+    for f in [100.0, 5000.0, 15000.0] {
+        let delay = node.group_delay(0, f).unwrap() * DEFAULT_SR;
+        assert!(abs(delay - 1.0) < 1.0e-6);
+    }
+
+    // Group delay agrees when the node is in a network.
+    let mut node = lowpass_hz(1000.0, 1.0);
+    let mut net = Net::wrap(Box::new(node.clone()));
+    let delay = node.group_delay(0, 500.0).unwrap();
+    assert!(delay > 0.0);
+    assert!(abs(net.group_delay(0, 500.0).unwrap() - delay) < 1.0e-9);
+
+    // Nonlinear nodes have no group delay.
+    assert_eq!(shape(Tanh(1.0)).group_delay(0, 100.0), None);
+}
+
+#[test]
+fn test_linear_phase_crossover() {
+    // The summed bands reconstruct the input with only a pure delay.
+    let mut node = linear_phase_crossover::<U3>(&[200.0, 2000.0], 255);
+    assert_eq!(node.delay(), 127);
+    assert_eq!(node.latency(), Some(127.0));
+    let mut sum = node.clone() >> (pass() + pass() + pass());
+    let mut source = noise().seed(1);
+    let mut input = Vec::new();
+    for i in 0..2000 {
+        let x = source.get_mono();
+        input.push(x);
+        let y = sum.filter_mono(x);
+        let expected = if i >= 127 { input[i - 127] } else { 0.0 };
+        assert!((y - expected).abs() < 1.0e-5);
+    }
+
+    // Each band passes its own range.
+    let mut node = linear_phase_crossover::<U3>(&[200.0, 2000.0], 2047);
+    let mut level = |f: f32| {
+        node.reset();
+        let mut source = sine_hz(f);
+        let mut sums = [0.0f32; 3];
+        for i in 0..12000 {
+            let y = node.tick(&[source.get_mono()].into());
+            if i >= 3000 {
+                for band in 0..3 {
+                    sums[band] += y[band] * y[band];
+                }
+            }
+        }
+        sums.map(|x| sqrt(x / 9000.0))
+    };
+    for (f, band) in [(50.0, 0), (700.0, 1), (8000.0, 2)] {
+        let bands = level(f);
+        assert!(bands[band] > 0.69 && bands[band] < 0.72);
+        assert!(bands.iter().sum::<f32>() < 0.72);
+    }
+
+    // Long kernels are convolved in the frequency domain. The impulse responses
+    // of the bands stay symmetric around the latency, so they have linear phase.
+    let mut node = linear_phase_crossover::<U3>(&[200.0, 2000.0], 4001);
+    let delay = node.delay();
+    let response: Vec<Frame<f32, U3>> = (0..2 * delay + 100)
+        .map(|i| node.tick(&[if i == 0 { 1.0 } else { 0.0 }].into()))
+        .collect();
+    for band in 0..3 {
+        for i in 1..=delay {
+            assert!((response[delay - i][band] - response[delay + i][band]).abs() < 1.0e-6);
+        }
+        for y in &response[2 * delay + 1..] {
+            assert!(y[band].abs() < 1.0e-6);
+        }
+    }
+
+    // Reported responses match the measured ones.
+    test_response(
+        linear_phase_crossover::<U3>(&[200.0, 2000.0], 255) >> (sink() | pass() | sink()),
+    );
+    test_response(linear_phase_crossover::<U2>(&[1000.0], 1001) >> (sink() | pass()));
+    test_response(sum);
+}
+
+#[test]
+fn test_resonator_chord() {
+    // Nine notes take two SIMD banks.
+    let chord = [
+        110.0, 164.81, 220.0, 277.18, 329.63, 440.0, 554.37, 659.26, 880.0,
+    ];
+    let mut node = resonator_chord(&chord, 0.0, 2.0);
+    // Resonant peaks appear at each listed frequency, above the response midway between notes.
+    for (i, &f) in chord.iter().enumerate() {
+        let peak = node.response(0, f as f64).unwrap().norm();
+        assert!(peak > 0.1);
+        if i + 1 < chord.len() {
+            let valley = node
+                .response(0, sqrt(f as f64 * chord[i + 1] as f64))
+                .unwrap()
+                .norm();
+            assert!(peak > valley * 10.0);
+        }
+    }
+
+    // Sine excitation at a resonance rings much louder than between resonances.
+    let rms = |f: f32| {
+        let mut node = sine_hz(f) >> resonator_chord(&[220.0, 330.0], 0.0, 0.5);
+        let wave = Wave::render(DEFAULT_SR, 2.0, &mut node);
+        let start = wave.length() / 2;
+        sqrt(
+            (start..wave.length())
+                .map(|i| squared(wave.at(0, i)))
+                .sum::<f32>()
+                / start as f32,
+        )
+    };
+    assert!(rms(220.0) > rms(270.0) * 10.0);
+    assert!(rms(330.0) > rms(270.0) * 10.0);
+}
@@ -0,0 +1,106 @@
+//! Prelude parity tests. The `hacker32` and `hacker` preludes
+//! should offer the same builders with `f32` parameters.
+
+#![allow(dead_code)]
+
+use fundsp::typenum::Unsigned;
+
+/// Instantiate filter and oscillator builders from a prelude with `f32` arguments.
+macro_rules! instantiate {
+    ($prelude:ident) => {{
+        use fundsp::$prelude::*;
+        let units: Vec<Box<dyn AudioUnit>> = vec![
+            Box::new(allpass()),
+            Box::new(allpass_hz(440.0f32, 1.0f32)),
+            Box::new(allpass_q(1.0f32)),
+            Box::new(bell()),
+            Box::new(bell_hz(440.0f32, 1.0f32, 2.0f32)),
+            Box::new(bell_q(1.0f32, 2.0f32)),
+            Box::new(lowshelf()),
+            Box::new(lowshelf_hz(440.0f32, 1.0f32, 2.0f32)),
+            Box::new(lowshelf_q(1.0f32, 2.0f32)),
+            Box::new(highshelf()),
+            Box::new(highshelf_hz(440.0f32, 1.0f32, 2.0f32)),
+            Box::new(highshelf_q(1.0f32, 2.0f32)),
+            Box::new(morph()),
+            Box::new(morph_hz(440.0f32, 1.0f32, 0.5f32)),
+            Box::new(lowrez()),
+            Box::new(lowrez_hz(440.0f32, 0.5f32)),
+            Box::new(lowrez_q(0.5f32)),
+            Box::new(bandrez()),
+            Box::new(bandrez_hz(440.0f32, 0.5f32)),
+            Box::new(bandrez_q(0.5f32)),
+            Box::new(pulse()),
+        ];
+        units
+            .into_iter()
+            .map(|unit| (unit.inputs(), unit.outputs()))
+            .collect::<Vec<_>>()
+    }};
+}
+
+#[test]
+fn test_prelude_parity() {
+    let hacker32 = instantiate!(hacker32);
+    let hacker = instantiate!(hacker);
+    assert_eq!(hacker32, hacker);
+    // Spot check arities.
+    assert_eq!(hacker32[0], (3, 1));
+    assert_eq!(hacker32[2], (2, 1));
+    assert_eq!(hacker32[5], (2, 1));
+    assert_eq!(hacker32[20], (2, 1));
+
+    // The biquad bank is as wide as the SIMD type of each prelude.
+    assert_eq!(fundsp::hacker32::biquad_bank().inputs(), 8);
+    assert_eq!(fundsp::hacker::biquad_bank().inputs(), 4);
+    assert_eq!(
+        fundsp::prelude::biquad_bank::<fundsp::wide::f32x8>().outputs(),
+        8
+    );
+}
+
+/// Check at compile time that each prelude type alias `UN` has the value `N`.
+macro_rules! check_aliases {
+    ($($alias:ident = $value:literal,)*) => {
+        $(
+            const _: () = assert!(<fundsp::prelude::$alias as Unsigned>::USIZE == $value);
+            const _: () = assert!(<fundsp::hacker::$alias as Unsigned>::USIZE == $value);
+            const _: () = assert!(<fundsp::hacker32::$alias as Unsigned>::USIZE == $value);
+        )*
+    };
+}
+
+check_aliases! {
+    U0 = 0, U1 = 1, U2 = 2, U3 = 3, U4 = 4, U5 = 5, U6 = 6, U7 = 7, U8 = 8, U9 = 9, U10 = 10,
+    U11 = 11, U12 = 12, U13 = 13, U14 = 14, U15 = 15, U16 = 16, U17 = 17, U18 = 18, U19 = 19,
+    U20 = 20, U21 = 21, U22 = 22, U23 = 23, U24 = 24, U25 = 25, U26 = 26, U27 = 27, U28 = 28,
+    U29 = 29, U30 = 30, U31 = 31, U32 = 32, U33 = 33, U34 = 34, U35 = 35, U36 = 36, U37 = 37,
+    U38 = 38, U39 = 39, U40 = 40, U41 = 41, U42 = 42, U43 = 43, U44 = 44, U45 = 45, U46 = 46,
+    U47 = 47, U48 = 48, U49 = 49, U50 = 50, U51 = 51, U52 = 52, U53 = 53, U54 = 54, U55 = 55,
+    U56 = 56, U57 = 57, U58 = 58, U59 = 59, U60 = 60, U61 = 61, U62 = 62, U63 = 63, U64 = 64,
+    U65 = 65, U66 = 66, U67 = 67, U68 = 68, U69 = 69, U70 = 70, U71 = 71, U72 = 72, U73 = 73,
+    U74 = 74, U75 = 75, U76 = 76, U77 = 77, U78 = 78, U79 = 79, U80 = 80, U81 = 81, U82 = 82,
+    U83 = 83, U84 = 84, U85 = 85, U86 = 86, U87 = 87, U88 = 88, U89 = 89, U90 = 90, U91 = 91,
+    U92 = 92, U93 = 93, U94 = 94, U95 = 95, U96 = 96, U97 = 97, U98 = 98, U99 = 99, U100 = 100,
+    U101 = 101, U102 = 102, U103 = 103, U104 = 104, U105 = 105, U106 = 106, U107 = 107,
+    U108 = 108, U109 = 109, U110 = 110, U111 = 111, U112 = 112, U113 = 113, U114 = 114,
+    U115 = 115, U116 = 116, U117 = 117, U118 = 118, U119 = 119, U120 = 120, U121 = 121,
+    U122 = 122, U123 = 123, U124 = 124, U125 = 125, U126 = 126, U127 = 127, U128 = 128,
+}
+
+#[test]
+fn test_size_aliases() {
+    assert_eq!(fundsp::prelude::U90::USIZE, 90);
+    assert_eq!(fundsp::hacker::U90::USIZE, 90);
+    assert_eq!(fundsp::hacker32::U90::USIZE, 90);
+}
+
+#[test]
+fn test_channels_macro() {
+    use fundsp::hacker32::*;
+    assert_eq!(<fundsp::channels!(90) as Unsigned>::USIZE, 90);
+    assert_eq!(<U<300> as Unsigned>::USIZE, 300);
+    // The macro and the aliases name the same types.
+    let node: An<MultiPass<U90>> = multipass::<fundsp::channels!(90)>();
+    assert_eq!(node.inputs(), 90);
+}
@@ -0,0 +1,138 @@
+//! Clock and rhythm component tests.
+
+use fundsp::hacker32::*;
+
+/// Return the sample indices of triggers emitted by `node` from a silent reset input.
+fn triggers(node: &mut dyn AudioUnit, samples: usize) -> Vec<usize> {
+    (0..samples)
+        .filter(|_| node.filter_mono(0.0) > 0.5)
+        .collect()
+}
+
+#[test]
+fn test_clock() {
+    // 120 BPM in sixteenths is 8 ticks per second, with a fractional period of 5512.5 samples.
+    let mut node = clock(120.0, 4.0);
+    assert_eq!(node.period(), 5512.5);
+    let ticks = triggers(&mut node, 441000);
+    assert_eq!(ticks.len(), 80);
+    assert_eq!(ticks[0], 0);
+    // Periods alternate between neighboring whole samples without drift.
+    for (i, tick) in ticks.iter().enumerate() {
+        assert_eq!(*tick, (i as f64 * 5512.5).ceil() as usize);
+    }
+
+    // Tempo can be changed at runtime.
+    node.reset();
+    node.set(Setting::value(60.0));
+    assert_eq!(node.bpm(), 60.0);
+    assert_eq!(triggers(&mut node, 441000).len(), 40);
+
+    // A rising edge on the reset input restarts the clock on the same sample.
+    let mut node = clock(120.0, 1.0);
+    assert_eq!(node.filter_mono(0.0), 1.0);
+    for _ in 0..1000 {
+        assert_eq!(node.filter_mono(0.0), 0.0);
+    }
+    assert_eq!(node.filter_mono(1.0), 1.0);
+    assert_eq!(node.filter_mono(1.0), 0.0);
+    let ticks = triggers(&mut node, 44100);
+    assert_eq!(ticks, vec![22048, 44098]);
+}
+
+#[test]
+fn test_euclid() {
+    assert_eq!(bjorklund(0, 4), vec![false; 4]);
+    assert_eq!(bjorklund(4, 4), vec![true; 4]);
+    assert_eq!(
+        bjorklund(5, 8),
+        vec![true, false, true, true, false, true, true, false]
+    );
+
+    // Over 8 input triggers, 3 pulses in 8 steps is the tresillo x..x..x.
+    let mut node = euclid(3, 8, 0);
+    let onsets: Vec<bool> = (0..8)
+        .map(|_| {
+            let onset = node.filter_mono(1.0);
+            assert_eq!(node.filter_mono(0.0), 0.0);
+            onset > 0.5
+        })
+        .collect();
+    assert_eq!(
+        onsets,
+        vec![true, false, false, true, false, false, true, false]
+    );
+
+    // Driven by a clock, onsets fall on the clock ticks of the pattern steps.
+    let mut node = clock(120.0, 4.0) >> euclid(3, 8, 0);
+    assert_eq!(triggers(&mut node, 44100), vec![0, 16538, 33075]);
+
+    // Rotation starts the rhythm from a later step.
+    let node = euclid(3, 8, 1);
+    assert_eq!(
+        node.pattern(),
+        &[false, false, true, false, false, true, false, true]
+    );
+
+    // The rhythm can be changed at runtime.
+    let mut node = euclid(3, 8, 0);
+    node.set_rhythm(2, 4, 0);
+    assert_eq!((node.pulses(), node.steps(), node.rotation()), (2, 4, 0));
+    assert_eq!(node.pattern(), &[true, false, true, false]);
+
+    // The rhythm can also be changed with a setting.
+    node.set(Setting::rhythm(3, 8, 1));
+    assert_eq!((node.pulses(), node.steps(), node.rotation()), (3, 8, 1));
+    assert_eq!(
+        node.pattern(),
+        &[false, false, true, false, false, true, false, true]
+    );
+
+    // Out-of-range settings are ignored.
+    node.set(Setting::rhythm(3, 0, 0));
+    node.set(Setting::rhythm(3, EUCLID_MAX_STEPS + 1, 0));
+    assert_eq!((node.pulses(), node.steps(), node.rotation()), (3, 8, 1));
+    node.set(Setting::rhythm(5, EUCLID_MAX_STEPS, 0));
+    assert_eq!(node.pattern(), &bjorklund(5, EUCLID_MAX_STEPS)[..]);
+}
+
+#[test]
+fn test_step_sequencer() {
+    let mut node = step_sequencer(&[1.0, 2.0, 3.0]);
+    let mut tick = |advance: f32, reset: f32| node.tick(&Frame::from([advance, reset]))[0];
+
+    // Holds the first step until triggered, then advances and wraps around.
+    assert_eq!(tick(0.0, 0.0), 1.0);
+    assert_eq!(tick(0.0, 0.0), 1.0);
+    let mut values = Vec::new();
+    for _ in 0..7 {
+        values.push(tick(1.0, 0.0));
+        // A held trigger does not advance again.
+        assert_eq!(tick(1.0, 0.0), *values.last().unwrap());
+        assert_eq!(tick(0.0, 0.0), *values.last().unwrap());
+    }
+    assert_eq!(values, vec![2.0, 3.0, 1.0, 2.0, 3.0, 1.0, 2.0]);
+
+    // Reset returns to the first step and wins over a simultaneous advance.
+    assert_eq!(tick(0.0, 1.0), 1.0);
+    assert_eq!(tick(0.0, 0.0), 1.0);
+    assert_eq!(tick(1.0, 0.0), 2.0);
+    assert_eq!(tick(0.0, 0.0), 2.0);
+    assert_eq!(tick(1.0, 1.0), 1.0);
+    assert_eq!(tick(0.0, 0.0), 1.0);
+
+    // Step values can be set at runtime.
+    node.set(Setting::step(1, 5.0));
+    assert_eq!(node.steps(), &[1.0, 5.0, 3.0]);
+    assert_eq!(node.tick(&Frame::from([1.0, 0.0]))[0], 5.0);
+
+    // Glide ramps linearly to the new step value over the glide time.
+    let mut node = step_sequencer(&[0.0, 1.0]);
+    node.set_sample_rate(1000.0);
+    node.set(Setting::time(0.004));
+    assert_eq!(node.glide(), 0.004);
+    let ramp: Vec<f32> = (0..6)
+        .map(|_| node.tick(&Frame::from([1.0, 0.0]))[0])
+        .collect();
+    assert_eq!(ramp, vec![0.25, 0.5, 0.75, 1.0, 1.0, 1.0]);
+}
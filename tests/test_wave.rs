@@ -0,0 +1,286 @@
+//! Wave tests.
+
+#![allow(clippy::manual_range_contains)]
+
+use fundsp::hacker::*;
+
+/// Estimate the frequency of a mono signal from its zero crossings, skipping the edges.
+fn zero_crossing_frequency(samples: &[f32], sample_rate: f64) -> f64 {
+    let skip = samples.len() / 10;
+    let mut first = None;
+    let mut last = 0.0;
+    let mut crossings = 0;
+    for i in skip..samples.len() - skip {
+        let (a, b) = (samples[i - 1] as f64, samples[i] as f64);
+        if a < 0.0 && b >= 0.0 {
+            let t = i as f64 - b / (b - a);
+            if first.is_none() {
+                first = Some(t);
+            } else {
+                crossings += 1;
+            }
+            last = t;
+        }
+    }
+    crossings as f64 * sample_rate / (last - first.unwrap())
+}
+
+#[test]
+fn test_resample() {
+    let wave = Wave::render(
+        48000.0,
+        1.0,
+        &mut (sine_hz(440.0) | (sine_hz(1000.0) * 0.5)),
+    );
+    for sample_rate in [44100.0, 96000.0, 22050.0] {
+        let resampled = wave.resample(sample_rate);
+        assert_eq!(resampled.channels(), 2);
+        assert_eq!(resampled.sample_rate(), sample_rate);
+        assert_eq!(resampled.len(), round(sample_rate) as usize);
+        // Frequencies in Hz are preserved.
+        let f0 = zero_crossing_frequency(resampled.channel(0), sample_rate);
+        let f1 = zero_crossing_frequency(resampled.channel(1), sample_rate);
+        assert!(abs(f0 - 440.0) < 0.01);
+        assert!(abs(f1 - 1000.0) < 0.01);
+        // Amplitudes are preserved away from the edges.
+        let length = resampled.len();
+        let peak = |channel: usize| {
+            resampled.channel(channel)[length / 10..length * 9 / 10]
+                .iter()
+                .fold(0.0f32, |a, x| a.max(x.abs()))
+        };
+        assert!(abs(peak(0) - 1.0) < 0.01);
+        assert!(abs(peak(1) - 0.5) < 0.01);
+    }
+
+    // Frequencies above the new Nyquist frequency are removed.
+    let wave = Wave::render(48000.0, 1.0, &mut sine_hz(20000.0));
+    let resampled = wave.resample(22050.0);
+    let length = resampled.len();
+    let peak = resampled.channel(0)[length / 10..length * 9 / 10]
+        .iter()
+        .fold(0.0f32, |a, x| a.max(x.abs()));
+    assert!(peak < 1.0e-3);
+}
+
+#[test]
+fn test_wave_levels() {
+    // Known amplitude stereo buffer.
+    let mut wave = Wave::new(2, 44100.0);
+    for i in 0..1000 {
+        let x = if i % 2 == 0 { 0.5 } else { -0.5 };
+        wave.push((x, x * 0.5));
+    }
+    assert_eq!(wave.amplitude(), 0.5);
+    assert!(abs(wave.rms() - sqrt((0.25 + 0.0625) / 2.0)) < 1.0e-6);
+
+    // Peak normalization applies one gain to all channels.
+    wave.normalize_peak(0.0);
+    assert_eq!(wave.amplitude(), 1.0);
+    assert_eq!(wave.at(0, 0), 1.0);
+    assert_eq!(wave.at(1, 0), 0.5);
+    wave.normalize_peak(-12.0);
+    assert!(abs(amp_db(wave.amplitude()) + 12.0) < 1.0e-4);
+    assert!(abs(wave.at(1, 0) / wave.at(0, 0) - 0.5) < 1.0e-6);
+
+    // RMS normalization.
+    wave.normalize_rms(-6.0);
+    assert!(abs(amp_db(wave.rms()) + 6.0) < 1.0e-4);
+    assert!(abs(wave.at(1, 0) / wave.at(0, 0) - 0.5) < 1.0e-6);
+
+    // A full scale sine wave is at -3 dB RMS.
+    let wave = Wave::render(44100.0, 1.0, &mut sine_hz(441.0));
+    assert!(abs(amp_db(wave.rms()) + 3.0103) < 1.0e-3);
+
+    // Silent and empty waves are left unchanged.
+    let mut wave = Wave::zero(2, 44100.0, 0.1);
+    wave.normalize_peak(0.0);
+    wave.normalize_rms(0.0);
+    assert_eq!(wave.amplitude(), 0.0);
+    assert_eq!(Wave::new(1, 44100.0).rms(), 0.0);
+}
+
+#[test]
+fn test_wave_channels() {
+    let mut wave = Wave::new(2, 44100.0);
+    for i in 0..100 {
+        wave.push((i as f32, -(i as f32)));
+    }
+
+    // Extract a channel.
+    let left = wave.extract_channel(0);
+    let right = wave.extract_channel(1);
+    assert_eq!(left.channels(), 1);
+    assert_eq!(left.len(), 100);
+    assert_eq!(left.sample_rate(), 44100.0);
+    assert_eq!(left.channel(0), wave.channel(0));
+    assert_eq!(right.channel(0), wave.channel(1));
+
+    // Mix to mono averages channels.
+    let mono = wave.mix_to_mono();
+    assert_eq!(mono.channels(), 1);
+    assert_eq!(mono.len(), 100);
+    assert_eq!(mono.amplitude(), 0.0);
+    let mut three = left.clone();
+    three.append_channels(&left);
+    three.append_channels(&right);
+    assert_eq!(three.mix_to_mono().at(0, 30), 10.0);
+
+    // Append channels.
+    let mut stereo = Wave::new(0, 44100.0);
+    stereo.append_channels(&right);
+    stereo.append_channels(&left);
+    assert_eq!(stereo.channels(), 2);
+    assert_eq!(stereo.len(), 100);
+    assert_eq!(stereo.at(0, 10), -10.0);
+    assert_eq!(stereo.at(1, 10), 10.0);
+
+    // Append in time.
+    let mut long = wave.clone();
+    long.append(&stereo);
+    assert_eq!(long.channels(), 2);
+    assert_eq!(long.len(), 200);
+    assert_eq!(long.at(0, 99), 99.0);
+    assert_eq!(long.at(0, 110), -10.0);
+    assert_eq!(long.at(1, 110), 10.0);
+}
+
+#[test]
+#[should_panic]
+fn test_append_channels_length_mismatch() {
+    let mut wave = Wave::zero(1, 44100.0, 1.0);
+    wave.append_channels(&Wave::zero(1, 44100.0, 0.5));
+}
+
+#[test]
+fn test_crossfade_loop() {
+    // A sine wave that does not contain a whole number of cycles.
+    let mut wave = Wave::render(44100.0, 1.0, &mut (sine_hz(101.3) | sine_hz(37.7)));
+    let max_step = |wave: &Wave, channel: usize| {
+        (1..wave.len())
+            .map(|i| abs(wave.at(channel, i) - wave.at(channel, i - 1)))
+            .fold(0.0f32, f32::max)
+    };
+    let steps = [max_step(&wave, 0), max_step(&wave, 1)];
+    // Without crossfading, the loop point jumps.
+    assert!(abs(wave.at(0, wave.len() - 1) - wave.at(0, 0)) > steps[0] * 10.0);
+
+    wave.crossfade_loop(0.1);
+    assert_eq!(wave.len(), 44100 - 4410);
+    for (channel, step) in steps.iter().enumerate() {
+        // The loop boundary is continuous.
+        let boundary = abs(wave.at(channel, wave.len() - 1) - wave.at(channel, 0));
+        assert!(boundary <= step * 1.01);
+        // The crossfade does not introduce discontinuities elsewhere.
+        assert!(max_step(&wave, channel) <= step * 1.5);
+    }
+
+    // Uncorrelated noise keeps its level through an equal power crossfade.
+    let mut wave = Wave::render(44100.0, 2.0, &mut noise().seed(1));
+    let level = wave.rms();
+    wave.crossfade_loop(1.0);
+    assert!(abs(wave.rms() / level - 1.0) < 0.02);
+}
+
+#[test]
+fn test_render_with_progress() {
+    // Progress is reported in increasing order and completes at 1.
+    let mut reports = Vec::new();
+    let wave = Wave::render_with_progress(44100.0, 1.0, &mut (noise() | noise()), |progress| {
+        reports.push(progress);
+        true
+    });
+    assert_eq!(wave.len(), 44100);
+    assert_eq!(reports.len(), 44100 / PROGRESS_INTERVAL + 1);
+    assert!(reports.windows(2).all(|w| w[0] < w[1]));
+    assert!(reports[0] > 0.0 && reports[reports.len() - 1] == 1.0);
+
+    // Returning false stops rendering early.
+    let mut calls = 0;
+    let wave = Wave::render_with_progress(44100.0, 10.0, &mut sine_hz(440.0), |progress| {
+        calls += 1;
+        progress < 0.25
+    });
+    assert!(wave.len() < 441000 / 4 + PROGRESS_INTERVAL);
+    assert!(wave.len() >= 441000 / 4);
+    assert!(calls < 441000 / PROGRESS_INTERVAL);
+    // The partial wave matches a complete render.
+    let full = Wave::render(44100.0, 10.0, &mut sine_hz(440.0));
+    assert!((0..wave.len()).all(|i| wave.at(0, i) == full.at(0, i)));
+}
+
+#[test]
+fn test_pluck_frequency() {
+    // Excite the string with one cycle of a sine wave.
+    let sample_rate = 44100.0;
+    let excitation = Wave::render(sample_rate, 200.0 / sample_rate, &mut sine_hz(220.5));
+    let excitation = std::sync::Arc::new(excitation);
+    let mut node = pluck(220.0, 0.5, 0.5);
+    node.set_excitation(Some(&excitation));
+    node.set_sample_rate(sample_rate);
+    let render =
+        |node: &mut An<Pluck>| -> Vec<f32> { (0..22050).map(|_| node.filter_mono(0.0)).collect() };
+    let before = render(&mut node);
+    assert!((zero_crossing_frequency(&before, sample_rate) / 220.0 - 1.0).abs() < 0.01);
+
+    // Retuning shifts the fundamental while the string keeps ringing.
+    node.set_frequency(330.0);
+    assert_eq!(node.frequency(), 330.0);
+    let after = render(&mut node);
+    assert!((zero_crossing_frequency(&after, sample_rate) / 330.0 - 1.0).abs() < 0.01);
+    let level = |x: &[f32]| x.iter().map(|x| x * x).sum::<f32>() / x.len() as f32;
+    assert!(level(&after[..2205]) > 0.1 * level(&before[before.len() - 2205..]));
+
+    // The crossfade avoids large jumps at the retuning point.
+    let mut node = pluck(220.0, 0.5, 0.5);
+    node.set_excitation(Some(&excitation));
+    let mut previous = 0.0;
+    let mut max_step = 0.0f32;
+    for i in 0..4410 {
+        if i == 2000 {
+            node.set(Setting::center(247.0));
+        }
+        let x = node.filter_mono(0.0);
+        if i > 1000 {
+            max_step = max_step.max((x - previous).abs());
+        }
+        previous = x;
+    }
+    assert!(max_step < 0.1);
+
+    // The delay loops are sized for the initial frequency by default.
+    let mut node = pluck(440.0, 0.5, 0.5);
+    node.allocate();
+    let memory = node.memory_usage();
+    node.set_frequency(220.0);
+    assert_eq!(node.frequency(), 440.0);
+
+    // Retuning reuses the delay loops allocated for the lowest frequency.
+    let mut node = An(Pluck::new(440.0, 0.5, 0.5).with_min_frequency(55.0));
+    assert_eq!(node.min_frequency(), 55.0);
+    node.allocate();
+    assert!(node.memory_usage() > 4 * memory);
+    let memory = node.memory_usage();
+    for frequency in [55.0, 1760.0, 10.0, 220.0] {
+        node.set(Setting::center(frequency));
+        node.filter_mono(0.0);
+        assert_eq!(node.memory_usage(), memory);
+    }
+    node.set_frequency(10.0);
+    assert_eq!(node.frequency(), 55.0);
+
+    // Retuning up to Nyquist keeps the delay loop at least 1 sample long.
+    let mut node = pluck(220.0, 0.8, 0.5);
+    node.set_sample_rate(sample_rate);
+    node.filter_mono(0.0);
+    for frequency in [21000.0, 22050.0, 1.0e6, 220.0] {
+        node.set(Setting::center(frequency));
+        for _ in 0..100 {
+            assert!(node.filter_mono(0.0).is_finite());
+        }
+    }
+    node.set_frequency(22050.0);
+    assert!(node.frequency() <= (sample_rate * PLUCK_MAX_RATIO) as f32);
+    let mut node = pluck(22050.0, 0.8, 0.5);
+    assert!(node.filter_mono(0.0).is_finite());
+}